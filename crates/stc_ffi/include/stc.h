@@ -0,0 +1,49 @@
+/* C binding of stc. See `src/lib.rs` for the documentation. */
+
+#ifndef STC_H
+#define STC_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct StcChecker StcChecker;
+typedef struct StcDiagnostics StcDiagnostics;
+
+typedef struct StcDiagnostic {
+    /* Null if the error is not in a file. */
+    const char *file;
+    /* Byte offsets in the file. */
+    size_t start;
+    size_t end;
+    /* 1-based. */
+    size_t line;
+    /* 0-based, in characters. */
+    size_t column;
+    /* Like 2322 for TS2322. */
+    size_t code;
+    const char *message;
+} StcDiagnostic;
+
+/* `libs` is comma-separated, like "es2015,dom". Null means "es5". */
+StcChecker *stc_checker_new(const char *libs);
+void stc_checker_free(StcChecker *checker);
+
+/* Returns 0 on success. `path` should be absolute. */
+int stc_checker_add_file(StcChecker *checker, const char *path, const char *content);
+
+/* Returns null on failure. */
+StcDiagnostics *stc_checker_check(StcChecker *checker, const char *entry);
+
+size_t stc_diagnostics_len(const StcDiagnostics *diagnostics);
+/* Valid until `diagnostics` is freed. Null if `index` is out of bounds. */
+const StcDiagnostic *stc_diagnostics_get(const StcDiagnostics *diagnostics, size_t index);
+void stc_diagnostics_free(StcDiagnostics *diagnostics);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,237 @@
+//! C binding of stc, which allows hosts written in other languages to embed
+//! the checker.
+//!
+//! The declarations are in `include/stc.h`. A checker reads files from memory,
+//! so the host should add all files which can be imported before checking.
+//!
+//! Functions return null or a negative value on failure, including panics.
+//! Strings are UTF-8 and nul-terminated.
+
+use std::{
+    ffi::{CStr, CString},
+    io,
+    os::raw::{c_char, c_int},
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::PathBuf,
+    ptr::{null, null_mut},
+    sync::Arc,
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{fs::MemoryFs, resolvers::node::NodeResolver};
+use stc_ts_type_checker::{loader::ModuleLoader, program, Checker};
+use swc_common::{
+    errors::{EmitterWriter, Handler},
+    FileName, Globals, SourceMap, GLOBALS,
+};
+use swc_ecma_ast::EsVersion;
+
+/// Files to check and the builtin libraries.
+pub struct StcChecker {
+    fs: Arc<MemoryFs>,
+    libs: Vec<Lib>,
+}
+
+/// See [program::Diagnostic].
+///
+/// The strings are owned by the [StcDiagnostics] containing this.
+#[repr(C)]
+pub struct StcDiagnostic {
+    /// Null if the error is not in a file.
+    pub file: *const c_char,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub code: usize,
+    pub message: *const c_char,
+}
+
+/// Result of [stc_checker_check].
+pub struct StcDiagnostics {
+    items: Vec<StcDiagnostic>,
+    /// Storage of the strings of `items`.
+    _strings: Vec<CString>,
+}
+
+impl StcDiagnostics {
+    fn new(diagnostics: Vec<program::Diagnostic>) -> Self {
+        let mut strings = vec![];
+        let mut store = |s: String| {
+            // Nul bytes can't be represented.
+            let s = CString::new(s.replace('\0', "")).unwrap();
+            let ptr = s.as_ptr();
+            strings.push(s);
+            ptr
+        };
+
+        let items = diagnostics
+            .into_iter()
+            .map(|d| StcDiagnostic {
+                file: d.file.map(&mut store).unwrap_or(null()),
+                start: d.start,
+                end: d.end,
+                line: d.line,
+                column: d.column,
+                code: d.code,
+                message: store(d.message),
+            })
+            .collect();
+
+        StcDiagnostics { items, _strings: strings }
+    }
+}
+
+/// Creates a checker with the comma-separated builtin libraries in `libs`,
+/// like `es2015,dom`. If `libs` is null, `es5` is used.
+///
+/// The checker should be freed by [stc_checker_free].
+///
+/// # Safety
+///
+/// `libs` should be null or a valid string.
+#[no_mangle]
+pub unsafe extern "C" fn stc_checker_new(libs: *const c_char) -> *mut StcChecker {
+    let libs = if libs.is_null() {
+        "es5"
+    } else {
+        match CStr::from_ptr(libs).to_str() {
+            Ok(libs) => libs,
+            Err(..) => return null_mut(),
+        }
+    };
+
+    let mut libs = libs.split(',').flat_map(|s| Lib::load(s.trim())).collect::<Vec<_>>();
+    libs.sort();
+    libs.dedup();
+
+    Box::into_raw(Box::new(StcChecker {
+        fs: Arc::new(MemoryFs::new()),
+        libs,
+    }))
+}
+
+/// # Safety
+///
+/// `checker` should be null or a checker created by [stc_checker_new], which
+/// is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn stc_checker_free(checker: *mut StcChecker) {
+    if !checker.is_null() {
+        drop(Box::from_raw(checker));
+    }
+}
+
+/// Adds or replaces the file at `path`, which should be absolute. Returns 0
+/// on success.
+///
+/// # Safety
+///
+/// `checker` should be a valid checker, and `path` and `content` should be
+/// valid strings.
+#[no_mangle]
+pub unsafe extern "C" fn stc_checker_add_file(checker: *mut StcChecker, path: *const c_char, content: *const c_char) -> c_int {
+    if checker.is_null() || path.is_null() || content.is_null() {
+        return -1;
+    }
+
+    let (path, content) = match (CStr::from_ptr(path).to_str(), CStr::from_ptr(content).to_str()) {
+        (Ok(path), Ok(content)) => (path, content),
+        _ => return -1,
+    };
+
+    (*checker).fs.insert(PathBuf::from(path), content);
+    0
+}
+
+/// Checks the file at `entry` and the files imported by it, and returns the
+/// errors, which should be freed by [stc_diagnostics_free].
+///
+/// # Safety
+///
+/// `checker` should be a valid checker, and `entry` should be a valid string.
+#[no_mangle]
+pub unsafe extern "C" fn stc_checker_check(checker: *mut StcChecker, entry: *const c_char) -> *mut StcDiagnostics {
+    if checker.is_null() || entry.is_null() {
+        return null_mut();
+    }
+
+    let entry = match CStr::from_ptr(entry).to_str() {
+        Ok(entry) => PathBuf::from(entry),
+        Err(..) => return null_mut(),
+    };
+    let checker = &*checker;
+
+    match catch_unwind(AssertUnwindSafe(|| GLOBALS.set(&Globals::new(), || check(checker, entry)))) {
+        Ok(diagnostics) => Box::into_raw(Box::new(StcDiagnostics::new(diagnostics))),
+        Err(..) => null_mut(),
+    }
+}
+
+fn check(checker: &StcChecker, entry: PathBuf) -> Vec<program::Diagnostic> {
+    let cm = Arc::new(SourceMap::default());
+    // Errors are returned instead of being printed.
+    let handler = Arc::new(Handler::with_emitter(
+        false,
+        false,
+        Box::new(EmitterWriter::new(Box::new(io::sink()), Some(cm.clone()), false, false)),
+    ));
+
+    let env = Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &checker.libs);
+    let resolver = NodeResolver::new().with_fs(checker.fs.clone());
+
+    let type_checker = Checker::new(
+        cm.clone(),
+        handler,
+        env.clone(),
+        None,
+        ModuleLoader::with_fs(cm, env, resolver, checker.fs.clone()),
+    );
+
+    let program = type_checker.check(Arc::new(FileName::Real(entry)));
+
+    program.errors().map(|err| type_checker.to_diagnostic(err)).collect()
+}
+
+/// # Safety
+///
+/// `diagnostics` should be a valid result of [stc_checker_check].
+#[no_mangle]
+pub unsafe extern "C" fn stc_diagnostics_len(diagnostics: *const StcDiagnostics) -> usize {
+    if diagnostics.is_null() {
+        return 0;
+    }
+
+    (*diagnostics).items.len()
+}
+
+/// Returns null if `index` is out of bounds. The returned diagnostic is valid
+/// until `diagnostics` is freed.
+///
+/// # Safety
+///
+/// `diagnostics` should be a valid result of [stc_checker_check].
+#[no_mangle]
+pub unsafe extern "C" fn stc_diagnostics_get(diagnostics: *const StcDiagnostics, index: usize) -> *const StcDiagnostic {
+    if diagnostics.is_null() {
+        return null();
+    }
+
+    match (*diagnostics).items.get(index) {
+        Some(d) => d,
+        None => null(),
+    }
+}
+
+/// # Safety
+///
+/// `diagnostics` should be null or a result of [stc_checker_check], which is
+/// not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn stc_diagnostics_free(diagnostics: *mut StcDiagnostics) {
+    if !diagnostics.is_null() {
+        drop(Box::from_raw(diagnostics));
+    }
+}
@@ -0,0 +1,40 @@
+use std::{ffi::CStr, ptr::null};
+
+use stc_ffi::*;
+
+#[test]
+fn check_files() {
+    unsafe {
+        let checker = stc_checker_new(null());
+        assert!(!checker.is_null());
+
+        assert_eq!(
+            stc_checker_add_file(
+                checker,
+                b"/project/main.ts\0".as_ptr().cast(),
+                b"import { a } from './a';\nconst b: string = a;\n\0".as_ptr().cast(),
+            ),
+            0
+        );
+        assert_eq!(
+            stc_checker_add_file(
+                checker,
+                b"/project/a.ts\0".as_ptr().cast(),
+                b"export const a = 1;\n\0".as_ptr().cast()
+            ),
+            0
+        );
+
+        let diagnostics = stc_checker_check(checker, b"/project/main.ts\0".as_ptr().cast());
+        assert!(!diagnostics.is_null());
+        assert_eq!(stc_diagnostics_len(diagnostics), 1);
+
+        let d = &*stc_diagnostics_get(diagnostics, 0);
+        assert_eq!(CStr::from_ptr(d.file).to_str().unwrap(), "/project/main.ts");
+        assert_eq!((d.code, d.line), (2322, 2));
+        assert!(stc_diagnostics_get(diagnostics, 1).is_null());
+
+        stc_diagnostics_free(diagnostics);
+        stc_checker_free(checker);
+    }
+}
@@ -0,0 +1,181 @@
+//! Node.js binding of stc, which allows build tools to check files in-process.
+
+use std::{
+    io,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use napi::{Either, Error, Result};
+use napi_derive::napi;
+use stc_ts_builtin_types::Lib;
+use stc_ts_config::Project;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{
+    fs::{FileSystem, RealFs},
+    resolvers::{cache::CachingResolver, ModuleResolver, Resolve},
+};
+use stc_ts_type_checker::{
+    loader::{LoadModule, ModuleLoader},
+    program::{self, Program},
+    Checker,
+};
+use swc_common::{
+    errors::{EmitterWriter, Handler},
+    FileName, Globals, SourceMap, GLOBALS,
+};
+use swc_ecma_ast::EsVersion;
+
+#[napi(object)]
+pub struct CheckOptions {
+    /// Paths of the entry files.
+    pub files: Vec<String>,
+    /// The builtin libraries to load. Defaults to `es5`.
+    pub libs: Option<Vec<String>>,
+    pub allow_js: Option<bool>,
+    /// Implies `allowJs`.
+    pub check_js: Option<bool>,
+    pub skip_lib_check: Option<bool>,
+}
+
+/// See [program::Diagnostic].
+#[napi(object)]
+pub struct Diagnostic {
+    pub file: Option<String>,
+    pub start: u32,
+    pub end: u32,
+    pub line: u32,
+    pub column: u32,
+    pub code: u32,
+    pub message: String,
+}
+
+impl From<program::Diagnostic> for Diagnostic {
+    fn from(d: program::Diagnostic) -> Self {
+        Diagnostic {
+            file: d.file,
+            start: d.start as u32,
+            end: d.end as u32,
+            line: d.line as u32,
+            column: d.column as u32,
+            code: d.code as u32,
+            message: d.message,
+        }
+    }
+}
+
+/// Checks a project, or the entry files and their dependencies, and returns
+/// the errors.
+///
+/// `project` is the path of a `tsconfig.json` or a directory containing it,
+/// like `stc test --project`, or the options.
+#[napi]
+pub fn check(project: Either<String, CheckOptions>) -> Result<Vec<Diagnostic>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        GLOBALS.set(&Globals::new(), || match project {
+            Either::A(path) => check_project(Path::new(&path)),
+            Either::B(options) => check_files(options),
+        })
+    }));
+
+    match result {
+        Ok(result) => result,
+        // A panic unwinding into node.js would abort the process.
+        Err(..) => Err(Error::from_reason("stc panicked while checking")),
+    }
+}
+
+fn check_project(path: &Path) -> Result<Vec<Diagnostic>> {
+    let reason = |err: anyhow::Error| Error::from_reason(format!("{:#}", err));
+
+    let project = Project::load(&RealFs, path).map_err(reason)?;
+    let env = project.env().map_err(reason)?;
+    let options = project.options();
+    let fs: Arc<dyn FileSystem> = Arc::new(RealFs);
+    let resolver: Arc<dyn Resolve> = Arc::new(project.resolver(project.module_resolver(fs.clone()).map_err(reason)?));
+    // Checkers for typings and the entries share the cache.
+    let resolver = CachingResolver::new(resolver, fs.clone());
+
+    let entries = project.root_files(&*fs);
+    if entries.is_empty() {
+        return Err(Error::from_reason("no inputs were found in the project"));
+    }
+
+    let (cm, handler) = silent_handler();
+    Checker::new(
+        cm.clone(),
+        handler.clone(),
+        env.clone(),
+        None,
+        ModuleLoader::new(cm.clone(), env.clone(), resolver.clone()),
+    )
+    .load_typings(project.dir(), options.type_roots.as_deref(), options.types.as_deref());
+
+    let checker = Checker::new(cm.clone(), handler, env.clone(), None, ModuleLoader::new(cm, env, resolver));
+    let program = checker.check_all(entries.into_iter().map(|path| Arc::new(FileName::Real(path))).collect());
+
+    Ok(diagnostics(&checker, &program))
+}
+
+fn check_files(options: CheckOptions) -> Result<Vec<Diagnostic>> {
+    for file in &options.files {
+        if !PathBuf::from(file).is_file() {
+            return Err(Error::from_reason(format!("`{}` is not a file", file)));
+        }
+    }
+
+    let (cm, handler) = silent_handler();
+
+    let mut libs = match &options.libs {
+        Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+        None => Lib::load("es5"),
+    };
+    libs.sort();
+    libs.dedup();
+
+    let check_js = options.check_js.unwrap_or(false);
+    let rule = Rule {
+        allow_js: options.allow_js.unwrap_or(false) || check_js,
+        check_js,
+        skip_lib_check: options.skip_lib_check.unwrap_or(false),
+        ..Default::default()
+    };
+    let env = Env::simple(rule, EsVersion::latest(), ModuleConfig::None, &libs);
+    let resolver = ModuleResolver::new(env.module().module_resolution()).with_allow_js(env.rule().allow_js);
+
+    let checker = Checker::new(cm.clone(), handler, env.clone(), None, ModuleLoader::new(cm, env, resolver));
+
+    if options.files.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let files = options
+        .files
+        .into_iter()
+        .map(|file| Arc::new(FileName::Real(file.into())))
+        .collect();
+    let program = checker.check_all(files);
+
+    Ok(diagnostics(&checker, &program))
+}
+
+/// Errors are returned instead of being printed.
+fn silent_handler() -> (Arc<SourceMap>, Arc<Handler>) {
+    let cm = Arc::new(SourceMap::default());
+    let handler = Arc::new(Handler::with_emitter(
+        false,
+        false,
+        Box::new(EmitterWriter::new(Box::new(io::sink()), Some(cm.clone()), false, false)),
+    ));
+
+    (cm, handler)
+}
+
+fn diagnostics<L>(checker: &Checker<L>, program: &Program) -> Vec<Diagnostic>
+where
+    L: LoadModule,
+{
+    program.errors().map(|err| Diagnostic::from(checker.to_diagnostic(err))).collect()
+}
@@ -0,0 +1,55 @@
+//! The order of projects connected by `references`, for `--build`.
+//!
+//! https://www.typescriptlang.org/docs/handbook/project-references.html
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Error};
+use stc_ts_module_loader::fs::FileSystem;
+
+use crate::Project;
+
+/// Loads the project at `path` and the projects it references directly or
+/// indirectly, and returns them in the order they should be built, which
+/// means that a project comes after the projects it references.
+///
+/// Referenced projects should enable `composite`, like `tsc`.
+pub fn build_order(fs: &dyn FileSystem, path: &Path) -> Result<Vec<Project>, Error> {
+    let mut order = vec![];
+    visit(fs, Project::load(fs, path)?, &mut vec![], &mut order)?;
+    Ok(order)
+}
+
+/// `stack` contains the configs which reference the config, and is used to
+/// detect cycles.
+fn visit(fs: &dyn FileSystem, project: Project, stack: &mut Vec<PathBuf>, order: &mut Vec<Project>) -> Result<(), Error> {
+    if let Some(idx) = stack.iter().position(|p| *p == project.path) {
+        let cycle = stack[idx..]
+            .iter()
+            .chain([&project.path])
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>();
+        bail!("project references form a cycle: {}", cycle.join(" -> "));
+    }
+    if order.iter().any(|p| p.path == project.path) {
+        return Ok(());
+    }
+
+    stack.push(project.path.clone());
+    for reference in project.references() {
+        let referenced =
+            Project::load(fs, &reference).with_context(|| format!("failed to load a reference of `{}`", project.path.display()))?;
+        if !referenced.options().composite.unwrap_or_default() {
+            bail!(
+                "referenced project `{}` must have setting \"composite\": true",
+                referenced.path.display()
+            );
+        }
+
+        visit(fs, referenced, stack, order)?;
+    }
+    stack.pop();
+
+    order.push(project);
+    Ok(())
+}
@@ -0,0 +1,282 @@
+//! Enumeration of root files using `files`, `include` and `exclude`.
+//!
+//! https://www.typescriptlang.org/tsconfig#include
+
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
+
+use path_clean::PathClean;
+use stc_ts_module_loader::fs::FileSystem;
+
+use crate::TsConfig;
+
+/// Directories of packages. They are excluded by default, and `**` doesn't
+/// match them.
+pub(crate) const PACKAGE_DIRS: &[&str] = &["node_modules", "bower_components", "jspm_packages"];
+
+/// Supported extensions, with the kind of module, the priority and whether
+/// it's enabled only by `allowJs`.
+///
+/// If files differ only in the extension and they are the same kind of
+/// module, only the files with the lowest priority are included.
+const EXTENSIONS: &[(&str, u8, u8, bool)] = &[
+    (".ts", 0, 0, false),
+    (".tsx", 0, 0, false),
+    (".d.ts", 0, 1, false),
+    (".js", 0, 2, true),
+    (".jsx", 0, 2, true),
+    (".mts", 1, 0, false),
+    (".d.mts", 1, 1, false),
+    (".mjs", 1, 2, true),
+    (".cts", 2, 0, false),
+    (".d.cts", 2, 1, false),
+    (".cjs", 2, 2, true),
+];
+
+pub(crate) fn root_files(fs: &dyn FileSystem, dir: &Path, config: &TsConfig, case_sensitive: bool) -> Vec<PathBuf> {
+    let options = &config.compiler_options;
+
+    let mut files = config.files.iter().flatten().map(|file| dir.join(file).clean()).collect::<Vec<_>>();
+
+    let include = match (&config.include, &config.files) {
+        (Some(include), _) => include.iter().map(|s| Glob::parse(dir, s, case_sensitive)).collect(),
+        (None, Some(..)) => vec![],
+        (None, None) => vec![Glob::parse(dir, "**/*", case_sensitive)],
+    };
+    if include.is_empty() {
+        return files;
+    }
+
+    let exclude = match &config.exclude {
+        Some(exclude) => exclude.iter().map(|s| Glob::parse(dir, s, case_sensitive)).collect(),
+        None => PACKAGE_DIRS
+            .iter()
+            .map(|s| Glob::parse(dir, s, case_sensitive))
+            .chain(
+                [&options.out_dir, &options.declaration_dir]
+                    .into_iter()
+                    .flatten()
+                    .map(|out_dir| Glob::parse(dir, &out_dir.to_string_lossy(), case_sensitive)),
+            )
+            .collect::<Vec<_>>(),
+    };
+
+    let allow_js = options.allow_js.unwrap_or_default() || options.check_js.unwrap_or_default();
+
+    // Files are grouped by the path without the extension and the kind of
+    // module.
+    let mut matched = BTreeMap::<(PathBuf, u8), Vec<(u8, PathBuf)>>::new();
+    for glob in &include {
+        walk(fs, &glob.base, &mut |path| {
+            if exclude.iter().any(|glob| glob.matches_ancestor(path)) {
+                return false;
+            }
+
+            if let Some((stem, kind, priority)) = split_extension(path, allow_js) {
+                if fs.is_file(path) && glob.matches_include(path) {
+                    let group = matched.entry((stem, kind)).or_default();
+                    if !group.iter().any(|(_, p)| p == path) {
+                        group.push((priority, path.to_path_buf()));
+                    }
+                }
+            }
+            true
+        });
+    }
+
+    let mut matched = matched
+        .into_values()
+        .flat_map(|group| {
+            let best = group.iter().map(|(priority, _)| *priority).min();
+            group.into_iter().filter(move |(priority, _)| Some(*priority) == best)
+        })
+        .map(|(_, file)| file)
+        .collect::<Vec<_>>();
+    matched.sort();
+
+    for file in matched {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    files
+}
+
+/// Returns the files in `files` which match any of `patterns`, keeping the
+/// order.
+///
+/// The patterns are like `include`, and relative patterns and relative files
+/// are resolved from `dir`. A pattern naming a directory matches the files
+/// under it.
+pub fn filter_files(files: &[PathBuf], dir: &Path, patterns: &[String], case_sensitive: bool) -> Vec<PathBuf> {
+    let globs = patterns.iter().map(|s| Glob::parse(dir, s, case_sensitive)).collect::<Vec<_>>();
+
+    files
+        .iter()
+        .filter(|file| {
+            let path = dir.join(file).clean();
+            globs.iter().any(|glob| glob.matches_include(&path))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Calls `op` with `path` and each file and directory under it. Children of
+/// a directory are visited only if `op` returns `true` for it.
+fn walk(fs: &dyn FileSystem, path: &Path, op: &mut dyn FnMut(&Path) -> bool) {
+    if !op(path) || !fs.is_dir(path) {
+        return;
+    }
+
+    if let Ok(mut children) = fs.read_dir(path) {
+        children.sort();
+        for child in children {
+            walk(fs, &child, op);
+        }
+    }
+}
+
+/// Returns the path without the extension, the kind of module and the
+/// priority of the extension. Returns [None] for unsupported files.
+fn split_extension(path: &Path, allow_js: bool) -> Option<(PathBuf, u8, u8)> {
+    let name = path.file_name()?.to_string_lossy();
+
+    // The longest extension is used, so `.d.ts` wins over `.ts`.
+    let (ext, kind, priority, _) = EXTENSIONS
+        .iter()
+        .filter(|(ext, _, _, is_js)| name.ends_with(ext) && (allow_js || !is_js))
+        .max_by_key(|(ext, ..)| ext.len())?;
+
+    Some((path.with_file_name(&name[..name.len() - ext.len()]), *kind, *priority))
+}
+
+/// A pattern of `include` or `exclude`.
+///
+/// `*` matches zero or more characters except `/`, `?` matches a character
+/// except `/`, and `**` matches zero or more directories. Wildcards don't
+/// match names starting with `.`.
+struct Glob {
+    /// The leading segments without wildcards, which is where matching files
+    /// are searched.
+    base: PathBuf,
+    /// The remaining segments, relative to `base`.
+    segments: Vec<String>,
+    case_sensitive: bool,
+}
+
+impl Glob {
+    /// A relative pattern is resolved from `dir`.
+    fn parse(dir: &Path, s: &str, case_sensitive: bool) -> Self {
+        let mut base = PathBuf::new();
+        let mut segments = vec![];
+
+        for c in dir.join(s).clean().components() {
+            match c {
+                Component::Normal(s) => {
+                    let s = s.to_string_lossy();
+                    if segments.is_empty() && !s.contains(['*', '?']) {
+                        base.push(&*s);
+                    } else {
+                        segments.push(s.into_owned());
+                    }
+                }
+                _ => base.push(c),
+            }
+        }
+
+        // `src/**` is treated as `src/**/*`.
+        if segments.last().map(|s| &**s) == Some("**") {
+            segments.push("*".into());
+        }
+
+        Glob {
+            base,
+            segments,
+            case_sensitive,
+        }
+    }
+
+    /// A pattern without wildcards in the last segment also matches the files
+    /// under the directory it names.
+    fn matches_include(&self, path: &Path) -> bool {
+        let rel = match self.relative(path) {
+            Some(rel) => rel,
+            None => return false,
+        };
+
+        match self.segments.last() {
+            Some(last) if last.contains(['*', '?']) => self.matches_segments(&self.segments, &rel),
+            _ => (0..=rel.len()).any(|len| self.matches_segments(&self.segments, &rel[..len])),
+        }
+    }
+
+    /// Returns `true` if the pattern matches `path` or one of its ancestors.
+    fn matches_ancestor(&self, path: &Path) -> bool {
+        match self.relative(path) {
+            Some(rel) => (0..=rel.len()).any(|len| self.matches_segments(&self.segments, &rel[..len])),
+            None => false,
+        }
+    }
+
+    /// The segments of `path` relative to `base`.
+    fn relative(&self, path: &Path) -> Option<Vec<String>> {
+        let mut components = path.components();
+        for base in self.base.components() {
+            let c = components.next()?;
+            if !self.eq(&base.as_os_str().to_string_lossy(), &c.as_os_str().to_string_lossy()) {
+                return None;
+            }
+        }
+
+        Some(components.map(|c| c.as_os_str().to_string_lossy().into_owned()).collect())
+    }
+
+    fn eq(&self, a: &str, b: &str) -> bool {
+        if self.case_sensitive {
+            a == b
+        } else {
+            a.eq_ignore_ascii_case(b)
+        }
+    }
+
+    fn matches_segments(&self, pattern: &[String], path: &[String]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((first, rest)) if first == "**" => (0..=path.len())
+                .take_while(|&skip| skip == 0 || is_visible_dir(&path[skip - 1]))
+                .any(|skip| self.matches_segments(rest, &path[skip..])),
+            Some((first, rest)) => match path.split_first() {
+                Some((name, path)) => {
+                    // Hidden files are matched only explicitly.
+                    let hidden = name.starts_with('.') && !first.starts_with('.');
+                    !(hidden && first.contains(['*', '?']))
+                        && self.matches_wildcard(&first.chars().collect::<Vec<_>>(), &name.chars().collect::<Vec<_>>())
+                        && self.matches_segments(rest, path)
+                }
+                None => false,
+            },
+        }
+    }
+
+    fn matches_wildcard(&self, pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| self.matches_wildcard(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && self.matches_wildcard(rest, &name[1..]),
+            Some((c, rest)) => match name.split_first() {
+                Some((n, name)) => {
+                    let eq = if self.case_sensitive { c == n } else { c.eq_ignore_ascii_case(n) };
+                    eq && self.matches_wildcard(rest, name)
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+/// Returns `true` if `**` can match the directory named `name`.
+fn is_visible_dir(name: &str) -> bool {
+    !name.starts_with('.') && !PACKAGE_DIRS.contains(&name)
+}
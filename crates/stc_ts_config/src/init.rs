@@ -0,0 +1,200 @@
+//! Recommendation of `tsconfig.json` for `stc init`.
+
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::{Context, Error};
+use serde::{Deserialize, Serialize};
+use stc_ts_module_loader::fs::FileSystem;
+
+use crate::{files::PACKAGE_DIRS, StcOptions, TsConfig};
+
+/// A recommended `tsconfig.json`.
+#[derive(Debug, Clone)]
+pub struct Init {
+    /// The content of `tsconfig.json`.
+    pub content: String,
+    /// Messages about options in `content` which are not supported by stc yet.
+    /// They are recommended for `tsc` and other tools.
+    pub warnings: Vec<String>,
+}
+
+/// Inspects `package.json` and the source files in `dir`, and returns a
+/// recommended `tsconfig.json` for it.
+///
+/// - `module` is `nodenext` for packages with `"type": "module"`.
+/// - `jsx` is enabled if there are `.tsx` files or `react` is a dependency.
+/// - `allowJs` is enabled if there are javascript files, and `checkJs` too if
+///   there are only javascript files.
+/// - `src` is used as `rootDir` if it exists, and `outDir` is inferred from
+///   `main` of `package.json`.
+/// - `declaration` is enabled for packages with `types`.
+pub fn init(fs: &dyn FileSystem, dir: &Path) -> Result<Init, Error> {
+    let pkg_path = dir.join("package.json");
+    let pkg = match fs.read_to_string(&pkg_path) {
+        Ok(content) => {
+            serde_json::from_str::<PackageJson>(&content).with_context(|| format!("failed to parse `{}`", pkg_path.display()))?
+        }
+        Err(..) => Default::default(),
+    };
+
+    let out_dir = pkg
+        .main
+        .as_deref()
+        .map(|main| main.trim_start_matches("./"))
+        .and_then(|main| main.split_once('/'))
+        .map(|(dir, _)| dir)
+        .filter(|dir| ["dist", "lib", "build", "out"].contains(dir))
+        .unwrap_or("dist");
+
+    let src_dir = dir.join("src");
+    let has_src = fs.is_dir(&src_dir);
+
+    // Outputs of a previous build are not sources.
+    let mut files = Files::default();
+    count_files(fs, if has_src { &src_dir } else { dir }, &dir.join(out_dir), &mut files);
+
+    let is_dependency = |name: &str| {
+        pkg.dependencies.contains_key(name) || pkg.dev_dependencies.contains_key(name) || pkg.peer_dependencies.contains_key(name)
+    };
+    let uses_jsx = files.tsx > 0 || files.jsx > 0 || is_dependency("react");
+    let is_esm = pkg.ty.as_deref() == Some("module");
+
+    let recommended = Recommended {
+        compiler_options: RecommendedOptions {
+            target: "es2020",
+            module: if is_esm { "nodenext" } else { "commonjs" },
+            module_resolution: if is_esm { "nodenext" } else { "node" },
+            lib: (uses_jsx || pkg.browser.is_some()).then(|| vec!["es2020", "dom", "dom.iterable"]),
+            jsx: uses_jsx.then_some("react-jsx"),
+            strict: true,
+            allow_js: (files.js > 0 || files.jsx > 0).then_some(true),
+            check_js: (files.ts == 0 && files.tsx == 0 && files.js + files.jsx > 0).then_some(true),
+            root_dir: has_src.then_some("./src"),
+            out_dir: format!("./{}", out_dir),
+            declaration: (pkg.types.is_some() || pkg.typings.is_some()).then_some(true),
+            es_module_interop: true,
+            force_consistent_casing_in_file_names: true,
+            skip_lib_check: true,
+        },
+        include: has_src.then(|| vec!["src"]),
+        stc: StcOptions {
+            no_circular_imports: Some(false),
+            no_unused_exports: Some(false),
+            error_baseline: None,
+            max_errors: None,
+            max_errors_per_file: None,
+            severity: Default::default(),
+        },
+    };
+
+    let content = format!("{}\n", serde_json::to_string_pretty(&recommended)?);
+
+    let config = TsConfig::parse(&content)?;
+    let warnings = config
+        .compiler_options
+        .unsupported
+        .keys()
+        .map(|name| format!("`{}` is not supported by stc yet, and it will be ignored", name))
+        .collect();
+
+    Ok(Init { content, warnings })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Recommended {
+    compiler_options: RecommendedOptions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    include: Option<Vec<&'static str>>,
+    stc: StcOptions,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecommendedOptions {
+    target: &'static str,
+    module: &'static str,
+    module_resolution: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lib: Option<Vec<&'static str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    jsx: Option<&'static str>,
+    strict: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_js: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check_js: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root_dir: Option<&'static str>,
+    out_dir: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    declaration: Option<bool>,
+    es_module_interop: bool,
+    force_consistent_casing_in_file_names: bool,
+    skip_lib_check: bool,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageJson {
+    #[serde(default, rename = "type")]
+    ty: Option<String>,
+    #[serde(default)]
+    main: Option<String>,
+    #[serde(default)]
+    types: Option<String>,
+    #[serde(default)]
+    typings: Option<String>,
+    #[serde(default)]
+    browser: Option<serde_json::Value>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    peer_dependencies: BTreeMap<String, serde_json::Value>,
+}
+
+/// The number of source files by the extension.
+#[derive(Default)]
+struct Files {
+    ts: usize,
+    tsx: usize,
+    js: usize,
+    jsx: usize,
+}
+
+/// Counts source files in `dir`, except for packages, hidden directories,
+/// declaration files and files in `out_dir`.
+fn count_files(fs: &dyn FileSystem, dir: &Path, out_dir: &Path, files: &mut Files) {
+    let children = match fs.read_dir(dir) {
+        Ok(children) => children,
+        Err(..) => return,
+    };
+
+    for child in children {
+        let name = match child.file_name().and_then(|name| name.to_str()) {
+            Some(name) => name,
+            None => continue,
+        };
+        if name.starts_with('.') || PACKAGE_DIRS.contains(&name) || child == out_dir {
+            continue;
+        }
+
+        if fs.is_dir(&child) {
+            count_files(fs, &child, out_dir, files);
+            continue;
+        }
+
+        if name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts") {
+            continue;
+        }
+        match child.extension().and_then(|ext| ext.to_str()) {
+            Some("ts" | "mts" | "cts") => files.ts += 1,
+            Some("tsx") => files.tsx += 1,
+            Some("js" | "mjs" | "cjs") => files.js += 1,
+            Some("jsx") => files.jsx += 1,
+            _ => {}
+        }
+    }
+}
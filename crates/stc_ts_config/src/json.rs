@@ -0,0 +1,79 @@
+//! `tsconfig.json` allows comments and trailing commas, which are not valid
+//! in JSON.
+
+/// Replaces comments with spaces and removes trailing commas, so the result
+/// can be parsed as JSON.
+///
+/// Line breaks in comments are preserved, so the line numbers of errors
+/// reported by the parser are still correct.
+pub(crate) fn strip_comments(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    // The index of the last comma in `buf` which is followed only by
+    // whitespaces.
+    let mut comma = None;
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                comma = None;
+                buf.push(c);
+
+                while let Some(c) = chars.next() {
+                    buf.push(c);
+                    match c {
+                        '\\' => {
+                            if let Some(c) = chars.next() {
+                                buf.push(c);
+                            }
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        buf.push(c);
+                        break;
+                    }
+                }
+            }
+
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    buf.push(if c == '\n' { '\n' } else { ' ' });
+                    prev = c;
+                }
+            }
+
+            '}' | ']' => {
+                if let Some(idx) = comma.take() {
+                    buf.replace_range(idx..idx + 1, " ");
+                }
+                buf.push(c);
+            }
+
+            ',' => {
+                comma = Some(buf.len());
+                buf.push(c);
+            }
+
+            _ => {
+                if !c.is_whitespace() {
+                    comma = None;
+                }
+                buf.push(c);
+            }
+        }
+    }
+
+    buf
+}
@@ -0,0 +1,445 @@
+//! Reads `tsconfig.json` and converts it to the configuration of the type
+//! checker.
+//!
+//! https://www.typescriptlang.org/tsconfig
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, bail, Context, Error};
+use path_clean::PathClean;
+use serde::Deserialize;
+use stc_ts_env::{Env, Rule};
+use stc_ts_errors::{Severity, SeverityOverrides};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{
+    fs::FileSystem,
+    resolvers::{references::ProjectReferenceResolver, tsc::TsConfigResolver, ModuleResolver, Resolve},
+};
+
+pub use self::{
+    build::build_order,
+    files::filter_files,
+    init::{init, Init},
+    options::{CompilerOptions, Paths, StcOptions},
+    unsupported::{Handling, UnsupportedOption},
+};
+
+mod build;
+mod files;
+mod init;
+mod json;
+mod options;
+mod unsupported;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TsConfig {
+    /// Configs which this config is based on. Resolved by [Project::load].
+    #[serde(default)]
+    pub extends: Option<Extends>,
+
+    #[serde(default)]
+    pub compiler_options: CompilerOptions,
+
+    /// Paths of root files, relative to the directory containing the config.
+    #[serde(default)]
+    pub files: Option<Vec<String>>,
+
+    /// Glob patterns of root files. Defaults to `**/*` if `files` is not
+    /// specified.
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+
+    /// Glob patterns removed from the matches of `include`. Defaults to
+    /// `node_modules`, `bower_components` and `jspm_packages`.
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+
+    /// Projects which this project depends on. They are not inherited by
+    /// `extends`.
+    #[serde(default)]
+    pub references: Option<Vec<ProjectReference>>,
+
+    /// Options only supported by stc.
+    #[serde(default)]
+    pub stc: Option<StcOptions>,
+}
+
+/// An element of `references`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectReference {
+    /// The path of the config of the referenced project, or the directory
+    /// containing `tsconfig.json`, relative to the directory containing the
+    /// config.
+    pub path: String,
+}
+
+impl TsConfig {
+    /// Parses the content of `tsconfig.json`. Comments and trailing commas are
+    /// allowed, like `tsc`.
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(&json::strip_comments(content))?)
+    }
+
+    pub fn read(fs: &dyn FileSystem, path: &Path) -> Result<Self, Error> {
+        let content = fs
+            .read_to_string(path)
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+
+        Self::parse(&content).with_context(|| format!("failed to deserialize `{}`", path.display()))
+    }
+
+    /// Uses the options of `base`, which is in `base_dir`, for options which
+    /// are not specified.
+    ///
+    /// `files`, `include`, `exclude` and `stc` are inherited as a whole, and
+    /// `compilerOptions` are inherited one by one.
+    fn inherit(&mut self, base: TsConfig, base_dir: &Path) {
+        self.compiler_options.inherit(base.compiler_options);
+        if self.stc.is_none() {
+            self.stc = base.stc;
+        }
+
+        for (field, base) in [
+            (&mut self.files, base.files),
+            (&mut self.include, base.include),
+            (&mut self.exclude, base.exclude),
+        ] {
+            if field.is_none() {
+                *field = base.map(|paths| {
+                    paths
+                        .into_iter()
+                        .map(|path| base_dir.join(path).to_string_lossy().into_owned())
+                        .collect()
+                });
+            }
+        }
+    }
+}
+
+/// The value of `extends`, which is a path or a package name.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Extends {
+    One(String),
+    /// Later configs override earlier configs.
+    Many(Vec<String>),
+}
+
+impl Extends {
+    pub fn specifiers(&self) -> &[String] {
+        match self {
+            Extends::One(s) => std::slice::from_ref(s),
+            Extends::Many(v) => v,
+        }
+    }
+}
+
+/// A `tsconfig.json` and the directory containing it.
+#[derive(Debug, Clone)]
+pub struct Project {
+    /// The path of `tsconfig.json`.
+    pub path: PathBuf,
+    pub config: TsConfig,
+    /// Whether `include` and `exclude` match file names case sensitively.
+    /// Defaults to `false` on Windows and macOS, like `tsc`.
+    pub case_sensitive: bool,
+}
+
+impl Project {
+    /// Loads the config at `path`, or `tsconfig.json` in `path` if it's a
+    /// directory, and the configs it extends.
+    ///
+    /// `extends` is resolved from the directory containing the config if it's
+    /// a relative path, and from `node_modules` otherwise, like
+    /// `@tsconfig/node18/tsconfig.json` or `@tsconfig/node18`.
+    pub fn load(fs: &dyn FileSystem, path: &Path) -> Result<Self, Error> {
+        let path = if fs.is_dir(path) {
+            path.join("tsconfig.json")
+        } else {
+            path.to_path_buf()
+        };
+
+        Ok(Project {
+            config: read_extended(fs, &path, &mut vec![])?,
+            path,
+            case_sensitive: !cfg!(any(windows, target_os = "macos")),
+        })
+    }
+
+    /// The directory relative paths of the config are resolved from.
+    pub fn dir(&self) -> &Path {
+        self.path.parent().unwrap_or_else(|| Path::new(""))
+    }
+
+    pub fn options(&self) -> &CompilerOptions {
+        &self.config.compiler_options
+    }
+
+    /// The directory containing the source files, which is `rootDir` or the
+    /// directory containing the config.
+    pub fn root_dir(&self) -> &Path {
+        self.options().root_dir.as_deref().unwrap_or_else(|| self.dir())
+    }
+
+    /// The directory where declaration files are emitted, which is
+    /// `declarationDir` or `outDir`. [None] if they are emitted next to the
+    /// source files.
+    pub fn declaration_dir(&self) -> Option<&Path> {
+        let options = self.options();
+        options.declaration_dir.as_deref().or(options.out_dir.as_deref())
+    }
+
+    /// Returns the path of the declaration file emitted for `source`, keeping
+    /// the path relative to [Project::root_dir].
+    ///
+    /// Returns [None] if `source` is not in [Project::root_dir] or it's not a
+    /// typescript file.
+    pub fn declaration_output(&self, source: &Path) -> Option<PathBuf> {
+        let rest = source.strip_prefix(self.root_dir()).ok()?;
+
+        let name = rest.file_name()?.to_str()?;
+        if name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts") {
+            return None;
+        }
+        let ext = match rest.extension()?.to_str()? {
+            "ts" | "tsx" => "d.ts",
+            "mts" => "d.mts",
+            "cts" => "d.cts",
+            _ => return None,
+        };
+
+        Some(
+            self.declaration_dir()
+                .unwrap_or_else(|| self.root_dir())
+                .join(rest)
+                .with_extension(ext),
+        )
+    }
+
+    /// The path of the file storing the results of the previous check, which is
+    /// `tsBuildInfoFile` or `.stcbuildinfo` in the directory containing the
+    /// config.
+    pub fn build_info_path(&self) -> PathBuf {
+        self.options()
+            .ts_build_info_file
+            .clone()
+            .unwrap_or_else(|| self.dir().join(".stcbuildinfo"))
+    }
+
+    /// Returns the paths of the projects in `references`, which may be
+    /// directories containing `tsconfig.json`.
+    pub fn references(&self) -> Vec<PathBuf> {
+        self.config
+            .references
+            .iter()
+            .flatten()
+            .map(|reference| self.dir().join(&reference.path).clean())
+            .collect()
+    }
+
+    /// Creates a [Rule] from `compilerOptions` and the `stc` section.
+    pub fn rule(&self) -> Result<Rule, Error> {
+        let mut rule = self
+            .options()
+            .rule()
+            .with_context(|| format!("invalid `compilerOptions` in `{}`", self.path.display()))?;
+
+        if let Some(stc) = &self.config.stc {
+            rule.no_circular_imports = stc.no_circular_imports.unwrap_or_default();
+            rule.no_unused_exports = stc.no_unused_exports.unwrap_or_default();
+        }
+
+        Ok(rule)
+    }
+
+    /// Creates the overrides of the severities of error codes from
+    /// `stc.severity`. Codes may be written with or without the `TS` prefix.
+    pub fn severity_overrides(&self) -> Result<SeverityOverrides, Error> {
+        let mut overrides = SeverityOverrides::default();
+
+        for (code, severity) in self.config.stc.iter().flat_map(|stc| &stc.severity) {
+            let invalid = || format!("invalid `stc.severity` in `{}`", self.path.display());
+
+            let parsed_code = code
+                .trim_start_matches("TS")
+                .parse()
+                .map_err(|_| anyhow!("`{}` is not an error code", code))
+                .with_context(invalid)?;
+            let severity = match &**severity {
+                "off" => None,
+                _ => Some(
+                    severity
+                        .parse::<Severity>()
+                        .map_err(|_| anyhow!("the severity of `{}` should be `error`, `warning`, `info` or `off`", code))
+                        .with_context(invalid)?,
+                ),
+            };
+
+            overrides = overrides.with_code(parsed_code, severity);
+        }
+
+        Ok(overrides)
+    }
+
+    /// Creates an [Env] from `compilerOptions` and the `stc` section.
+    pub fn env(&self) -> Result<Env, Error> {
+        let options = self.options();
+        let invalid = || format!("invalid `compilerOptions` in `{}`", self.path.display());
+
+        Ok(Env::simple(
+            self.rule()?,
+            options.target().with_context(invalid)?,
+            options.module().with_context(invalid)?,
+            &options.libs().with_context(invalid)?,
+        )
+        .with_severity_overrides(self.severity_overrides()?))
+    }
+
+    /// Creates a resolver which handles `moduleResolution`, `moduleSuffixes`
+    /// and other options of the resolution strategy.
+    ///
+    /// Wrap it using [Project::resolver] to handle `baseUrl`, `paths` and
+    /// `rootDirs` too.
+    pub fn module_resolver(&self, fs: Arc<dyn FileSystem>) -> Result<ModuleResolver, Error> {
+        let options = self.options();
+        let module_resolution = options
+            .module_resolution()
+            .with_context(|| format!("invalid `compilerOptions` in `{}`", self.path.display()))?;
+
+        Ok(ModuleResolver::new(module_resolution)
+            .with_allow_js(options.allow_js.unwrap_or_default() || options.check_js.unwrap_or_default())
+            .with_preserve_symlinks(options.preserve_symlinks.unwrap_or_default())
+            .with_module_suffixes(options.module_suffixes.clone().unwrap_or_default())
+            .with_fs(fs))
+    }
+
+    /// Creates a resolver which resolves the source files of `references` to
+    /// their declaration outputs. `references` should be the loaded projects
+    /// of [Project::references].
+    pub fn reference_resolver<R>(&self, inner: R, fs: Arc<dyn FileSystem>, references: &[Project]) -> ProjectReferenceResolver<R>
+    where
+        R: Resolve,
+    {
+        references
+            .iter()
+            .fold(ProjectReferenceResolver::new(inner, fs), |resolver, reference| {
+                resolver.with_reference(
+                    reference.root_dir().to_path_buf(),
+                    reference.declaration_dir().map(Path::to_path_buf),
+                )
+            })
+    }
+
+    /// Creates a resolver which handles `baseUrl`, `paths` and `rootDirs`, and
+    /// passes other specifiers to `inner`.
+    pub fn resolver<R>(&self, inner: R) -> TsConfigResolver<R>
+    where
+        R: Resolve,
+    {
+        let options = self.options();
+        let paths = options.paths.clone().map(|paths| paths.0).unwrap_or_default();
+
+        TsConfigResolver::new(inner, self.dir().to_path_buf(), options.base_url.clone(), paths)
+            .with_root_dirs(options.root_dirs.clone().unwrap_or_default())
+    }
+
+    /// Returns the root files of the project, which are `files` and the files
+    /// matched by `include` but not by `exclude`, like `tsc`.
+    ///
+    /// - `include` defaults to `**/*` unless `files` is specified.
+    /// - `exclude` defaults to the directories of packages, `outDir` and
+    ///   `declarationDir`.
+    /// - Wildcards don't match names starting with `.`, and `**` doesn't match
+    ///   the directories of packages.
+    /// - If files differ only in the extension, only the file with the highest
+    ///   priority is included, like `a.ts` over `a.d.ts` and `a.js`.
+    ///
+    /// Files listed in `files` are returned even if they don't exist, so that
+    /// the checker can report them.
+    pub fn root_files(&self, fs: &dyn FileSystem) -> Vec<PathBuf> {
+        files::root_files(fs, self.dir(), &self.config, self.case_sensitive)
+    }
+}
+
+/// Reads the config at `path` and merges the configs it extends.
+///
+/// `stack` contains the configs which extend the config, and is used to
+/// detect cycles.
+fn read_extended(fs: &dyn FileSystem, path: &Path, stack: &mut Vec<PathBuf>) -> Result<TsConfig, Error> {
+    if stack.iter().any(|p| p == path) {
+        bail!("`extends` of `{}` is circular", path.display());
+    }
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut config = TsConfig::read(fs, path)?;
+    config.compiler_options.make_paths_absolute(dir);
+
+    let extends = match config.extends.take() {
+        Some(extends) => extends,
+        None => return Ok(config),
+    };
+
+    stack.push(path.to_path_buf());
+    // Later configs override earlier configs, so they are inherited first.
+    for specifier in extends.specifiers().iter().rev() {
+        let base_path =
+            resolve_extends(fs, dir, specifier).with_context(|| format!("failed to resolve `extends` of `{}`", path.display()))?;
+        let base = read_extended(fs, &base_path, stack)?;
+
+        config.inherit(base, base_path.parent().unwrap_or_else(|| Path::new("")));
+    }
+    stack.pop();
+
+    Ok(config)
+}
+
+fn resolve_extends(fs: &dyn FileSystem, dir: &Path, specifier: &str) -> Result<PathBuf, Error> {
+    let with_json = |path: PathBuf| {
+        if fs.is_file(&path) {
+            return Some(path);
+        }
+        let path = PathBuf::from(format!("{}.json", path.display()));
+        if fs.is_file(&path) {
+            return Some(path);
+        }
+        None
+    };
+
+    if specifier.starts_with("./") || specifier.starts_with("../") || Path::new(specifier).is_absolute() {
+        return with_json(dir.join(specifier).clean()).ok_or_else(|| anyhow!("`{}` does not exist", specifier));
+    }
+
+    for dir in dir.ancestors() {
+        let pkg = dir.join("node_modules").join(specifier);
+        if let Some(path) = with_json(pkg.clone()) {
+            return Ok(path);
+        }
+
+        if fs.is_dir(&pkg) {
+            // A package can specify its config using the `tsconfig` field.
+            let field = fs
+                .read_to_string(&pkg.join("package.json"))
+                .ok()
+                .and_then(|content| serde_json::from_str::<PackageJson>(&content).ok())
+                .and_then(|pkg| pkg.tsconfig);
+            if let Some(path) = field.and_then(|field| with_json(pkg.join(field).clean())) {
+                return Ok(path);
+            }
+            if let Some(path) = with_json(pkg.join("tsconfig.json")) {
+                return Ok(path);
+            }
+        }
+    }
+
+    bail!("package `{}` is not found", specifier)
+}
+
+#[derive(Deserialize)]
+struct PackageJson {
+    #[serde(default)]
+    tsconfig: Option<String>,
+}
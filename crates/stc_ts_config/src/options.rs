@@ -0,0 +1,442 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Error};
+use path_clean::PathClean;
+use serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, JsxMode, ModuleConfig, ModuleResolution, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use swc_ecma_ast::EsVersion;
+
+/// `compilerOptions` of `tsconfig.json`.
+///
+/// Options which don't affect type checking, like `sourceMap`, are ignored,
+/// and they are stored in `unsupported`. Values of enum options are not
+/// validated until they are converted.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilerOptions {
+    pub target: Option<String>,
+    pub module: Option<String>,
+    pub module_resolution: Option<String>,
+    pub lib: Option<Vec<String>>,
+    pub no_lib: Option<bool>,
+    pub jsx: Option<String>,
+
+    pub strict: Option<bool>,
+    pub no_implicit_any: Option<bool>,
+    pub no_implicit_this: Option<bool>,
+    pub always_strict: Option<bool>,
+    pub strict_null_checks: Option<bool>,
+    pub strict_function_types: Option<bool>,
+
+    pub allow_unreachable_code: Option<bool>,
+    pub allow_unused_labels: Option<bool>,
+    pub no_fallthrough_cases_in_switch: Option<bool>,
+    pub no_implicit_returns: Option<bool>,
+    pub suppress_excess_property_errors: Option<bool>,
+    pub suppress_implicit_any_index_errors: Option<bool>,
+    pub no_strict_generic_checks: Option<bool>,
+    pub no_unused_locals: Option<bool>,
+    pub no_unused_parameters: Option<bool>,
+    pub use_define_for_class_fields: Option<bool>,
+
+    pub allow_js: Option<bool>,
+    pub check_js: Option<bool>,
+    pub skip_lib_check: Option<bool>,
+    pub isolated_modules: Option<bool>,
+
+    pub base_url: Option<PathBuf>,
+    pub paths: Option<Paths>,
+    pub root_dirs: Option<Vec<PathBuf>>,
+    pub module_suffixes: Option<Vec<String>>,
+    pub preserve_symlinks: Option<bool>,
+    pub type_roots: Option<Vec<PathBuf>>,
+    pub types: Option<Vec<String>>,
+
+    pub no_emit: Option<bool>,
+    pub no_emit_on_error: Option<bool>,
+    pub composite: Option<bool>,
+    pub declaration: Option<bool>,
+    pub emit_declaration_only: Option<bool>,
+    pub root_dir: Option<PathBuf>,
+    pub out_dir: Option<PathBuf>,
+    pub declaration_dir: Option<PathBuf>,
+
+    pub incremental: Option<bool>,
+    pub ts_build_info_file: Option<PathBuf>,
+
+    /// Options which are not supported by stc, keyed by the name.
+    #[serde(flatten)]
+    pub unsupported: BTreeMap<String, serde_json::Value>,
+}
+
+/// The `stc` section of `tsconfig.json`, which contains options only
+/// supported by stc.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StcOptions {
+    /// Report imports which form a cycle.
+    pub no_circular_imports: Option<bool>,
+    /// Report exports which are not imported by any checked file.
+    pub no_unused_exports: Option<bool>,
+    /// The check succeeds if the number of errors does not exceed this, which
+    /// helps adopting stc in a large codebase gradually.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_baseline: Option<usize>,
+    /// At most this number of errors and warnings are reported, and a summary
+    /// of the rest is reported instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_errors: Option<usize>,
+    /// Like [StcOptions::max_errors], but for the errors and warnings of each
+    /// file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_errors_per_file: Option<usize>,
+    /// Severities of error codes, like `{ "TS2322": "warning" }`, which can be
+    /// `error`, `warning`, `info` or `off`. See [Project::severity_overrides].
+    ///
+    /// [Project::severity_overrides]: crate::Project::severity_overrides
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+}
+
+/// `compilerOptions.paths`, in the order of declaration.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Paths(pub Vec<(String, Vec<String>)>);
+
+impl<'de> Deserialize<'de> for Paths {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PathsVisitor;
+
+        impl<'de> Visitor<'de> for PathsVisitor {
+            type Value = Paths;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map from patterns to lists of paths")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut paths = vec![];
+                while let Some(entry) = map.next_entry()? {
+                    paths.push(entry);
+                }
+                Ok(Paths(paths))
+            }
+        }
+
+        deserializer.deserialize_map(PathsVisitor)
+    }
+}
+
+impl CompilerOptions {
+    /// `ES5` is used if `target` is not specified.
+    pub fn target(&self) -> Result<EsVersion, Error> {
+        let target = match &self.target {
+            Some(target) => target.to_ascii_lowercase(),
+            None => return Ok(EsVersion::Es5),
+        };
+
+        Ok(match &*target {
+            "es3" => EsVersion::Es3,
+            "es5" => EsVersion::Es5,
+            "es6" | "es2015" => EsVersion::Es2015,
+            "es2016" => EsVersion::Es2016,
+            "es2017" => EsVersion::Es2017,
+            "es2018" => EsVersion::Es2018,
+            "es2019" => EsVersion::Es2019,
+            "es2020" => EsVersion::Es2020,
+            "es2021" => EsVersion::Es2021,
+            "es2022" => EsVersion::Es2022,
+            "esnext" => EsVersion::EsNext,
+            _ => bail!("unknown target `{}`", target),
+        })
+    }
+
+    /// If `module` is not specified, `commonjs` is used for `ES3` and `ES5`,
+    /// and `es2015` is used for other targets.
+    pub fn module(&self) -> Result<ModuleConfig, Error> {
+        match &self.module {
+            Some(module) => match module.to_ascii_lowercase().parse() {
+                Ok(module) => Ok(module),
+                Err(..) => bail!("unknown module `{}`", module),
+            },
+            None => Ok(match self.target()? {
+                EsVersion::Es3 | EsVersion::Es5 => ModuleConfig::CommonJs,
+                _ => ModuleConfig::Es2015,
+            }),
+        }
+    }
+
+    /// Falls back to the default of `module`. `node10` is an alias of `node`.
+    pub fn module_resolution(&self) -> Result<ModuleResolution, Error> {
+        match &self.module_resolution {
+            Some(v) => match &*v.to_ascii_lowercase() {
+                "node10" => Ok(ModuleResolution::Node),
+                lower => match lower.parse() {
+                    Ok(v) => Ok(v),
+                    Err(..) => bail!("unsupported moduleResolution `{}`", v),
+                },
+            },
+            None => Ok(self.module()?.module_resolution()),
+        }
+    }
+
+    /// The builtin libraries to load.
+    ///
+    /// If `lib` is not specified, the default libraries of `target` are used.
+    /// No library is loaded if `noLib` is enabled.
+    pub fn libs(&self) -> Result<Vec<Lib>, Error> {
+        if self.no_lib.unwrap_or_default() {
+            return Ok(vec![]);
+        }
+
+        let names = match &self.lib {
+            Some(names) => names.iter().map(|name| name.to_ascii_lowercase()).collect(),
+            None => vec![match self.target()? {
+                EsVersion::Es3 | EsVersion::Es5 => "es5.full",
+                EsVersion::Es2015 => "es2015.full",
+                EsVersion::Es2016 => "es2016.full",
+                EsVersion::Es2017 => "es2017.full",
+                EsVersion::Es2018 => "es2018.full",
+                EsVersion::Es2019 => "es2019.full",
+                EsVersion::Es2020 => "es2020.full",
+                EsVersion::Es2021 => "es2021.full",
+                EsVersion::Es2022 => "es2022.full",
+                _ => "esnext.full",
+            }
+            .to_string()],
+        };
+
+        let mut libs = vec![];
+        for name in names {
+            // Libraries of hosts are generated, and the names of the files differ.
+            let file = match &*name {
+                "es6" => "es2015",
+                "es7" => "es2016",
+                "dom" => "dom.generated",
+                "dom.iterable" => "dom.iterable.generated",
+                "webworker" => "webworker.generated",
+                "webworker.iterable" => "webworker.iterable.generated",
+                name => name,
+            };
+
+            let loaded = Lib::load(file);
+            if loaded.is_empty() {
+                bail!("unknown lib `{}`", name);
+            }
+            libs.extend(loaded);
+        }
+        libs.sort();
+        libs.dedup();
+
+        Ok(libs)
+    }
+
+    /// Options of `strict` are enabled by `strict`, unless they are disabled
+    /// explicitly.
+    pub fn rule(&self) -> Result<Rule, Error> {
+        let strict = self.strict.unwrap_or_default();
+        let flag = |v: Option<bool>| v.unwrap_or_default();
+
+        let jsx = match &self.jsx {
+            Some(jsx) => match jsx.to_ascii_lowercase().parse::<JsxMode>() {
+                Ok(jsx) => jsx,
+                Err(..) => bail!("unknown jsx `{}`", jsx),
+            },
+            None => Default::default(),
+        };
+
+        Ok(Rule {
+            no_implicit_any: self.no_implicit_any.unwrap_or(strict),
+            no_implicit_this: self.no_implicit_this.unwrap_or(strict),
+            always_strict: self.always_strict.unwrap_or(strict),
+            strict_null_checks: self.strict_null_checks.unwrap_or(strict),
+            strict_function_types: self.strict_function_types.unwrap_or(strict),
+
+            allow_unreachable_code: flag(self.allow_unreachable_code),
+            allow_unused_labels: flag(self.allow_unused_labels),
+            no_fallthrough_cases_in_switch: flag(self.no_fallthrough_cases_in_switch),
+            no_implicit_returns: flag(self.no_implicit_returns),
+            suppress_excess_property_errors: flag(self.suppress_excess_property_errors),
+            suppress_implicit_any_index_errors: flag(self.suppress_implicit_any_index_errors),
+            no_strict_generic_checks: flag(self.no_strict_generic_checks),
+            no_unused_locals: flag(self.no_unused_locals),
+            no_unused_parameters: flag(self.no_unused_parameters),
+            use_define_property_for_class_fields: self
+                .use_define_for_class_fields
+                .unwrap_or(matches!(self.target()?, EsVersion::Es2022 | EsVersion::EsNext)),
+
+            allow_js: flag(self.allow_js) || flag(self.check_js),
+            check_js: flag(self.check_js),
+            skip_lib_check: flag(self.skip_lib_check),
+            isolated_modules: flag(self.isolated_modules),
+
+            jsx,
+            ..Default::default()
+        })
+    }
+
+    pub fn env(&self) -> Result<Env, Error> {
+        Ok(Env::simple(self.rule()?, self.target()?, self.module()?, &self.libs()?))
+    }
+
+    /// Uses the options of `base` for options which are not specified.
+    ///
+    /// Relative paths of `base` should be made absolute first using
+    /// [CompilerOptions::make_paths_absolute], because they are relative to
+    /// the config of `base`.
+    pub(crate) fn inherit(&mut self, base: CompilerOptions) {
+        // Destructured to make sure that new options are inherited too.
+        let CompilerOptions {
+            target,
+            module,
+            module_resolution,
+            lib,
+            no_lib,
+            jsx,
+            strict,
+            no_implicit_any,
+            no_implicit_this,
+            always_strict,
+            strict_null_checks,
+            strict_function_types,
+            allow_unreachable_code,
+            allow_unused_labels,
+            no_fallthrough_cases_in_switch,
+            no_implicit_returns,
+            suppress_excess_property_errors,
+            suppress_implicit_any_index_errors,
+            no_strict_generic_checks,
+            no_unused_locals,
+            no_unused_parameters,
+            use_define_for_class_fields,
+            allow_js,
+            check_js,
+            skip_lib_check,
+            isolated_modules,
+            base_url,
+            paths,
+            root_dirs,
+            module_suffixes,
+            preserve_symlinks,
+            type_roots,
+            types,
+            no_emit,
+            no_emit_on_error,
+            composite,
+            declaration,
+            emit_declaration_only,
+            root_dir,
+            out_dir,
+            declaration_dir,
+            incremental,
+            ts_build_info_file,
+            unsupported,
+        } = base;
+
+        macro_rules! inherit {
+            ($($field:ident),*) => {
+                $(
+                    if self.$field.is_none() {
+                        self.$field = $field;
+                    }
+                )*
+            };
+        }
+
+        inherit!(
+            target,
+            module,
+            module_resolution,
+            lib,
+            no_lib,
+            jsx,
+            strict,
+            no_implicit_any,
+            no_implicit_this,
+            always_strict,
+            strict_null_checks,
+            strict_function_types,
+            allow_unreachable_code,
+            allow_unused_labels,
+            no_fallthrough_cases_in_switch,
+            no_implicit_returns,
+            suppress_excess_property_errors,
+            suppress_implicit_any_index_errors,
+            no_strict_generic_checks,
+            no_unused_locals,
+            no_unused_parameters,
+            use_define_for_class_fields,
+            allow_js,
+            check_js,
+            skip_lib_check,
+            isolated_modules,
+            base_url,
+            paths,
+            root_dirs,
+            module_suffixes,
+            preserve_symlinks,
+            type_roots,
+            types,
+            no_emit,
+            no_emit_on_error,
+            composite,
+            declaration,
+            emit_declaration_only,
+            root_dir,
+            out_dir,
+            declaration_dir,
+            incremental,
+            ts_build_info_file
+        );
+
+        for (name, value) in unsupported {
+            self.unsupported.entry(name).or_insert(value);
+        }
+    }
+
+    /// Resolves relative paths of the options from `dir`, which is the
+    /// directory containing the config.
+    ///
+    /// The targets of `paths` are resolved too if `baseUrl` is not specified,
+    /// because they are relative to the config which specifies `paths` in
+    /// that case.
+    pub(crate) fn make_paths_absolute(&mut self, dir: &Path) {
+        let paths = [
+            &mut self.base_url,
+            &mut self.root_dir,
+            &mut self.out_dir,
+            &mut self.declaration_dir,
+            &mut self.ts_build_info_file,
+        ];
+        for path in paths.into_iter().flatten() {
+            *path = dir.join(&*path).clean();
+        }
+        for dirs in [&mut self.root_dirs, &mut self.type_roots].into_iter().flatten() {
+            for path in dirs {
+                *path = dir.join(&*path).clean();
+            }
+        }
+
+        if let (None, Some(paths)) = (&self.base_url, &mut self.paths) {
+            for (_, targets) in &mut paths.0 {
+                for target in targets {
+                    *target = dir.join(&*target).clean().to_string_lossy().into_owned();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Options of `tsc` which are not implemented by stc, so that the differences
+//! from `tsc` are reported instead of being silent.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::CompilerOptions;
+
+/// An option in `compilerOptions` which stc doesn't implement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsupportedOption {
+    /// The name in `tsconfig.json`, like `exactOptionalPropertyTypes`.
+    pub name: String,
+    pub handling: Handling,
+    /// How the result differs from `tsc`.
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Handling {
+    /// The option has no effect.
+    Ignored,
+    /// The behavior of the option is implemented partially.
+    Approximated,
+}
+
+impl fmt::Display for UnsupportedOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let handling = match self.handling {
+            Handling::Ignored => "ignored",
+            Handling::Approximated => "approximated",
+        };
+
+        write!(f, "`{}` is {} by stc: {}", self.name, handling, self.message)
+    }
+}
+
+/// Options which don't affect the errors or the outputs, like the options of
+/// the output of `tsc` itself.
+const NO_EFFECT: &[&str] = &[
+    "assumeChangesOnlyAffectDirectDependencies",
+    "diagnostics",
+    "disableSizeLimit",
+    "explainFiles",
+    "extendedDiagnostics",
+    "forceConsistentCasingInFileNames",
+    "generateCpuProfile",
+    "generateTrace",
+    "listEmittedFiles",
+    "listFiles",
+    "newLine",
+    "noErrorTruncation",
+    "preserveWatchOutput",
+    "pretty",
+    "traceResolution",
+];
+
+/// Options of `strict` which are enabled by it. Disabling them is not the
+/// default if `strict` is enabled, so they are reported even if they are
+/// `false`.
+const STRICT: &[&str] = &["strictBindCallApply", "strictPropertyInitialization", "useUnknownInCatchVariables"];
+
+/// Known options and how the result differs from `tsc`. Other options are
+/// reported as ignored with a generic message.
+const KNOWN: &[(&str, Handling, &str)] = &[
+    (
+        "allowSyntheticDefaultImports",
+        Handling::Ignored,
+        "errors of default imports of modules without a default export may differ from tsc",
+    ),
+    (
+        "emitDecoratorMetadata",
+        Handling::Ignored,
+        "metadata of decorators is not written to the javascript outputs",
+    ),
+    (
+        "esModuleInterop",
+        Handling::Ignored,
+        "errors of default and namespace imports of CommonJS modules may differ from tsc",
+    ),
+    (
+        "exactOptionalPropertyTypes",
+        Handling::Ignored,
+        "`undefined` is assignable to optional properties, like when the option is disabled",
+    ),
+    (
+        "experimentalDecorators",
+        Handling::Ignored,
+        "errors of legacy decorators may differ from tsc",
+    ),
+    (
+        "moduleDetection",
+        Handling::Approximated,
+        "files with imports or exports are modules, and other files are scripts, like `auto` without `package.json`",
+    ),
+    (
+        "noImplicitOverride",
+        Handling::Ignored,
+        "methods overriding a method of the base class without `override` are not reported",
+    ),
+    (
+        "noPropertyAccessFromIndexSignature",
+        Handling::Ignored,
+        "properties of index signatures accessed with `.` are not reported",
+    ),
+    (
+        "noUncheckedIndexedAccess",
+        Handling::Ignored,
+        "`undefined` is not added to the types of index signatures, like when the option is disabled",
+    ),
+    (
+        "outFile",
+        Handling::Ignored,
+        "a javascript file is written for each source file. Use a bundler to concatenate them",
+    ),
+    (
+        "resolveJsonModule",
+        Handling::Approximated,
+        "json files are loaded only if they are imported with `with { type: \"json\" }`",
+    ),
+    (
+        "sourceMap",
+        Handling::Ignored,
+        "source maps are not written. Use another compiler to emit them",
+    ),
+    (
+        "strictBindCallApply",
+        Handling::Ignored,
+        "the arguments of `bind`, `call` and `apply` are checked like when the option is disabled",
+    ),
+    (
+        "strictPropertyInitialization",
+        Handling::Ignored,
+        "errors of properties not assigned in the constructor may differ from tsc",
+    ),
+    (
+        "useUnknownInCatchVariables",
+        Handling::Ignored,
+        "the types of the variables of `catch` clauses may differ from tsc",
+    ),
+    (
+        "verbatimModuleSyntax",
+        Handling::Ignored,
+        "imports of types without `import type` are not reported. `isolatedModules` reports some of them",
+    ),
+];
+
+impl CompilerOptions {
+    /// Returns the options in `unsupported` which make the result differ from
+    /// `tsc`, sorted by the name.
+    ///
+    /// Options which don't affect the result, like `pretty`, are not returned.
+    /// Boolean options set to `false` are not returned either, because it's
+    /// their default, except the options enabled by `strict`.
+    pub fn unsupported_options(&self) -> Vec<UnsupportedOption> {
+        self.unsupported
+            .iter()
+            .filter(|(name, _)| !NO_EFFECT.contains(&name.as_str()))
+            .filter(|(name, value)| value.as_bool() != Some(false) || STRICT.contains(&name.as_str()))
+            .map(|(name, _)| match KNOWN.iter().find(|(known, ..)| *known == name.as_str()) {
+                Some((_, handling, message)) => UnsupportedOption {
+                    name: name.clone(),
+                    handling: *handling,
+                    message: message.to_string(),
+                },
+                None => UnsupportedOption {
+                    name: name.clone(),
+                    handling: Handling::Ignored,
+                    message: "the option is not implemented yet, so the result may differ from tsc".into(),
+                },
+            })
+            .collect()
+    }
+}
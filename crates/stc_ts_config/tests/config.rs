@@ -0,0 +1,407 @@
+use std::path::{Path, PathBuf};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_config::{build_order, filter_files, Handling, Project, TsConfig};
+use stc_ts_env::{ModuleConfig, ModuleResolution};
+use stc_ts_errors::{Severity, SeverityOverrides};
+use stc_ts_module_loader::fs::MemoryFs;
+use swc_ecma_ast::EsVersion;
+
+#[test]
+fn parse_with_comments() {
+    let config = TsConfig::parse(
+        r#"{
+    // Comments are allowed.
+    "compilerOptions": {
+        /* "target": "es5", */
+        "target": "ES2020",
+        "lib": ["ES2020", "DOM"],
+        "strict": true,
+        "strictNullChecks": false,
+        "baseUrl": "./src",
+        "paths": {
+            "@app/*": ["app/*"],
+            "*": ["types/*", "*"],
+        },
+    },
+    "include": ["src/**/*"],
+}"#,
+    )
+    .unwrap();
+    let options = &config.compiler_options;
+
+    assert_eq!(options.target().unwrap(), EsVersion::Es2020);
+    assert!(matches!(options.module().unwrap(), ModuleConfig::Es2015));
+    assert!(options.module_resolution().unwrap() == ModuleResolution::Classic);
+
+    let libs = options.libs().unwrap();
+    assert!(libs.contains(&Lib::Es2020Promise));
+    assert!(libs.contains(&Lib::Dom));
+
+    let rule = options.rule().unwrap();
+    assert!(rule.no_implicit_any);
+    assert!(!rule.strict_null_checks);
+
+    assert_eq!(
+        options.paths.clone().unwrap().0,
+        vec![
+            ("@app/*".to_string(), vec!["app/*".to_string()]),
+            ("*".to_string(), vec!["types/*".to_string(), "*".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn defaults() {
+    let options = TsConfig::parse("{}").unwrap().compiler_options;
+
+    assert_eq!(options.target().unwrap(), EsVersion::Es5);
+    assert!(matches!(options.module().unwrap(), ModuleConfig::CommonJs));
+    assert!(options.module_resolution().unwrap() == ModuleResolution::Node);
+    assert!(options.libs().unwrap().contains(&Lib::Es5Full));
+    assert!(!options.rule().unwrap().no_implicit_any);
+}
+
+#[test]
+fn invalid_options() {
+    let options = TsConfig::parse(r#"{ "compilerOptions": { "target": "es1999", "lib": ["nope"] } }"#)
+        .unwrap()
+        .compiler_options;
+
+    assert!(options.target().is_err());
+    assert!(options.libs().is_err());
+}
+
+#[test]
+fn root_files() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "compilerOptions": { "allowJs": true }, "exclude": ["src/**/*.test.ts", "node_modules"] }"#,
+    );
+    fs.insert(PathBuf::from("/project/src/index.ts"), "");
+    fs.insert(PathBuf::from("/project/src/index.test.ts"), "");
+    fs.insert(PathBuf::from("/project/src/util/a.js"), "");
+    fs.insert(PathBuf::from("/project/src/README.md"), "");
+    fs.insert(PathBuf::from("/project/node_modules/pkg/index.d.ts"), "");
+
+    let project = Project::load(&fs, Path::new("/project/tsconfig.json")).unwrap();
+    assert_eq!(project.dir(), Path::new("/project"));
+    assert_eq!(
+        project.root_files(&fs),
+        vec![PathBuf::from("/project/src/index.ts"), PathBuf::from("/project/src/util/a.js")]
+    );
+}
+
+#[test]
+fn files_and_include() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "files": ["main.ts"], "include": ["lib"] }"#,
+    );
+    fs.insert(PathBuf::from("/project/main.ts"), "");
+    fs.insert(PathBuf::from("/project/other.ts"), "");
+    fs.insert(PathBuf::from("/project/lib/a.ts"), "");
+    fs.insert(PathBuf::from("/project/lib/nested/b.d.ts"), "");
+
+    let project = Project::load(&fs, Path::new("/project/tsconfig.json")).unwrap();
+    assert_eq!(
+        project.root_files(&fs),
+        vec![
+            PathBuf::from("/project/main.ts"),
+            PathBuf::from("/project/lib/a.ts"),
+            PathBuf::from("/project/lib/nested/b.d.ts"),
+        ]
+    );
+}
+
+#[test]
+fn extends() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/project/node_modules/@tsconfig/node18/tsconfig.json"),
+        r#"{ "compilerOptions": { "target": "es2022", "strict": true, "lib": ["es2023"] } }"#,
+    );
+    fs.insert(
+        PathBuf::from("/project/configs/base.json"),
+        r#"{
+    "extends": "@tsconfig/node18/tsconfig.json",
+    "compilerOptions": { "strict": false, "baseUrl": "..", "typeRoots": ["./types"] },
+    "include": ["../src"]
+}"#,
+    );
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "extends": "./configs/base", "compilerOptions": { "noImplicitAny": true } }"#,
+    );
+    fs.insert(PathBuf::from("/project/src/index.ts"), "");
+    fs.insert(PathBuf::from("/project/other/index.ts"), "");
+
+    // A directory is resolved to `tsconfig.json` in it.
+    let project = Project::load(&fs, Path::new("/project")).unwrap();
+    assert_eq!(project.path, PathBuf::from("/project/tsconfig.json"));
+
+    let options = project.options();
+    assert_eq!(options.target().unwrap(), EsVersion::Es2022);
+    assert!(options.libs().unwrap().contains(&Lib::Es2023Array));
+    assert_eq!(options.base_url, Some(PathBuf::from("/project")));
+    assert_eq!(options.type_roots, Some(vec![PathBuf::from("/project/configs/types")]));
+
+    let rule = options.rule().unwrap();
+    assert!(!rule.strict_null_checks);
+    assert!(rule.no_implicit_any);
+
+    // `include` is relative to the config which specifies it.
+    assert_eq!(project.root_files(&fs), vec![PathBuf::from("/project/src/index.ts")]);
+}
+
+#[test]
+fn extends_package() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/node_modules/preset/package.json"),
+        r#"{ "name": "preset", "tsconfig": "./configs/strict.json" }"#,
+    );
+    fs.insert(
+        PathBuf::from("/node_modules/preset/configs/strict.json"),
+        r#"{ "compilerOptions": { "strict": true } }"#,
+    );
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "extends": ["preset", "./override.json"] }"#,
+    );
+    fs.insert(
+        PathBuf::from("/project/override.json"),
+        r#"{ "compilerOptions": { "strictNullChecks": false } }"#,
+    );
+
+    let project = Project::load(&fs, Path::new("/project/tsconfig.json")).unwrap();
+    let rule = project.options().rule().unwrap();
+    assert!(rule.no_implicit_any);
+    assert!(!rule.strict_null_checks);
+}
+
+#[test]
+fn circular_extends() {
+    let fs = MemoryFs::new();
+    fs.insert(PathBuf::from("/project/tsconfig.json"), r#"{ "extends": "./a.json" }"#);
+    fs.insert(PathBuf::from("/project/a.json"), r#"{ "extends": "./tsconfig.json" }"#);
+
+    let err = Project::load(&fs, Path::new("/project/tsconfig.json")).unwrap_err();
+    assert!(format!("{:#}", err).contains("circular"));
+}
+
+#[test]
+fn include_semantics() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "compilerOptions": { "outDir": "dist" }, "include": ["src/**", "types/*.d.ts", "lib/.hidden/a.ts"] }"#,
+    );
+    // `a.d.ts` and `a.js` are generated from `a.ts`.
+    fs.insert(PathBuf::from("/project/src/a.ts"), "");
+    fs.insert(PathBuf::from("/project/src/a.d.ts"), "");
+    fs.insert(PathBuf::from("/project/src/a.js"), "");
+    fs.insert(PathBuf::from("/project/src/b.mts"), "");
+    fs.insert(PathBuf::from("/project/src/b.d.ts"), "");
+    fs.insert(PathBuf::from("/project/src/.cache/c.ts"), "");
+    fs.insert(PathBuf::from("/project/src/node_modules/pkg/index.ts"), "");
+    fs.insert(PathBuf::from("/project/types/global.d.ts"), "");
+    fs.insert(PathBuf::from("/project/types/nested/skipped.d.ts"), "");
+    fs.insert(PathBuf::from("/project/lib/.hidden/a.ts"), "");
+    fs.insert(PathBuf::from("/project/dist/index.d.ts"), "");
+
+    let project = Project::load(&fs, Path::new("/project/tsconfig.json")).unwrap();
+    assert_eq!(
+        project.root_files(&fs),
+        vec![
+            PathBuf::from("/project/lib/.hidden/a.ts"),
+            PathBuf::from("/project/src/a.ts"),
+            PathBuf::from("/project/src/b.d.ts"),
+            PathBuf::from("/project/src/b.mts"),
+            PathBuf::from("/project/types/global.d.ts"),
+        ]
+    );
+}
+
+#[test]
+fn out_dir_is_excluded() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "compilerOptions": { "outDir": "./build" } }"#,
+    );
+    fs.insert(PathBuf::from("/project/index.ts"), "");
+    fs.insert(PathBuf::from("/project/build/index.d.ts"), "");
+
+    let project = Project::load(&fs, Path::new("/project/tsconfig.json")).unwrap();
+    assert_eq!(project.root_files(&fs), vec![PathBuf::from("/project/index.ts")]);
+}
+
+#[test]
+fn case_sensitivity() {
+    let fs = MemoryFs::new();
+    fs.insert(PathBuf::from("/project/tsconfig.json"), r#"{ "include": ["src/index*"] }"#);
+    fs.insert(PathBuf::from("/project/src/Index.ts"), "");
+
+    let mut project = Project::load(&fs, Path::new("/project/tsconfig.json")).unwrap();
+
+    project.case_sensitive = true;
+    assert_eq!(project.root_files(&fs), Vec::<PathBuf>::new());
+
+    project.case_sensitive = false;
+    assert_eq!(project.root_files(&fs), vec![PathBuf::from("/project/src/Index.ts")]);
+}
+
+#[test]
+fn project_references() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/repo/tsconfig.json"),
+        r#"{ "files": [], "references": [{ "path": "./app" }, { "path": "./core" }] }"#,
+    );
+    fs.insert(
+        PathBuf::from("/repo/app/tsconfig.json"),
+        r#"{ "compilerOptions": { "composite": true }, "references": [{ "path": "../core/tsconfig.json" }] }"#,
+    );
+    fs.insert(
+        PathBuf::from("/repo/core/tsconfig.json"),
+        r#"{ "compilerOptions": { "composite": true, "rootDir": "src", "outDir": "lib" } }"#,
+    );
+
+    let order = build_order(&fs, Path::new("/repo")).unwrap();
+    assert_eq!(
+        order.iter().map(|project| project.path.clone()).collect::<Vec<_>>(),
+        vec![
+            PathBuf::from("/repo/core/tsconfig.json"),
+            PathBuf::from("/repo/app/tsconfig.json"),
+            PathBuf::from("/repo/tsconfig.json"),
+        ]
+    );
+
+    let core = &order[0];
+    assert_eq!(
+        core.declaration_output(Path::new("/repo/core/src/util/index.ts")),
+        Some(PathBuf::from("/repo/core/lib/util/index.d.ts"))
+    );
+    assert_eq!(core.declaration_output(Path::new("/repo/core/src/types.d.ts")), None);
+    assert_eq!(core.declaration_output(Path::new("/repo/app/index.ts")), None);
+}
+
+#[test]
+fn invalid_project_references() {
+    let fs = MemoryFs::new();
+    fs.insert(PathBuf::from("/repo/a/tsconfig.json"), r#"{ "references": [{ "path": "../b" }] }"#);
+    fs.insert(PathBuf::from("/repo/b/tsconfig.json"), r#"{ "references": [{ "path": "../c" }] }"#);
+    fs.insert(
+        PathBuf::from("/repo/c/tsconfig.json"),
+        r#"{ "compilerOptions": { "composite": true } }"#,
+    );
+
+    let err = build_order(&fs, Path::new("/repo/a")).unwrap_err();
+    assert!(format!("{:#}", err).contains("must have setting \"composite\": true"));
+
+    fs.insert(
+        PathBuf::from("/repo/b/tsconfig.json"),
+        r#"{ "compilerOptions": { "composite": true }, "references": [{ "path": "../c" }] }"#,
+    );
+    fs.insert(
+        PathBuf::from("/repo/c/tsconfig.json"),
+        r#"{ "compilerOptions": { "composite": true }, "references": [{ "path": "../b" }] }"#,
+    );
+
+    let err = build_order(&fs, Path::new("/repo/a")).unwrap_err();
+    assert!(format!("{:#}", err).contains("cycle"));
+}
+
+#[test]
+fn unsupported_options() {
+    let config = TsConfig::parse(
+        r#"{
+    "compilerOptions": {
+        "strict": true,
+        "pretty": true,
+        "exactOptionalPropertyTypes": true,
+        "noImplicitOverride": false,
+        "strictPropertyInitialization": false,
+        "resolveJsonModule": true,
+        "someFutureOption": 1,
+    },
+}"#,
+    )
+    .unwrap();
+
+    let options = config.compiler_options.unsupported_options();
+    assert_eq!(
+        options.iter().map(|option| (&*option.name, option.handling)).collect::<Vec<_>>(),
+        vec![
+            ("exactOptionalPropertyTypes", Handling::Ignored),
+            ("resolveJsonModule", Handling::Approximated),
+            ("someFutureOption", Handling::Ignored),
+            ("strictPropertyInitialization", Handling::Ignored),
+        ]
+    );
+    assert_eq!(
+        options[1].to_string(),
+        "`resolveJsonModule` is approximated by stc: json files are loaded only if they are imported with `with { type: \"json\" }`"
+    );
+}
+
+#[test]
+fn filter_root_files() {
+    let files = vec![
+        PathBuf::from("/project/src/auth/login.ts"),
+        PathBuf::from("/project/src/auth/session.test.ts"),
+        PathBuf::from("/project/src/billing/invoice.ts"),
+        PathBuf::from("src/auth/logout.ts"),
+    ];
+
+    assert_eq!(
+        filter_files(&files, Path::new("/project"), &["src/auth".into()], true),
+        vec![
+            PathBuf::from("/project/src/auth/login.ts"),
+            PathBuf::from("/project/src/auth/session.test.ts"),
+            PathBuf::from("src/auth/logout.ts"),
+        ]
+    );
+    assert_eq!(
+        filter_files(
+            &files,
+            Path::new("/project"),
+            &["src/**/*.test.ts".into(), "/project/src/billing/*".into()],
+            true
+        ),
+        vec![
+            PathBuf::from("/project/src/auth/session.test.ts"),
+            PathBuf::from("/project/src/billing/invoice.ts"),
+        ]
+    );
+    assert_eq!(filter_files(&files, Path::new("/project"), &["SRC/auth".into()], true).len(), 0);
+    assert_eq!(filter_files(&files, Path::new("/project"), &["SRC/auth".into()], false).len(), 3);
+}
+
+#[test]
+fn severity_overrides() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "stc": { "severity": { "TS2322": "warning", "7006": "off" } } }"#,
+    );
+
+    let project = Project::load(&fs, Path::new("/project")).unwrap();
+    assert_eq!(
+        project.severity_overrides().unwrap(),
+        SeverityOverrides::default()
+            .with_code(2322, Some(Severity::Warning))
+            .with_code(7006, None)
+    );
+
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "stc": { "severity": { "TS2322": "fatal" } } }"#,
+    );
+    let project = Project::load(&fs, Path::new("/project")).unwrap();
+    let err = project.severity_overrides().unwrap_err();
+    assert!(format!("{:#}", err).contains("the severity of `TS2322` should be"));
+}
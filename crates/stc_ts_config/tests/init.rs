@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use stc_ts_config::{init, Project, TsConfig};
+use stc_ts_env::JsxMode;
+use stc_ts_module_loader::fs::MemoryFs;
+
+#[test]
+fn typescript_package() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/project/package.json"),
+        r#"{ "type": "module", "main": "./lib/index.js", "types": "./lib/index.d.ts", "dependencies": { "react": "*" } }"#,
+    );
+    fs.insert(PathBuf::from("/project/src/index.ts"), "");
+    fs.insert(PathBuf::from("/project/src/app.tsx"), "");
+    fs.insert(PathBuf::from("/project/lib/index.js"), "");
+
+    let init = init(&fs, Path::new("/project")).unwrap();
+    let config = TsConfig::parse(&init.content).unwrap();
+    let options = &config.compiler_options;
+
+    assert_eq!(options.module.as_deref(), Some("nodenext"));
+    assert_eq!(options.out_dir, Some(PathBuf::from("./lib")));
+    assert_eq!(options.root_dir, Some(PathBuf::from("./src")));
+    assert_eq!(options.declaration, Some(true));
+    assert_eq!(options.allow_js, None);
+    assert!(matches!(options.rule().unwrap().jsx, JsxMode::ReactJsx));
+    assert!(options.rule().unwrap().strict_null_checks);
+    assert_eq!(config.include, Some(vec!["src".to_string()]));
+
+    assert_eq!(
+        init.warnings,
+        vec![
+            "`esModuleInterop` is not supported by stc yet, and it will be ignored".to_string(),
+            "`forceConsistentCasingInFileNames` is not supported by stc yet, and it will be ignored".to_string(),
+        ]
+    );
+
+    // The recommended config can be loaded.
+    fs.insert(PathBuf::from("/project/tsconfig.json"), init.content);
+    let project = Project::load(&fs, Path::new("/project")).unwrap();
+    assert_eq!(
+        project.root_files(&fs),
+        vec![PathBuf::from("/project/src/app.tsx"), PathBuf::from("/project/src/index.ts")]
+    );
+}
+
+#[test]
+fn javascript_project() {
+    let fs = MemoryFs::new();
+    fs.insert(PathBuf::from("/project/index.js"), "");
+    fs.insert(PathBuf::from("/project/util/a.js"), "");
+    fs.insert(PathBuf::from("/project/node_modules/pkg/index.ts"), "");
+
+    let init = init(&fs, Path::new("/project")).unwrap();
+    let options = TsConfig::parse(&init.content).unwrap().compiler_options;
+
+    assert_eq!(options.module.as_deref(), Some("commonjs"));
+    assert_eq!(options.allow_js, Some(true));
+    assert_eq!(options.check_js, Some(true));
+    assert_eq!(options.root_dir, None);
+    assert_eq!(options.jsx, None);
+}
+
+#[test]
+fn stc_options() {
+    let fs = MemoryFs::new();
+    fs.insert(
+        PathBuf::from("/project/base.json"),
+        r#"{ "stc": { "noCircularImports": true, "errorBaseline": 10, "maxErrorsPerFile": 50 } }"#,
+    );
+    fs.insert(
+        PathBuf::from("/project/tsconfig.json"),
+        r#"{ "extends": "./base.json", "compilerOptions": { "sourceMap": true } }"#,
+    );
+
+    let project = Project::load(&fs, Path::new("/project")).unwrap();
+    let rule = project.rule().unwrap();
+    assert!(rule.no_circular_imports);
+    assert!(!rule.no_unused_exports);
+    assert_eq!(project.config.stc.as_ref().unwrap().error_baseline, Some(10));
+    assert_eq!(project.config.stc.as_ref().unwrap().max_errors_per_file, Some(50));
+
+    assert_eq!(project.options().unsupported.keys().collect::<Vec<_>>(), vec!["sourceMap"]);
+}
@@ -3,7 +3,7 @@ use std::{str::FromStr, sync::Arc};
 use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
-use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_errors::{Error, ErrorKind, SeverityOverrides};
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{Id, Type};
 use stc_utils::{cache::Freeze, dev_span};
@@ -38,6 +38,9 @@ pub struct Env {
     builtin: Arc<BuiltIn>,
     global_types: Arc<Mutex<FxHashMap<JsWord, Type>>>,
     global_vars: Arc<Mutex<FxHashMap<JsWord, Type>>>,
+    /// Declared by `export as namespace` in declaration files.
+    umd_globals: Arc<Mutex<FxHashMap<JsWord, Type>>>,
+    severity_overrides: Arc<SeverityOverrides>,
 }
 
 impl Env {
@@ -49,10 +52,23 @@ impl Env {
             module,
             global_types: Default::default(),
             global_vars: Default::default(),
+            umd_globals: Default::default(),
             rule,
+            severity_overrides: Default::default(),
         }
     }
 
+    /// Reports errors with the severities of `overrides` instead of the
+    /// default severities.
+    pub fn with_severity_overrides(mut self, overrides: SeverityOverrides) -> Self {
+        self.severity_overrides = Arc::new(overrides);
+        self
+    }
+
+    pub fn severity_overrides(&self) -> &SeverityOverrides {
+        &self.severity_overrides
+    }
+
     pub const fn shared(&self) -> &StableEnv {
         &self.stable
     }
@@ -77,6 +93,37 @@ impl Env {
         // name);
     }
 
+    /// Declares `name` as a global variable for `export as namespace name`.
+    ///
+    /// Unlike [Env::declare_global_var], this does not override other globals
+    /// with the same name.
+    pub fn declare_umd_global(&mut self, name: JsWord, ty: Type) {
+        ty.assert_clone_cheap();
+
+        self.umd_globals.lock().entry(name).or_insert(ty);
+    }
+
+    /// Returns `true` if `name` refers to a global declared by
+    /// [Env::declare_umd_global].
+    pub fn is_umd_global(&self, name: &JsWord) -> bool {
+        !self.global_vars.lock().contains_key(name) && !self.builtin.vars.contains_key(name) && self.umd_globals.lock().contains_key(name)
+    }
+
+    /// Declares the variables and types in `globals`, like an ambient
+    /// declaration file.
+    ///
+    /// `BuiltInGen::from_source` of `stc_ts_file_analyzer` creates them from
+    /// declarations like `declare const __DEV__: boolean;`.
+    pub fn declare_globals(&mut self, globals: &BuiltIn) {
+        for (name, ty) in &globals.vars {
+            self.declare_global_var(name.clone(), ty.clone());
+        }
+
+        for (name, ty) in &globals.types {
+            self.declare_global_type(name.clone(), ty.clone());
+        }
+    }
+
     pub fn declare_global_type(&mut self, name: JsWord, ty: Type) {
         ty.assert_clone_cheap();
 
@@ -105,6 +152,10 @@ impl Env {
             return Ok(v.clone());
         }
 
+        if let Some(ty) = self.umd_globals.lock().get(name) {
+            return Ok((*ty).clone());
+        }
+
         Err(ErrorKind::NoSuchVar {
             span,
             name: Id::word(name.clone()),
@@ -112,6 +163,27 @@ impl Env {
         .into())
     }
 
+    /// Returns the names of the global variables, including the variables
+    /// declared by the builtin libraries, sorted by the name.
+    pub fn global_var_names(&self) -> Vec<JsWord> {
+        let mut names = self.builtin.vars.keys().cloned().collect::<Vec<_>>();
+        names.extend(self.global_vars.lock().keys().cloned());
+        names.extend(self.umd_globals.lock().keys().cloned());
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Returns the names of the global types, including the types declared by
+    /// the builtin libraries, sorted by the name.
+    pub fn global_type_names(&self) -> Vec<JsWord> {
+        let mut names = self.builtin.types.keys().cloned().collect::<Vec<_>>();
+        names.extend(self.global_types.lock().keys().cloned());
+        names.sort();
+        names.dedup();
+        names
+    }
+
     pub fn get_global_type(&self, span: Span, name: &JsWord) -> Result<Type, Error> {
         let _tracing = dev_span!("get_global_type");
 
@@ -180,6 +252,39 @@ pub enum ModuleConfig {
     System,
     /// `esnext`
     EsNext,
+    /// `node16`
+    Node16,
+    /// `nodenext`
+    NodeNext,
+}
+
+impl ModuleConfig {
+    /// The module resolution strategy used if `moduleResolution` is not
+    /// specified.
+    ///
+    /// [ModuleConfig::None] is treated as `commonjs`, because it's used when
+    /// `module` is not specified.
+    pub const fn module_resolution(self) -> ModuleResolution {
+        match self {
+            ModuleConfig::CommonJs | ModuleConfig::None => ModuleResolution::Node,
+            ModuleConfig::Node16 => ModuleResolution::Node16,
+            ModuleConfig::NodeNext => ModuleResolution::NodeNext,
+            _ => ModuleResolution::Classic,
+        }
+    }
+}
+
+/// https://www.typescriptlang.org/tsconfig#moduleResolution
+#[derive(Clone, Copy, PartialEq, Eq, StringEnum)]
+pub enum ModuleResolution {
+    /// `classic`
+    Classic,
+    /// `node`
+    Node,
+    /// `node16`
+    Node16,
+    /// `nodenext`
+    NodeNext,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -201,6 +306,27 @@ pub struct Rule {
     pub no_unused_parameters: bool,
     pub use_define_property_for_class_fields: bool,
 
+    /// Load javascript files.
+    pub allow_js: bool,
+    /// Report errors in javascript files. Exports of javascript files are
+    /// typed as `any` unless this is enabled.
+    pub check_js: bool,
+
+    /// Don't report errors in declaration files. Types declared in them are
+    /// still used.
+    pub skip_lib_check: bool,
+
+    /// Report code which can't be transpiled file by file, like re-exports of
+    /// types without `export type`.
+    pub isolated_modules: bool,
+
+    /// Report imports which form a cycle. This is not an option of tsc.
+    pub no_circular_imports: bool,
+
+    /// Report exports which are not imported by any checked file, except the
+    /// exports of the entries. This is not an option of tsc.
+    pub no_unused_exports: bool,
+
     pub jsx: JsxMode,
 }
 
@@ -0,0 +1,902 @@
+//! Descriptions of error codes, for tools which list the rules of stc.
+
+use std::ops::RangeFrom;
+
+use serde::Serialize;
+
+/// Returns the message of `tsc` for the error code, with placeholders like
+/// `{0}` for the names and types in the message.
+///
+/// Codes should be normalized by [crate::ErrorKind::normalize_error_code].
+pub fn message_template(code: usize) -> Option<&'static str> {
+    Some(match code {
+        1005 => "'{0}' expected.",
+        1109 => "Expression expected.",
+        1308 => "'await' expressions are only allowed within async functions and at the top levels of modules.",
+        2300 => "Duplicate identifier '{0}'.",
+        2304 => "Cannot find name '{0}'.",
+        2305 => "Module '{0}' has no exported member '{1}'.",
+        2306 => "File '{0}' is not a module.",
+        2307 => "Cannot find module '{0}' or its corresponding type declarations.",
+        2314 => "Generic type '{0}' requires {1} type argument(s).",
+        2322 => "Type '{0}' is not assignable to type '{1}'.",
+        2326 => "Types of property '{0}' are incompatible.",
+        2328 => "Types of parameters '{0}' and '{1}' are incompatible.",
+        2339 => "Property '{0}' does not exist on type '{1}'.",
+        2344 => "Type '{0}' does not satisfy the constraint '{1}'.",
+        2345 => "Argument of type '{0}' is not assignable to parameter of type '{1}'.",
+        2349 => "This expression is not callable.",
+        2350 => "Only a void function can be called with the 'new' keyword.",
+        2352 => "Conversion of type '{0}' to type '{1}' may be a mistake because neither type sufficiently overlaps with the other.",
+        2353 => "Object literal may only specify known properties, and '{0}' does not exist in type '{1}'.",
+        2355 => "A function whose declared type is neither 'void' nor 'any' must return a value.",
+        2356 => "An arithmetic operand must be of type 'any', 'number', 'bigint' or an enum type.",
+        2362 => "The left-hand side of an arithmetic operation must be of type 'any', 'number', 'bigint' or an enum type.",
+        2363 => "The right-hand side of an arithmetic operation must be of type 'any', 'number', 'bigint' or an enum type.",
+        2365 => "Operator '{0}' cannot be applied to types '{1}' and '{2}'.",
+        2366 => "Function lacks ending return statement and return type does not include 'undefined'.",
+        2367 => "This comparison appears to be unintentional because the types '{0}' and '{1}' have no overlap.",
+        2377 => "Constructors for derived classes must contain a 'super' call.",
+        2403 => "Subsequent variable declarations must have the same type.",
+        2416 => "Property '{0}' in type '{1}' is not assignable to the same property in base type '{2}'.",
+        2420 => "Class '{0}' incorrectly implements interface '{1}'.",
+        2448 => "Block-scoped variable '{0}' used before its declaration.",
+        2451 => "Cannot redeclare block-scoped variable '{0}'.",
+        2454 => "Variable '{0}' is used before being assigned.",
+        2503 => "Cannot find namespace '{0}'.",
+        2511 => "Cannot create an instance of an abstract class.",
+        2531 => "Object is possibly 'null'.",
+        2532 => "Object is possibly 'undefined'.",
+        2540 => "Cannot assign to '{0}' because it is a read-only property.",
+        2551 => "Property '{0}' does not exist on type '{1}'. Did you mean '{2}'?",
+        2552 => "Cannot find name '{0}'. Did you mean '{1}'?",
+        2554 => "Expected {0} arguments, but got {1}.",
+        2555 => "Expected at least {0} arguments, but got {1}.",
+        2564 => "Property '{0}' has no initializer and is not definitely assigned in the constructor.",
+        2571 => "Object is of type 'unknown'.",
+        2578 => "Unused '@ts-expect-error' directive.",
+        2588 => "Cannot assign to '{0}' because it is a constant.",
+        2683 => "'this' implicitly has type 'any' because it does not have a type annotation.",
+        2688 => "Cannot find type definition file for '{0}'.",
+        2739 => "Type '{0}' is missing the following properties from type '{1}': {2}",
+        2741 => "Property '{0}' is missing in type '{1}' but required in type '{2}'.",
+        2749 => "'{0}' refers to a value, but is being used as a type here.",
+        2769 => "No overload matches this call.",
+        5012 => "Cannot read file '{0}': {1}.",
+        6133 => "'{0}' is declared but its value is never read.",
+        7005 => "Variable '{0}' implicitly has an '{1}' type.",
+        7006 => "Parameter '{0}' implicitly has an '{1}' type.",
+        7027 => "Unreachable code detected.",
+        7028 => "Unused label.",
+        7029 => "Fallthrough case in switch.",
+        7030 => "Not all code paths return a value.",
+        7053 => "Element implicitly has an 'any' type because expression of type '{0}' can't be used to index type '{1}'.",
+        18046 => "'{0}' is of type 'unknown'.",
+        18047 => "'{0}' is possibly 'null'.",
+        18048 => "'{0}' is possibly 'undefined'.",
+        _ => return None,
+    })
+}
+
+/// An error code which stc reports, for generating documentation and the
+/// rules of tools like SARIF viewers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ErrorCode {
+    /// Like `2322`. The rules of stc which are not errors of tsc use the codes
+    /// in [STC_CODES].
+    pub code: usize,
+    /// The name of the [crate::ErrorKind] reporting the code, like
+    /// `AssignFailed`.
+    pub name: &'static str,
+    /// The message of tsc with placeholders if it's known, like
+    /// [message_template], or a brief description of the error.
+    pub description: &'static str,
+    /// `true` if tsc reports the same code for the error.
+    pub tsc: bool,
+}
+
+/// Returns the registered error code of `code`, or of the code normalized by
+/// [crate::ErrorKind::normalize_error_code] if `code` is not registered.
+pub fn error_code(code: usize) -> Option<&'static ErrorCode> {
+    if code == 0 {
+        return None;
+    }
+
+    let find = |code| ERROR_CODES.iter().find(|error_code| error_code.code == code);
+    find(code).or_else(|| find(crate::ErrorKind::normalize_error_code(code)))
+}
+
+const fn tsc(code: usize, name: &'static str, description: &'static str) -> ErrorCode {
+    ErrorCode {
+        code,
+        name,
+        description,
+        tsc: true,
+    }
+}
+
+/// The codes of the rules of stc which are not errors of tsc. tsc does not
+/// use them.
+pub const STC_CODES: RangeFrom<usize> = 100001..;
+
+const fn stc(code: usize, name: &'static str, description: &'static str) -> ErrorCode {
+    ErrorCode {
+        code,
+        name,
+        description,
+        tsc: false,
+    }
+}
+
+/// All error codes stc reports, sorted by the code. Codes reported by
+/// multiple kinds of errors are listed once.
+pub static ERROR_CODES: &[ErrorCode] = &[
+    tsc(1016, "TS1016", "A required parameter cannot follow an optional parameter."),
+    tsc(1063, "TS1063", "An export assignment cannot be used in a namespace."),
+    tsc(1094, "TS1094", "An accessor cannot have type parameters."),
+    tsc(1095, "TS1095", "A 'set' accessor cannot have a return type annotation."),
+    tsc(
+        1166,
+        "TS1166",
+        "A computed property name in a class property declaration must have a simple literal type or a 'unique symbol' type.",
+    ),
+    tsc(
+        1168,
+        "TS1168",
+        "A computed property name in a method overload must refer to an expression whose type is a literal type or a 'unique symbol' type.",
+    ),
+    tsc(
+        1169,
+        "TS1169",
+        "A computed property name in an interface must refer to an expression whose type is a literal type or a 'unique symbol' type.",
+    ),
+    tsc(1183, "TS1183", "An implementation cannot be declared in ambient contexts."),
+    tsc(
+        1205,
+        "ReExportTypeWithIsolatedModules",
+        "A type is re-exported without `export type` while `isolatedModules` is enabled.",
+    ),
+    tsc(
+        1208,
+        "ScriptWithIsolatedModules",
+        "A file is not a module while `isolatedModules` is enabled.",
+    ),
+    tsc(1318, "TS1318", "An abstract method has a body."),
+    tsc(1319, "TS1319", "A default export can only be used in an ECMAScript-style module."),
+    tsc(1345, "TS1345", "An expression of type 'void' cannot be tested for truthiness."),
+    tsc(2300, "DuplicateName", "Duplicate identifier '{0}'."),
+    tsc(
+        2302,
+        "StaticMemberCannotUseTypeParamOfClass",
+        "A static member references a type parameter of its class.",
+    ),
+    tsc(2304, "TypeNotFound", "Cannot find name '{0}'."),
+    tsc(
+        2307,
+        "ModuleNotFound",
+        "Cannot find module '{0}' or its corresponding type declarations.",
+    ),
+    tsc(
+        2309,
+        "ExportEqualsMixedWithOtherExports",
+        "`export =` is used in a module with other exports.",
+    ),
+    tsc(
+        2312,
+        "NotExtendableType",
+        "An interface extends a type which is not an object type.",
+    ),
+    tsc(2315, "NotGeneric", "Type arguments are given to a type which is not generic."),
+    tsc(
+        2320,
+        "InterfaceNotCompatible",
+        "An interface extends types whose members are not compatible.",
+    ),
+    tsc(2322, "AssignFailed", "Type '{0}' is not assignable to type '{1}'."),
+    tsc(2323, "DuplicateExport", "A name is exported more than once."),
+    tsc(
+        2331,
+        "ThisRefToModuleOrNamespace",
+        "`this` is used in the body of a module or a namespace.",
+    ),
+    tsc(2333, "ThisInConstructorParam", "`this` is used in a parameter of a constructor."),
+    tsc(
+        2334,
+        "ThisInStaticPropertyInitializer",
+        "`this` is used in the initializer of a static property.",
+    ),
+    tsc(
+        2335,
+        "SuperInClassWithoutSuper",
+        "`super` is used in a class which does not extend another class.",
+    ),
+    tsc(
+        2337,
+        "SuperInNestedFunction",
+        "`super` is used in a nested function, where it's not available.",
+    ),
+    tsc(2339, "NoSuchProperty", "Property '{0}' does not exist on type '{1}'."),
+    tsc(
+        2340,
+        "SuperCanOnlyAccessPublicAndProtectedMethod",
+        "`super` is used to access a property which is not a public or protected method.",
+    ),
+    tsc(2344, "NotSatisfyConstraint", "Type '{0}' does not satisfy the constraint '{1}'."),
+    tsc(
+        2345,
+        "WrongArgType",
+        "Argument of type '{0}' is not assignable to parameter of type '{1}'.",
+    ),
+    tsc(
+        2347,
+        "AnyTypeUsedAsCalleeWithTypeArgs",
+        "Type arguments are given to a call of a value of type `any`, or of a function which is not generic.",
+    ),
+    tsc(
+        2348,
+        "NoConstructablePropertyWithName",
+        "A value without a call signature is called, but it could be constructed with `new`.",
+    ),
+    tsc(2349, "NoCallSignature", "This expression is not callable."),
+    tsc(
+        2350,
+        "CannotCallWithNewNonVoidFunction",
+        "Only a void function can be called with the 'new' keyword.",
+    ),
+    tsc(
+        2351,
+        "NoNewSignature",
+        "`new` is used with a value which has no construct signature.",
+    ),
+    tsc(
+        2352,
+        "NonOverlappingTypeCast",
+        "Conversion of type '{0}' to type '{1}' may be a mistake because neither type sufficiently overlaps with the other.",
+    ),
+    tsc(
+        2353,
+        "TS2353",
+        "Object literal may only specify known properties, and '{0}' does not exist in type '{1}'.",
+    ),
+    tsc(
+        2355,
+        "ReturnRequired",
+        "A function whose declared type is neither 'void' nor 'any' must return a value.",
+    ),
+    tsc(
+        2356,
+        "TypeInvalidForUpdateArg",
+        "An arithmetic operand must be of type 'any', 'number', 'bigint' or an enum type.",
+    ),
+    tsc(
+        2357,
+        "ExprInvalidForUpdateArg",
+        "The operand of `++` or `--` is not a variable or a property access.",
+    ),
+    tsc(2358, "InvalidLhsInInstanceOf", "The left operand of `instanceof` is not an object."),
+    tsc(
+        2359,
+        "InvalidRhsInInstanceOf",
+        "The right operand of `instanceof` is not callable or constructible.",
+    ),
+    tsc(
+        2360,
+        "InvalidLhsOfInOperator",
+        "The left operand of `in` is not a string, a number or a symbol.",
+    ),
+    tsc(
+        2362,
+        "WrongTypeForLhsOfNumericOperation",
+        "The left-hand side of an arithmetic operation must be of type 'any', 'number', 'bigint' or an enum type.",
+    ),
+    tsc(
+        2363,
+        "WrongTypeForRhsOfNumericOperation",
+        "The right-hand side of an arithmetic operation must be of type 'any', 'number', 'bigint' or an enum type.",
+    ),
+    tsc(
+        2364,
+        "InvalidLhsOfAssign",
+        "The left side of an assignment is not a variable or a property access.",
+    ),
+    tsc(
+        2365,
+        "AssignOpCannotBeApplied",
+        "Operator '{0}' cannot be applied to types '{1}' and '{2}'.",
+    ),
+    tsc(
+        2367,
+        "NoOverlap",
+        "This comparison appears to be unintentional because the types '{0}' and '{1}' have no overlap.",
+    ),
+    tsc(
+        2369,
+        "ParamPropIsNotAllowedInAmbientConstructor",
+        "A parameter property is declared in an ambient constructor.",
+    ),
+    tsc(2370, "TS2370", "A rest parameter must be of an array type."),
+    tsc(
+        2371,
+        "InitializerDisallowedInAmbientContext",
+        "A parameter has an initializer in an ambient context.",
+    ),
+    tsc(2372, "ReferencedInInit", "A variable is referenced in its own initializer."),
+    tsc(
+        2377,
+        "SuperNotCalled",
+        "Constructors for derived classes must contain a 'super' call.",
+    ),
+    tsc(2378, "TS2378", "A 'get' accessor must return a value."),
+    tsc(
+        2386,
+        "OptionalAndNonOptionalMethodPropertyMixed",
+        "Overloads of a method disagree on whether it's optional.",
+    ),
+    tsc(2387, "ShouldBeStaticMethod", "An overload of a static method is not static."),
+    tsc(2388, "ShouldBeInstanceMethod", "An overload of an instance method is static."),
+    tsc(2389, "TS2389", "Function implementation name must be '{0}'."),
+    tsc(
+        2390,
+        "ConstructorImplMissingOrNotFollowedByDecl",
+        "The implementation of a constructor is missing, or it doesn't follow the overloads.",
+    ),
+    tsc(
+        2391,
+        "FnImplMissingOrNotFollowedByDecl",
+        "The implementation of a function is missing, or it doesn't follow the overloads.",
+    ),
+    tsc(
+        2392,
+        "DuplicateConstructor",
+        "A class has more than one constructor implementation.",
+    ),
+    tsc(2393, "DuplicateFnImpl", "A function has more than one implementation."),
+    tsc(
+        2394,
+        "WrongOverloadSignature",
+        "An overload signature is not compatible with the implementation.",
+    ),
+    tsc(
+        2395,
+        "ExportMixedWithLocal",
+        "The declarations of a merged name are not all exported, or all local.",
+    ),
+    tsc(
+        2403,
+        "VarDeclNotCompatible",
+        "Subsequent variable declarations must have the same type.",
+    ),
+    tsc(
+        2404,
+        "TypeAnnOnLhsOfForInLoops",
+        "The variable of a `for...in` loop has a type annotation.",
+    ),
+    tsc(
+        2405,
+        "WrongTypeForLhsOfForInLoop",
+        "The variable of a `for...in` loop is not of type `string` or `any`.",
+    ),
+    tsc(
+        2406,
+        "InvalidExprOfLhsOfForIn",
+        "The left side of a `for...in` loop is not a variable or a property access.",
+    ),
+    tsc(2410, "WithStmtNotSupported", "A `with` statement is used."),
+    tsc(
+        2411,
+        "ClassMemberNotCompatibleWithStringIndexSignature",
+        "A property of a class is not assignable to the index signature of the class.",
+    ),
+    tsc(
+        2414,
+        "InvalidClassName",
+        "A class is named with a reserved type name, like `string`.",
+    ),
+    tsc(
+        2420,
+        "InvalidImplOfInterface",
+        "Class '{0}' incorrectly implements interface '{1}'.",
+    ),
+    tsc(
+        2427,
+        "InvalidInterfaceName",
+        "An interface is named with a reserved type name, like `string`.",
+    ),
+    tsc(
+        2430,
+        "InvalidInterfaceInheritance",
+        "An interface incorrectly extends another interface.",
+    ),
+    tsc(
+        2432,
+        "OnlyOneEnumCanOmitInit",
+        "More than one declaration of a merged enum omits the initializer of the first member.",
+    ),
+    tsc(
+        2447,
+        "TS2447",
+        "The '{0}' operator is not allowed for boolean types. Consider using '{1}' instead.",
+    ),
+    tsc(
+        2448,
+        "BlockScopedVarUsedBeforeInit",
+        "Block-scoped variable '{0}' used before its declaration.",
+    ),
+    tsc(2451, "DuplicateVar", "Cannot redeclare block-scoped variable '{0}'."),
+    tsc(2452, "EnumMemberIdCannotBeNumber", "A member of an enum is named with a number."),
+    tsc(2454, "VarMayNotBeInitialized", "Variable '{0}' is used before being assigned."),
+    tsc(
+        2461,
+        "NotArrayType",
+        "A value which is not an array is destructured or spread as an array.",
+    ),
+    tsc(2462, "RestPropertyNotLast", "A rest element is not the last element of a pattern."),
+    tsc(
+        2463,
+        "OptionalBindingPatternInImplSignature",
+        "A binding pattern parameter is optional in an implementation signature.",
+    ),
+    tsc(
+        2464,
+        "InvalidTypeForComputedProperty",
+        "A computed property name is not of type `string`, `number`, `symbol` or `any`.",
+    ),
+    tsc(
+        2465,
+        "CannotReferenceThisInComputedPropName",
+        "`this` is used in a computed property name.",
+    ),
+    tsc(
+        2466,
+        "CannotReferenceSuperInComputedPropName",
+        "`super` is used in a computed property name.",
+    ),
+    tsc(
+        2467,
+        "DeclaringTypeParamReferencedByComputedPropName",
+        "A computed property name references a type parameter of the declaring type.",
+    ),
+    tsc(2469, "NumericOpToSymbol", "An arithmetic operator is applied to a symbol."),
+    tsc(
+        2474,
+        "InvalidInitInConstEnum",
+        "A member of a const enum has an initializer which is not a constant expression.",
+    ),
+    tsc(
+        2475,
+        "InvalidUseOfConstEnum",
+        "A const enum is used other than for accessing its members.",
+    ),
+    tsc(
+        2476,
+        "ConstEnumNonIndexAccess",
+        "A member of a const enum is accessed with a value which is not a string literal.",
+    ),
+    tsc(
+        2477,
+        "ConstEnumMemberHasInfinityAsInit",
+        "A member of a const enum evaluates to `Infinity`.",
+    ),
+    tsc(2478, "ConstEnumMemberHasNaNAsInit", "A member of a const enum evaluates to `NaN`."),
+    tsc(
+        2480,
+        "LetOrConstIsNotValidIdInLetOrConstVarDecls",
+        "`let` is used as the name of a `let` or `const` declaration.",
+    ),
+    tsc(
+        2483,
+        "TypeAnnOnLhsOfForOfLoops",
+        "The variable of a `for...of` loop has a type annotation.",
+    ),
+    tsc(
+        2487,
+        "InvalidExprOfLhsOfForOf",
+        "The left side of a `for...of` loop is not a variable or a property access.",
+    ),
+    tsc(
+        2488,
+        "MustHaveSymbolIteratorThatReturnsIterator",
+        "A value is iterated, but it has no `[Symbol.iterator]()` method returning an iterator.",
+    ),
+    tsc(2489, "NoMethodNamedNext", "An iterator has no `next()` method."),
+    tsc(
+        2490,
+        "NextOfIteratorShouldReturnTypeWithPropertyValue",
+        "The `next()` method of an iterator does not return a type with a `value` property.",
+    ),
+    tsc(
+        2491,
+        "DestructuringBindingNotAllowedInLhsOfForIn",
+        "The left side of a `for...in` loop is a destructuring pattern.",
+    ),
+    tsc(2493, "TupleIndexError", "A tuple is indexed out of its bounds."),
+    tsc(
+        2494,
+        "ForOfStringUsedInEs3",
+        "A string is iterated with `for...of` while targeting ES3.",
+    ),
+    tsc(
+        2495,
+        "NotArrayTypeNorStringType",
+        "A value which is not an array or a string is iterated while targeting ES5 or below.",
+    ),
+    tsc(
+        2496,
+        "InvalidUseOfArgumentsInEs3OrEs5",
+        "`arguments` is used in an arrow function while targeting ES5 or below.",
+    ),
+    tsc(
+        2501,
+        "BindingPatNotAllowedInRestPatArg",
+        "A rest element contains a binding pattern.",
+    ),
+    tsc(2503, "NamespaceNotFound", "Cannot find namespace '{0}'."),
+    tsc(
+        2504,
+        "MustHaveSymbolAsyncIteratorThatReturnsIterator",
+        "A value is iterated with `for await`, but it has no `[Symbol.asyncIterator]()` method returning an async iterator.",
+    ),
+    tsc(
+        2505,
+        "GeneratorCannotHaveVoidAsReturnType",
+        "A generator is declared to return `void`.",
+    ),
+    tsc(2506, "SelfReferentialSuperClass", "A class extends itself directly or indirectly."),
+    tsc(2507, "NotConstructorType", "A class extends a value which is not a constructor."),
+    tsc(
+        2511,
+        "CannotCreateInstanceOfAbstractClass",
+        "Cannot create an instance of an abstract class.",
+    ),
+    tsc(
+        2512,
+        "AbstractAndConcreteIsMixed",
+        "Overloads of a method disagree on whether it's abstract.",
+    ),
+    tsc(2513, "CannotAccessAbstractMember", "An abstract member is accessed with `super`."),
+    tsc(2514, "NegativeTupleIndex", "A tuple is indexed with a negative number."),
+    tsc(
+        2515,
+        "ClassDoesNotImplementMember",
+        "A non-abstract class does not implement an abstract member of its base class.",
+    ),
+    tsc(
+        2516,
+        "AbstractClassMethodShouldBeSequential",
+        "Overloads of an abstract method are not adjacent.",
+    ),
+    tsc(
+        2522,
+        "ArgumentsCannotBeUsedInAsyncFnInEs3OrEs5",
+        "`arguments` is used in an async function while targeting ES5 or below.",
+    ),
+    tsc(
+        2525,
+        "NoInitAndNoDefault",
+        "A destructured property is missing, and the pattern has no default value for it.",
+    ),
+    tsc(2528, "DuplicateDefaultExport", "A module has more than one default export."),
+    tsc(2531, "ObjectIsPossiblyNull", "Object is possibly 'null'."),
+    tsc(2532, "ObjectIsPossiblyUndefined", "Object is possibly 'undefined'."),
+    tsc(
+        2533,
+        "ObjectIsPossiblyNullOrUndefined",
+        "A value which may be `null` or `undefined` is used as an object.",
+    ),
+    tsc(
+        2538,
+        "CannotUseTypeAsIndexIndex",
+        "A type which can't be used as an index type is used to index a type.",
+    ),
+    tsc(2539, "CannotAssignToNonVariable", "A value which is not a variable is assigned to."),
+    tsc(2540, "InvalidLValue", "Cannot assign to '{0}' because it is a read-only property."),
+    tsc(
+        2548,
+        "MustHaveSymbolIteratorThatReturnsIteratorOrMustBeArray",
+        "A value is iterated, but it's not an array and it has no `[Symbol.iterator]()` method.",
+    ),
+    tsc(
+        2551,
+        "NoSuchPropertyWithSuggestion",
+        "Property '{0}' does not exist on type '{1}'. Did you mean '{2}'?",
+    ),
+    tsc(2552, "NoSuchVarWithSuggestion", "Cannot find name '{0}'. Did you mean '{1}'?"),
+    tsc(
+        2553,
+        "ComputedMemberInEnumWithStrMember",
+        "A computed member is used in an enum with string members.",
+    ),
+    tsc(2554, "ExpectedNArgsButGotM", "Expected {0} arguments, but got {1}."),
+    tsc(2555, "ExpectedAtLeastNArgsButGotM", "Expected at least {0} arguments, but got {1}."),
+    tsc(
+        2556,
+        "ExpectedNArgsButGotMOrMore",
+        "A spread argument is passed to a function which does not accept that many arguments.",
+    ),
+    tsc(
+        2557,
+        "ExpectedAtLeastNArgsButGotMOrMore",
+        "A spread argument is passed where a minimum number of arguments is required.",
+    ),
+    tsc(
+        2558,
+        "TypeParameterCountMismatch",
+        "The number of type arguments does not match the number of type parameters.",
+    ),
+    tsc(
+        2564,
+        "ClassPropNotInitialized",
+        "Property '{0}' has no initializer and is not definitely assigned in the constructor.",
+    ),
+    tsc(
+        2567,
+        "TS2567",
+        "Enum declarations can only merge with namespace or other enum declarations.",
+    ),
+    tsc(
+        2569,
+        "NotArrayTypeNorStringTypeButDownlevelIterationWouldWork",
+        "A value which is not an array or a string is iterated, which would work with `downlevelIteration`.",
+    ),
+    tsc(2571, "Unknown", "Object is of type 'unknown'."),
+    tsc(2578, "UnusedTsExpectError", "Unused '@ts-expect-error' directive."),
+    tsc(
+        2585,
+        "TS2585",
+        "Type used as a variable, but changing target library can fix the issue.",
+    ),
+    tsc(
+        2610,
+        "DefinedWithAccessorInSuper",
+        "A property overrides an accessor of the base class.",
+    ),
+    tsc(2628, "CannotAssignToEnum", "An enum is assigned to."),
+    tsc(2629, "CannotAssignToClass", "A class is assigned to."),
+    tsc(2630, "CannotAssignToFunction", "A function is assigned to."),
+    tsc(2631, "CannotAssignToNamespace", "A namespace is assigned to."),
+    tsc(2632, "NotVariable", "An imported binding is assigned to."),
+    tsc(2638, "InvalidRhsForInOperator", "The right operand of `in` is not an object."),
+    tsc(
+        2660,
+        "SuperCanBeOnlyReferencedInDerivedClass",
+        "`super` is used in a class which does not extend another class.",
+    ),
+    tsc(
+        2661,
+        "CannotExportNonLocalVar",
+        "A name which is not declared in the module is exported.",
+    ),
+    tsc(
+        2663,
+        "NoSuchVarButThisHasSuchProperty",
+        "A name is not found, but `this` has a property with the name.",
+    ),
+    tsc(
+        2668,
+        "ExportAmbientModule",
+        "`export` is used in a declaration of an ambient module.",
+    ),
+    tsc(
+        2673,
+        "ClassConstructorPrivate",
+        "A class with a private constructor is constructed outside of it.",
+    ),
+    tsc(
+        2674,
+        "ClassConstructorProtected",
+        "A class with a protected constructor is constructed outside of it.",
+    ),
+    tsc(
+        2675,
+        "InvalidExtendDueToConstructorPrivate",
+        "A class with a private constructor is extended.",
+    ),
+    tsc(
+        2678,
+        "SwitchCaseTestNotCompatible",
+        "A `case` value is not comparable to the value of the `switch`.",
+    ),
+    tsc(
+        2686,
+        "UmdGlobalInModule",
+        "A global declared by `export as namespace` is used in a module.",
+    ),
+    tsc(2688, "CannotFindTypeDefinitionFile", "Cannot find type definition file for '{0}'."),
+    tsc(
+        2689,
+        "CannotExtendTypeOnlyItem",
+        "A class extends an interface or another type-only name.",
+    ),
+    tsc(2693, "TypeUsedAsVar", "A name which only refers to a type is used as a value."),
+    tsc(
+        2695,
+        "UselessSeqExpr",
+        "The left operand of a comma operator is unused and has no side effects.",
+    ),
+    tsc(
+        2698,
+        "NonObjectInSpread",
+        "A value which is not an object is spread into an object.",
+    ),
+    tsc(
+        2699,
+        "StaticPropertyCannotBeNamedPrototype",
+        "A static property is named `prototype`.",
+    ),
+    tsc(
+        2701,
+        "RestArgMustBeVarOrMemberAccess",
+        "The target of a rest element in an assignment is not a variable or a property access.",
+    ),
+    tsc(2703, "InvalidDeleteOperand", "The operand of `delete` is not a property reference."),
+    tsc(2704, "TS2704", "The operand of a 'delete' operator cannot be a read-only property."),
+    tsc(2708, "CannotAssignToModule", "A module is assigned to."),
+    tsc(
+        2725,
+        "ClassNameCannotBeObjectWhenTargetingEs5WithModule",
+        "A class is named `Object` while targeting ES5 with a module system.",
+    ),
+    tsc(
+        2741,
+        "MissingFields",
+        "Property '{0}' is missing in type '{1}' but required in type '{2}'.",
+    ),
+    tsc(
+        2748,
+        "AmbientConstEnumWithIsolatedModules",
+        "An ambient const enum is used while `isolatedModules` is enabled.",
+    ),
+    tsc(
+        2749,
+        "NoSuchTypeButVarExists",
+        "'{0}' refers to a value, but is being used as a type here.",
+    ),
+    tsc(2754, "SuperCannotUseTypeArgs", "Type arguments are given to a `super` call."),
+    tsc(
+        2767,
+        "ReturnPropertyOfIteratorMustBeMethod",
+        "The `return` property of an iterator is not a method.",
+    ),
+    tsc(2769, "NoMatchingOverload", "No overload matches this call."),
+    tsc(
+        2777,
+        "InvalidOperandOfIncDecOptionalProp",
+        "The operand of `++` or `--` is an optional property access.",
+    ),
+    tsc(
+        2778,
+        "InvalidRestPatternInOptionalChain",
+        "The target of a rest element is an optional chain.",
+    ),
+    tsc(
+        2779,
+        "InvalidLhsOfAssignOptionalProp",
+        "The left side of an assignment is an optional property access.",
+    ),
+    tsc(
+        2780,
+        "InvalidRestPatternInForIn",
+        "The left side of a `for...in` loop is an optional property access.",
+    ),
+    tsc(
+        2781,
+        "InvalidRestPatternInForOf",
+        "The left side of a `for...of` loop is an optional property access.",
+    ),
+    tsc(
+        2790,
+        "DeleteOperandMustBeOptional",
+        "The operand of `delete` is a property which is not optional.",
+    ),
+    tsc(
+        2795,
+        "IntrinsicIsBuiltinOnly",
+        "The `intrinsic` keyword is used outside of the builtin libraries.",
+    ),
+    tsc(
+        2804,
+        "DuplicatePrivateStaticInstance",
+        "A static and an instance private member have the same name.",
+    ),
+    tsc(
+        2837,
+        "ImportAttributeValueNotString",
+        "A value of `with { type: \"json\" }` or `assert { type: \"json\" }` is not a string literal.",
+    ),
+    tsc(5012, "CannotReadFile", "Cannot read file '{0}': {1}."),
+    tsc(
+        5048,
+        "OptionInvalidForEs3",
+        "An option is used which is not supported while targeting ES3.",
+    ),
+    tsc(5061, "TooManyAsterisk", "A pattern of `paths` has more than one `*`."),
+    tsc(
+        5076,
+        "NullishCoalescingMixedWithLogicalWithoutParen",
+        "`??` is mixed with `||` or `&&` without parentheses.",
+    ),
+    tsc(7005, "ImplicitAny", "Variable '{0}' implicitly has an '{1}' type."),
+    tsc(
+        7009,
+        "TargetLacksConstructSignature",
+        "A function without a construct signature is called with `new`, and it implicitly returns `any`.",
+    ),
+    tsc(
+        7010,
+        "ImplicitReturnType",
+        "The return type of a declaration without a body implicitly has type `any`.",
+    ),
+    tsc(
+        7022,
+        "ImplicitAnyBecauseOfSelfRef",
+        "A variable implicitly has type `any` because it's referenced in its own initializer.",
+    ),
+    tsc(7027, "UnreachableCode", "Unreachable code detected."),
+    tsc(
+        7052,
+        "ImplicitAnyBecauseNoIndexSignatureExists",
+        "An index access implicitly has type `any` because the type has no index signature.",
+    ),
+    tsc(
+        7053,
+        "ImplicitAnyBecauseIndexTypeIsWrong",
+        "Element implicitly has an 'any' type because expression of type '{0}' can't be used to index type '{1}'.",
+    ),
+    tsc(
+        17009,
+        "ThisUsedBeforeCallingSuper",
+        "`this` is used before `super()` is called in a constructor.",
+    ),
+    tsc(
+        17011,
+        "SuperUsedBeforeCallingSuper",
+        "`super` is used to access a property before `super()` is called in a constructor.",
+    ),
+    tsc(
+        17013,
+        "InvalidUsageOfNewTarget",
+        "`new.target` is used outside of a function or a constructor.",
+    ),
+    tsc(
+        18004,
+        "NoSuchVarForShorthand",
+        "A shorthand property refers to a name which is not in scope.",
+    ),
+    tsc(18011, "CannotDeletePrivateProperty", "A private property is deleted."),
+    tsc(
+        18012,
+        "ConstructorIsKeyword",
+        "`#constructor` is used as the name of a private member.",
+    ),
+    tsc(
+        18013,
+        "CannotAccessPrivatePropertyFromOutside",
+        "A private property is accessed outside of its class.",
+    ),
+    tsc(
+        18022,
+        "PrivateIdUsedAsMethodName",
+        "A private name is used as the name of a method in an object literal.",
+    ),
+    tsc(
+        18030,
+        "OptionalChainCannotContainPrivateIdentifier",
+        "An optional chain contains a private name.",
+    ),
+    tsc(
+        18050,
+        "UndefinedOrNullIsNotValidOperand",
+        "`null` or `undefined` is used as an operand of an operator which does not accept them.",
+    ),
+    stc(
+        100001,
+        "CircularImport",
+        "Imports form a cycle. Reported only if `noCircularImports` is enabled.",
+    ),
+    stc(
+        100002,
+        "UnusedExport",
+        "An export is not imported by any checked file. Reported only if `noUnusedExports` is enabled.",
+    ),
+    stc(100003, "Lint", "A lint rule registered by the user reports an error."),
+];
@@ -0,0 +1,70 @@
+//! Removal of errors which only repeat another error, used by
+//! [ErrorKind::flatten].
+
+use fxhash::FxHashSet;
+use swc_common::{Span, Spanned};
+
+use crate::{Error, ErrorKind};
+
+/// Removes errors with the same code, span and message as a previous error.
+///
+/// Errors without a code of tsc and the errors of the rules of stc are
+/// removed only if they are equal to a previous error, as their messages may
+/// not contain everything in the error, like the name of a lint rule.
+pub(crate) fn dedup(errors: Vec<Error>) -> Vec<Error> {
+    let mut seen = FxHashSet::default();
+
+    errors
+        .into_iter()
+        .filter(|err| {
+            let code = err.code();
+            let message = if code == 0 || err.is_warning() {
+                format!("{:?}", &**err)
+            } else {
+                err.message()
+            };
+
+            seen.insert((err.span(), code, message))
+        })
+        .collect()
+}
+
+/// Removes errors caused by a primary failure, which are the errors in the
+/// span of the failure.
+///
+/// The checker falls back to `any` for the failures, but the errors reported
+/// before falling back only repeat the failure.
+pub(crate) fn remove_follow_on_errors(errors: Vec<Error>) -> Vec<Error> {
+    let primary_spans = errors
+        .iter()
+        .filter(|err| err.is_primary_failure())
+        .map(|err| err.span())
+        .filter(|span| !span.is_dummy())
+        .collect::<Vec<Span>>();
+
+    if primary_spans.is_empty() {
+        return errors;
+    }
+
+    errors
+        .into_iter()
+        .filter(|err| {
+            // Warnings are reported by other rules of stc, so they are not caused by the
+            // failure.
+            if err.is_primary_failure() || err.is_warning() {
+                return true;
+            }
+
+            let span = err.span();
+            !primary_spans.iter().any(|primary| primary.contains(span))
+        })
+        .collect()
+}
+
+impl ErrorKind {
+    /// Returns `true` for failures to resolve a module or a name, which cause
+    /// other errors in their span.
+    fn is_primary_failure(&self) -> bool {
+        matches!(self, ErrorKind::ModuleNotFound { .. }) || self.is_var_not_found() || self.is_type_not_found()
+    }
+}
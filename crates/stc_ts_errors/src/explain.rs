@@ -0,0 +1,184 @@
+//! Explanations of error codes for `stc explain`, like `rustc --explain`.
+
+use crate::{codes::message_template, ErrorKind};
+
+/// The explanation of an error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Explanation {
+    /// The normalized code, like `2322`.
+    pub code: usize,
+    /// The message of the given code. See [message_template].
+    pub message: &'static str,
+    pub description: &'static str,
+    pub causes: &'static [&'static str],
+    /// Code which causes the error.
+    pub example: &'static str,
+}
+
+/// Returns the explanation of `code`. `code` is normalized by
+/// [ErrorKind::normalize_error_code], so codes of variants like `TS2551` are
+/// explained by the generic error.
+pub fn explain(code: usize) -> Option<Explanation> {
+    let normalized = ErrorKind::normalize_error_code(code);
+
+    let (description, causes, example): (&str, &[&str], &str) = match normalized {
+        2304 => (
+            "A name is used, but no variable, function, class or type with the name is in scope.",
+            &[
+                "The name is misspelled.",
+                "The declaration is in another file, and it's not imported.",
+                "The name is a global of a library which is not loaded, like `document` without the `dom` library.",
+                "The name is a global of a package whose types are not installed, like `describe` without `@types/jest`.",
+            ],
+            "console.log(coutn);",
+        ),
+        2307 => (
+            "An imported module can't be resolved to a file, or the file has no type declarations.",
+            &[
+                "The path of a relative import is wrong.",
+                "The package is not installed, or it doesn't ship declaration files and `@types` for it is not installed.",
+                "`moduleResolution` or `paths` of `tsconfig.json` don't match how the module is resolved at runtime.",
+            ],
+            "import { helper } from './helpres';",
+        ),
+        2322 => (
+            "A value is assigned to a variable, a property or a return value whose type doesn't accept it.",
+            &[
+                "The declared type is narrower than the value, like a literal type or a union missing a member.",
+                "The value may be `null` or `undefined` while `strictNullChecks` is enabled.",
+                "An object is missing a property which is required by the declared type.",
+            ],
+            "const count: number = '1';",
+        ),
+        2339 => (
+            "A property is accessed, but the type of the object has no property with the name.",
+            &[
+                "The property name is misspelled.",
+                "The object is a union, and the property exists only in some members of it. Narrow the type first.",
+                "The type is inferred from an initializer which doesn't have the property, like `{}`.",
+            ],
+            "const point = { x: 1 };\npoint.y;",
+        ),
+        2345 => (
+            "An argument of a call has a type which is not assignable to the type of the parameter.",
+            &[
+                "The arguments are passed in the wrong order.",
+                "The argument may be `null` or `undefined` while the parameter doesn't accept them.",
+                "A callback has parameters which don't match the parameters the callee passes to it.",
+            ],
+            "function double(n: number) {\n    return n * 2;\n}\ndouble('2');",
+        ),
+        2349 => (
+            "An expression is called, but its type has no call signature.",
+            &[
+                "The value is an object containing a function, not the function itself.",
+                "The type is a union whose members have incompatible call signatures.",
+            ],
+            "const config = { run() {} };\nconfig();",
+        ),
+        2353 => (
+            "An object literal has a property which is not declared in the type it is assigned to. Excess properties are reported only \
+             for fresh object literals, because they are likely to be typos.",
+            &[
+                "The property name is misspelled.",
+                "The property is declared in another type of a union.",
+            ],
+            "interface Options {\n    verbose?: boolean;\n}\nconst options: Options = { verbos: true };",
+        ),
+        2355 => (
+            "A function declares a return type other than `void` and `any`, but it has no `return` statement with a value.",
+            &["The function only logs or throws, and the return type should be `void` or `never`."],
+            "function name(): string {\n    console.log('name');\n}",
+        ),
+        2366 => (
+            "A function declares a return type which doesn't include `undefined`, but the end of the function is reachable without \
+             returning a value.",
+            &[
+                "A branch of an `if` or a `switch` doesn't return.",
+                "A `switch` doesn't handle all cases, and has no `default` case.",
+            ],
+            "function sign(n: number): string {\n    if (n > 0) {\n        return '+';\n    }\n}",
+        ),
+        2451 => (
+            "A `let`, `const` or `class` is declared twice in the same scope.",
+            &[
+                "The name is reused instead of assigning to the existing variable.",
+                "Two scripts without imports and exports declare the same global variable. Add `export {}` to make them modules.",
+            ],
+            "let value = 1;\nlet value = 2;",
+        ),
+        2454 => (
+            "A variable is read, but it's not assigned in every path reaching the read.",
+            &[
+                "A branch of an `if` doesn't assign the variable.",
+                "The variable is assigned only in a `try` block.",
+            ],
+            "let name: string;\nif (Math.random() > 0.5) {\n    name = 'a';\n}\nconsole.log(name);",
+        ),
+        2531 | 2532 => (
+            "A value which may be `null` or `undefined` is used as an object, like accessing a property of it or calling it.",
+            &[
+                "The value comes from an optional property or parameter.",
+                "The value comes from a lookup which may fail, like `Array.prototype.find` or `document.getElementById`.",
+                "A check for `null` is done in another function, so the type is not narrowed.",
+            ],
+            "const found = [1, 2].find((n) => n > 1);\nfound.toFixed();",
+        ),
+        2554 => (
+            "A function is called with a number of arguments its signatures don't accept.",
+            &[
+                "An argument is missing, and the parameter is not optional.",
+                "An extra argument is passed, like a callback with fewer parameters.",
+            ],
+            "function add(a: number, b: number) {\n    return a + b;\n}\nadd(1);",
+        ),
+        2564 => (
+            "A property of a class is not optional and not initialized, and the constructor doesn't assign it. It is reported only if \
+             `strictPropertyInitialization` is enabled.",
+            &[
+                "The property is initialized by a method called from the constructor. Use `!` after the name to assert it's assigned.",
+                "The property should be optional.",
+            ],
+            "class User {\n    name: string;\n}",
+        ),
+        2588 => (
+            "A value is assigned to a variable declared with `const`.",
+            &["The variable should be declared with `let`."],
+            "const count = 0;\ncount = 1;",
+        ),
+        2741 => (
+            "An object is assigned to a type with a required property, but the object doesn't have the property.",
+            &[
+                "The property is missing from an object literal.",
+                "The property should be optional in the declared type.",
+            ],
+            "interface Point {\n    x: number;\n    y: number;\n}\nconst p: Point = { x: 1 };",
+        ),
+        2769 => (
+            "A function has multiple overloads, and none of them accepts the arguments. The error of each overload is reported with it.",
+            &[
+                "An argument has a wrong type for every overload.",
+                "The combination of the arguments is not declared by any overload.",
+            ],
+            "declare function parse(s: string): number;\ndeclare function parse(n: number, radix: number): number;\nparse(1);",
+        ),
+        7005 => (
+            "A variable or a parameter has no type annotation and its type can't be inferred, so it is `any`. It is reported only if \
+             `noImplicitAny` is enabled.",
+            &[
+                "The function is not a callback of a typed call, so the parameter type is not inferred from it.",
+                "A variable is declared without an initializer, and its type can't be inferred from the assignments.",
+            ],
+            "function greet(name) {\n    return 'Hello ' + name;\n}",
+        ),
+        _ => return None,
+    };
+
+    Some(Explanation {
+        code: normalized,
+        message: message_template(code).or_else(|| message_template(normalized))?,
+        description,
+        causes,
+        example,
+    })
+}
@@ -0,0 +1,200 @@
+//! Changes of the source code which fix an error, like quick fixes of tsc.
+
+use stc_ts_ast_rnode::RTsEntityName;
+use stc_ts_types::{Key, Type, TypeElement};
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+
+use crate::{Error, ErrorKind};
+
+/// A fix of an error, which is applied by replacing the source code in all
+/// `edits`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Describes the fix, like `Add missing 'await'`.
+    pub message: String,
+    /// Edits in the file of the error. The edits don't overlap.
+    pub edits: Vec<Edit>,
+}
+
+/// Replaces the source code in `span` with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    /// An empty span inserts `new_text`.
+    pub span: Span,
+    pub new_text: String,
+}
+
+impl Edit {
+    pub fn insert(pos: BytePos, new_text: impl Into<String>) -> Self {
+        Edit {
+            span: Span::new(pos, pos, Default::default()),
+            new_text: new_text.into(),
+        }
+    }
+
+    pub fn replace(span: Span, new_text: impl Into<String>) -> Self {
+        Edit {
+            span,
+            new_text: new_text.into(),
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Returns the fixes of the error, if the fix is obvious.
+    ///
+    /// `cm` is used to read the source code around the error, and no fix is
+    /// returned if it does not look like the code the fix expects.
+    pub fn fixes(&self, cm: &SourceMap) -> Vec<Fix> {
+        let fix = match self {
+            ErrorKind::Lint { fix, .. } => fix.as_deref().cloned(),
+            ErrorKind::NoSuchProperty {
+                span,
+                obj: Some(obj),
+                prop: Some(prop),
+            } if is_promise(obj) => add_await(cm, *span, prop),
+            ErrorKind::AssignFailed { left, right, cause, .. } => find_missing_fields(cause)
+                .and_then(|fields| add_missing_properties(cm, right.span(), fields))
+                .or_else(|| add_undefined(cm, self.span(), left, right)),
+            ErrorKind::MissingFields { span, fields } => add_missing_properties(cm, *span, fields),
+            ErrorKind::NoSuchVarWithSuggestion {
+                span, name, suggestion, ..
+            }
+            | ErrorKind::NoSuchTypeWithSuggestion {
+                span, name, suggestion, ..
+            } => change_spelling(cm, *span, name.sym(), suggestion),
+            ErrorKind::NoSuchPropertyWithSuggestion { prop, suggestion, .. } => match &**prop {
+                Key::Normal { span, sym } => change_spelling(cm, *span, sym, suggestion),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        fix.into_iter().collect()
+    }
+}
+
+fn is_promise(ty: &Type) -> bool {
+    match ty.normalize() {
+        Type::Ref(r) => matches!(&r.type_name, RTsEntityName::Ident(i) if &*i.sym == "Promise"),
+        _ => false,
+    }
+}
+
+/// `promise.prop` => `(await promise).prop`
+fn add_await(cm: &SourceMap, span: Span, prop: &Key) -> Option<Fix> {
+    let prop = prop.span();
+    if prop.lo <= span.lo || prop.hi > span.hi {
+        return None;
+    }
+
+    let obj = cm.span_to_snippet(span.with_hi(prop.lo)).ok()?;
+    let obj = obj.trim_end().strip_suffix('.')?.trim_end();
+    if obj.is_empty() || obj.ends_with('?') {
+        return None;
+    }
+
+    Some(Fix {
+        message: "Add missing 'await'".to_string(),
+        edits: vec![
+            Edit::insert(span.lo, "(await "),
+            Edit::insert(span.lo + BytePos(obj.len() as u32), ")"),
+        ],
+    })
+}
+
+/// `let a: string = undefined` => `let a: string | undefined = undefined`
+fn add_undefined(cm: &SourceMap, span: Span, left: &Type, right: &Type) -> Option<Fix> {
+    if !right.iter_union().any(|ty| ty.normalize().is_undefined()) {
+        return None;
+    }
+    // `| undefined` would be a part of other types, like the return type of a
+    // function type.
+    if !matches!(
+        left.normalize(),
+        Type::Keyword(..) | Type::Lit(..) | Type::Ref(..) | Type::Union(..) | Type::Intersection(..) | Type::Array(..) | Type::Tuple(..)
+    ) {
+        return None;
+    }
+
+    // Only a type annotation is changed.
+    let ty = left.span();
+    if ty.is_dummy() || ty.hi <= ty.lo || ty.contains(span) || span.contains(ty) {
+        return None;
+    }
+    let file = cm.lookup_byte_offset(ty.lo).sf;
+    if file.name != cm.lookup_byte_offset(span.lo).sf.name {
+        return None;
+    }
+    let before = cm.span_to_snippet(Span::new(file.start_pos, ty.lo, Default::default())).ok()?;
+    if !before.trim_end().ends_with(':') {
+        return None;
+    }
+
+    Some(Fix {
+        message: "Add 'undefined' to the type".to_string(),
+        edits: vec![Edit::insert(ty.hi, " | undefined")],
+    })
+}
+
+/// `coutn` => `count`
+fn change_spelling(cm: &SourceMap, span: Span, name: &str, suggestion: &str) -> Option<Fix> {
+    // The span may contain more than the name, like type arguments or quotes.
+    if cm.span_to_snippet(span).ok()? != name {
+        return None;
+    }
+
+    Some(Fix {
+        message: format!("Change spelling to '{}'", suggestion),
+        edits: vec![Edit::replace(span, suggestion)],
+    })
+}
+
+fn find_missing_fields(errors: &[Error]) -> Option<&[TypeElement]> {
+    errors.iter().find_map(|err| match &**err {
+        ErrorKind::MissingFields { fields, .. } => Some(&**fields),
+        ErrorKind::ObjectAssignFailed { errors, .. } => find_missing_fields(errors),
+        _ => None,
+    })
+}
+
+fn is_identifier(s: &str) -> bool {
+    let is_part = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+
+    s.chars().next().map_or(false, |c| is_part(c) && !c.is_ascii_digit()) && s.chars().all(is_part)
+}
+
+/// `{ a: 1 }` => `{ a: 1, b: undefined }`
+fn add_missing_properties(cm: &SourceMap, span: Span, fields: &[TypeElement]) -> Option<Fix> {
+    let props = fields
+        .iter()
+        .filter_map(|field| match field.key()? {
+            Key::Normal { sym, .. } if is_identifier(sym) => Some(format!("{}: undefined", sym)),
+            Key::Normal { sym, .. } => Some(format!("{:?}: undefined", &**sym)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if props.is_empty() || props.len() != fields.len() {
+        return None;
+    }
+
+    let lit = cm.span_to_snippet(span).ok()?;
+    let body = lit.strip_prefix('{')?.strip_suffix('}')?;
+
+    let body_end = body.trim_end();
+    let edit = if body_end.trim_start().is_empty() {
+        Edit::replace(span, format!("{{ {} }}", props.join(", ")))
+    } else {
+        let pos = span.lo + BytePos(1 + body_end.len() as u32);
+        let sep = if body_end.ends_with(',') { " " } else { ", " };
+        Edit::insert(pos, format!("{}{}", sep, props.join(", ")))
+    };
+
+    Some(Fix {
+        message: match fields.len() {
+            1 => "Add missing property".to_string(),
+            _ => "Add missing properties".to_string(),
+        },
+        edits: vec![edit],
+    })
+}
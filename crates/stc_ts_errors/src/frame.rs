@@ -0,0 +1,195 @@
+//! Code frames, which are excerpts of the source code annotated with errors,
+//! like the output of `tsc --pretty`.
+//!
+//! ```text
+//! src/main.ts:3:5 - error TS2322: Type 'string' is not assignable to type 'number'.
+//!
+//! 3 let a: number = "";
+//!       ~
+//! ```
+
+use std::path::PathBuf;
+
+use ansi_term::{Color, Style};
+use swc_common::{FileName, SourceMap, Span, Spanned};
+
+use crate::{Error, ErrorKind, Severity};
+
+/// Lines of a span are elided if the span has more lines than this.
+const MAX_LINES: usize = 4;
+
+/// An error to render with [FrameRenderer].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub span: Span,
+    pub severity: Severity,
+    /// The normalized code, like `2322` for `TS2322`.
+    pub code: usize,
+    /// The first line is printed after the code, and the other lines are
+    /// printed below it. See [ErrorKind::message_chain].
+    pub message: String,
+    /// Rendered after the frame of `span`. See [ErrorKind::related_spans].
+    pub related: Vec<(Span, String)>,
+}
+
+impl Report {
+    pub fn new(err: &Error) -> Self {
+        Report {
+            span: err.span(),
+            severity: err.severity(),
+            code: ErrorKind::normalize_error_code(err.code()),
+            message: err.message_chain(),
+            related: err.related_spans(),
+        }
+    }
+}
+
+/// Renders [Report]s as code frames.
+#[derive(Debug, Clone, Default)]
+pub struct FrameRenderer {
+    color: bool,
+    cwd: Option<PathBuf>,
+}
+
+impl FrameRenderer {
+    /// Paints the output with ANSI escape codes.
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Prints the paths of files in `cwd` relative to it.
+    pub fn with_cwd(mut self, cwd: PathBuf) -> Self {
+        self.cwd = Some(cwd);
+        self
+    }
+
+    /// Renders `report`, whose spans are in `cm`. The output ends with an empty
+    /// line, so that reports can be printed one after another.
+    ///
+    /// Errors without a location are rendered without a frame.
+    pub fn render(&self, cm: &SourceMap, report: &Report) -> String {
+        let mut out = String::new();
+
+        if !report.span.is_dummy() {
+            out.push_str(&self.location(cm, report.span));
+            out.push_str(" - ");
+        }
+        let (severity, color) = match report.severity {
+            Severity::Error => ("error", Color::Red),
+            Severity::Warning => ("warning", Color::Yellow),
+            Severity::Info => ("info", Color::Blue),
+        };
+        out.push_str(&self.paint(color.normal(), severity));
+        out.push(' ');
+        out.push_str(&self.paint(Color::Fixed(8).normal(), &format!("TS{}:", report.code)));
+        out.push(' ');
+        out.push_str(&report.message);
+        out.push('\n');
+
+        if !report.span.is_dummy() {
+            out.push('\n');
+            self.frame(&mut out, cm, report.span, color, "");
+        }
+
+        for (span, message) in &report.related {
+            out.push('\n');
+            if !span.is_dummy() {
+                out.push_str("  ");
+                out.push_str(&self.location(cm, *span));
+                out.push('\n');
+                self.frame(&mut out, cm, *span, Color::Cyan, "    ");
+            }
+            out.push_str("    ");
+            out.push_str(message);
+            out.push('\n');
+        }
+
+        out.push('\n');
+        out
+    }
+
+    fn paint(&self, style: Style, s: &str) -> String {
+        if self.color {
+            style.paint(s).to_string()
+        } else {
+            s.to_string()
+        }
+    }
+
+    /// `file:line:column`, where the line and the column are 1-based.
+    fn location(&self, cm: &SourceMap, span: Span) -> String {
+        let loc = cm.lookup_char_pos(span.lo);
+
+        format!(
+            "{}:{}:{}",
+            self.paint(Color::Cyan.normal(), &self.file_name(&loc.file.name)),
+            self.paint(Color::Yellow.normal(), &loc.line.to_string()),
+            self.paint(Color::Yellow.normal(), &(loc.col.0 + 1).to_string())
+        )
+    }
+
+    fn file_name(&self, name: &FileName) -> String {
+        match (name, &self.cwd) {
+            (FileName::Real(path), Some(cwd)) => path.strip_prefix(cwd).unwrap_or(path).display().to_string(),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Writes the lines of `span` with a gutter of line numbers, and marks
+    /// `span` below each line with `~`.
+    fn frame(&self, out: &mut String, cm: &SourceMap, span: Span, color: Color, indent: &str) {
+        let lo = cm.lookup_char_pos(span.lo);
+        let hi = cm.lookup_char_pos(span.hi);
+        let (last_line, last_col) = if lo.file.name == hi.file.name && hi.line >= lo.line {
+            (hi.line, hi.col.0)
+        } else {
+            (lo.line, lo.col.0)
+        };
+
+        let gutter_width = last_line.to_string().len();
+        let mut line = lo.line;
+        while line <= last_line {
+            // Only the first two lines and the last two lines are shown.
+            if last_line - lo.line >= MAX_LINES && lo.line + 1 < line && line < last_line - 1 {
+                out.push_str(indent);
+                out.push_str(&self.paint(Style::new().reverse(), &format!("{:>1$}", "...", gutter_width)));
+                out.push('\n');
+                line = last_line - 1;
+                continue;
+            }
+
+            let content = lo
+                .file
+                .get_line(line - 1)
+                .map(|content| content.trim_end().replace('\t', " "))
+                .unwrap_or_default();
+            let len = content.chars().count();
+            let start = if line == lo.line { lo.col.0.min(len) } else { 0 };
+            let end = if line == last_line { last_col.min(len) } else { len };
+            let mut marker = "~".repeat(end.saturating_sub(start));
+            // Mark empty spans too.
+            if marker.is_empty() && lo.line == last_line {
+                marker.push('~');
+            }
+
+            out.push_str(indent);
+            out.push_str(&self.gutter(&format!("{:>1$}", line, gutter_width)));
+            out.push_str(&content);
+            out.push('\n');
+            out.push_str(indent);
+            out.push_str(&self.gutter(&" ".repeat(gutter_width)));
+            if !marker.is_empty() {
+                out.push_str(&" ".repeat(start));
+                out.push_str(&self.paint(color.normal(), &marker));
+            }
+            out.push('\n');
+
+            line += 1;
+        }
+    }
+
+    fn gutter(&self, s: &str) -> String {
+        format!("{} ", self.paint(Style::new().reverse(), s))
+    }
+}
@@ -20,15 +20,28 @@ use stc_ts_types::{name::Name, Id, Key, ModuleId, Type, TypeElement, TypeParamIn
 use stc_utils::stack::StackOverflowError;
 use swc_atoms::JsWord;
 use swc_common::{
-    errors::{DiagnosticId, Handler},
+    errors::{DiagnosticId, Handler, Level},
     Span, Spanned, DUMMY_SP,
 };
 use swc_ecma_ast::{AssignOp, BinaryOp, UpdateOp};
 
-pub use self::result_ext::DebugExt;
+pub use self::{
+    fix::{Edit, Fix},
+    frame::{FrameRenderer, Report},
+    result_ext::DebugExt,
+    severity::{Severity, SeverityOverrides},
+};
 
+pub mod codes;
 pub mod debug;
+mod dedup;
+pub mod explain;
+mod fix;
+mod frame;
+mod message;
+mod related;
 mod result_ext;
+mod severity;
 
 /// [ErrorKind] with debug contexts attached.
 #[derive(Clone, PartialEq, Spanned)]
@@ -37,6 +50,8 @@ pub struct Error {
     contexts: Vec<String>,
     #[span]
     inner: Box<ErrorKind>,
+    /// Replaces [ErrorKind::default_severity]. See [SeverityOverrides].
+    severity: Option<Severity>,
 }
 
 impl std::ops::Deref for Error {
@@ -53,6 +68,7 @@ impl From<ErrorKind> for Error {
             #[cfg(debug_assertions)]
             contexts: Default::default(),
             inner: Box::new(kind),
+            severity: None,
         }
     }
 }
@@ -73,15 +89,37 @@ impl Error {
         self
     }
 
+    pub fn severity(&self) -> Severity {
+        self.severity.unwrap_or_else(|| self.inner.default_severity())
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
     #[cold]
     pub fn emit(&self, h: &Handler) {
         let span = self.span();
-
-        let mut err = h.struct_span_err_with_code(
-            span,
-            &format!("{:#?}", self),
-            DiagnosticId::Error(format!("TS{}", ErrorKind::normalize_error_code(self.code()))),
-        );
+        let msg = self.message_chain();
+        let code = DiagnosticId::Error(format!("TS{}", ErrorKind::normalize_error_code(self.code())));
+
+        let mut err = match self.severity() {
+            Severity::Error => h.struct_span_err_with_code(span, &msg, code),
+            Severity::Warning => h.struct_span_warn_with_code(span, &msg, code),
+            Severity::Info => {
+                let mut err = h.struct_span_warn_with_code(span, &msg, code);
+                err.level = Level::Note;
+                err
+            }
+        };
+        for (span, message) in self.related_spans() {
+            err.span_note(span, &message);
+        }
+        if cfg!(debug_assertions) {
+            // The debug contexts are useful while developing stc.
+            err.note(&format!("{:#?}", self));
+        }
 
         err.emit();
     }
@@ -376,6 +414,9 @@ pub enum ErrorKind {
     /// TS2307
     ModuleNotFound {
         span: Span,
+        /// [None] if the module specifier is resolved, but the module can't be
+        /// loaded.
+        hint: Option<Box<ModuleNotFoundHint>>,
     },
 
     /// TS5061
@@ -383,6 +424,25 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS5012
+    ///
+    /// An entry file which can't be loaded, because it doesn't exist or it
+    /// can't be parsed. The span is dummy.
+    CannotReadFile {
+        span: Span,
+        file: String,
+        reason: String,
+    },
+
+    /// TS2688
+    ///
+    /// An entry of `types` which can't be resolved in the type roots or
+    /// `node_modules`. The span is dummy.
+    CannotFindTypeDefinitionFile {
+        span: Span,
+        name: String,
+    },
+
     /// TS2451
     DuplicateVar {
         name: Id,
@@ -936,6 +996,28 @@ pub enum ErrorKind {
         name: Id,
     },
 
+    /// TS2552
+    ///
+    /// [ErrorKind::NoSuchVar], but a variable with a similar name is in
+    /// scope. `declared` is the span of its declaration, which is dummy for
+    /// globals of libraries.
+    NoSuchVarWithSuggestion {
+        span: Span,
+        name: Id,
+        suggestion: JsWord,
+        declared: Span,
+    },
+
+    /// TS2552
+    ///
+    /// [ErrorKind::NoSuchType], but a type with a similar name is in scope.
+    NoSuchTypeWithSuggestion {
+        span: Span,
+        name: Id,
+        suggestion: JsWord,
+        declared: Span,
+    },
+
     /// TS2689
     CannotExtendTypeOnlyItem {
         span: Span,
@@ -990,6 +1072,17 @@ pub enum ErrorKind {
         prop: Option<Box<Key>>,
     },
 
+    /// TS2551
+    ///
+    /// [ErrorKind::NoSuchProperty], but `obj` has a property with a similar
+    /// name.
+    NoSuchPropertyWithSuggestion {
+        span: Span,
+        obj: Box<Type>,
+        prop: Box<Key>,
+        suggestion: JsWord,
+    },
+
     NoInitAndNoDefault {
         span: Span,
     },
@@ -1100,6 +1193,26 @@ pub enum ErrorKind {
         cause: Vec<Error>,
     },
 
+    /// A property could not be assigned to the property of the other type.
+    /// This is reported as the error of `cause`, and the property is shown in
+    /// [ErrorKind::message_chain].
+    PropertyIncompatible {
+        span: Span,
+        key: Key,
+        cause: Box<Error>,
+    },
+
+    /// A parameter could not be assigned to the parameter of the other
+    /// function. This is reported as the error of `cause`, and the parameters
+    /// are shown in [ErrorKind::message_chain].
+    ParamIncompatible {
+        span: Span,
+        /// The parameter of the assigned function.
+        right: Id,
+        left: Id,
+        cause: Box<Error>,
+    },
+
     InvalidAssignmentOfArray {
         span: Span,
     },
@@ -1499,6 +1612,79 @@ pub enum ErrorKind {
     ExportAmbientModule {
         span: Span,
     },
+
+    /// TS2837
+    ///
+    /// A value of `with { type: "json" }` or `assert { type: "json" }` is not a
+    /// string literal.
+    ImportAttributeValueNotString {
+        span: Span,
+    },
+
+    /// TS2686
+    ///
+    /// A global declared by `export as namespace` is used in a module.
+    UmdGlobalInModule {
+        span: Span,
+        name: Id,
+    },
+
+    /// TS1205
+    ///
+    /// A type is re-exported without `export type` while `isolatedModules` is
+    /// enabled.
+    ReExportTypeWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS1208
+    ///
+    /// A file is not a module while `isolatedModules` is enabled.
+    ScriptWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS2748
+    ///
+    /// An ambient const enum is used while `isolatedModules` is enabled.
+    AmbientConstEnumWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS2578
+    ///
+    /// A `@ts-expect-error` comment does not suppress any error. `span` is the
+    /// span of the comment.
+    UnusedTsExpectError {
+        span: Span,
+    },
+
+    /// Not an error of tsc. Reported only if `noCircularImports` is enabled.
+    ///
+    /// `cycle` is the list of files in the cycle, starting from the file
+    /// containing `span`.
+    CircularImport {
+        span: Span,
+        cycle: Vec<String>,
+    },
+
+    /// Not an error of tsc. Reported only if `noUnusedExports` is enabled.
+    ///
+    /// `name` is exported but not imported by any checked file.
+    UnusedExport {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// Not an error of tsc. Reported by the lint rule named `rule`, which is
+    /// registered to the type checker by the user.
+    Lint {
+        span: Span,
+        rule: String,
+        message: String,
+        /// See [ErrorKind::fixes].
+        fix: Option<Box<Fix>>,
+    },
 }
 
 #[cfg(target_pointer_width = "64")]
@@ -1653,6 +1839,7 @@ impl ErrorKind {
                 vec![Error {
                     contexts: Default::default(),
                     inner: box self,
+                    severity: None,
                 }]
             }
         }
@@ -1661,6 +1848,8 @@ impl ErrorKind {
     /// TypeScript error code.
     pub fn code(&self) -> usize {
         match self {
+            ErrorKind::PropertyIncompatible { cause, .. } | ErrorKind::ParamIncompatible { cause, .. } => cause.code(),
+
             ErrorKind::TS1016 { .. } => 1016,
             ErrorKind::TS1063 { .. } => 1063,
             ErrorKind::TS1094 { .. } => 1094,
@@ -1715,6 +1904,7 @@ impl ErrorKind {
             | ErrorKind::NoSuchPropertyInThis { .. }
             | ErrorKind::NoSuchPropertyInClass { .. }
             | ErrorKind::NoSuchPropertyInModule { .. } => 2339,
+            ErrorKind::NoSuchPropertyWithSuggestion { .. } => 2551,
 
             ErrorKind::AssignOpCannotBeApplied { .. } => 2365,
             ErrorKind::TypeUsedAsVar { .. } => 2693,
@@ -1748,6 +1938,7 @@ impl ErrorKind {
 
             ErrorKind::NoSuchVar { .. } => 2304,
             ErrorKind::NoSuchType { .. } => 2304,
+            ErrorKind::NoSuchVarWithSuggestion { .. } | ErrorKind::NoSuchTypeWithSuggestion { .. } => 2552,
             ErrorKind::NoSuchTypeButVarExists { .. } => 2749,
             ErrorKind::NoSuchVarButThisHasSuchProperty { .. } => 2663,
 
@@ -1953,6 +2144,8 @@ impl ErrorKind {
             ErrorKind::DuplicateVar { .. } => 2451,
 
             ErrorKind::TooManyAsterisk { .. } => 5061,
+            ErrorKind::CannotReadFile { .. } => 5012,
+            ErrorKind::CannotFindTypeDefinitionFile { .. } => 2688,
 
             ErrorKind::ModuleNotFound { .. } => 2307,
 
@@ -2069,6 +2262,23 @@ impl ErrorKind {
 
             ErrorKind::ExportAmbientModule { .. } => 2668,
 
+            ErrorKind::UmdGlobalInModule { .. } => 2686,
+
+            ErrorKind::ImportAttributeValueNotString { .. } => 2837,
+
+            ErrorKind::ReExportTypeWithIsolatedModules { .. } => 1205,
+
+            ErrorKind::ScriptWithIsolatedModules { .. } => 1208,
+
+            ErrorKind::AmbientConstEnumWithIsolatedModules { .. } => 2748,
+
+            ErrorKind::UnusedTsExpectError { .. } => 2578,
+
+            // Not errors of tsc. See `codes::STC_CODES`.
+            ErrorKind::CircularImport { .. } => 100001,
+            ErrorKind::UnusedExport { .. } => 100002,
+            ErrorKind::Lint { .. } => 100003,
+
             _ => 0,
         }
     }
@@ -2077,6 +2287,7 @@ impl ErrorKind {
         matches!(
             self,
             ErrorKind::NoSuchProperty { .. }
+                | ErrorKind::NoSuchPropertyWithSuggestion { .. }
                 | ErrorKind::NoSuchPropertyInClass { .. }
                 | ErrorKind::NoSuchPropertyInModule { .. }
                 | ErrorKind::NoSuchPropertyInThis { .. }
@@ -2086,7 +2297,10 @@ impl ErrorKind {
     pub fn is_var_not_found(&self) -> bool {
         matches!(
             self,
-            Self::NoSuchVar { .. } | Self::NoSuchVarButThisHasSuchProperty { .. } | Self::NoSuchVarForShorthand { .. }
+            Self::NoSuchVar { .. }
+                | Self::NoSuchVarWithSuggestion { .. }
+                | Self::NoSuchVarButThisHasSuchProperty { .. }
+                | Self::NoSuchVarForShorthand { .. }
         )
     }
 
@@ -2099,17 +2313,33 @@ impl ErrorKind {
     }
 
     pub fn is_type_not_found(&self) -> bool {
-        matches!(self, Self::NoSuchType { .. } | Self::NoSuchTypeButVarExists { .. })
+        matches!(
+            self,
+            Self::NoSuchType { .. } | Self::NoSuchTypeWithSuggestion { .. } | Self::NoSuchTypeButVarExists { .. }
+        )
     }
 
+    /// Returns `true` for the rules of stc which are not errors of tsc, like
+    /// `noUnusedExports` and lint rules. They are reported as warnings.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Self::CircularImport { .. } | Self::UnusedExport { .. } | Self::Lint { .. })
+    }
+
+    /// Flattens [ErrorKind::Errors] and [ErrorKind::TupleAssignError], and
+    /// removes duplicate errors and errors caused by a failure to resolve a
+    /// module or a name.
     #[cold]
     pub fn flatten(vec: Vec<Error>) -> Vec<Error> {
+        dedup::remove_follow_on_errors(dedup::dedup(Self::flatten_nested(vec)))
+    }
+
+    fn flatten_nested(vec: Vec<Error>) -> Vec<Error> {
         let mut buf = Vec::with_capacity(vec.len());
 
         for e in vec {
             match *e.inner {
                 ErrorKind::Errors { errors, .. } | ErrorKind::TupleAssignError { errors, .. } => {
-                    buf.extend(Self::flatten(errors).into_iter().map(|mut err| {
+                    buf.extend(Self::flatten_nested(errors).into_iter().map(|mut err| {
                         #[cfg(debug_assertions)]
                         for context in &e.contexts {
                             err.contexts.push(context.clone());
@@ -2140,6 +2370,24 @@ impl From<Errors> for ErrorKind {
     }
 }
 
+/// Information to make [ErrorKind::ModuleNotFound] actionable.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleNotFoundHint {
+    /// Paths tried while resolving the module specifier.
+    pub probed: Vec<String>,
+    pub suggestion: Option<ModuleSuggestion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleSuggestion {
+    /// A module specifier which would be resolved, like the one with the name
+    /// of a similar file or the relative path.
+    DidYouMean(String),
+    /// The package exists but does not have types, so `npm i --save-dev
+    /// <package>` would fix it.
+    InstallTypes(String),
+}
+
 /// A utility type to track
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Errors(Vec<Error>);
@@ -0,0 +1,196 @@
+//! Messages of errors like the messages of `tsc`, with the printed types and
+//! names in the error.
+
+use stc_ts_types::{name::Name, printer::print_type, Key, Type};
+
+use crate::{codes::message_template, Error, ErrorKind};
+
+impl ErrorKind {
+    /// Returns the message of `tsc` for the error, like `Type 'string' is not
+    /// assignable to type 'number'.`, to compare it with the baselines of
+    /// `tsc`.
+    ///
+    /// Falls back to the debug representation if the message of the code is
+    /// not known, or it needs names or types which the error does not have.
+    pub fn message(&self) -> String {
+        match self {
+            ErrorKind::Lint { message, .. } => message.clone(),
+            ErrorKind::PropertyIncompatible { cause, .. } | ErrorKind::ParamIncompatible { cause, .. } => cause.message(),
+            _ => self.tsc_message().unwrap_or_else(|| format!("{:?}", self)),
+        }
+    }
+
+    /// Returns [ErrorKind::message] followed by the errors which caused the
+    /// error, like the message chains of `tsc`.
+    ///
+    /// Each cause is on its own line, indented by two spaces for each level.
+    /// Causes without a message of `tsc`, like the errors grouping the errors
+    /// of properties, are not shown, but their causes are.
+    ///
+    /// ```text
+    /// Type '{ a: string; }' is not assignable to type '{ a: number; }'.
+    ///   Types of property 'a' are incompatible.
+    ///     Type 'string' is not assignable to type 'number'.
+    /// ```
+    pub fn message_chain(&self) -> String {
+        let mut chain = self.message();
+
+        for cause in self.head().causes() {
+            cause.push_chain(&mut chain, 1);
+        }
+
+        chain
+    }
+
+    fn push_chain(&self, chain: &mut String, depth: usize) {
+        let depth = match self.elaboration() {
+            Some(line) => {
+                chain.push('\n');
+                chain.push_str(&"  ".repeat(depth));
+                chain.push_str(&line);
+                depth + 1
+            }
+            None => depth,
+        };
+
+        for cause in self.causes() {
+            cause.push_chain(chain, depth);
+        }
+    }
+
+    /// The error whose message is [ErrorKind::message] of `self`.
+    fn head(&self) -> &ErrorKind {
+        match self {
+            ErrorKind::PropertyIncompatible { cause, .. } | ErrorKind::ParamIncompatible { cause, .. } => cause.head(),
+            ErrorKind::WrongArgType { inner, .. } if matches!(&***inner, ErrorKind::AssignFailed { .. }) => &***inner,
+            _ => self,
+        }
+    }
+
+    /// The line of the error in a message chain.
+    fn elaboration(&self) -> Option<String> {
+        match self {
+            ErrorKind::PropertyIncompatible { key: prop, .. } => fill(message_template(2326)?, &[key(prop)]),
+            ErrorKind::ParamIncompatible { left, right, .. } => {
+                fill(message_template(2328)?, &[right.sym().to_string(), left.sym().to_string()])
+            }
+            _ => self.tsc_message(),
+        }
+    }
+
+    /// The errors which are elaborated by the message chain of the error.
+    fn causes(&self) -> Vec<&Error> {
+        match self {
+            // Like tsc, only the member of the union which the value nearly matches is elaborated. The value nearly
+            // matches a member if it's the only member whose error has causes.
+            ErrorKind::AssignFailed { left, cause, .. } if matches!(left.normalize(), Type::Union(..)) => {
+                let nearly_matched = cause.iter().filter(|err| !err.head().causes().is_empty()).collect::<Vec<_>>();
+                if nearly_matched.len() == 1 {
+                    nearly_matched
+                } else {
+                    vec![]
+                }
+            }
+            ErrorKind::AssignFailed { cause: errors, .. }
+            | ErrorKind::SimpleAssignFailedWithCause { cause: errors, .. }
+            | ErrorKind::ObjectAssignFailed { errors, .. }
+            | ErrorKind::TupleAssignError { errors, .. }
+            | ErrorKind::UnionError { errors, .. }
+            | ErrorKind::Errors { errors, .. } => errors.iter().collect(),
+            ErrorKind::SimpleAssignFailed { cause: Some(cause), .. }
+            | ErrorKind::PropertyIncompatible { cause, .. }
+            | ErrorKind::ParamIncompatible { cause, .. } => vec![&**cause],
+            ErrorKind::WrongArgType { inner, .. } => vec![&**inner],
+            _ => vec![],
+        }
+    }
+
+    /// Returns [None] if the message of the code is not known, or it needs
+    /// names or types which the error does not have.
+    ///
+    /// Codes which are variants of another code, like `TS2551` which is
+    /// `TS2339` with a suggestion, use their own message if it's known.
+    fn tsc_message(&self) -> Option<String> {
+        let code = self.code();
+        let args = self.message_args();
+
+        message_template(code)
+            .and_then(|template| fill(template, &args))
+            .or_else(|| fill(message_template(ErrorKind::normalize_error_code(code))?, &args))
+    }
+
+    /// The arguments for the placeholders of [message_template], in order.
+    fn message_args(&self) -> Vec<String> {
+        match self {
+            ErrorKind::AssignFailed { left, right, .. } => vec![ty(right), ty(left)],
+            // The argument is assigned to the parameter.
+            ErrorKind::WrongArgType { inner, .. } => match &***inner {
+                ErrorKind::AssignFailed { .. } => inner.message_args(),
+                _ => vec![],
+            },
+            ErrorKind::NotSatisfyConstraint { left, right, .. } | ErrorKind::NoOverlap { left, right, .. } => vec![ty(left), ty(right)],
+            ErrorKind::InvalidBinaryOp { op, left, right, .. } | ErrorKind::CannotCompareWithOp { op, left, right, .. } => {
+                vec![op.to_string(), ty(left), ty(right)]
+            }
+            ErrorKind::NoSuchProperty {
+                obj: Some(obj),
+                prop: Some(prop),
+                ..
+            } => vec![key(prop), ty(obj)],
+            ErrorKind::NoSuchPropertyWithSuggestion { obj, prop, suggestion, .. } => vec![key(prop), ty(obj), suggestion.to_string()],
+            ErrorKind::NoSuchVarWithSuggestion { name, suggestion, .. } | ErrorKind::NoSuchTypeWithSuggestion { name, suggestion, .. } => {
+                vec![name.sym().to_string(), suggestion.to_string()]
+            }
+            ErrorKind::NoSuchVar { name, .. }
+            | ErrorKind::NoSuchType { name, .. }
+            | ErrorKind::DuplicateName { name, .. }
+            | ErrorKind::DuplicateVar { name, .. } => vec![name.sym().to_string()],
+            ErrorKind::TypeNotFound { name, .. } | ErrorKind::NamespaceNotFound { name, .. } => vec![print_name(name)],
+            ErrorKind::CannotReadFile { file, reason, .. } => vec![file.clone(), reason.clone()],
+            ErrorKind::CannotFindTypeDefinitionFile { name, .. } => vec![name.clone()],
+            _ => vec![],
+        }
+    }
+}
+
+/// Replaces the placeholders like `{0}` in `template` with `args`. Returns
+/// [None] if an argument is missing.
+fn fill(template: &str, args: &[String]) -> Option<String> {
+    let mut message = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        let index = rest[start + 1..end].parse::<usize>().ok()?;
+
+        message.push_str(&rest[..start]);
+        message.push_str(args.get(index)?);
+        rest = &rest[end + 1..];
+    }
+    message.push_str(rest);
+
+    Some(message)
+}
+
+fn ty(ty: &Type) -> String {
+    print_type(ty)
+}
+
+fn key(key: &Key) -> String {
+    match key {
+        Key::Normal { sym, .. } => sym.to_string(),
+        _ => format!("{:?}", key),
+    }
+}
+
+/// Prints `name` without the syntax context, like `ns.Foo`.
+fn print_name(name: &Name) -> String {
+    let (top, rest) = name.inner();
+
+    let mut printed = top.sym().to_string();
+    for sym in rest {
+        printed.push('.');
+        printed.push_str(sym);
+    }
+    printed
+}
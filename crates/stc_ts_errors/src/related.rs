@@ -0,0 +1,71 @@
+//! Locations related to an error, like `relatedInformation` of tsc.
+
+use swc_common::{Span, Spanned};
+
+use crate::{Error, ErrorKind};
+
+impl ErrorKind {
+    /// Returns the locations related to the error other than [Spanned::span],
+    /// with messages describing them.
+    ///
+    /// These are the other operand of the error, like the declaration of a
+    /// callee, where the expected type is declared, and the spans of the
+    /// errors which caused this error. Dummy spans and spans equal to the
+    /// span of the error are not returned.
+    ///
+    /// These are emitted as notes by [Error::emit].
+    pub fn related_spans(&self) -> Vec<(Span, String)> {
+        let mut spans = match self {
+            ErrorKind::ConstructorRequired { lhs, rhs, .. } => vec![
+                (*lhs, "The left operand is here".to_string()),
+                (*rhs, "The right operand is here".to_string()),
+            ],
+            ErrorKind::InvalidTupleCast { left, right, .. } => vec![
+                (*left, "The expression is here".to_string()),
+                (*right, "The target type is here".to_string()),
+            ],
+            ErrorKind::NotVariable { left, .. } => vec![(*left, "The assigned expression is here".to_string())],
+            ErrorKind::WrongTypeParams { callee, .. } | ErrorKind::WrongParams { callee, .. } => {
+                vec![(*callee, "The callee is declared here".to_string())]
+            }
+            ErrorKind::AssignFailed {
+                left, right_ident, cause, ..
+            } => std::iter::once((left.span(), "The expected type comes from here".to_string()))
+                .chain(
+                    right_ident
+                        .iter()
+                        .map(|span| (*span, "The assigned value is declared here".to_string())),
+                )
+                .chain(causes(cause))
+                .collect(),
+            ErrorKind::NoSuchVarWithSuggestion { suggestion, declared, .. }
+            | ErrorKind::NoSuchTypeWithSuggestion { suggestion, declared, .. } => {
+                vec![(*declared, format!("'{}' is declared here.", suggestion))]
+            }
+            ErrorKind::NotSatisfyConstraint { right, .. } => vec![(right.span(), "The constraint is declared here".to_string())],
+            ErrorKind::WrongArgType { inner, .. }
+            | ErrorKind::PropertyIncompatible { cause: inner, .. }
+            | ErrorKind::ParamIncompatible { cause: inner, .. } => inner.related_spans(),
+            ErrorKind::InvalidInterfaceInheritance { cause, .. }
+            | ErrorKind::VarDeclNotCompatible { cause, .. }
+            | ErrorKind::IncompatibleFnOverload { cause, .. }
+            | ErrorKind::InvalidImplOfInterface { cause, .. }
+            | ErrorKind::IntersectionError { error: cause, .. } => causes(std::slice::from_ref(&**cause)).collect(),
+            ErrorKind::SimpleAssignFailed { cause: Some(cause), .. } => causes(std::slice::from_ref(&**cause)).collect(),
+            ErrorKind::TupleAssignError { errors, .. }
+            | ErrorKind::ObjectAssignFailed { errors, .. }
+            | ErrorKind::SimpleAssignFailedWithCause { cause: errors, .. }
+            | ErrorKind::UnionError { errors, .. } => causes(errors).collect(),
+            _ => vec![],
+        };
+
+        let span = self.span();
+        spans.retain(|(related, _)| !related.is_dummy() && *related != span);
+        spans
+    }
+}
+
+/// The spans and the messages of errors which caused an error.
+fn causes(errors: &[Error]) -> impl '_ + Iterator<Item = (Span, String)> {
+    errors.iter().map(|err| (err.span(), err.message()))
+}
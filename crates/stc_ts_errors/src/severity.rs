@@ -0,0 +1,80 @@
+//! Severities of errors, which can be overridden for each error code.
+
+use std::str::FromStr;
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, ErrorKind};
+
+/// How an error is reported. Only errors fail the check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl FromStr for Severity {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Severity::Error),
+            "warning" => Ok(Severity::Warning),
+            "info" => Ok(Severity::Info),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Severities of error codes which replace the default severities, so that
+/// checks which are noisy or not accurate yet can be downgraded or turned off.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeverityOverrides {
+    /// Keyed by the normalized code. [None] turns off the errors of the code.
+    codes: FxHashMap<usize, Option<Severity>>,
+}
+
+impl SeverityOverrides {
+    /// Reports the errors of `code` with `severity`, or does not report them if
+    /// `severity` is [None]. `code` is normalized by
+    /// [ErrorKind::normalize_error_code].
+    pub fn with_code(mut self, code: usize, severity: Option<Severity>) -> Self {
+        self.codes.insert(ErrorKind::normalize_error_code(code), severity);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Applies the overrides to `errors`, removing the errors which are turned
+    /// off.
+    pub fn apply(&self, errors: Vec<Error>) -> Vec<Error> {
+        if self.is_empty() {
+            return errors;
+        }
+
+        errors
+            .into_iter()
+            .filter_map(|err| match self.codes.get(&ErrorKind::normalize_error_code(err.code())) {
+                Some(Some(severity)) => Some(err.with_severity(*severity)),
+                Some(None) => None,
+                None => Some(err),
+            })
+            .collect()
+    }
+}
+
+impl ErrorKind {
+    /// The severity of the error if it's not overridden.
+    pub fn default_severity(&self) -> Severity {
+        if self.is_warning() {
+            Severity::Warning
+        } else {
+            Severity::Error
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use stc_ts_errors::{
+    codes::{error_code, message_template, ERROR_CODES, STC_CODES},
+    ErrorKind,
+};
+use swc_common::DUMMY_SP;
+
+#[test]
+fn sorted_and_unique() {
+    let codes = ERROR_CODES.iter().map(|c| c.code).collect::<Vec<_>>();
+
+    assert!(codes.windows(2).all(|w| w[0] < w[1]), "codes should be sorted and unique");
+    assert!(ERROR_CODES.iter().all(|c| c.tsc != STC_CODES.contains(&c.code)));
+}
+
+#[test]
+fn lookup() {
+    let code = error_code(2322).unwrap();
+    assert_eq!(code.name, "AssignFailed");
+    assert_eq!(code.description, "Type '{0}' is not assignable to type '{1}'.");
+
+    // TS2550 is TS2339 with a suggestion to change `lib`.
+    assert_eq!(error_code(2550).unwrap().code, 2339);
+    assert_eq!(error_code(2551).unwrap().name, "NoSuchPropertyWithSuggestion");
+
+    assert_eq!(error_code(0), None);
+    assert_eq!(error_code(99999), None);
+}
+
+#[test]
+fn registered() {
+    let err = ErrorKind::UnusedTsExpectError { span: DUMMY_SP };
+    assert_eq!(error_code(err.code()).unwrap().name, "UnusedTsExpectError");
+
+    let err = ErrorKind::CircularImport {
+        span: DUMMY_SP,
+        cycle: vec![],
+    };
+    assert_eq!(error_code(err.code()).unwrap().name, "CircularImport");
+    assert!(!error_code(err.code()).unwrap().tsc);
+}
+
+/// The source of [ErrorKind]. Variants can't be listed at runtime, so they are
+/// found by parsing the source.
+const SOURCE: &str = include_str!("../src/lib.rs");
+
+/// Variants which are reported without a code, like wrappers of other errors.
+const UNREGISTERED: &[&str] = &[
+    "AssignFailedDueToOptionalityDifference",
+    "CannotAssignToThis",
+    "ClassPropertyInitRequired",
+    "ConstructorRequired",
+    "DestructuringAssignInAmbientContext",
+    "Errors",
+    "ExportAllFailed",
+    "ExportFailed",
+    "ImportFailed",
+    "IntersectionError",
+    "InvalidEnumInit",
+    "InvalidOperatorForLhs",
+    "MayBeUndefined",
+    "ModuleLoadFailed",
+    "NoSuchConstructor",
+    "NoSuchExport",
+    "NotTuple",
+    "ParameterCountMismatch",
+    "PrivateMethodIsDifferent",
+    "PrivatePropertyIsDifferent",
+    "RedeclaredVarWithDifferentType",
+    "ResolvedFailed",
+    "StackOverflow",
+    "TooManyTupleElements",
+    "UndefinedSymbol",
+    "Unimplemented",
+    "UnionError",
+    "WrongParams",
+    "WrongTypeParams",
+];
+
+/// Returns the part of [SOURCE] from `start` to the first `end` after it.
+fn source_between(start: &str, end: &str) -> &'static str {
+    let start = SOURCE.find(start).unwrap_or_else(|| panic!("`{}` is not found", start));
+    let len = SOURCE[start..].find(end).unwrap();
+
+    &SOURCE[start..start + len]
+}
+
+fn ident(s: &str) -> &str {
+    s.split(|c: char| !c.is_alphanumeric() && c != '_').next().unwrap()
+}
+
+/// Returns the names of the variants of [ErrorKind].
+fn variants() -> Vec<&'static str> {
+    source_between("pub enum ErrorKind {", "\n}\n")
+        .lines()
+        .filter_map(|line| line.strip_prefix("    "))
+        .filter(|line| line.starts_with(char::is_uppercase))
+        .map(ident)
+        .collect()
+}
+
+/// Returns the arms of [ErrorKind::code], as the variants matched by each arm
+/// and the code if it's a literal.
+///
+/// The arms of a nested match belong to the variant of the outer arm.
+fn code_arms() -> Vec<(Vec<&'static str>, Option<usize>)> {
+    let body = source_between("    pub fn code(&self) -> usize {", "\n    }\n");
+
+    let mut arms = vec![];
+    let mut variants = vec![];
+    let mut prev = 0;
+    while let Some(pos) = body[prev..].find("=>").map(|pos| prev + pos) {
+        let pattern = body[prev..pos].split("ErrorKind::").skip(1).map(ident).collect::<Vec<_>>();
+        if !pattern.is_empty() {
+            variants = pattern;
+        }
+
+        let code = body[pos + 2..].trim_start();
+        let code = code[..code.find(|c: char| !c.is_ascii_digit()).unwrap_or(code.len())].parse().ok();
+        // `_ => 0` is the fallback.
+        if code != Some(0) {
+            arms.push((variants.clone(), code));
+        }
+
+        prev = pos + 2;
+    }
+
+    arms
+}
+
+#[test]
+fn every_kind_is_registered() {
+    let arms = code_arms();
+
+    for variant in variants() {
+        let matched = arms.iter().any(|(variants, _)| variants.contains(&variant));
+        assert_eq!(
+            matched,
+            !UNREGISTERED.contains(&variant),
+            "`ErrorKind::{}` should be matched by `ErrorKind::code` unless it's in `UNREGISTERED`",
+            variant
+        );
+    }
+
+    for (variants, code) in &arms {
+        let code = match code {
+            Some(code) => *code,
+            None => continue,
+        };
+
+        assert!(
+            ERROR_CODES.iter().any(|c| c.code == code),
+            "TS{} of {:?} is not in `ERROR_CODES`",
+            code,
+            variants
+        );
+    }
+
+    // Codes reported by multiple kinds are registered with one of them.
+    for c in ERROR_CODES {
+        assert!(
+            arms.iter()
+                .any(|(variants, code)| *code == Some(c.code) && variants.contains(&c.name)),
+            "TS{} is not reported by `ErrorKind::{}`",
+            c.code,
+            c.name
+        );
+    }
+}
+
+#[test]
+fn descriptions() {
+    for c in ERROR_CODES {
+        assert!(!c.description.is_empty(), "{}", c.name);
+
+        // The message of tsc is used if it's known.
+        if let Some(template) = message_template(c.code) {
+            assert_eq!(c.description, template, "TS{}", c.code);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_types::{Id, Type};
+use swc_common::{BytePos, Span, Spanned, SyntaxContext};
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::new(BytePos(lo), BytePos(hi), SyntaxContext::empty())
+}
+
+fn assign_failed(span: Span) -> Error {
+    ErrorKind::AssignFailed {
+        span,
+        left: Box::new(Type::never(span, Default::default())),
+        right_ident: None,
+        right: Box::new(Type::void(span, Default::default())),
+        cause: vec![],
+    }
+    .into()
+}
+
+fn codes_and_spans(errors: Vec<Error>) -> Vec<(usize, Span)> {
+    errors.iter().map(|err| (err.code(), err.span())).collect()
+}
+
+#[test]
+fn duplicates() {
+    let nested = ErrorKind::Errors {
+        span: span(1, 5),
+        errors: vec![assign_failed(span(1, 5)), assign_failed(span(6, 9))],
+    };
+
+    let errors = ErrorKind::flatten(vec![assign_failed(span(1, 5)), nested.into()]);
+
+    assert_eq!(codes_and_spans(errors), vec![(2322, span(1, 5)), (2322, span(6, 9))]);
+}
+
+#[test]
+fn different_messages() {
+    let no_such_var = |name: &str| -> Error {
+        ErrorKind::NoSuchVar {
+            span: span(1, 5),
+            name: Id::word(name.into()),
+        }
+        .into()
+    };
+
+    let errors = ErrorKind::flatten(vec![no_such_var("foo"), no_such_var("bar"), no_such_var("foo")]);
+
+    assert_eq!(
+        errors.iter().map(|err| err.message()).collect::<Vec<_>>(),
+        vec!["Cannot find name 'foo'.", "Cannot find name 'bar'."]
+    );
+}
+
+#[test]
+fn lint_duplicates() {
+    let lint = |rule: &str| -> Error {
+        ErrorKind::Lint {
+            span: span(1, 5),
+            rule: rule.into(),
+            message: "Do not use foo".into(),
+            fix: None,
+        }
+        .into()
+    };
+
+    // Errors of lint rules are removed only if they are equal.
+    let errors = ErrorKind::flatten(vec![lint("no-foo"), lint("no-foo"), lint("no-bar")]);
+
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn follow_on_errors() {
+    let not_found = ErrorKind::ModuleNotFound {
+        span: span(1, 30),
+        hint: None,
+    };
+    let no_such_var = ErrorKind::NoSuchVar {
+        span: span(40, 43),
+        name: Id::word("foo".into()),
+    };
+
+    let errors = ErrorKind::flatten(vec![
+        not_found.into(),
+        assign_failed(span(10, 20)),
+        no_such_var.into(),
+        assign_failed(span(40, 43)),
+        assign_failed(span(40, 50)),
+    ]);
+
+    assert_eq!(
+        codes_and_spans(errors),
+        vec![(2307, span(1, 30)), (2304, span(40, 43)), (2322, span(40, 50))]
+    );
+}
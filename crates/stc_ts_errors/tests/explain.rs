@@ -0,0 +1,41 @@
+use stc_ts_errors::explain::explain;
+
+#[test]
+fn explain_code() {
+    let explanation = explain(2322).unwrap();
+    assert_eq!(explanation.code, 2322);
+    assert_eq!(explanation.message, "Type '{0}' is not assignable to type '{1}'.");
+    assert!(!explanation.causes.is_empty());
+}
+
+#[test]
+fn normalized_code() {
+    // TS2551 is TS2339 with a suggestion.
+    let explanation = explain(2551).unwrap();
+    assert_eq!(explanation.code, 2339);
+    assert_eq!(
+        explanation.message,
+        "Property '{0}' does not exist on type '{1}'. Did you mean '{2}'?"
+    );
+
+    // The message of the given code is used if it's known.
+    let explanation = explain(18048).unwrap();
+    assert_eq!(explanation.code, 2532);
+    assert_eq!(explanation.message, "'{0}' is possibly 'undefined'.");
+}
+
+#[test]
+fn unknown_code() {
+    assert_eq!(explain(1), None);
+}
+
+#[test]
+fn all_explanations() {
+    for code in 0..20000 {
+        if let Some(explanation) = explain(code) {
+            assert!(!explanation.description.is_empty(), "TS{}", code);
+            assert!(!explanation.causes.is_empty(), "TS{}", code);
+            assert!(!explanation.example.is_empty(), "TS{}", code);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::PathBuf;
+
+use stc_ts_ast_rnode::{RIdent, RTsEntityName};
+use stc_ts_errors::{Edit, ErrorKind, Fix};
+use stc_ts_types::{Id, Key, PropertySignature, Ref, Type, TypeElement};
+use swc_common::{BytePos, FileName, SourceMap, Span, SyntaxContext};
+
+/// Creates a source map with `src` and returns the span of `lo..hi` in it.
+fn source(src: &str) -> (SourceMap, impl Fn(u32, u32) -> Span) {
+    let cm = SourceMap::default();
+    let start = cm
+        .new_source_file(FileName::Real(PathBuf::from("/project/main.ts")), src.into())
+        .start_pos;
+
+    (cm, move |lo, hi| {
+        Span::new(start + BytePos(lo), start + BytePos(hi), SyntaxContext::empty())
+    })
+}
+
+/// Applies the only fix of `err` to the source code.
+fn apply(cm: &SourceMap, err: &ErrorKind) -> String {
+    let fixes = err.fixes(cm);
+    assert_eq!(fixes.len(), 1, "{:?}", fixes);
+
+    let mut edits = fixes[0].edits.clone();
+    let fm = cm.lookup_byte_offset(edits[0].span.lo).sf;
+    let mut src = fm.src.to_string();
+    edits.sort_by_key(|edit| edit.span.lo);
+    for edit in edits.into_iter().rev() {
+        let lo = (edit.span.lo - fm.start_pos).0 as usize;
+        let hi = (edit.span.hi - fm.start_pos).0 as usize;
+        src.replace_range(lo..hi, &edit.new_text);
+    }
+    src
+}
+
+fn property(name: &str) -> TypeElement {
+    TypeElement::Property(PropertySignature {
+        span: Default::default(),
+        accessibility: None,
+        readonly: false,
+        key: Key::Normal {
+            span: Default::default(),
+            sym: name.into(),
+        },
+        optional: false,
+        params: Default::default(),
+        type_ann: None,
+        type_params: Default::default(),
+        metadata: Default::default(),
+        accessor: Default::default(),
+    })
+}
+
+#[test]
+fn missing_await() {
+    let (cm, span) = source("p.length;\n");
+    let promise = Type::Ref(Ref {
+        span: span(0, 1),
+        type_name: RTsEntityName::Ident(RIdent::new("Promise".into(), span(0, 1))),
+        type_args: None,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    });
+
+    let err = ErrorKind::NoSuchProperty {
+        span: span(0, 8),
+        obj: Some(Box::new(promise)),
+        prop: Some(Box::new(Key::Normal {
+            span: span(2, 8),
+            sym: "length".into(),
+        })),
+    };
+    assert_eq!(err.fixes(&cm)[0].message, "Add missing 'await'");
+    assert_eq!(apply(&cm, &err), "(await p).length;\n");
+
+    // Other objects don't have the fix.
+    let err = ErrorKind::NoSuchProperty {
+        span: span(0, 8),
+        obj: Some(Box::new(Type::never(span(0, 1), Default::default()))),
+        prop: None,
+    };
+    assert_eq!(err.fixes(&cm), vec![]);
+}
+
+#[test]
+fn add_undefined() {
+    let (cm, span) = source("let a: never = undefined;\nlet b = a;\n");
+    let assign_failed = |left: Span| ErrorKind::AssignFailed {
+        span: span(4, 5),
+        left: Box::new(Type::never(left, Default::default())),
+        right_ident: None,
+        right: Box::new(Type::undefined(span(15, 24), Default::default())),
+        cause: vec![],
+    };
+
+    assert_eq!(
+        apply(&cm, &assign_failed(span(7, 12))),
+        "let a: never | undefined = undefined;\nlet b = a;\n"
+    );
+
+    // The type is not from a type annotation.
+    assert_eq!(assign_failed(span(34, 35)).fixes(&cm), vec![]);
+}
+
+#[test]
+fn missing_properties() {
+    let (cm, span) = source("const a: A = { x: 1 };\nconst b: A = {};\n");
+
+    let err = ErrorKind::MissingFields {
+        span: span(13, 21),
+        fields: vec![property("y")],
+    };
+    assert_eq!(err.fixes(&cm)[0].message, "Add missing property");
+    assert_eq!(apply(&cm, &err), "const a: A = { x: 1, y: undefined };\nconst b: A = {};\n");
+
+    let err = ErrorKind::MissingFields {
+        span: span(36, 38),
+        fields: vec![property("x"), property("a-b")],
+    };
+    assert_eq!(err.fixes(&cm)[0].message, "Add missing properties");
+    assert_eq!(
+        apply(&cm, &err),
+        "const a: A = { x: 1 };\nconst b: A = { x: undefined, \"a-b\": undefined };\n"
+    );
+
+    // Not an object literal.
+    let err = ErrorKind::MissingFields {
+        span: span(6, 7),
+        fields: vec![property("y")],
+    };
+    assert_eq!(err.fixes(&cm), vec![]);
+}
+
+#[test]
+fn lint() {
+    let (cm, span) = source("a == b;\n");
+    let fix = Fix {
+        message: "Use '==='".into(),
+        edits: vec![Edit::replace(span(2, 4), "===")],
+    };
+
+    let err = ErrorKind::Lint {
+        span: span(0, 6),
+        rule: "eqeqeq".into(),
+        message: "Use '===' instead of '=='".into(),
+        fix: Some(Box::new(fix)),
+    };
+    assert_eq!(apply(&cm, &err), "a === b;\n");
+}
+
+#[test]
+fn change_spelling() {
+    let (cm, span) = source("coutn;\n");
+
+    let err = ErrorKind::NoSuchVarWithSuggestion {
+        span: span(0, 5),
+        name: Id::word("coutn".into()),
+        suggestion: "count".into(),
+        declared: Default::default(),
+    };
+    assert_eq!(err.fixes(&cm)[0].message, "Change spelling to 'count'");
+    assert_eq!(apply(&cm, &err), "count;\n");
+}
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use stc_ts_errors::{FrameRenderer, Report, Severity};
+use swc_common::{BytePos, FileName, SourceMap, Span, SyntaxContext, DUMMY_SP};
+
+/// Creates a source map with `src` and returns the span of `lo..hi` in it.
+fn source(src: &str) -> (SourceMap, impl Fn(u32, u32) -> Span) {
+    let cm = SourceMap::default();
+    let start = cm
+        .new_source_file(FileName::Real(PathBuf::from("/project/main.ts")), src.into())
+        .start_pos;
+
+    (cm, move |lo, hi| {
+        Span::new(start + BytePos(lo), start + BytePos(hi), SyntaxContext::empty())
+    })
+}
+
+fn report(span: Span, code: usize, message: &str) -> Report {
+    Report {
+        span,
+        severity: Severity::Error,
+        code,
+        message: message.into(),
+        related: vec![],
+    }
+}
+
+#[test]
+fn single_line() {
+    let (cm, span) = source("let a: number = \"\";\n");
+    let report = report(span(4, 5), 2322, "Type 'string' is not assignable to type 'number'.");
+
+    assert_eq!(
+        FrameRenderer::default().render(&cm, &report),
+        "/project/main.ts:1:5 - error TS2322: Type 'string' is not assignable to type 'number'.
+
+1 let a: number = \"\";
+      ~
+
+"
+    );
+
+    let rendered = FrameRenderer::default().with_cwd(PathBuf::from("/project")).render(&cm, &report);
+    assert!(rendered.starts_with("main.ts:1:5 - error TS2322"), "{}", rendered);
+}
+
+#[test]
+fn multiple_lines() {
+    let (cm, span) = source("f({\n  a: 1,\n  b: 2,\n  c: 3,\n});\n");
+    let report = Report {
+        severity: Severity::Warning,
+        ..report(
+            span(2, 29),
+            2345,
+            "Argument is not assignable to the parameter.\n  Property 'c' is extra.",
+        )
+    };
+
+    // The third line is elided.
+    assert_eq!(
+        FrameRenderer::default().render(&cm, &report),
+        "/project/main.ts:1:3 - warning TS2345: Argument is not assignable to the parameter.
+  Property 'c' is extra.
+
+1 f({
+    ~
+2   a: 1,
+  ~~~~~~~
+...
+4   c: 3,
+  ~~~~~~~
+5 });
+  ~
+
+"
+    );
+}
+
+#[test]
+fn related() {
+    let (cm, span) = source("const count = 1;\ncoutn;\n");
+    let report = Report {
+        related: vec![
+            (span(6, 11), "'count' is declared here.".into()),
+            (DUMMY_SP, "Not in a file.".into()),
+        ],
+        ..report(span(17, 22), 2304, "Cannot find name 'coutn'. Did you mean 'count'?")
+    };
+
+    assert_eq!(
+        FrameRenderer::default().render(&cm, &report),
+        "/project/main.ts:2:1 - error TS2304: Cannot find name 'coutn'. Did you mean 'count'?
+
+2 coutn;
+  ~~~~~
+
+  /project/main.ts:1:7
+    1 const count = 1;
+            ~~~~~
+    'count' is declared here.
+
+    Not in a file.
+
+"
+    );
+}
+
+#[test]
+fn no_location() {
+    let (cm, _) = source("");
+
+    assert_eq!(
+        FrameRenderer::default().render(&cm, &report(DUMMY_SP, 2318, "Cannot find global type 'Array'.")),
+        "error TS2318: Cannot find global type 'Array'.\n\n"
+    );
+}
+
+#[test]
+fn color() {
+    let (cm, span) = source("a;\n");
+    let rendered = FrameRenderer::default()
+        .with_color(true)
+        .render(&cm, &report(span(0, 1), 2304, "Cannot find name 'a'."));
+
+    assert!(rendered.contains("\u{1b}[31merror\u{1b}[0m"), "{:?}", rendered);
+    assert!(rendered.contains("\u{1b}[31m~\u{1b}[0m"), "{:?}", rendered);
+}
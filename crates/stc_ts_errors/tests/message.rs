@@ -0,0 +1,176 @@
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_types::{Id, Key, Type};
+use swc_common::DUMMY_SP;
+
+#[test]
+fn assign_failed() {
+    let err = ErrorKind::AssignFailed {
+        span: DUMMY_SP,
+        left: Box::new(Type::never(DUMMY_SP, Default::default())),
+        right_ident: None,
+        right: Box::new(Type::void(DUMMY_SP, Default::default())),
+        cause: vec![],
+    };
+
+    assert_eq!(err.message(), "Type 'void' is not assignable to type 'never'.");
+
+    // The argument is assigned to the parameter.
+    let err = ErrorKind::WrongArgType {
+        span: DUMMY_SP,
+        inner: Box::new(err.into()),
+    };
+    assert_eq!(
+        err.message(),
+        "Argument of type 'void' is not assignable to parameter of type 'never'."
+    );
+}
+
+#[test]
+fn name() {
+    let err = ErrorKind::NoSuchVar {
+        span: DUMMY_SP,
+        name: Id::word("foo".into()),
+    };
+
+    assert_eq!(err.message(), "Cannot find name 'foo'.");
+}
+
+#[test]
+fn lint() {
+    let err = ErrorKind::Lint {
+        span: DUMMY_SP,
+        rule: "no-foo".into(),
+        message: "Do not use foo".into(),
+        fix: None,
+    };
+
+    assert_eq!(err.message(), "Do not use foo");
+}
+
+#[test]
+fn unknown_message() {
+    let err = ErrorKind::DefinedWithAccessorInSuper { span: DUMMY_SP };
+
+    assert_eq!(err.message(), format!("{:?}", err));
+}
+
+fn assign_failed_with_cause(left: Type, right: Type, cause: Vec<Error>) -> ErrorKind {
+    ErrorKind::AssignFailed {
+        span: DUMMY_SP,
+        left: Box::new(left),
+        right_ident: None,
+        right: Box::new(right),
+        cause,
+    }
+}
+
+#[test]
+fn property_chain() {
+    let prop = assign_failed_with_cause(
+        Type::never(DUMMY_SP, Default::default()),
+        Type::void(DUMMY_SP, Default::default()),
+        vec![],
+    );
+    let prop = ErrorKind::PropertyIncompatible {
+        span: DUMMY_SP,
+        key: Key::Normal {
+            span: DUMMY_SP,
+            sym: "a".into(),
+        },
+        cause: Box::new(prop.into()),
+    };
+    // Errors grouping the errors of properties are not shown.
+    let grouped = ErrorKind::ObjectAssignFailed {
+        span: DUMMY_SP,
+        errors: vec![prop.clone().into()],
+    };
+    let err = assign_failed_with_cause(
+        Type::unknown(DUMMY_SP, Default::default()),
+        Type::any(DUMMY_SP, Default::default()),
+        vec![grouped.into()],
+    );
+
+    assert_eq!(
+        err.message_chain(),
+        "Type 'any' is not assignable to type 'unknown'.\n  Types of property 'a' are incompatible.\n    Type 'void' is not assignable to \
+         type 'never'."
+    );
+
+    // The error of a property is reported as the error of the property.
+    assert_eq!(prop.message_chain(), "Type 'void' is not assignable to type 'never'.");
+}
+
+#[test]
+fn param_chain() {
+    let param = ErrorKind::ParamIncompatible {
+        span: DUMMY_SP,
+        right: Id::word("x".into()),
+        left: Id::word("y".into()),
+        cause: Box::new(
+            assign_failed_with_cause(
+                Type::never(DUMMY_SP, Default::default()),
+                Type::void(DUMMY_SP, Default::default()),
+                vec![],
+            )
+            .into(),
+        ),
+    };
+    let err = assign_failed_with_cause(
+        Type::unknown(DUMMY_SP, Default::default()),
+        Type::any(DUMMY_SP, Default::default()),
+        vec![param.into()],
+    );
+
+    assert_eq!(
+        err.message_chain().lines().skip(1).collect::<Vec<_>>(),
+        vec![
+            "  Types of parameters 'x' and 'y' are incompatible.",
+            "    Type 'void' is not assignable to type 'never'."
+        ]
+    );
+}
+
+#[test]
+fn union_chain() {
+    let shallow = assign_failed_with_cause(
+        Type::void(DUMMY_SP, Default::default()),
+        Type::any(DUMMY_SP, Default::default()),
+        vec![],
+    );
+    let nearly_matched = assign_failed_with_cause(
+        Type::undefined(DUMMY_SP, Default::default()),
+        Type::any(DUMMY_SP, Default::default()),
+        vec![
+            ErrorKind::DefinedWithAccessorInSuper { span: DUMMY_SP }.into(),
+            shallow.clone().into(),
+        ],
+    );
+    let union = Type::new_union(
+        DUMMY_SP,
+        vec![
+            Type::void(DUMMY_SP, Default::default()),
+            Type::undefined(DUMMY_SP, Default::default()),
+        ],
+    );
+
+    // Only the member which the value nearly matches is elaborated.
+    let err = assign_failed_with_cause(
+        union.clone(),
+        Type::any(DUMMY_SP, Default::default()),
+        vec![shallow.clone().into(), nearly_matched.into()],
+    );
+    assert_eq!(
+        err.message_chain().lines().skip(1).collect::<Vec<_>>(),
+        vec![
+            "  Type 'any' is not assignable to type 'undefined'.",
+            "    Type 'any' is not assignable to type 'void'."
+        ]
+    );
+
+    let err = assign_failed_with_cause(
+        union,
+        Type::any(DUMMY_SP, Default::default()),
+        vec![shallow.clone().into(), shallow.into()],
+    );
+    assert_eq!(err.message_chain().lines().count(), 1);
+}
@@ -0,0 +1,68 @@
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::Type;
+use swc_common::{BytePos, Span, SyntaxContext, DUMMY_SP};
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::new(BytePos(lo), BytePos(hi), SyntaxContext::empty())
+}
+
+#[test]
+fn callee() {
+    let err = ErrorKind::WrongParams {
+        span: span(10, 20),
+        callee: span(1, 5),
+        expected: 1..=1,
+        actual: 2,
+    };
+
+    assert_eq!(err.related_spans(), vec![(span(1, 5), "The callee is declared here".to_string())]);
+}
+
+#[test]
+fn expected_type() {
+    let err = ErrorKind::AssignFailed {
+        span: span(10, 20),
+        left: Box::new(Type::any(span(1, 4), Default::default())),
+        right_ident: Some(span(5, 6)),
+        right: Box::new(Type::any(span(10, 20), Default::default())),
+        cause: vec![],
+    };
+
+    assert_eq!(
+        err.related_spans(),
+        vec![
+            (span(1, 4), "The expected type comes from here".to_string()),
+            (span(5, 6), "The assigned value is declared here".to_string()),
+        ]
+    );
+
+    // Related spans of the assignment are the related spans of the argument.
+    let err = ErrorKind::WrongArgType {
+        span: span(10, 20),
+        inner: Box::new(err.into()),
+    };
+    assert_eq!(err.related_spans().len(), 2);
+}
+
+#[test]
+fn causes() {
+    let cause = ErrorKind::DefinedWithAccessorInSuper { span: span(3, 4) };
+    let err = ErrorKind::SimpleAssignFailedWithCause {
+        span: span(1, 5),
+        cause: vec![
+            cause.clone().into(),
+            // Dummy spans and the span of the error itself are not related locations.
+            ErrorKind::DefinedWithAccessorInSuper { span: DUMMY_SP }.into(),
+            ErrorKind::DefinedWithAccessorInSuper { span: span(1, 5) }.into(),
+        ],
+    };
+
+    assert_eq!(err.related_spans(), vec![(span(3, 4), cause.message())]);
+}
+
+#[test]
+fn no_related_spans() {
+    let err = ErrorKind::DefinedWithAccessorInSuper { span: span(1, 5) };
+
+    assert_eq!(err.related_spans(), vec![]);
+}
@@ -665,7 +665,15 @@ impl Analyzer<'_, '_> {
             }
         }
 
-        self.assign_param_type(data, &l.ty, &r.ty, opts)
+        self.assign_param_type(data, &l.ty, &r.ty, opts).convert_err(|err| match (&l.pat, &r.pat) {
+            (RPat::Ident(l_name), RPat::Ident(r_name)) => ErrorKind::ParamIncompatible {
+                span: err.span(),
+                right: r_name.id.clone().into(),
+                left: l_name.id.clone().into(),
+                cause: Box::new(err.into()),
+            },
+            _ => err,
+        })
     }
 
     /// Implementation of `assign_param`.
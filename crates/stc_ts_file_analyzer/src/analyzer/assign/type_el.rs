@@ -1130,7 +1130,12 @@ impl Analyzer<'_, '_> {
                                                 rp.type_ann.as_deref().unwrap_or(&Type::any(span, Default::default())),
                                                 opts,
                                             )
-                                        })()?;
+                                        })()
+                                        .convert_err(|err| ErrorKind::PropertyIncompatible {
+                                            span: err.span(),
+                                            key: l_key.clone(),
+                                            cause: Box::new(err.into()),
+                                        })?;
 
                                         if let Some(pos) = unhandled_rhs.iter().position(|span| *span == rm.span()) {
                                             unhandled_rhs.remove(pos);
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, time::Instant};
+use std::borrow::Cow;
 
 use itertools::Itertools;
 use stc_ts_ast_rnode::{RArrayLit, RExpr, RExprOrSpread, RInvalid, RNumber, RTsLit};
@@ -14,6 +14,7 @@ use stc_ts_types::{
 use stc_utils::{
     cache::Freeze,
     ext::{SpanExt, TypeVecExt},
+    time::Instant,
 };
 use swc_atoms::js_word;
 use swc_common::{Span, Spanned, SyntaxContext};
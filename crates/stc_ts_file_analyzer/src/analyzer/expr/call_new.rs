@@ -111,11 +111,9 @@ impl Analyzer<'_, '_> {
             }
             RCallee::Expr(callee) => callee,
             RCallee::Import(..) => {
-                return Err(ErrorKind::Unimplemented {
-                    span: e.span,
-                    msg: "validation of dynamic import".to_string(),
-                }
-                .into())
+                let args = self.validate_args(args)?;
+
+                return Ok(self.type_of_dynamic_import(span, &args));
             }
         };
 
@@ -379,22 +377,34 @@ impl Analyzer<'_, '_> {
 
                 let spread_arg_types = self.spread_args(&arg_types).context("tried to handle spreads in arguments")?;
 
-                return self
-                    .call_property(
-                        span,
-                        kind,
-                        expr,
-                        &obj_type,
-                        &obj_type,
-                        &prop,
-                        type_args.as_ref(),
-                        args,
-                        &arg_types,
-                        &spread_arg_types,
-                        type_ann,
-                        Default::default(),
-                    )
-                    .map(|ty| ty.fixed());
+                let ret_ty = self.call_property(
+                    span,
+                    kind,
+                    expr,
+                    &obj_type,
+                    &obj_type,
+                    &prop,
+                    type_args.as_ref(),
+                    args,
+                    &arg_types,
+                    &spread_arg_types,
+                    type_ann,
+                    Default::default(),
+                );
+
+                // The type of the method is not recorded by validating the callee.
+                // `call_property` records it if it looks up the property, and it's
+                // looked up here only if the method is found from the members.
+                if let Some(recorder) = self.type_recorder.clone() {
+                    if recorder.type_of(callee.span()).is_none() {
+                        let callee_ty = self.access_property(span, &obj_type, &prop, TypeOfMode::RValue, IdCtx::Var, Default::default());
+                        if let Ok(callee_ty) = callee_ty {
+                            recorder.record(callee.span(), &callee_ty);
+                        }
+                    }
+                }
+
+                return ret_ty.map(|ty| ty.fixed());
             }
             _ => {}
         }
@@ -831,6 +841,10 @@ impl Analyzer<'_, '_> {
                 .access_property(span, &obj_type, prop, TypeOfMode::RValue, IdCtx::Var, Default::default())
                 .context("tried to access property to call it")?;
 
+            if let (Some(recorder), Some(callee_span)) = (&self.type_recorder, expr.member_callee_span()) {
+                recorder.record(callee_span, &callee);
+            }
+
             let callee_before_expanding = force_dump_type_as_string(&callee);
             let callee = self
                 .normalize(Some(span), Cow::Owned(callee), NormalizeTypeOpts { ..Default::default() })?
@@ -3558,6 +3572,25 @@ pub(crate) enum ReEvalMode<'a> {
     NoReEval,
 }
 
+impl ReEvalMode<'_> {
+    /// The span of the callee if it's a member expression like `obj.method`.
+    fn member_callee_span(&self) -> Option<Span> {
+        let callee = match self {
+            ReEvalMode::Call(RCallExpr {
+                callee: RCallee::Expr(callee),
+                ..
+            }) => callee,
+            ReEvalMode::New(RNewExpr { callee, .. }) => callee,
+            _ => return None,
+        };
+
+        match &**callee {
+            RExpr::Member(m) => Some(m.span),
+            _ => None,
+        }
+    }
+}
+
 impl Default for ReEvalMode<'_> {
     fn default() -> Self {
         Self::NoReEval
@@ -2,7 +2,7 @@ use std::{
     borrow::Cow,
     collections::HashMap,
     convert::{TryFrom, TryInto},
-    time::{Duration, Instant},
+    time::Duration,
 };
 
 use optional_chaining::is_obj_opt_chaining;
@@ -25,7 +25,7 @@ use stc_ts_types::{
     KeywordType, KeywordTypeMetadata, LitType, LitTypeMetadata, Method, Module, ModuleTypeData, Operator, OptionalType, PropertySignature,
     QueryExpr, QueryType, QueryTypeMetadata, StaticThis, ThisType, TplElem, TplType, TplTypeMetadata, TypeParamInstantiation,
 };
-use stc_utils::{cache::Freeze, dev_span, ext::TypeVecExt, panic_ctx, stack};
+use stc_utils::{cache::Freeze, dev_span, ext::TypeVecExt, panic_ctx, stack, time::Instant};
 use swc_atoms::js_word;
 use swc_common::{SourceMapper, Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::{op, EsVersion, TruePlusMinus, TsKeywordTypeKind, TsTypeOperatorOp, VarDeclKind};
@@ -326,6 +326,12 @@ impl Analyzer<'_, '_> {
             self.dump_type(span, &ty);
         }
 
+        if let Some(recorder) = &self.type_recorder {
+            if !span.is_dummy() {
+                recorder.record(span, &ty);
+            }
+        }
+
         Ok(ty)
     }
 }
@@ -3682,6 +3688,16 @@ impl Analyzer<'_, '_> {
                     self.storage.report(ErrorKind::CannotExportNonLocalVar { span: i.span }.into());
                 }
 
+                if self.ctx.in_es_module && self.env.is_umd_global(&i.sym) {
+                    self.storage.report(
+                        ErrorKind::UmdGlobalInModule {
+                            span: i.span,
+                            name: i.into(),
+                        }
+                        .into(),
+                    );
+                }
+
                 return Ok(ty);
             }
         }
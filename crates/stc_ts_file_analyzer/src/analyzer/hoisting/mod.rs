@@ -4,7 +4,7 @@ use stc_ts_ast_rnode::{RDecl, RIdent, RModuleDecl, RStmt};
 use stc_ts_ordering::{calc_eval_order, stmt::TypedId, types::Sortable};
 use stc_ts_types::Id;
 use stc_ts_utils::{AsModuleDecl, HasNodeId};
-use stc_utils::dedup;
+use stc_utils::{dedup, time::Instant};
 
 use crate::{analyzer::Analyzer, util::ModuleItemOrStmt};
 
@@ -33,6 +33,10 @@ impl Analyzer<'_, '_> {
         }
 
         for idx in order {
+            if self.is_cancelled() {
+                break;
+            }
+
             if self.scope.is_root() {
                 let module_id = self.storage.module_id(idx);
                 self.ctx.module_id = module_id;
@@ -43,8 +47,25 @@ impl Analyzer<'_, '_> {
                 let type_decl_id = type_decl_id(stmts[idx]);
 
                 let node_id = stmts[idx].node_id();
+                let start = Instant::now();
                 stmts[idx].visit_with(self);
 
+                if let (Some(profiler), true) = (&self.profiler, self.scope.is_root()) {
+                    let mut names = stmts[idx]
+                        .get_decls()
+                        .into_keys()
+                        .map(|id| id.id.sym().to_string())
+                        .collect::<Vec<_>>();
+                    names.sort();
+                    let name = if names.is_empty() {
+                        "(statement)".to_string()
+                    } else {
+                        names.join(", ")
+                    };
+
+                    profiler.record("declaration", format!("{}: {}", self.storage.path(self.ctx.module_id), name), start);
+                }
+
                 if self.scope.is_root() {
                     let prepended = self.data.prepend_stmts.drain(..);
                     let appended = self.data.append_stmts.drain(..);
@@ -1,16 +1,19 @@
+use std::sync::Arc;
+
 #[cfg(not(feature = "no-threading"))]
 use rayon::prelude::*;
 use rnode::{Visit, VisitWith};
 use stc_ts_ast_rnode::{
-    RCallExpr, RCallee, RExportAll, RExpr, RImportDecl, RImportSpecifier, RLit, RModuleItem, RNamedExport, RStr, RTsExternalModuleRef,
+    RCallExpr, RCallee, RExportAll, RExpr, RIdent, RImportDecl, RImportSpecifier, RKeyValueProp, RLit, RModuleItem, RNamedExport,
+    RObjectLit, RProp, RPropOrSpread, RStr, RTsEntityName, RTsExternalModuleRef, RTsLit,
 };
 use stc_ts_errors::ErrorKind;
 use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_storage::Storage;
-use stc_ts_types::{Id, ModuleId, Type};
+use stc_ts_types::{Id, LitType, ModuleId, Ref, Type, TypeOrSpread, TypeParamInstantiation};
 use stc_ts_utils::imports::find_imports_in_comments;
 use swc_atoms::{js_word, JsWord};
-use swc_common::{comments::Comments, Span, Spanned, GLOBALS};
+use swc_common::{comments::Comments, FileName, Span, Spanned, DUMMY_SP, GLOBALS};
 
 use crate::{
     analyzer::{scope::VarKind, util::ResultExt, Analyzer},
@@ -30,7 +33,7 @@ impl Analyzer<'_, '_> {
         let dep_id = match dep_id {
             Some(v) => v,
             None => {
-                self.storage.report(ErrorKind::ModuleNotFound { span }.into());
+                self.report_module_not_found(span, &base, dst);
 
                 return (ctxt, Type::any(span, Default::default()));
             }
@@ -38,7 +41,12 @@ impl Analyzer<'_, '_> {
         let data = match self.data.imports.get(&(ctxt, dep_id)).cloned() {
             Some(v) => v,
             None => {
-                self.storage.report(ErrorKind::ModuleNotFound { span }.into());
+                // Modules in the same circular group are analyzed together, so their types
+                // are not available here. Those imports are typed as `any`
+                // instead of reporting errors for each of them.
+                if !self.loader.is_in_same_circular_group(&base, dst) {
+                    self.storage.report(ErrorKind::ModuleNotFound { span, hint: None }.into());
+                }
 
                 return (ctxt, Type::any(span, Default::default()));
             }
@@ -47,6 +55,83 @@ impl Analyzer<'_, '_> {
         (dep_id, data)
     }
 
+    /// Returns `Promise<typeof import(specifier)>` for `import(specifier)`.
+    ///
+    /// The specifier can be any expression of a string literal type. Otherwise
+    /// the module is typed as `any`.
+    pub(crate) fn type_of_dynamic_import(&mut self, span: Span, args: &[TypeOrSpread]) -> Type {
+        let module = match args.first().map(|arg| arg.ty.normalize()) {
+            Some(Type::Lit(LitType { lit: RTsLit::Str(src), .. })) => self.load_dynamic_import(span, &src.value),
+            _ => None,
+        };
+
+        Type::Ref(Ref {
+            span,
+            type_name: RTsEntityName::Ident(RIdent::new("Promise".into(), DUMMY_SP)),
+            type_args: Some(box TypeParamInstantiation {
+                span,
+                params: vec![module.unwrap_or_else(|| Type::any(span, Default::default()))],
+            }),
+            metadata: Default::default(),
+            tracker: Default::default(),
+        })
+    }
+
+    fn report_module_not_found(&mut self, span: Span, base: &Arc<FileName>, src: &str) {
+        let hint = self.loader.module_not_found_hint(base, src);
+
+        self.storage.report(ErrorKind::ModuleNotFound { span, hint }.into());
+    }
+
+    /// Returns the type of the module `src` imported by `import()`.
+    fn load_dynamic_import(&mut self, span: Span, src: &JsWord) -> Option<Type> {
+        if self.config.is_builtin {
+            return None;
+        }
+
+        let ctxt = self.ctx.module_id;
+        let base = self.storage.path(ctxt);
+        let dep_id = match self.loader.module_id(&base, src) {
+            Some(v) => v,
+            None => {
+                self.report_module_not_found(span, &base, src);
+                return None;
+            }
+        };
+
+        if let Some(ty) = self.data.imports.get(&(ctxt, dep_id)) {
+            return Some(ty.clone());
+        }
+
+        // Modules in the same circular group are analyzed together, so their types
+        // are not available yet.
+        if self.loader.is_in_same_circular_group(&base, src) {
+            return None;
+        }
+
+        let ty = self.loader.load_non_circular_dep(&base, src).report(&mut self.storage)?;
+        self.insert_import_info(ctxt, dep_id, ty.clone()).report(&mut self.storage);
+
+        Some(ty)
+    }
+
+    /// Reports an error for each value of import attributes which is not a
+    /// string literal.
+    fn validate_import_attributes(&mut self, attrs: &RObjectLit) {
+        for prop in &attrs.props {
+            match prop {
+                RPropOrSpread::Prop(box RProp::KeyValue(RKeyValueProp {
+                    value: box RExpr::Lit(RLit::Str(..)),
+                    ..
+                })) => {}
+                _ => {
+                    self.storage
+                        .report(ErrorKind::ImportAttributeValueNotString { span: prop.span() }.into());
+                }
+            }
+        }
+    }
+
     pub(super) fn find_imported_var(&self, id: &Id) -> VResult<Option<Type>> {
         if let Some(ModuleInfo { module_id, data }) = self.data.imports_by_id.get(id) {
             match data.normalize() {
@@ -90,7 +175,7 @@ impl Analyzer<'_, '_> {
             let dep_id = match dep_id {
                 Some(v) => v,
                 None => {
-                    self.storage.report(ErrorKind::ModuleNotFound { span }.into());
+                    self.report_module_not_found(span, &base, &import.src);
                     continue;
                 }
             };
@@ -193,6 +278,10 @@ impl Analyzer<'_, '_> {
         let span = node.span;
         let base = self.ctx.module_id;
 
+        if let Some(attrs) = &node.asserts {
+            self.validate_import_attributes(attrs);
+        }
+
         let (dep, data) = self.get_imported_items(span, &node.src.value);
 
         for specifier in &node.specifiers {
@@ -21,7 +21,7 @@ use stc_ts_storage::{Builtin, Info, Storage};
 use stc_ts_type_cache::TypeCache;
 use stc_ts_types::{type_id::DestructureId, Id, IdCtx, ModuleId, ModuleTypeData, Namespace};
 use stc_ts_utils::StcComments;
-use stc_utils::{cache::Freeze, AHashMap, AHashSet};
+use stc_utils::{cache::Freeze, cancel::CancellationToken, profile::Profiler, AHashMap, AHashSet};
 use swc_atoms::{js_word, JsWord};
 use swc_common::{FileName, SourceMap, Span, DUMMY_SP, GLOBALS};
 use swc_ecma_ast::*;
@@ -36,6 +36,7 @@ use self::{
 pub(crate) use self::{scope::ScopeKind, types::NormalizeTypeOpts};
 use crate::{
     loader::{Load, ModuleInfo},
+    recorder::TypeRecorder,
     ty,
     ty::Type,
     validator,
@@ -191,6 +192,10 @@ pub(crate) struct Ctx {
 
     in_module: bool,
 
+    /// `true` if the file has imports or exports. UMD globals can't be used in
+    /// such files.
+    in_es_module: bool,
+
     checking_switch_discriminant_as_bin: bool,
 
     /// If true, obj of the expression statement is `super` keyword.
@@ -244,6 +249,13 @@ pub struct Analyzer<'scope, 'b> {
 
     debugger: Option<Debugger>,
 
+    type_recorder: Option<TypeRecorder>,
+
+    cancellation_token: Option<CancellationToken>,
+
+    /// Only used by the root analyzer.
+    profiler: Option<Profiler>,
+
     data: Box<AnalyzerData>,
 
     destructure_count: Rc<Cell<DestructureId>>,
@@ -412,10 +424,32 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             Scope::root(),
             false,
             debugger,
+            None,
+            None,
             Default::default(),
         )
     }
 
+    /// Records the types of expressions to `recorder`.
+    pub fn with_type_recorder(mut self, recorder: TypeRecorder) -> Self {
+        self.type_recorder = Some(recorder);
+        self
+    }
+
+    /// Stops validating statements once `token` is cancelled. The result of a
+    /// cancelled analysis is incomplete.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Records the time taken to validate each top-level statement to
+    /// `profiler`.
+    pub fn with_profiler(mut self, profiler: Profiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
     pub(crate) fn for_builtin(env: StableEnv, storage: &'b mut Builtin) -> Self {
         Self::new_inner(
             Env::new(env, Default::default(), EsVersion::latest(), ModuleConfig::None, Default::default()),
@@ -427,6 +461,8 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             Scope::root(),
             true,
             None,
+            None,
+            None,
             Default::default(),
         )
     }
@@ -443,6 +479,8 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             scope,
             self.config.is_builtin,
             self.debugger.clone(),
+            self.type_recorder.clone(),
+            self.cancellation_token.clone(),
             data,
         )
     }
@@ -457,6 +495,8 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         scope: Scope<'scope>,
         is_builtin: bool,
         debugger: Option<Debugger>,
+        type_recorder: Option<TypeRecorder>,
+        cancellation_token: Option<CancellationToken>,
         data: Box<AnalyzerData>,
     ) -> Self {
         let is_dts = storage.is_dts();
@@ -524,6 +564,7 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
                 is_not_topmost_type: false,
                 is_fn_param: false,
                 in_module: false,
+                in_es_module: false,
                 checking_switch_discriminant_as_bin: false,
                 obj_is_super: false,
                 use_properties_of_this_implicitly: false,
@@ -533,6 +574,9 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             cur_facts: Default::default(),
             mapped_type_param_name: vec![],
             debugger,
+            type_recorder,
+            cancellation_token,
+            profiler: None,
             data,
             destructure_count: Default::default(),
         }
@@ -663,6 +707,10 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         WithCtx { analyzer: self, orig_ctx }
     }
 
+    fn is_cancelled(&self) -> bool {
+        self.cancellation_token.as_ref().map_or(false, |token| token.is_cancelled())
+    }
+
     fn rule(&self) -> Rule {
         self.env.rule()
     }
@@ -746,6 +794,7 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     fn validate(&mut self, m: &RModule) {
         self.ctx.in_module = true;
+        self.ctx.in_es_module = m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..)));
         let is_dts = self.config.is_dts;
 
         debug_assert!(GLOBALS.is_set(), "Analyzer requires swc_common::GLOBALS");
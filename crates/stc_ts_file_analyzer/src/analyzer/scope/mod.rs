@@ -5,7 +5,6 @@ use std::{
     iter,
     mem::{replace, take},
     slice,
-    time::Instant,
 };
 
 use fxhash::{FxHashMap, FxHashSet};
@@ -27,6 +26,7 @@ use stc_ts_types::{
 use stc_utils::{
     cache::{Freeze, ALLOW_DEEP_CLONE},
     dev_span, stack,
+    time::Instant,
 };
 use swc_atoms::js_word;
 use swc_common::{util::move_map::MoveMap, Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
@@ -840,6 +840,12 @@ impl Analyzer<'_, '_> {
 
             if !self.config.is_builtin {
                 self.storage.export_type(ty.span(), self.ctx.module_id, name.clone());
+
+                if let Some(recorder) = &self.type_recorder {
+                    if !ty.is_type_param() {
+                        recorder.record_type_decl(&name, &ty);
+                    }
+                }
             }
 
             self.scope.register_type(name, ty.clone(), false);
@@ -883,6 +889,12 @@ impl Analyzer<'_, '_> {
                 }
             }
 
+            if let Some(recorder) = &self.type_recorder {
+                if !ty.is_type_param() {
+                    recorder.record_type_decl(&name, &ty);
+                }
+            }
+
             self.scope.register_type(name, ty.clone(), should_override);
 
             ty
@@ -1301,6 +1313,12 @@ impl Analyzer<'_, '_> {
             ty.assert_valid();
         }
 
+        if let Some(recorder) = &self.type_recorder {
+            if let Some(ty) = ty.as_ref().or(actual_ty.as_ref()) {
+                recorder.record_var(&name, ty);
+            }
+        }
+
         let allow_multiple = allow_multiple && {
             // Consult previous variable declarations to know if we can declare
             // this variable.
@@ -1,10 +1,8 @@
-use std::time::Instant;
-
 use rnode::VisitWith;
 use stc_ts_ast_rnode::{RBlockStmt, RBool, RExpr, RExprStmt, RForStmt, RModuleItem, RStmt, RTsExprWithTypeArgs, RTsLit, RWithStmt};
 use stc_ts_errors::{DebugExt, ErrorKind};
 use stc_ts_types::{LitType, Type};
-use stc_utils::{dev_span, stack};
+use stc_utils::{dev_span, stack, time::Instant};
 use swc_common::{Spanned, DUMMY_SP};
 use swc_ecma_utils::Value::Known;
 use tracing::{trace, warn};
@@ -0,0 +1,69 @@
+//! Assignability checks for tools which don't check files, like custom lint
+//! rules.
+
+use std::{fmt, sync::Arc};
+
+use stc_ts_env::Env;
+use stc_ts_errors::Error;
+use stc_ts_storage::Single;
+use stc_ts_types::{ModuleId, Type};
+use stc_utils::stack;
+use swc_common::{FileName, SourceMap, Spanned, SyntaxContext};
+
+use crate::analyzer::{Analyzer, NoopLoader};
+
+/// The reason why a type is not assignable to another type.
+#[derive(Debug)]
+pub struct AssignabilityError {
+    /// The error which would be reported by the type checker, with the causes.
+    pub error: Error,
+}
+
+impl AssignabilityError {
+    /// The code of the error, like `2322`.
+    pub fn code(&self) -> usize {
+        self.error.code()
+    }
+}
+
+impl fmt::Display for AssignabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TS{}: {:?}", self.code(), self.error)
+    }
+}
+
+impl std::error::Error for AssignabilityError {}
+
+/// Returns [Ok] if `source` is assignable to `target`, using the same relation
+/// as the type checker.
+///
+/// References in the types are resolved using the global types of `env`, so
+/// references to types declared in files should be expanded before calling
+/// this.
+///
+/// This should be called while [swc_common::GLOBALS] used to create `env` is
+/// set.
+pub fn is_assignable_to(source: &Type, target: &Type, env: &Env) -> Result<(), AssignabilityError> {
+    let _stack = stack::start(256);
+
+    let mut storage = Single {
+        parent: None,
+        id: ModuleId::builtin(),
+        top_level_ctxt: SyntaxContext::empty(),
+        path: Arc::new(FileName::Anon),
+        is_dts: false,
+        info: Default::default(),
+    };
+    let mut analyzer = Analyzer::root(
+        env.clone(),
+        Arc::new(SourceMap::default()),
+        Default::default(),
+        box &mut storage,
+        &NoopLoader,
+        None,
+    );
+
+    analyzer
+        .assign(source.span(), &mut Default::default(), target, source)
+        .map_err(|error| AssignabilityError { error })
+}
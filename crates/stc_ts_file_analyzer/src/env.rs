@@ -1,4 +1,4 @@
-use std::{collections::hash_map::Entry, error::Error, path::Path, sync::Arc, time::Instant};
+use std::{collections::hash_map::Entry, error::Error, fmt, path::Path, sync::Arc};
 
 use dashmap::DashMap;
 use once_cell::sync::{Lazy, OnceCell};
@@ -11,10 +11,12 @@ use stc_ts_env::{BuiltIn, Env, ModuleConfig, Rule, StableEnv};
 use stc_ts_storage::Builtin;
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{ClassDef, ModuleTypeData, Type};
-use stc_utils::{cache::Freeze, stack};
+use stc_utils::{cache::Freeze, stack, time::Instant};
 use swc_atoms::JsWord;
-use swc_common::DUMMY_SP;
+use swc_common::{FileName, SourceMap, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_visit::{span_remover, FoldWith};
 use tracing::{info, warn};
 
 use crate::{
@@ -22,6 +24,29 @@ use crate::{
     validator::ValidateWith,
 };
 
+/// Error from [BuiltInGen::from_source].
+#[derive(Debug)]
+pub enum AmbientSourceError {
+    Parse(swc_ecma_parser::error::Error),
+    /// A statement which can't be declared as a global, like an expression
+    /// statement or `export`, at the byte offsets in the source.
+    Unsupported {
+        start: usize,
+        end: usize,
+    },
+}
+
+impl fmt::Display for AmbientSourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmbientSourceError::Parse(err) => write!(f, "failed to parse ambient declarations: {}", err.kind().msg()),
+            AmbientSourceError::Unsupported { start, end } => write!(f, "unsupported ambient declaration at {}..{}", start, end),
+        }
+    }
+}
+
+impl Error for AmbientSourceError {}
+
 pub trait BuiltInGen: Sized {
     #[allow(clippy::new_ret_no_self)]
     fn new(vars: FxHashMap<JsWord, Type>, types: FxHashMap<JsWord, Type>) -> BuiltIn;
@@ -30,6 +55,9 @@ pub trait BuiltInGen: Sized {
         debug_assert_ne!(libs, &[], "No typescript library file is specified");
 
         // Loading builtin is very slow, so we cache it to a file using serde_json
+        //
+        // There's no file system on wasm.
+        let use_cache = !cfg!(target_arch = "wasm32");
 
         let key = {
             let mut hasher = Sha1::new();
@@ -41,7 +69,7 @@ pub trait BuiltInGen: Sized {
 
         let cache_path = Path::new(".stc").join(".builtin-cache").join(&format!("{}.rmp", key));
 
-        if cache_path.is_file() {
+        if use_cache && cache_path.is_file() {
             let res = || -> Result<BuiltIn, Box<dyn Error>> {
                 let data = std::fs::read(&cache_path)?;
 
@@ -79,6 +107,10 @@ pub trait BuiltInGen: Sized {
 
         let builtin = Self::from_module_items(env, iter);
 
+        if !use_cache {
+            return builtin;
+        }
+
         let json_data = rmp_serde::encode::to_vec(&builtin).unwrap_or_else(|err| panic!("failed to serialize builtin cache: {:?}", err));
 
         std::fs::create_dir_all(cache_path.parent().unwrap())
@@ -90,6 +122,88 @@ pub trait BuiltInGen: Sized {
         builtin
     }
 
+    /// Creates globals from `src`, which is the content of an ambient
+    /// declaration file like `declare const __DEV__: boolean;`. The result can
+    /// be added to an [Env] by [Env::declare_globals].
+    ///
+    /// Only variables, functions, classes without heritage clauses, namespaces,
+    /// type aliases and interfaces can be declared. Spans are removed like
+    /// builtin libraries, because `src` is not in the source map of the
+    /// checked files.
+    fn from_source(env: &StableEnv, src: &str) -> Result<BuiltIn, AmbientSourceError> {
+        let cm = SourceMap::default();
+        let fm = cm.new_source_file(FileName::Anon, src.to_string());
+        let lexer = Lexer::new(
+            Syntax::Typescript(TsConfig {
+                dts: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            StringInput::from(&*fm),
+            None,
+        );
+
+        let mut parser = Parser::new_from(lexer);
+        let script = parser.parse_script().map_err(AmbientSourceError::Parse)?;
+        if let Some(err) = parser.take_errors().into_iter().next() {
+            return Err(AmbientSourceError::Parse(err));
+        }
+
+        for stmt in &script.body {
+            let supported = match stmt {
+                Stmt::Decl(Decl::Var(..) | Decl::Fn(..) | Decl::TsTypeAlias(..) | Decl::TsInterface(..)) => true,
+                Stmt::Decl(Decl::Class(c)) => c.class.super_class.is_none() && c.class.implements.is_empty(),
+                Stmt::Decl(Decl::TsModule(m)) => {
+                    matches!(m.id, TsModuleName::Ident(..))
+                        && matches!(&m.body, Some(TsNamespaceBody::TsModuleBlock(b)) if !b.body.is_empty())
+                }
+                _ => false,
+            };
+
+            if !supported {
+                let span = stmt.span();
+                return Err(AmbientSourceError::Unsupported {
+                    start: (span.lo - fm.start_pos).0 as usize,
+                    end: (span.hi - fm.start_pos).0 as usize,
+                });
+            }
+        }
+
+        let mut node_id_gen = NodeIdGenerator::default();
+
+        let items = script
+            .body
+            .fold_with(&mut span_remover())
+            .into_iter()
+            // Variables are declared one by one.
+            .flat_map(|stmt| match stmt {
+                Stmt::Decl(Decl::Var(v)) => {
+                    let VarDecl {
+                        span,
+                        kind,
+                        declare,
+                        decls,
+                    } = *v;
+                    decls
+                        .into_iter()
+                        .map(|decl| {
+                            Stmt::Decl(Decl::Var(box VarDecl {
+                                span,
+                                kind,
+                                declare,
+                                decls: vec![decl],
+                            }))
+                        })
+                        .collect()
+                }
+                _ => vec![stmt],
+            })
+            .map(|stmt| RModuleItem::from_orig(&mut node_id_gen, ModuleItem::Stmt(stmt)))
+            .collect::<Vec<_>>();
+
+        Ok(Self::from_module_items(env, items))
+    }
+
     fn from_modules(env: &StableEnv, modules: Vec<RModule>) -> BuiltIn {
         Self::from_module_items(env, modules.into_iter().flat_map(|module| module.body))
     }
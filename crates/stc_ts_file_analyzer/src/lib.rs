@@ -25,8 +25,10 @@ use swc_atoms::JsWord;
 use swc_common::Span;
 
 pub mod analyzer;
+pub mod assignability;
 pub mod env;
 pub mod loader;
+pub mod recorder;
 #[cfg(test)]
 mod tests;
 pub mod ty;
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use auto_impl::auto_impl;
+use stc_ts_errors::ModuleNotFoundHint;
 use stc_ts_types::{ModuleId, ModuleTypeData, Type};
 use swc_atoms::JsWord;
 use swc_common::FileName;
@@ -43,4 +44,10 @@ pub trait Load: 'static + Send + Sync {
 
     /// `module` should be [Type::Arc] of [Type::Module].
     fn declare_module(&self, name: &JsWord, module: Type);
+
+    /// Returns information which makes the error for an unresolved module
+    /// specifier actionable, like similar file names.
+    fn module_not_found_hint(&self, _base: &Arc<FileName>, _src: &str) -> Option<Box<ModuleNotFoundHint>> {
+        None
+    }
 }
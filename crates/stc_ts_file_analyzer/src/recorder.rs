@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use fxhash::FxBuildHasher;
+use stc_ts_types::{Id, Type};
+use stc_utils::cache::{Freeze, ALLOW_DEEP_CLONE};
+use swc_common::{BytePos, Span};
+
+/// Records the types of expressions and declarations while analyzing files, so
+/// that tools like editors can query the type at a position after checking.
+///
+/// Clones share the recorded types.
+#[derive(Clone, Default)]
+pub struct TypeRecorder {
+    types: Arc<DashMap<Span, Type, FxBuildHasher>>,
+    /// Types of variables, keyed by the resolved name.
+    vars: Arc<DashMap<Id, Type, FxBuildHasher>>,
+    /// Declared types, keyed by the resolved name.
+    type_decls: Arc<DashMap<Id, Type, FxBuildHasher>>,
+}
+
+impl TypeRecorder {
+    /// If an expression is validated multiple times, the last type is kept.
+    pub(crate) fn record(&self, span: Span, ty: &Type) {
+        let ty = ALLOW_DEEP_CLONE.set(&(), || ty.clone()).freezed();
+
+        self.types.insert(span.with_ctxt(Default::default()), ty);
+    }
+
+    /// If a variable is declared multiple times, the last type is kept.
+    pub(crate) fn record_var(&self, name: &Id, ty: &Type) {
+        let ty = ALLOW_DEEP_CLONE.set(&(), || ty.clone()).freezed();
+
+        self.vars.insert(name.clone(), ty);
+    }
+
+    /// Declarations of a type are merged by the analyzer, so the last type is
+    /// the merged one.
+    pub(crate) fn record_type_decl(&self, name: &Id, ty: &Type) {
+        let ty = ALLOW_DEEP_CLONE.set(&(), || ty.clone()).freezed();
+
+        self.type_decls.insert(name.clone(), ty);
+    }
+
+    /// Returns the type of the variable named `name`, which includes
+    /// functions, classes and enums.
+    pub fn var_type(&self, name: &Id) -> Option<Type> {
+        self.vars.get(name).map(|ty| ty.value().clone())
+    }
+
+    /// Returns the type declared as `name`, like an interface or a type alias.
+    pub fn type_decl(&self, name: &Id) -> Option<Type> {
+        self.type_decls.get(name).map(|ty| ty.value().clone())
+    }
+
+    /// Returns the type of the node at `span`.
+    pub fn type_of(&self, span: Span) -> Option<Type> {
+        self.types.get(&span.with_ctxt(Default::default())).map(|ty| ty.value().clone())
+    }
+
+    /// Returns the type of the smallest recorded node containing `pos`.
+    pub fn type_at(&self, pos: BytePos) -> Option<(Span, Type)> {
+        self.types
+            .iter()
+            .filter(|entry| entry.key().lo <= pos && pos < entry.key().hi)
+            .min_by_key(|entry| (entry.key().hi - entry.key().lo, entry.key().lo))
+            .map(|entry| (*entry.key(), entry.value().clone()))
+    }
+
+    /// Removes all recorded types.
+    pub fn clear(&self) {
+        self.types.clear();
+        self.vars.clear();
+        self.type_decls.clear();
+    }
+}
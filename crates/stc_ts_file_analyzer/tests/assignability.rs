@@ -0,0 +1,34 @@
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::{assignability::is_assignable_to, env::EnvFactory};
+use stc_ts_types::{KeywordType, Type};
+use swc_common::{BytePos, Span};
+use swc_ecma_ast::{EsVersion, TsKeywordTypeKind};
+
+fn kwd(kind: TsKeywordTypeKind) -> Type {
+    Type::Keyword(KeywordType {
+        span: Span::new(BytePos(1), BytePos(2), Default::default()),
+        kind,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+#[test]
+fn keywords() {
+    testing::run_test2(false, |_, _| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+
+        let number = kwd(TsKeywordTypeKind::TsNumberKeyword);
+        let string = kwd(TsKeywordTypeKind::TsStringKeyword);
+        let unknown = kwd(TsKeywordTypeKind::TsUnknownKeyword);
+
+        assert!(is_assignable_to(&number, &number, &env).is_ok());
+        assert!(is_assignable_to(&number, &unknown, &env).is_ok());
+        assert!(is_assignable_to(&number, &string, &env).is_err());
+        assert!(is_assignable_to(&unknown, &number, &env).is_err());
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -1,12 +1,31 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
 use clap::Args;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use tower_lsp::{
     async_trait,
     jsonrpc::{self},
     lsp_types::*,
     Client, LanguageServer, LspService, Server,
 };
-use tracing::info;
+use tracing::{error, info};
+
+use crate::workspace::Workspace;
 
+mod position;
+mod semantic_tokens;
+mod session;
+mod workspace;
+
+/// Start the language server, which communicates with the editor using
+/// stdin and stdout.
 #[derive(Debug, Args)]
 pub struct LspCommand {}
 
@@ -17,23 +36,170 @@ impl LspCommand {
         let stdin = tokio::io::stdin();
         let stdout = tokio::io::stdout();
 
-        let (service, socket) = LspService::new(|client| StcLangServer { client });
+        let (service, socket) = LspService::new(|client| StcLangServer {
+            client,
+            workspace: Default::default(),
+            pull_diagnostics: Default::default(),
+            watch_files: Default::default(),
+        });
         Server::new(stdin, stdout, socket).serve(service).await;
 
         Ok(())
     }
 }
 
+/// The maximum number of symbols returned for `workspace/symbol`, as editors
+/// request them again while the query is typed.
+const MAX_WORKSPACE_SYMBOLS: usize = 256;
+
 pub struct StcLangServer {
-    #[allow(unused)]
     client: Client,
+    /// [None] until the server is initialized.
+    workspace: Arc<Mutex<Option<Workspace>>>,
+    /// `true` if the client requests diagnostics with `textDocument/diagnostic`
+    /// instead of receiving `textDocument/publishDiagnostics`.
+    pull_diagnostics: AtomicBool,
+    /// `true` if the client can watch files for
+    /// `workspace/didChangeWatchedFiles` after the server registers the
+    /// patterns.
+    watch_files: AtomicBool,
+}
+
+impl StcLangServer {
+    /// Runs `op` on a blocking thread, because checking files may take a long
+    /// time. Returns [None] if the server is not initialized.
+    async fn with_workspace<F, R>(&self, op: F) -> Option<R>
+    where
+        F: 'static + Send + FnOnce(&mut Workspace) -> R,
+        R: 'static + Send,
+    {
+        let workspace = self.workspace.clone();
+
+        match tokio::task::spawn_blocking(move || workspace.lock().as_mut().map(op)).await {
+            Ok(result) => result,
+            Err(err) => {
+                error!("Failed to run a request: {:?}", err);
+                None
+            }
+        }
+    }
+
+    /// Publishes the errors of all open documents, because a change may affect
+    /// the errors of other files.
+    async fn publish_diagnostics(&self) {
+        if self.pull_diagnostics.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let diagnostics = self
+            .with_workspace(|workspace| {
+                workspace
+                    .documents()
+                    .into_iter()
+                    .map(|path| {
+                        let diagnostics = workspace
+                            .project(&path)
+                            .map(|project| project.diagnostics(&path))
+                            .unwrap_or_default();
+                        (path, diagnostics)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .await
+            .unwrap_or_default();
+
+        for (path, diagnostics) in diagnostics {
+            if let Ok(uri) = Url::from_file_path(&path) {
+                self.client.publish_diagnostics(uri, diagnostics, None).await;
+            }
+        }
+    }
 }
 
 #[async_trait]
 impl LanguageServer for StcLangServer {
-    async fn initialize(&self, _params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+        let pull_diagnostics = params
+            .capabilities
+            .text_document
+            .as_ref()
+            .map_or(false, |text_document| text_document.diagnostic.is_some());
+        self.pull_diagnostics.store(pull_diagnostics, Ordering::Relaxed);
+
+        let watch_files = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.did_change_watched_files.as_ref())
+            .and_then(|capabilities| capabilities.dynamic_registration)
+            .unwrap_or_default();
+        self.watch_files.store(watch_files, Ordering::Relaxed);
+
+        // `rootUri` is used only if the client does not support workspace folders.
+        let mut roots = params
+            .workspace_folders
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect::<Vec<_>>();
+        if roots.is_empty() {
+            roots.push(match params.root_uri.and_then(|uri| uri.to_file_path().ok()) {
+                Some(root) => root,
+                None => std::env::current_dir().map_err(|err| internal_error(err.into()))?,
+            });
+        }
+
+        let workspace = tokio::task::spawn_blocking(move || {
+            let mut workspace = Workspace::default();
+            for root in roots {
+                info!("Loading the project in `{}`", root.display());
+                workspace.add_folder(&root)?;
+            }
+            Ok::<_, anyhow::Error>(workspace)
+        })
+        .await
+        .map_err(|err| internal_error(err.into()))?
+        .map_err(internal_error)?;
+        *self.workspace.lock() = Some(workspace);
+
         Ok(InitializeResult {
-            capabilities: ServerCapabilities { ..Default::default() },
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::INCREMENTAL)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                    code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                    // The fixes are stored in the diagnostics.
+                    resolve_provider: Some(false),
+                    work_done_progress_options: Default::default(),
+                })),
+                code_lens_provider: Some(CodeLensOptions {
+                    // References are counted when the lenses are shown.
+                    resolve_provider: Some(true),
+                }),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                    legend: semantic_tokens::legend(),
+                    full: Some(SemanticTokensFullOptions::Bool(true)),
+                    range: None,
+                    work_done_progress_options: Default::default(),
+                })),
+                workspace: Some(WorkspaceServerCapabilities {
+                    workspace_folders: Some(WorkspaceFoldersServerCapabilities {
+                        supported: Some(true),
+                        change_notifications: Some(OneOf::Left(true)),
+                    }),
+                    file_operations: None,
+                }),
+                diagnostic_provider: Some(DiagnosticServerCapabilities::Options(DiagnosticOptions {
+                    identifier: Some("stc".into()),
+                    // Errors of a file depend on the files it imports.
+                    inter_file_dependencies: true,
+                    workspace_diagnostics: false,
+                    work_done_progress_options: Default::default(),
+                })),
+                ..Default::default()
+            },
             server_info: Some(ServerInfo {
                 name: "stc-ts-lsp".to_string(),
                 version: Some(env!("CARGO_PKG_VERSION").to_string()),
@@ -41,14 +207,321 @@ impl LanguageServer for StcLangServer {
         })
     }
 
+    async fn initialized(&self, _: InitializedParams) {
+        if !self.watch_files.load(Ordering::Relaxed) {
+            return;
+        }
+
+        // Configs are watched too, because they affect the module resolution.
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: ["**/*.{ts,tsx,mts,cts,js,jsx,mjs,cjs}", "**/tsconfig.json", "**/package.json"]
+                .into_iter()
+                .map(|glob_pattern| FileSystemWatcher {
+                    glob_pattern: glob_pattern.to_string(),
+                    kind: None,
+                })
+                .collect(),
+        };
+        let registration = Registration {
+            id: "stc-watched-files".to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options: serde_json::to_value(options).ok(),
+        };
+
+        if let Err(err) = self.client.register_capability(vec![registration]).await {
+            error!("Failed to watch files: {:?}", err);
+        }
+    }
+
     async fn shutdown(&self) -> jsonrpc::Result<()> {
         Ok(())
     }
 
-    async fn hover(&self, _params: HoverParams) -> jsonrpc::Result<Option<Hover>> {
-        Ok(Some(Hover {
-            contents: HoverContents::Scalar(MarkedString::String("hover test".to_string())),
-            range: None,
-        }))
+    async fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
+        let WorkspaceFoldersChangeEvent { added, removed } = params.event;
+
+        let errors = self
+            .with_workspace(move |workspace| {
+                for folder in removed {
+                    if let Ok(root) = folder.uri.to_file_path() {
+                        workspace.remove_folder(&root);
+                    }
+                }
+
+                added
+                    .into_iter()
+                    .filter_map(|folder| {
+                        let root = folder.uri.to_file_path().ok()?;
+                        info!("Loading the project in `{}`", root.display());
+                        workspace.add_folder(&root).err()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .await
+            .unwrap_or_default();
+
+        // Other folders are usable even if a folder fails to load.
+        for err in errors {
+            error!("Failed to load a workspace folder: {:?}", err);
+            self.client
+                .show_message(MessageType::ERROR, format!("Failed to load a workspace folder: {:?}", err))
+                .await;
+        }
+        self.publish_diagnostics().await;
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        let changes = params
+            .changes
+            .into_iter()
+            .filter_map(|event| Some((event.uri.to_file_path().ok()?, event.typ)))
+            .collect();
+
+        let errors = self
+            .with_workspace(move |workspace| workspace.files_changed(changes))
+            .await
+            .unwrap_or_default();
+
+        for err in errors {
+            error!("Failed to load a project again: {:?}", err);
+            self.client
+                .show_message(MessageType::ERROR, format!("Failed to load a project again: {:?}", err))
+                .await;
+        }
+        self.publish_diagnostics().await;
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let path = match params.text_document.uri.to_file_path() {
+            Ok(path) => path,
+            Err(..) => return,
+        };
+
+        self.with_workspace(move |workspace| workspace.set_document(&path, params.text_document.text))
+            .await;
+        self.publish_diagnostics().await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let path = match params.text_document.uri.to_file_path() {
+            Ok(path) => path,
+            Err(..) => return,
+        };
+
+        self.with_workspace(move |workspace| workspace.change_document(&path, params.content_changes))
+            .await;
+        self.publish_diagnostics().await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let path = match params.text_document.uri.to_file_path() {
+            Ok(path) => path,
+            Err(..) => return,
+        };
+
+        self.with_workspace(move |workspace| workspace.close_document(&path)).await;
+        // Errors of closed documents are not shown.
+        self.client.publish_diagnostics(params.text_document.uri, vec![], None).await;
+        self.publish_diagnostics().await;
+    }
+
+    async fn diagnostic(&self, params: DocumentDiagnosticParams) -> jsonrpc::Result<DocumentDiagnosticReportResult> {
+        let path = params
+            .text_document
+            .uri
+            .to_file_path()
+            .map_err(|_| jsonrpc::Error::invalid_params("the document is not a file"))?;
+
+        let items = self
+            .with_workspace(move |workspace| {
+                let project = workspace.project_mut(&path)?;
+                project.check_file(&path);
+                Some(project.diagnostics(&path))
+            })
+            .await
+            .flatten()
+            .unwrap_or_default();
+
+        Ok(DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(
+            RelatedFullDocumentDiagnosticReport {
+                related_documents: None,
+                full_document_diagnostic_report: FullDocumentDiagnosticReport { result_id: None, items },
+            },
+        )))
+    }
+
+    async fn symbol(&self, params: WorkspaceSymbolParams) -> jsonrpc::Result<Option<Vec<SymbolInformation>>> {
+        let symbols = self
+            .with_workspace(move |workspace| workspace.workspace_symbols(&params.query, MAX_WORKSPACE_SYMBOLS))
+            .await
+            .unwrap_or_default();
+
+        Ok(Some(symbols))
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> jsonrpc::Result<Option<CodeActionResponse>> {
+        if let Some(only) = &params.context.only {
+            if !only.contains(&CodeActionKind::QUICKFIX) {
+                return Ok(None);
+            }
+        }
+
+        let mut actions = vec![];
+        for diagnostic in params.context.diagnostics {
+            let fixes = match diagnostic.data.clone().map(serde_json::from_value::<Vec<QuickFix>>) {
+                Some(Ok(fixes)) => fixes,
+                _ => continue,
+            };
+
+            for fix in fixes {
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: fix.title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(params.text_document.uri.clone(), fix.edits)])),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(Some(actions))
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> jsonrpc::Result<Option<Vec<CodeLens>>> {
+        let uri = params.text_document.uri;
+        let path = match uri.to_file_path() {
+            Ok(path) => path,
+            Err(..) => return Ok(None),
+        };
+
+        let ranges = self
+            .with_workspace(move |workspace| workspace.project(&path).map(|project| project.code_lenses(&path)))
+            .await
+            .flatten()
+            .unwrap_or_default();
+
+        Ok(Some(
+            ranges
+                .into_iter()
+                .map(|range| CodeLens {
+                    range,
+                    command: None,
+                    data: serde_json::to_value(CodeLensData {
+                        uri: uri.clone(),
+                        position: range.start,
+                    })
+                    .ok(),
+                })
+                .collect(),
+        ))
+    }
+
+    async fn code_lens_resolve(&self, mut lens: CodeLens) -> jsonrpc::Result<CodeLens> {
+        let CodeLensData { uri, position } = match lens.data.clone().map(serde_json::from_value) {
+            Some(Ok(data)) => data,
+            _ => return Ok(lens),
+        };
+        let path = match uri.to_file_path() {
+            Ok(path) => path,
+            Err(..) => return Ok(lens),
+        };
+
+        let count = self
+            .with_workspace(move |workspace| workspace.project(&path).map(|project| project.reference_count(&path, position)))
+            .await
+            .flatten()
+            .unwrap_or_default();
+
+        lens.command = Some(Command {
+            title: match count {
+                1 => "(1 reference)".to_string(),
+                _ => format!("({} references)", count),
+            },
+            // The lens is only a label.
+            command: String::new(),
+            arguments: None,
+        });
+        Ok(lens)
+    }
+
+    async fn semantic_tokens_full(&self, params: SemanticTokensParams) -> jsonrpc::Result<Option<SemanticTokensResult>> {
+        let path = match params.text_document.uri.to_file_path() {
+            Ok(path) => path,
+            Err(..) => return Ok(None),
+        };
+
+        let data = self
+            .with_workspace(move |workspace| workspace.project(&path).map(|project| project.semantic_tokens(&path)))
+            .await
+            .flatten()
+            .unwrap_or_default();
+
+        Ok(Some(SemanticTokensResult::Tokens(SemanticTokens { result_id: None, data })))
+    }
+
+    async fn hover(&self, params: HoverParams) -> jsonrpc::Result<Option<Hover>> {
+        let HoverParams {
+            text_document_position_params: TextDocumentPositionParams { text_document, position },
+            ..
+        } = params;
+        let path = match text_document.uri.to_file_path() {
+            Ok(path) => path,
+            Err(..) => return Ok(None),
+        };
+
+        Ok(self
+            .with_workspace(move |workspace| workspace.project(&path)?.hover(&path, position))
+            .await
+            .flatten())
+    }
+
+    async fn goto_definition(&self, params: GotoDefinitionParams) -> jsonrpc::Result<Option<GotoDefinitionResponse>> {
+        let GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams { text_document, position },
+            ..
+        } = params;
+        let path = match text_document.uri.to_file_path() {
+            Ok(path) => path,
+            Err(..) => return Ok(None),
+        };
+
+        let locations = self
+            .with_workspace(move |workspace| workspace.project(&path).map(|project| project.definition(&path, position)))
+            .await
+            .flatten()
+            .unwrap_or_default();
+        if locations.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(GotoDefinitionResponse::Array(locations)))
+    }
+}
+
+/// The data of a code lens, which is used to count the references when the
+/// lens is resolved.
+#[derive(Serialize, Deserialize)]
+struct CodeLensData {
+    uri: Url,
+    /// The start of the name of the declaration.
+    position: Position,
+}
+
+/// A fix of a diagnostic. The fixes of a diagnostic are stored in its data,
+/// which the client sends back to get the code actions.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct QuickFix {
+    pub title: String,
+    pub edits: Vec<TextEdit>,
+}
+
+fn internal_error(err: anyhow::Error) -> jsonrpc::Error {
+    jsonrpc::Error {
+        code: jsonrpc::ErrorCode::InternalError,
+        message: format!("{:?}", err).into(),
+        data: None,
     }
 }
@@ -0,0 +1,50 @@
+//! Conversions between byte offsets used by stc and positions of LSP, whose
+//! characters are counted in UTF-16 code units.
+
+use tower_lsp::lsp_types::{Position, Range};
+
+/// Returns the byte offset of `position` in `text`. Positions after the end of
+/// a line are clamped to the end of the line.
+pub(crate) fn offset_at(text: &str, position: Position) -> usize {
+    let line_start = text.split_inclusive('\n').take(position.line as usize).map(str::len).sum::<usize>();
+    let line = text[line_start..].split('\n').next().unwrap_or_default();
+
+    let mut character = 0;
+    for (i, c) in line.char_indices() {
+        if character >= position.character as usize {
+            return line_start + i;
+        }
+        character += c.len_utf16();
+    }
+
+    line_start + line.trim_end_matches('\r').len()
+}
+
+/// Returns the position of the byte offset `offset` in `text`.
+pub(crate) fn position_at(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+pub(crate) fn range_at(text: &str, start: usize, end: usize) -> Range {
+    Range::new(position_at(text, start), position_at(text, end))
+}
+
+/// Returns the position of the `column`th character of the `line`th line,
+/// where both are 0-based.
+pub(crate) fn position_of_char(text: &str, line: usize, column: usize) -> Position {
+    let line_text = text.split('\n').nth(line).unwrap_or_default();
+    let character = line_text.chars().take(column).map(char::len_utf16).sum::<usize>();
+
+    Position::new(line as u32, character as u32)
+}
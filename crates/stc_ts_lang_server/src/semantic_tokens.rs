@@ -0,0 +1,94 @@
+//! Encoding of [stc_ts_type_checker::semantic_tokens] for
+//! `textDocument/semanticTokens`.
+
+use stc_ts_type_checker::semantic_tokens::{SemanticToken as Token, TokenKind};
+use tower_lsp::lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend};
+
+/// Indices of the types are [token_type], and bits of the modifiers are
+/// [token_modifiers].
+pub(crate) fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: vec![
+            SemanticTokenType::NAMESPACE,
+            SemanticTokenType::CLASS,
+            SemanticTokenType::ENUM,
+            SemanticTokenType::INTERFACE,
+            SemanticTokenType::TYPE,
+            SemanticTokenType::TYPE_PARAMETER,
+            SemanticTokenType::PARAMETER,
+            SemanticTokenType::VARIABLE,
+            SemanticTokenType::PROPERTY,
+            SemanticTokenType::ENUM_MEMBER,
+            SemanticTokenType::FUNCTION,
+            SemanticTokenType::METHOD,
+        ],
+        token_modifiers: vec![
+            SemanticTokenModifier::DECLARATION,
+            SemanticTokenModifier::READONLY,
+            SemanticTokenModifier::STATIC,
+        ],
+    }
+}
+
+fn token_type(kind: TokenKind) -> u32 {
+    match kind {
+        TokenKind::Namespace => 0,
+        TokenKind::Class => 1,
+        TokenKind::Enum => 2,
+        TokenKind::Interface => 3,
+        TokenKind::TypeAlias => 4,
+        TokenKind::TypeParameter => 5,
+        TokenKind::Parameter => 6,
+        TokenKind::Variable => 7,
+        TokenKind::Property => 8,
+        TokenKind::EnumMember => 9,
+        TokenKind::Function => 10,
+        TokenKind::Method => 11,
+    }
+}
+
+fn token_modifiers(token: &Token) -> u32 {
+    let mut bits = 0;
+    if token.modifiers.declaration {
+        bits |= 1 << 0;
+    }
+    if token.modifiers.readonly {
+        bits |= 1 << 1;
+    }
+    if token.modifiers.is_static {
+        bits |= 1 << 2;
+    }
+    bits
+}
+
+/// Encodes `tokens`, which should be sorted by the position, relative to the
+/// previous token.
+pub(crate) fn encode(text: &str, tokens: &[Token]) -> Vec<SemanticToken> {
+    let mut prev_offset = 0;
+    let mut prev_start = 0;
+
+    tokens
+        .iter()
+        .filter(|token| token.start >= prev_offset && token.end <= text.len())
+        .map(|token| {
+            // Only the text between the tokens is scanned, as the tokens are sorted.
+            let between = &text[prev_offset..token.start];
+            let delta_line = between.matches('\n').count() as u32;
+            let start = match between.rfind('\n') {
+                Some(i) => between[i + 1..].encode_utf16().count() as u32,
+                None => prev_start + between.encode_utf16().count() as u32,
+            };
+            let delta_start = if delta_line == 0 { start - prev_start } else { start };
+            prev_offset = token.start;
+            prev_start = start;
+
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length: text[token.start..token.end].encode_utf16().count() as u32,
+                token_type: token_type(token.kind),
+                token_modifiers_bitset: token_modifiers(token),
+            }
+        })
+        .collect()
+}
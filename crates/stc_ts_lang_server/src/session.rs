@@ -0,0 +1,417 @@
+//! The checked state of a project, which is a folder of the workspace.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Error;
+use stc_ts_config::{Project, TsConfig};
+use stc_ts_errors::Severity;
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_module_loader::{
+    fs::{FileSystem, MemoryFs, OverlayFs, RealFs},
+    resolvers::{cache::CachingResolver, Resolve},
+};
+use stc_ts_type_checker::{
+    limit::{truncation_summary, ErrorLimit},
+    loader::ModuleLoader,
+    outline::SymbolKind,
+    program::{DiagnosticTag, RelatedLocation},
+    query::ReferenceKind,
+    symbol_index::SymbolIndex,
+    Checker,
+};
+use swc_common::{
+    errors::{EmitterWriter, Handler},
+    FileName, SourceMap, Spanned,
+};
+use tower_lsp::lsp_types::{
+    self, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Hover, HoverContents, Location, MarkupContent, MarkupKind,
+    NumberOrString, Position, Range, SemanticToken, SymbolInformation, TextEdit, Url,
+};
+
+use crate::{
+    position::{offset_at, position_of_char, range_at},
+    semantic_tokens, QuickFix,
+};
+
+type Resolver = CachingResolver<Arc<dyn Resolve>>;
+
+pub(crate) struct Session {
+    root: PathBuf,
+    cm: Arc<SourceMap>,
+    resolver: Resolver,
+    checker: Checker<ModuleLoader<Resolver>>,
+    /// The errors of all checked files.
+    errors: Vec<stc_ts_errors::Error>,
+    /// Only the errors of each file are published at once, so only the limit
+    /// per file is used.
+    limit: ErrorLimit,
+    /// [None] if files are checked after the index is created.
+    symbol_index: Option<SymbolIndex>,
+}
+
+impl Session {
+    /// Loads the project in `root`, or the default options if there's no
+    /// `tsconfig.json`, and the typings.
+    ///
+    /// `documents` are the contents of the open documents, which shadow the
+    /// files on the disk.
+    pub fn new(root: &Path, documents: Arc<MemoryFs>) -> Result<Self, Error> {
+        let fs: Arc<dyn FileSystem> = Arc::new(OverlayFs::new(documents.clone(), Arc::new(RealFs)));
+
+        let project = if fs.is_file(&root.join("tsconfig.json")) {
+            Project::load(&*fs, root)?
+        } else {
+            Project {
+                path: root.join("tsconfig.json"),
+                config: TsConfig::default(),
+                case_sensitive: !cfg!(any(windows, target_os = "macos")),
+            }
+        };
+
+        let cm = Arc::new(SourceMap::default());
+        // Errors are published to the client instead of being printed.
+        let handler = Arc::new(Handler::with_emitter(
+            false,
+            false,
+            Box::new(EmitterWriter::new(Box::new(io::sink()), Some(cm.clone()), false, false)),
+        ));
+
+        let env = project.env()?;
+        let options = project.options();
+        let limit = ErrorLimit::default().with_max_errors_per_file(project.config.stc.as_ref().and_then(|stc| stc.max_errors_per_file));
+        let resolver: Arc<dyn Resolve> = Arc::new(project.resolver(project.module_resolver(fs.clone())?));
+        let resolver = CachingResolver::new(resolver, fs.clone());
+
+        Checker::new(
+            cm.clone(),
+            handler.clone(),
+            env.clone(),
+            None,
+            ModuleLoader::with_fs(cm.clone(), env.clone(), resolver.clone(), fs.clone()),
+        )
+        .load_typings(project.dir(), options.type_roots.as_deref(), options.types.as_deref());
+
+        // Types are recorded for hovers.
+        let checker = Checker::new(
+            cm.clone(),
+            handler,
+            env.clone(),
+            None,
+            ModuleLoader::with_fs(cm.clone(), env, resolver.clone(), fs),
+        )
+        .with_type_recorder(TypeRecorder::default());
+
+        Ok(Session {
+            root: root.to_path_buf(),
+            cm,
+            resolver,
+            checker,
+            errors: vec![],
+            limit,
+            symbol_index: None,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Checks the file at `path` again with the files which depend on it, if
+    /// it's checked. `is_created` is `true` if the file may be created or
+    /// deleted, or if it affects the module resolution like `package.json`.
+    pub fn update(&mut self, path: &Path, is_created: bool) {
+        let mut changed = vec![];
+        if is_created {
+            // Imports which could not be resolved may be resolved to the file.
+            self.resolver.clear();
+            changed.extend(self.unresolved_importers());
+        }
+        if self.is_loaded(path) {
+            changed.push(path.to_path_buf());
+        }
+
+        if changed.is_empty() {
+            return;
+        }
+        self.symbol_index = None;
+
+        for path in changed {
+            for file in self.checker.invalidate_file(&path).files {
+                let cm = &self.cm;
+                self.errors.retain(|err| cm.span_to_filename(err.span()) != *file.file);
+                self.errors.extend(file.errors);
+            }
+        }
+        self.errors.extend(self.checker.take_errors());
+    }
+
+    /// Returns the checked files which import a module which could not be
+    /// resolved.
+    fn unresolved_importers(&self) -> Vec<PathBuf> {
+        let mut files = self
+            .checker
+            .module_graph()
+            .imports
+            .into_iter()
+            .filter(|import| import.resolved.is_none())
+            .map(|import| PathBuf::from(import.from))
+            .collect::<Vec<_>>();
+        files.sort();
+        files.dedup();
+        files
+    }
+
+    /// Checks the file at `path` if it's not checked yet, like a document which
+    /// is opened or a file whose diagnostics are pulled.
+    pub fn check_file(&mut self, path: &Path) {
+        if self.is_loaded(path) {
+            return;
+        }
+        self.symbol_index = None;
+
+        self.checker.check_all(vec![Arc::new(FileName::Real(path.to_path_buf()))]);
+        self.errors.extend(self.checker.take_errors());
+    }
+
+    fn is_loaded(&self, path: &Path) -> bool {
+        let filename = FileName::Real(path.to_path_buf()).to_string();
+
+        self.checker.module_graph().modules.iter().any(|module| module.path == filename)
+    }
+
+    /// Returns the errors in the file at `path`.
+    pub fn diagnostics(&self, path: &Path) -> Vec<Diagnostic> {
+        let filename = FileName::Real(path.to_path_buf());
+        let fm = match self.checker.source_file(&filename) {
+            Some(fm) => fm,
+            None => return vec![],
+        };
+
+        let errors = self
+            .errors
+            .iter()
+            .filter(|err| self.cm.span_to_filename(err.span()) == filename)
+            .collect();
+        let (errors, omitted) = self.limit.truncate(errors, |_| ());
+
+        let mut diagnostics = errors
+            .into_iter()
+            .map(|err| {
+                let d = self.checker.to_diagnostic(err);
+                let related = d
+                    .related
+                    .into_iter()
+                    .filter_map(|related| self.related_information(related))
+                    .collect::<Vec<_>>();
+                let tags = d
+                    .tags
+                    .iter()
+                    .map(|tag| match tag {
+                        DiagnosticTag::Unnecessary => lsp_types::DiagnosticTag::UNNECESSARY,
+                    })
+                    .collect::<Vec<_>>();
+
+                let fixes = d
+                    .fixes
+                    .into_iter()
+                    .map(|fix| QuickFix {
+                        title: fix.message,
+                        edits: fix
+                            .edits
+                            .into_iter()
+                            .map(|edit| TextEdit::new(range_at(&fm.src, edit.start, edit.end), edit.new_text))
+                            .collect(),
+                    })
+                    .collect::<Vec<_>>();
+
+                Diagnostic {
+                    range: range_at(&fm.src, d.start, d.end),
+                    severity: Some(match d.severity {
+                        Severity::Error => DiagnosticSeverity::ERROR,
+                        Severity::Warning => DiagnosticSeverity::WARNING,
+                        Severity::Info => DiagnosticSeverity::INFORMATION,
+                    }),
+                    code: Some(NumberOrString::Number(d.code as i32)),
+                    source: Some("stc".into()),
+                    message: d.message,
+                    related_information: (!related.is_empty()).then_some(related),
+                    tags: (!tags.is_empty()).then_some(tags),
+                    data: if fixes.is_empty() { None } else { serde_json::to_value(fixes).ok() },
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // The summary is shown at the last reported error.
+        if let (Some(summary), Some(last)) = (truncation_summary(omitted), diagnostics.last()) {
+            diagnostics.push(Diagnostic {
+                range: last.range,
+                severity: Some(DiagnosticSeverity::INFORMATION),
+                source: Some("stc".into()),
+                message: summary,
+                ..Default::default()
+            });
+        }
+
+        diagnostics
+    }
+
+    /// Returns [None] if the location is not in a real file, like a builtin
+    /// library.
+    fn related_information(&self, related: RelatedLocation) -> Option<DiagnosticRelatedInformation> {
+        let path = PathBuf::from(related.file);
+        let fm = self.checker.source_file(&FileName::Real(path.clone()))?;
+
+        Some(DiagnosticRelatedInformation {
+            location: Location::new(Url::from_file_path(path).ok()?, range_at(&fm.src, related.start, related.end)),
+            message: related.message,
+        })
+    }
+
+    /// Returns the classified identifiers in the file at `path`.
+    pub fn semantic_tokens(&self, path: &Path) -> Vec<SemanticToken> {
+        let filename = FileName::Real(path.to_path_buf());
+
+        match (self.checker.source_file(&filename), self.checker.semantic_tokens(&filename)) {
+            (Some(fm), Some(tokens)) => semantic_tokens::encode(&fm.src, &tokens),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the ranges of the names of the exported functions and classes
+    /// in the file at `path`, which are shown with their reference counts.
+    pub fn code_lenses(&self, path: &Path) -> Vec<Range> {
+        let filename = FileName::Real(path.to_path_buf());
+        let (fm, defs) = match (self.checker.source_file(&filename), self.checker.exported_declarations(&filename)) {
+            (Some(fm), Some(defs)) => (fm, defs),
+            _ => return vec![],
+        };
+
+        defs.into_iter()
+            .map(|def| {
+                let start = (def.span.lo - fm.start_pos).0 as usize;
+                let end = (def.span.hi - fm.start_pos).0 as usize;
+                range_at(&fm.src, start, end)
+            })
+            .collect()
+    }
+
+    /// Returns the number of references to the symbol at `position`, other
+    /// than its declarations. Only the checked files are searched.
+    pub fn reference_count(&self, path: &Path, position: Position) -> usize {
+        let filename = FileName::Real(path.to_path_buf());
+        let fm = match self.checker.source_file(&filename) {
+            Some(fm) => fm,
+            None => return 0,
+        };
+
+        self.checker
+            .references_at(&filename, offset_at(&fm.src, position))
+            .into_iter()
+            .filter(|reference| reference.kind != ReferenceKind::Declaration)
+            .count()
+    }
+
+    /// Returns at most `limit` top-level declarations of the checked files
+    /// whose names match `query`, with the best matches first.
+    pub fn workspace_symbols(&mut self, query: &str, limit: usize) -> Vec<SymbolInformation> {
+        let checker = &self.checker;
+        let index = self.symbol_index.get_or_insert_with(|| checker.symbol_index());
+
+        index
+            .search(query, limit)
+            .into_iter()
+            .filter_map(|symbol| {
+                let uri = match &*symbol.file {
+                    FileName::Real(path) => Url::from_file_path(path).ok()?,
+                    _ => return None,
+                };
+                let fm = checker.source_file(&symbol.file)?;
+
+                #[allow(deprecated)]
+                Some(SymbolInformation {
+                    name: symbol.name.clone(),
+                    kind: symbol_kind(symbol.kind),
+                    tags: None,
+                    deprecated: None,
+                    location: Location::new(uri, range_at(&fm.src, symbol.span.start, symbol.span.end)),
+                    container_name: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the declaration and the documentation of the symbol at
+    /// `position`.
+    pub fn hover(&self, path: &Path, position: Position) -> Option<Hover> {
+        let filename = FileName::Real(path.to_path_buf());
+        let fm = self.checker.source_file(&filename)?;
+        let info = self.checker.quick_info(&filename, offset_at(&fm.src, position))?;
+
+        let mut value = format!("```typescript\n{}\n```", info.display_string);
+        if !info.documentation.is_empty() {
+            value.push_str("\n\n");
+            value.push_str(&info.documentation);
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(Range::new(
+                position_of_char(&fm.src, info.start.line - 1, info.start.offset - 1),
+                position_of_char(&fm.src, info.end.line - 1, info.end.offset - 1),
+            )),
+        })
+    }
+
+    /// Returns the declarations of the symbol at `position`. Declarations in
+    /// the builtin libraries are not returned.
+    pub fn definition(&self, path: &Path, position: Position) -> Vec<Location> {
+        let filename = FileName::Real(path.to_path_buf());
+        let fm = match self.checker.source_file(&filename) {
+            Some(fm) => fm,
+            None => return vec![],
+        };
+
+        self.checker
+            .definition_at(&filename, offset_at(&fm.src, position))
+            .into_iter()
+            .filter_map(|def| {
+                let uri = match &*def.file {
+                    FileName::Real(path) => Url::from_file_path(path).ok()?,
+                    _ => return None,
+                };
+                let fm = self.cm.lookup_byte_offset(def.span.lo).sf;
+                let start = (def.span.lo - fm.start_pos).0 as usize;
+                let end = (def.span.hi - fm.start_pos).0 as usize;
+
+                Some(Location::new(uri, range_at(&fm.src, start, end)))
+            })
+            .collect()
+    }
+}
+
+fn symbol_kind(kind: SymbolKind) -> lsp_types::SymbolKind {
+    match kind {
+        SymbolKind::Module => lsp_types::SymbolKind::MODULE,
+        SymbolKind::Namespace => lsp_types::SymbolKind::NAMESPACE,
+        SymbolKind::Class => lsp_types::SymbolKind::CLASS,
+        SymbolKind::Constructor => lsp_types::SymbolKind::CONSTRUCTOR,
+        SymbolKind::Method => lsp_types::SymbolKind::METHOD,
+        SymbolKind::Property => lsp_types::SymbolKind::PROPERTY,
+        SymbolKind::Interface => lsp_types::SymbolKind::INTERFACE,
+        // LSP does not have a kind for type aliases.
+        SymbolKind::TypeAlias => lsp_types::SymbolKind::TYPE_PARAMETER,
+        SymbolKind::Enum => lsp_types::SymbolKind::ENUM,
+        SymbolKind::EnumMember => lsp_types::SymbolKind::ENUM_MEMBER,
+        SymbolKind::Function => lsp_types::SymbolKind::FUNCTION,
+        SymbolKind::Variable => lsp_types::SymbolKind::VARIABLE,
+        SymbolKind::Constant => lsp_types::SymbolKind::CONSTANT,
+    }
+}
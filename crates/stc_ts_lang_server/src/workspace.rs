@@ -0,0 +1,188 @@
+//! The folders of the workspace, each of which is checked as a separate
+//! project with its own `tsconfig.json`.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Error;
+use stc_ts_module_loader::fs::MemoryFs;
+use tower_lsp::lsp_types::{FileChangeType, SymbolInformation, TextDocumentContentChangeEvent};
+
+use crate::{position::offset_at, session::Session};
+
+/// Projects share the open documents, and the builtin libraries, which are
+/// cached by [stc_ts_file_analyzer::env::EnvFactory::simple] for each set of
+/// `lib`s, so adding a folder does not load them again.
+#[derive(Default)]
+pub(crate) struct Workspace {
+    /// The contents of the open documents, which shadow the files on the disk.
+    documents: Arc<MemoryFs>,
+    projects: Vec<Session>,
+}
+
+impl Workspace {
+    /// Loads the project in `root` and checks the open documents in it.
+    pub fn add_folder(&mut self, root: &Path) -> Result<(), Error> {
+        if self.projects.iter().any(|project| project.root() == root) {
+            return Ok(());
+        }
+
+        let project = Session::new(root, self.documents.clone())?;
+        self.projects.push(project);
+        self.check_documents();
+
+        Ok(())
+    }
+
+    /// Removes the project in `root`. Open documents in it are checked by the
+    /// project which contains them now.
+    pub fn remove_folder(&mut self, root: &Path) {
+        self.projects.retain(|project| project.root() != root);
+        self.check_documents();
+    }
+
+    fn check_documents(&mut self) {
+        for path in self.documents() {
+            if let Some(project) = self.project_mut(&path) {
+                project.check_file(&path);
+            }
+        }
+    }
+
+    /// Returns the paths of the open documents.
+    pub fn documents(&self) -> Vec<PathBuf> {
+        self.documents.paths()
+    }
+
+    /// Returns the project of the innermost folder which contains `path`, or
+    /// the first project if no folder contains it.
+    pub fn project(&self, path: &Path) -> Option<&Session> {
+        self.project_index(path).map(|i| &self.projects[i])
+    }
+
+    pub fn project_mut(&mut self, path: &Path) -> Option<&mut Session> {
+        self.project_index(path).map(move |i| &mut self.projects[i])
+    }
+
+    fn project_index(&self, path: &Path) -> Option<usize> {
+        self.projects
+            .iter()
+            .enumerate()
+            .filter(|(_, project)| path.starts_with(project.root()))
+            .max_by_key(|(_, project)| project.root().components().count())
+            .map(|(i, _)| i)
+            .or_else(|| (!self.projects.is_empty()).then_some(0))
+    }
+
+    /// Returns at most `limit` top-level declarations of all projects whose
+    /// names match `query`. Declarations in files checked by multiple projects
+    /// are returned once.
+    pub fn workspace_symbols(&mut self, query: &str, limit: usize) -> Vec<SymbolInformation> {
+        let mut symbols = Vec::<SymbolInformation>::new();
+
+        for project in &mut self.projects {
+            for symbol in project.workspace_symbols(query, limit) {
+                if !symbols.iter().any(|found| found.location == symbol.location) {
+                    symbols.push(symbol);
+                }
+            }
+        }
+
+        symbols.truncate(limit);
+        symbols
+    }
+
+    /// Opens or changes the document at `path`, and checks it again with the
+    /// files which depend on it.
+    pub fn set_document(&mut self, path: &Path, text: String) {
+        let is_new = self.documents.get(path).is_none();
+        self.documents.insert(path.to_path_buf(), text);
+
+        // Other projects may import the document.
+        for project in &mut self.projects {
+            project.update(path, is_new);
+        }
+        if let Some(project) = self.project_mut(path) {
+            project.check_file(path);
+        }
+    }
+
+    /// Checks the files affected by the changes of files on the disk, which are
+    /// not open. Projects whose `tsconfig.json` is changed are loaded again.
+    ///
+    /// Returns the errors of the projects which could not be loaded again,
+    /// which are kept as they were.
+    pub fn files_changed(&mut self, changes: Vec<(PathBuf, FileChangeType)>) -> Vec<Error> {
+        let mut errors = vec![];
+        let mut reloaded = vec![];
+
+        for (path, typ) in changes {
+            if path.file_name() == Some("tsconfig.json".as_ref()) {
+                let root = path.parent().unwrap_or(&path).to_path_buf();
+                if !reloaded.contains(&root) && self.projects.iter().any(|project| project.root() == root) {
+                    reloaded.push(root);
+                }
+                continue;
+            }
+
+            // Open documents shadow the files on the disk.
+            if self.documents.get(&path).is_some() {
+                continue;
+            }
+
+            for project in &mut self.projects {
+                project.update(&path, typ != FileChangeType::CHANGED);
+            }
+        }
+
+        for root in reloaded {
+            match Session::new(&root, self.documents.clone()) {
+                Ok(reloaded) => {
+                    if let Some(project) = self.projects.iter_mut().find(|project| project.root() == root) {
+                        *project = reloaded;
+                    }
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        self.check_documents();
+
+        errors
+    }
+
+    /// Applies the edits of `textDocument/didChange` to the open document at
+    /// `path`, and checks it again with the files which depend on it.
+    pub fn change_document(&mut self, path: &Path, changes: Vec<TextDocumentContentChangeEvent>) {
+        let mut text = match self.documents.get(path) {
+            Some(text) => text.to_string(),
+            None => return,
+        };
+
+        // Edits are applied in order, and positions of an edit are relative to the
+        // text after the previous edits.
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let start = offset_at(&text, range.start);
+                    let end = offset_at(&text, range.end).max(start);
+                    text.replace_range(start..end, &change.text);
+                }
+                None => text = change.text,
+            }
+        }
+
+        self.set_document(path, text);
+    }
+
+    /// Closes the document at `path`, so that the file on the disk is used
+    /// again.
+    pub fn close_document(&mut self, path: &Path) {
+        if self.documents.remove(path).is_some() {
+            for project in &mut self.projects {
+                project.update(path, true);
+            }
+        }
+    }
+}
@@ -10,7 +10,7 @@ use serde_json::{json, Value};
 use stc_ts_testing::lsp::LspClient;
 use stc_utils::AHashSet;
 use testing::run_test;
-use tower_lsp::lsp_types::{Diagnostic, PublishDiagnosticsParams};
+use tower_lsp::lsp_types::{Diagnostic, NumberOrString, Position, PublishDiagnosticsParams, Url};
 use tracing::info;
 
 /// Builds the example lsp command, and returns to the path to it.
@@ -93,18 +93,12 @@ fn handle_configuration_request(client: &mut LspClient, result: Value) {
 }
 
 #[tracing::instrument(skip_all)]
-#[allow(unused)]
 fn read_diagnostics(client: &mut LspClient) -> CollectedDiagnostics {
-    CollectedDiagnostics(Default::default())
-
-    // TODO: Implement this after implementing lsp
-
-    // let mut diagnostics = vec![];
-    // let (method, response) =
-    // client.read_notification::<PublishDiagnosticsParams>().unwrap();
-    // assert_eq!(method, "textDocument/publishDiagnostics");
-    // diagnostics.push(response.unwrap());
-    // CollectedDiagnostics(diagnostics)
+    let mut diagnostics = vec![];
+    let (method, response) = client.read_notification::<PublishDiagnosticsParams>().unwrap();
+    assert_eq!(method, "textDocument/publishDiagnostics");
+    diagnostics.push(response.unwrap());
+    CollectedDiagnostics(diagnostics)
 }
 
 fn shutdown(client: &mut LspClient) {
@@ -179,21 +173,21 @@ fn test_hover() {
                 "uri": "file:///a/file.ts",
                 "languageId": "typescript",
                 "version": 1,
-                "text": "console.log('foo');\n"
+                "text": "const foo = 1;\nfoo;\n"
               }
             }),
         );
         dbg!("After did_open");
         let (maybe_res, maybe_err) = client
-            .write_request(
+            .write_request::<_, _, Value>(
                 "textDocument/hover",
                 json!({
                   "textDocument": {
                     "uri": "file:///a/file.ts"
                   },
                   "position": {
-                    "line": 0,
-                    "character": 5
+                    "line": 1,
+                    "character": 1
                   }
                 }),
             )
@@ -201,11 +195,15 @@ fn test_hover() {
         dbg!("After client.write_request");
 
         assert!(maybe_err.is_none());
+        let res = maybe_res.unwrap();
+        assert_eq!(res["contents"]["kind"], "markdown");
+        assert!(res["contents"]["value"].as_str().unwrap().contains("const foo"));
         assert_eq!(
-            maybe_res,
-            Some(json!({
-              "contents": "hover test",
-            }))
+            res["range"],
+            json!({
+              "start": { "line": 1, "character": 0 },
+              "end": { "line": 1, "character": 3 }
+            })
         );
         shutdown(&mut client);
 
@@ -213,3 +211,384 @@ fn test_hover() {
     })
     .unwrap();
 }
+
+#[test]
+fn test_diagnostics() {
+    let mut client = init("initialize_params.json");
+    let diagnostics = did_open(
+        &mut client,
+        json!({
+          "textDocument": {
+            "uri": "file:///a/file.ts",
+            "languageId": "typescript",
+            "version": 1,
+            "text": "const a: string = 1;\n"
+          }
+        }),
+    );
+
+    let diagnostics = CollectedDiagnostics(diagnostics).with_source("stc").diagnostics;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, Some(NumberOrString::Number(2322)));
+    assert_eq!(diagnostics[0].range.start, Position::new(0, 6));
+
+    // The unsaved text is checked.
+    client
+        .write_notification(
+            "textDocument/didChange",
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts",
+                "version": 2
+              },
+              "contentChanges": [{ "text": "const a: string = '';\n" }]
+            }),
+        )
+        .unwrap();
+    assert_eq!(read_diagnostics(&mut client).viewed(), vec![]);
+
+    shutdown(&mut client);
+}
+
+#[test]
+fn test_incremental_change() {
+    let mut client = init("initialize_params.json");
+    let diagnostics = did_open(
+        &mut client,
+        json!({
+          "textDocument": {
+            "uri": "file:///a/file.ts",
+            "languageId": "typescript",
+            "version": 1,
+            "text": "const a: string = 1;\nconst b: number = a;\n"
+          }
+        }),
+    );
+    assert_eq!(CollectedDiagnostics(diagnostics).viewed().len(), 1);
+
+    // `1` is replaced with `''`, and then `number` is replaced with `string`.
+    client
+        .write_notification(
+            "textDocument/didChange",
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts",
+                "version": 2
+              },
+              "contentChanges": [
+                {
+                  "range": {
+                    "start": { "line": 0, "character": 18 },
+                    "end": { "line": 0, "character": 19 }
+                  },
+                  "text": "''"
+                },
+                {
+                  "range": {
+                    "start": { "line": 1, "character": 9 },
+                    "end": { "line": 1, "character": 15 }
+                  },
+                  "text": "string"
+                }
+              ]
+            }),
+        )
+        .unwrap();
+    assert_eq!(read_diagnostics(&mut client).viewed(), vec![]);
+
+    shutdown(&mut client);
+}
+
+#[test]
+fn test_pull_diagnostics() {
+    let mut client = init("initialize_params.json");
+    did_open(
+        &mut client,
+        json!({
+          "textDocument": {
+            "uri": "file:///a/file.ts",
+            "languageId": "typescript",
+            "version": 1,
+            "text": "const a: string = 1;\n"
+          }
+        }),
+    );
+
+    let (maybe_res, maybe_err) = client
+        .write_request::<_, _, Value>(
+            "textDocument/diagnostic",
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts"
+              }
+            }),
+        )
+        .unwrap();
+
+    assert!(maybe_err.is_none());
+    let res = maybe_res.unwrap();
+    assert_eq!(res["kind"], "full");
+    let items = res["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["code"], 2322);
+    assert_eq!(items[0]["source"], "stc");
+
+    shutdown(&mut client);
+}
+
+#[test]
+fn test_definition() {
+    let mut client = init("initialize_params.json");
+    did_open(
+        &mut client,
+        json!({
+          "textDocument": {
+            "uri": "file:///a/file.ts",
+            "languageId": "typescript",
+            "version": 1,
+            "text": "const foo = 1;\nfoo;\n"
+          }
+        }),
+    );
+
+    let (maybe_res, maybe_err) = client
+        .write_request::<_, _, Value>(
+            "textDocument/definition",
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts"
+              },
+              "position": {
+                "line": 1,
+                "character": 1
+              }
+            }),
+        )
+        .unwrap();
+
+    assert!(maybe_err.is_none());
+    assert_eq!(
+        maybe_res,
+        Some(json!([{
+          "uri": "file:///a/file.ts",
+          "range": {
+            "start": { "line": 0, "character": 6 },
+            "end": { "line": 0, "character": 9 }
+          }
+        }]))
+    );
+
+    shutdown(&mut client);
+}
+
+#[test]
+fn test_semantic_tokens() {
+    let mut client = init("initialize_params.json");
+    did_open(
+        &mut client,
+        json!({
+          "textDocument": {
+            "uri": "file:///a/file.ts",
+            "languageId": "typescript",
+            "version": 1,
+            "text": "const foo = 1;\nfoo;\n"
+          }
+        }),
+    );
+
+    let (maybe_res, maybe_err) = client
+        .write_request::<_, _, Value>(
+            "textDocument/semanticTokens/full",
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts"
+              }
+            }),
+        )
+        .unwrap();
+
+    assert!(maybe_err.is_none());
+    // `variable` with `declaration | readonly`, and `variable` with `readonly`.
+    assert_eq!(maybe_res, Some(json!({ "data": [0, 6, 3, 7, 3, 1, 0, 3, 7, 2] })));
+
+    shutdown(&mut client);
+}
+
+#[test]
+fn test_workspace_folders() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixture")
+        .join("workspace");
+    let uri = |path: &str| Url::from_file_path(workspace.join(path)).unwrap();
+
+    let mut params = load_fixture("initialize_params.json");
+    params["workspaceFolders"] = json!([
+      { "uri": uri("strict"), "name": "strict" },
+      { "uri": uri("loose"), "name": "loose" }
+    ]);
+
+    let mut client = LspClient::new(&exec_path(), true).expect("failed to create a lsp client");
+    client.write_request::<_, _, Value>("initialize", params).unwrap();
+    client.write_notification("initialized", json!({})).unwrap();
+
+    // `null` is assignable to `string` only if `strictNullChecks` is disabled.
+    for (folder, errors) in [("strict", 1), ("loose", 0)] {
+        let uri = uri(&format!("{}/file.ts", folder));
+        did_open(
+            &mut client,
+            json!({
+              "textDocument": {
+                "uri": uri,
+                "languageId": "typescript",
+                "version": 1,
+                "text": "const a: string = null;\n"
+              }
+            }),
+        );
+
+        let (maybe_res, maybe_err) = client
+            .write_request::<_, _, Value>(
+                "textDocument/diagnostic",
+                json!({
+                  "textDocument": {
+                    "uri": uri
+                  }
+                }),
+            )
+            .unwrap();
+
+        assert!(maybe_err.is_none());
+        assert_eq!(maybe_res.unwrap()["items"].as_array().unwrap().len(), errors, "{}", folder);
+    }
+
+    shutdown(&mut client);
+}
+
+#[test]
+fn test_code_lens() {
+    let mut client = init("initialize_params.json");
+    did_open(
+        &mut client,
+        json!({
+          "textDocument": {
+            "uri": "file:///a/file.ts",
+            "languageId": "typescript",
+            "version": 1,
+            "text": "export function foo() {}\nfoo();\nfoo();\n"
+          }
+        }),
+    );
+
+    let (maybe_res, maybe_err) = client
+        .write_request::<_, _, Value>(
+            "textDocument/codeLens",
+            json!({
+              "textDocument": {
+                "uri": "file:///a/file.ts"
+              }
+            }),
+        )
+        .unwrap();
+
+    assert!(maybe_err.is_none());
+    let lenses = maybe_res.unwrap();
+    assert_eq!(lenses.as_array().unwrap().len(), 1);
+    assert_eq!(lenses[0]["range"]["start"], json!({ "line": 0, "character": 16 }));
+    // References are counted when the lens is resolved.
+    assert_eq!(lenses[0].get("command"), None);
+
+    let (maybe_res, maybe_err) = client.write_request::<_, _, Value>("codeLens/resolve", lenses[0].clone()).unwrap();
+
+    assert!(maybe_err.is_none());
+    assert_eq!(maybe_res.unwrap()["command"]["title"], "(2 references)");
+
+    shutdown(&mut client);
+}
+
+#[test]
+fn test_workspace_symbol() {
+    let mut client = init("initialize_params.json");
+    did_open(
+        &mut client,
+        json!({
+          "textDocument": {
+            "uri": "file:///a/file.ts",
+            "languageId": "typescript",
+            "version": 1,
+            "text": "export function getUserName() {}\nconst other = 1;\n"
+          }
+        }),
+    );
+
+    let (maybe_res, maybe_err) = client
+        .write_request::<_, _, Value>("workspace/symbol", json!({ "query": "gun" }))
+        .unwrap();
+
+    assert!(maybe_err.is_none());
+    assert_eq!(
+        maybe_res,
+        Some(json!([{
+          "name": "getUserName",
+          "kind": 12,
+          "location": {
+            "uri": "file:///a/file.ts",
+            "range": {
+              "start": { "line": 0, "character": 16 },
+              "end": { "line": 0, "character": 27 }
+            }
+          }
+        }]))
+    );
+
+    shutdown(&mut client);
+}
+
+#[test]
+fn test_watched_files() {
+    let dir = std::env::temp_dir().join(format!("stc-lsp-watched-files-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let main = "import { b } from './b';\nb;\n";
+    std::fs::write(dir.join("main.ts"), main).unwrap();
+
+    let mut params = load_fixture("initialize_params.json");
+    params["workspaceFolders"] = json!([{ "uri": Url::from_file_path(&dir).unwrap(), "name": "project" }]);
+
+    let mut client = LspClient::new(&exec_path(), true).expect("failed to create a lsp client");
+    client.write_request::<_, _, Value>("initialize", params).unwrap();
+    client.write_notification("initialized", json!({})).unwrap();
+
+    let diagnostics = did_open(
+        &mut client,
+        json!({
+          "textDocument": {
+            "uri": Url::from_file_path(dir.join("main.ts")).unwrap(),
+            "languageId": "typescript",
+            "version": 1,
+            "text": main
+          }
+        }),
+    );
+    assert_eq!(diagnostics[0].diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].diagnostics[0].code, Some(NumberOrString::Number(2307)));
+
+    // The import is resolved to the created file without restarting the server.
+    std::fs::write(dir.join("b.ts"), "export const b = 1;\n").unwrap();
+    client
+        .write_notification(
+            "workspace/didChangeWatchedFiles",
+            json!({
+              "changes": [{
+                "uri": Url::from_file_path(dir.join("b.ts")).unwrap(),
+                "type": 1
+              }]
+            }),
+        )
+        .unwrap();
+
+    let diagnostics = read_diagnostics(&mut client).0;
+    assert_eq!(diagnostics[0].diagnostics, vec![]);
+
+    shutdown(&mut client);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
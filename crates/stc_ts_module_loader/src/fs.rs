@@ -0,0 +1,216 @@
+//! File system abstraction used by resolvers and the module loader.
+//!
+//! Embedders can provide their own implementation of [FileSystem] to supply
+//! file contents which are not stored on the disk, like unsaved buffers of an
+//! editor.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use auto_impl::auto_impl;
+use parking_lot::RwLock;
+
+#[auto_impl(&, Box, Arc)]
+pub trait FileSystem: 'static + Send + Sync {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Returns the paths of the direct children of `path`.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+
+    /// Returns the absolute path with all symbolic links resolved.
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Returns the last modification time of a file.
+    ///
+    /// File systems which don't track it return an error, and the content is
+    /// compared instead where it matters.
+    fn modified(&self, path: &Path) -> io::Result<SystemTime> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("modification time of {} is not available", path.display()),
+        ))
+    }
+}
+
+/// The file system of the host.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFs;
+
+impl FileSystem for RealFs {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::read_dir(path)?.map(|entry| entry.map(|e| e.path())).collect()
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::canonicalize(path)
+    }
+
+    fn modified(&self, path: &Path) -> io::Result<SystemTime> {
+        fs::metadata(path)?.modified()
+    }
+}
+
+/// An in-memory file system.
+///
+/// Directories are not stored explicitly, and a path is a directory if any
+/// file is stored under it. Symbolic links are not supported.
+#[derive(Debug, Default)]
+pub struct MemoryFs {
+    files: RwLock<BTreeMap<PathBuf, Arc<str>>>,
+}
+
+impl MemoryFs {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds or replaces a file.
+    pub fn insert(&self, path: PathBuf, content: impl Into<Arc<str>>) {
+        self.files.write().insert(path, content.into());
+    }
+
+    /// Returns the removed content.
+    pub fn remove(&self, path: &Path) -> Option<Arc<str>> {
+        self.files.write().remove(path)
+    }
+
+    pub fn get(&self, path: &Path) -> Option<Arc<str>> {
+        self.files.read().get(path).cloned()
+    }
+
+    /// Returns the paths of all stored files.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files.read().keys().cloned().collect()
+    }
+}
+
+impl FileSystem for MemoryFs {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        match self.files.read().get(path) {
+            Some(content) => Ok(content.to_string()),
+            None => Err(io::Error::new(io::ErrorKind::NotFound, format!("{} not found", path.display()))),
+        }
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.read().contains_key(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.files.read().keys().any(|file| file != path && file.starts_with(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let files = self.files.read();
+
+        let mut children = files
+            .keys()
+            .filter(|file| *file != path)
+            .filter_map(|file| {
+                let rel = file.strip_prefix(path).ok()?;
+                let first = rel.components().next()?;
+
+                Some(path.join(first))
+            })
+            .collect::<Vec<_>>();
+        children.dedup();
+
+        if children.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("{} not found", path.display())));
+        }
+
+        Ok(children)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        if self.is_file(path) || self.is_dir(path) {
+            Ok(path.to_path_buf())
+        } else {
+            Err(io::Error::new(io::ErrorKind::NotFound, format!("{} not found", path.display())))
+        }
+    }
+}
+
+/// A file system which stores files of `upper` on top of `lower`.
+///
+/// Files in `upper` shadow the files with the same path in `lower`. This can
+/// be used to add virtual modules or unsaved files to [RealFs].
+#[derive(Clone)]
+pub struct OverlayFs {
+    upper: Arc<dyn FileSystem>,
+    lower: Arc<dyn FileSystem>,
+}
+
+impl OverlayFs {
+    pub fn new(upper: Arc<dyn FileSystem>, lower: Arc<dyn FileSystem>) -> Self {
+        Self { upper, lower }
+    }
+}
+
+impl FileSystem for OverlayFs {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        if self.upper.is_file(path) {
+            return self.upper.read_to_string(path);
+        }
+
+        self.lower.read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.upper.is_file(path) || self.lower.is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.upper.is_dir(path) || self.lower.is_dir(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let (upper, lower) = (self.upper.read_dir(path), self.lower.read_dir(path));
+
+        let mut children = match (upper, lower) {
+            (Err(err), Err(..)) => return Err(err),
+            (upper, lower) => upper.into_iter().chain(lower).flatten().collect::<Vec<_>>(),
+        };
+        children.sort();
+        children.dedup();
+
+        Ok(children)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        if self.upper.is_file(path) || self.upper.is_dir(path) {
+            return self.upper.canonicalize(path);
+        }
+
+        self.lower.canonicalize(path)
+    }
+
+    fn modified(&self, path: &Path) -> io::Result<SystemTime> {
+        if self.upper.is_file(path) {
+            return self.upper.modified(path);
+        }
+
+        self.lower.modified(path)
+    }
+}
@@ -1,3 +1,4 @@
 #![deny(warnings)]
 
+pub mod fs;
 pub mod resolvers;
@@ -0,0 +1,166 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use anyhow::{anyhow, Error};
+use dashmap::DashMap;
+use fxhash::FxBuildHasher;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use crate::fs::FileSystem;
+
+/// A resolver which caches results of the inner resolver, so repeated checks
+/// like watch mode or a language server don't probe the file system for every
+/// import again.
+///
+/// Results are keyed on the directory of the importer and the module
+/// specifier. The extension of the importer is part of the key too, because it
+/// decides between ESM and CommonJS in `node16`.
+///
+/// A cached result is reused only if the resolved file still exists and has
+/// not been modified. Changes which affect resolution otherwise, like added
+/// files or modified `package.json` files, should be reported using
+/// [CachingResolver::invalidate].
+///
+/// Clones share the cache.
+#[derive(Clone)]
+pub struct CachingResolver<R>
+where
+    R: Resolve,
+{
+    inner: R,
+    fs: Arc<dyn FileSystem>,
+    cache: Arc<DashMap<CacheKey, CacheEntry, FxBuildHasher>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    dir: PathBuf,
+    ext: Option<String>,
+    specifier: String,
+}
+
+#[derive(Clone)]
+enum CacheEntry {
+    Resolved {
+        resolved: FileName,
+        stamp: Option<Stamp>,
+    },
+    /// The message of the error.
+    Failed(String),
+}
+
+/// The version of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Stamp {
+    Modified(SystemTime),
+    /// Hash of the content, used if the file system does not track modification
+    /// times.
+    Content(u64),
+}
+
+impl<R> CachingResolver<R>
+where
+    R: Resolve,
+{
+    /// `fs` should be the file system used by `inner`.
+    pub fn new(inner: R, fs: Arc<dyn FileSystem>) -> Self {
+        Self {
+            inner,
+            fs,
+            cache: Default::default(),
+        }
+    }
+
+    /// Removes cached results which may be affected by a change of `path`,
+    /// which is a file or a directory.
+    ///
+    /// These are results resolved to `path` or a file in it, results for
+    /// importers in `path`, and all failed results because `path` may be a
+    /// new file.
+    pub fn invalidate(&self, path: &Path) {
+        self.cache.retain(|key, entry| match entry {
+            CacheEntry::Resolved {
+                resolved: FileName::Real(resolved),
+                ..
+            } => !resolved.starts_with(path) && !key.dir.starts_with(path),
+            CacheEntry::Resolved { .. } => !key.dir.starts_with(path),
+            CacheEntry::Failed(..) => false,
+        });
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+
+    fn stamp(&self, path: &Path) -> Option<Stamp> {
+        match self.fs.modified(path) {
+            Ok(time) => Some(Stamp::Modified(time)),
+            Err(..) => {
+                let content = self.fs.read_to_string(path).ok()?;
+                Some(Stamp::Content(fxhash::hash64(&content)))
+            }
+        }
+    }
+
+    /// Returns the cached result if it's still valid.
+    fn get(&self, key: &CacheKey) -> Option<Result<FileName, Error>> {
+        let entry = self.cache.get(key)?.clone();
+
+        match entry {
+            CacheEntry::Resolved {
+                resolved: FileName::Real(path),
+                stamp,
+            } => {
+                // Resolve again if the file is removed or modified.
+                let current = self.stamp(&path)?;
+                if Some(current) == stamp {
+                    Some(Ok(FileName::Real(path)))
+                } else {
+                    None
+                }
+            }
+            CacheEntry::Resolved { resolved, .. } => Some(Ok(resolved)),
+            CacheEntry::Failed(msg) => Some(Err(anyhow!("{}", msg))),
+        }
+    }
+}
+
+impl<R> Resolve for CachingResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let key = match base {
+            FileName::Real(base) => CacheKey {
+                dir: base.parent().map(Path::to_path_buf).unwrap_or_default(),
+                ext: base.extension().map(|ext| ext.to_string_lossy().into_owned()),
+                specifier: target.to_string(),
+            },
+            _ => return self.inner.resolve(base, target),
+        };
+
+        if let Some(result) = self.get(&key) {
+            return result;
+        }
+
+        let result = self.inner.resolve(base, target);
+        let entry = match &result {
+            Ok(resolved) => CacheEntry::Resolved {
+                stamp: match resolved {
+                    FileName::Real(path) => self.stamp(path),
+                    _ => None,
+                },
+                resolved: resolved.clone(),
+            },
+            Err(err) => CacheEntry::Failed(format!("{:#}", err)),
+        };
+        self.cache.insert(key, entry);
+
+        result
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Error};
+use path_clean::PathClean;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+use super::{
+    node::{types_package_name, NodeResolver},
+    trace::Tracer,
+};
+use crate::fs::{FileSystem, RealFs};
+
+static EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts"];
+
+/// Extensions tried after [EXTENSIONS] if `allowJs` is enabled.
+static ALLOW_JS_EXTENSIONS: &[&str] = &["js", "jsx"];
+
+/// The legacy module resolution strategy of tsc, used by
+/// `moduleResolution: classic`.
+///
+/// - Relative imports are resolved only as files.
+/// - Non-relative imports are resolved by looking for the file in the directory
+///   of the importer and then its ancestors. `node_modules` is not used, except
+///   for `node_modules/@types`.
+#[derive(Clone)]
+pub struct ClassicResolver {
+    fs: Arc<dyn FileSystem>,
+    /// Used for `node_modules/@types`.
+    node: NodeResolver,
+    /// Resolve javascript files too.
+    allow_js: bool,
+
+    /// `moduleSuffixes`. This is `[""]` if not specified.
+    module_suffixes: Vec<String>,
+
+    tracer: Tracer,
+}
+
+impl Default for ClassicResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClassicResolver {
+    pub fn new() -> Self {
+        Self {
+            fs: Arc::new(RealFs),
+            node: NodeResolver::new(),
+            allow_js: false,
+            module_suffixes: vec![String::new()],
+            tracer: Default::default(),
+        }
+    }
+
+    /// Use `fs` instead of the file system of the host.
+    pub fn with_fs(self, fs: Arc<dyn FileSystem>) -> Self {
+        Self {
+            node: self.node.with_fs(fs.clone()),
+            fs,
+            ..self
+        }
+    }
+
+    /// Resolve javascript files if there's no typescript file for them.
+    pub fn with_allow_js(mut self, allow_js: bool) -> Self {
+        self.allow_js = allow_js;
+        self
+    }
+
+    /// See [NodeResolver::with_preserve_symlinks].
+    pub fn with_preserve_symlinks(self, preserve_symlinks: bool) -> Self {
+        Self {
+            node: self.node.with_preserve_symlinks(preserve_symlinks),
+            ..self
+        }
+    }
+
+    /// See [NodeResolver::with_module_suffixes].
+    pub fn with_module_suffixes(self, module_suffixes: Vec<String>) -> Self {
+        Self {
+            node: self.node.with_module_suffixes(module_suffixes.clone()),
+            module_suffixes: if module_suffixes.is_empty() {
+                vec![String::new()]
+            } else {
+                module_suffixes
+            },
+            ..self
+        }
+    }
+
+    /// See [NodeResolver::with_tracer].
+    pub fn with_tracer(self, tracer: Tracer) -> Self {
+        Self {
+            node: self.node.with_tracer(tracer.clone()),
+            tracer,
+            ..self
+        }
+    }
+
+    /// Tries `path` and then `path` + each extension.
+    fn resolve_as_file(&self, path: &Path) -> Result<PathBuf, Error> {
+        if self.node.file_exists(path) {
+            return Ok(path.to_path_buf());
+        }
+
+        let js: &[&str] = if self.allow_js { ALLOW_JS_EXTENSIONS } else { &[] };
+
+        for ext in EXTENSIONS.iter().chain(js) {
+            for suffix in &self.module_suffixes {
+                let mut ext_path = path.as_os_str().to_os_string();
+                ext_path.push(suffix);
+                ext_path.push(".");
+                ext_path.push(ext);
+
+                let ext_path = PathBuf::from(ext_path);
+                if self.node.file_exists(&ext_path) {
+                    return Ok(ext_path);
+                }
+            }
+        }
+
+        bail!("file not found: {}", path.display())
+    }
+
+    /// Looks for `target` in `base_dir` and its ancestors.
+    fn resolve_in_ancestors(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        let mut cur = Some(base_dir);
+        while let Some(dir) = cur {
+            if let Ok(path) = self.resolve_as_file(&dir.join(target)) {
+                return Ok(path);
+            }
+            cur = dir.parent();
+        }
+
+        // Fall back to DefinitelyTyped packages.
+        let name = types_package_name(target);
+        let mut cur = Some(base_dir);
+        while let Some(dir) = cur {
+            let types = dir.join("node_modules").join("@types");
+            if self.fs.is_dir(&types) {
+                let path = types.join(&name);
+                let result = self.node.resolve_as_file(&path).or_else(|_| self.node.resolve_as_directory(&path));
+                if let Ok(path) = result {
+                    return Ok(self.node.real_path(path));
+                }
+            }
+            cur = dir.parent();
+        }
+
+        bail!("module not found: {}", target)
+    }
+}
+
+impl Resolve for ClassicResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let base = match base {
+            FileName::Real(base) => &**base,
+            _ => {
+                unreachable!("base = {:?}; target = {:?}", base, target)
+            }
+        };
+
+        self.tracer.start(base, target);
+
+        let cwd = &Path::new(".");
+        let base_dir = base.parent().unwrap_or(cwd);
+
+        let result = if target.starts_with('/') || target.starts_with("./") || target.starts_with("../") {
+            self.resolve_as_file(&base_dir.join(target))
+                .with_context(|| format!("failed to resolve `{}` as a file dependency from `{}`", target, base.display()))
+        } else {
+            self.resolve_in_ancestors(base_dir, target)
+                .with_context(|| format!("failed to resolve `{}` from `{}`", target, base.display()))
+        };
+        let result = result.map(|path| FileName::Real(path.clean()));
+
+        self.tracer.finish(target, &result);
+
+        result
+    }
+}
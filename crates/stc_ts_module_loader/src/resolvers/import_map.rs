@@ -0,0 +1,127 @@
+//! https://github.com/WICG/import-maps
+
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{anyhow, Context, Error};
+use serde::Deserialize;
+use url::Url;
+
+use crate::fs::FileSystem;
+
+#[derive(Deserialize)]
+struct RawImportMap {
+    #[serde(default)]
+    imports: HashMap<String, Option<String>>,
+    #[serde(default)]
+    scopes: HashMap<String, HashMap<String, Option<String>>>,
+}
+
+/// A parsed import map, with all addresses normalized to absolute urls.
+#[derive(Debug, Clone, Default)]
+pub struct ImportMap {
+    imports: SpecifierMap,
+    /// Sorted by the length of the scope prefix, from the longest one.
+    scopes: Vec<(String, SpecifierMap)>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct SpecifierMap(Vec<(String, Url)>);
+
+impl ImportMap {
+    /// `base_url` is the url of the import map itself, and relative
+    /// specifiers and addresses are resolved from it.
+    pub fn parse(json: &str, base_url: &Url) -> Result<Self, Error> {
+        let raw: RawImportMap = serde_json::from_str(json).context("failed to parse import map")?;
+
+        let imports = SpecifierMap::normalize(raw.imports, base_url);
+
+        let mut scopes = raw
+            .scopes
+            .into_iter()
+            .filter_map(|(prefix, map)| {
+                let prefix = base_url.join(&prefix).ok()?;
+
+                Some((prefix.to_string(), SpecifierMap::normalize(map, base_url)))
+            })
+            .collect::<Vec<_>>();
+        scopes.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+
+        Ok(ImportMap { imports, scopes })
+    }
+
+    /// Reads the import map at `path` from `fs`. Relative specifiers and
+    /// addresses are resolved from `path`.
+    pub fn load(fs: &dyn FileSystem, path: &Path) -> Result<Self, Error> {
+        let json = fs
+            .read_to_string(path)
+            .with_context(|| format!("failed to read import map `{}`", path.display()))?;
+        let base_url = Url::from_file_path(path).map_err(|()| anyhow!("import map path is not absolute: {}", path.display()))?;
+
+        Self::parse(&json, &base_url)
+    }
+
+    /// Returns the mapped url, or [None] if `specifier` is not mapped.
+    pub fn resolve(&self, specifier: &str, referrer: &Url) -> Option<Url> {
+        let normalized = normalize_specifier(specifier, referrer);
+
+        for (prefix, map) in &self.scopes {
+            if referrer.as_str().starts_with(&**prefix) {
+                if let Some(url) = map.resolve(&normalized) {
+                    return Some(url);
+                }
+            }
+        }
+
+        self.imports.resolve(&normalized)
+    }
+}
+
+impl SpecifierMap {
+    fn normalize(map: HashMap<String, Option<String>>, base_url: &Url) -> Self {
+        let mut entries = map
+            .into_iter()
+            .filter_map(|(key, address)| {
+                // `null` blocks the specifier, and we treat it as not mapped.
+                let address = base_url.join(&address?).ok()?;
+
+                Some((normalize_specifier(&key, base_url), address))
+            })
+            .collect::<Vec<_>>();
+
+        // Longest key wins.
+        entries.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+
+        SpecifierMap(entries)
+    }
+
+    fn resolve(&self, specifier: &str) -> Option<Url> {
+        for (key, address) in &self.0 {
+            if key == specifier {
+                return Some(address.clone());
+            }
+
+            if key.ends_with('/') {
+                if let Some(rest) = specifier.strip_prefix(&**key) {
+                    return address.join(rest).ok();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Converts url-like specifiers to absolute urls, and returns bare specifiers
+/// as-is.
+fn normalize_specifier(specifier: &str, base_url: &Url) -> String {
+    if specifier.starts_with('/') || specifier.starts_with("./") || specifier.starts_with("../") {
+        if let Ok(url) = base_url.join(specifier) {
+            return url.to_string();
+        }
+    }
+
+    match Url::parse(specifier) {
+        Ok(url) => url.to_string(),
+        Err(_) => specifier.to_string(),
+    }
+}
@@ -1 +1,90 @@
+use std::sync::Arc;
+
+use anyhow::Error;
+use stc_ts_env::ModuleResolution;
+use swc_common::FileName;
+/// Module resolvers implement this trait, and any implementation can be used by
+/// the module loader.
+pub use swc_ecma_loader::resolve::Resolve;
+
+use self::{classic::ClassicResolver, node::NodeResolver, trace::Tracer};
+use crate::fs::FileSystem;
+
+pub mod cache;
+pub mod classic;
+pub mod import_map;
 pub mod node;
+mod package_json;
+pub mod plugin;
+pub mod references;
+pub mod suggest;
+pub mod trace;
+pub mod tsc;
+pub mod url;
+
+/// A resolver which implements the module resolution strategy selected by
+/// `moduleResolution`.
+#[derive(Clone)]
+pub enum ModuleResolver {
+    Classic(ClassicResolver),
+    Node(NodeResolver),
+}
+
+impl ModuleResolver {
+    pub fn new(module_resolution: ModuleResolution) -> Self {
+        match module_resolution {
+            ModuleResolution::Classic => ModuleResolver::Classic(ClassicResolver::new()),
+            ModuleResolution::Node => ModuleResolver::Node(NodeResolver::new()),
+            ModuleResolution::Node16 | ModuleResolution::NodeNext => ModuleResolver::Node(NodeResolver::node16()),
+        }
+    }
+
+    /// Resolve javascript files if there's no typescript file for them.
+    pub fn with_allow_js(self, allow_js: bool) -> Self {
+        match self {
+            ModuleResolver::Classic(r) => ModuleResolver::Classic(r.with_allow_js(allow_js)),
+            ModuleResolver::Node(r) => ModuleResolver::Node(r.with_allow_js(allow_js)),
+        }
+    }
+
+    /// See [NodeResolver::with_preserve_symlinks].
+    pub fn with_preserve_symlinks(self, preserve_symlinks: bool) -> Self {
+        match self {
+            ModuleResolver::Classic(r) => ModuleResolver::Classic(r.with_preserve_symlinks(preserve_symlinks)),
+            ModuleResolver::Node(r) => ModuleResolver::Node(r.with_preserve_symlinks(preserve_symlinks)),
+        }
+    }
+
+    /// See [NodeResolver::with_module_suffixes].
+    pub fn with_module_suffixes(self, module_suffixes: Vec<String>) -> Self {
+        match self {
+            ModuleResolver::Classic(r) => ModuleResolver::Classic(r.with_module_suffixes(module_suffixes)),
+            ModuleResolver::Node(r) => ModuleResolver::Node(r.with_module_suffixes(module_suffixes)),
+        }
+    }
+
+    /// See [NodeResolver::with_tracer].
+    pub fn with_tracer(self, tracer: Tracer) -> Self {
+        match self {
+            ModuleResolver::Classic(r) => ModuleResolver::Classic(r.with_tracer(tracer)),
+            ModuleResolver::Node(r) => ModuleResolver::Node(r.with_tracer(tracer)),
+        }
+    }
+
+    /// Use `fs` instead of the file system of the host.
+    pub fn with_fs(self, fs: Arc<dyn FileSystem>) -> Self {
+        match self {
+            ModuleResolver::Classic(r) => ModuleResolver::Classic(r.with_fs(fs)),
+            ModuleResolver::Node(r) => ModuleResolver::Node(r.with_fs(fs)),
+        }
+    }
+}
+
+impl Resolve for ModuleResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        match self {
+            ModuleResolver::Classic(r) => r.resolve(base, target),
+            ModuleResolver::Node(r) => r.resolve(base, target),
+        }
+    }
+}
@@ -1,29 +1,184 @@
 use std::{
-    fs::File,
-    io::BufReader,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use anyhow::{bail, Context, Error};
 use path_clean::PathClean;
-use serde::Deserialize;
 use swc_common::FileName;
 use swc_ecma_loader::resolve::Resolve;
 
+use super::{
+    package_json::{split_package_name, PackageJson, Target},
+    trace::Tracer,
+};
+use crate::fs::{FileSystem, RealFs};
+
 static EXTENSIONS: &[&str] = &["tsx", "ts", "d.ts"];
 
-#[derive(Deserialize)]
-struct PackageJson {
-    #[serde(default)]
-    types: Option<String>,
+/// Extensions tried after [EXTENSIONS] if `allowJs` is enabled.
+static ALLOW_JS_EXTENSIONS: &[&str] = &["js", "jsx"];
+
+/// Extensions of javascript files and the extensions of typescript files which
+/// can be used instead of them.
+static JS_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("js", &["ts", "tsx", "d.ts"]),
+    ("jsx", &["tsx", "ts", "d.ts"]),
+    ("mjs", &["mts", "d.mts"]),
+    ("cjs", &["cts", "d.cts"]),
+];
+
+/// Conditions used for `exports` and `imports` of `package.json` by default.
+static DEFAULT_CONDITIONS: &[&str] = &["types", "import", "require", "node"];
+
+#[derive(Clone)]
+pub struct NodeResolver {
+    fs: Arc<dyn FileSystem>,
+
+    /// Conditions of `exports` and `imports` which are considered as active.
+    /// `default` is always active.
+    conditions: Vec<String>,
+
+    /// Use the semantics of `moduleResolution: node16` and `nodenext`.
+    node16: bool,
+
+    /// `true` while resolving an import of an ES module in node16 mode.
+    esm: bool,
+
+    /// Resolve javascript files too.
+    allow_js: bool,
+
+    /// Do not resolve symbolic links of packages in `node_modules`.
+    preserve_symlinks: bool,
+
+    /// `moduleSuffixes`. This is `[""]` if not specified.
+    module_suffixes: Vec<String>,
+
+    tracer: Tracer,
 }
 
-#[derive(Default)]
-pub struct NodeResolver;
+impl Default for NodeResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl NodeResolver {
     pub fn new() -> Self {
-        Self
+        Self::with_conditions(DEFAULT_CONDITIONS.iter().map(|s| s.to_string()).collect())
+    }
+
+    pub fn with_conditions(conditions: Vec<String>) -> Self {
+        Self {
+            fs: Arc::new(RealFs),
+            conditions,
+            node16: false,
+            esm: false,
+            allow_js: false,
+            preserve_symlinks: false,
+            module_suffixes: vec![String::new()],
+            tracer: Default::default(),
+        }
+    }
+
+    /// Creates a resolver for `moduleResolution: node16` and `nodenext`.
+    ///
+    /// Whether an import is an ES module import is determined by the importing
+    /// file, using its extension or the `type` field of the closest
+    /// `package.json`. ES module imports use the `import` condition instead of
+    /// `require`, and relative specifiers in them require explicit extensions.
+    pub fn node16() -> Self {
+        Self {
+            node16: true,
+            ..Self::new()
+        }
+    }
+
+    /// Use `fs` instead of the file system of the host.
+    pub fn with_fs(mut self, fs: Arc<dyn FileSystem>) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// Resolve javascript files if there's no typescript file for them.
+    pub fn with_allow_js(mut self, allow_js: bool) -> Self {
+        self.allow_js = allow_js;
+        self
+    }
+
+    /// By default, files in `node_modules` are identified by their real path,
+    /// so a package linked from multiple places (like pnpm or a monorepo)
+    /// is loaded only once. This disables it.
+    pub fn with_preserve_symlinks(mut self, preserve_symlinks: bool) -> Self {
+        self.preserve_symlinks = preserve_symlinks;
+        self
+    }
+
+    /// Suffixes tried before extensions, in order, like `.ios` for
+    /// `foo.ios.ts`. An empty string means the file without a suffix.
+    ///
+    /// See https://www.typescriptlang.org/tsconfig#moduleSuffixes
+    pub fn with_module_suffixes(mut self, module_suffixes: Vec<String>) -> Self {
+        self.module_suffixes = if module_suffixes.is_empty() {
+            vec![String::new()]
+        } else {
+            module_suffixes
+        };
+        self
+    }
+
+    /// Reports each step of resolution to `tracer`, like `traceResolution` of
+    /// tsc.
+    pub fn with_tracer(mut self, tracer: Tracer) -> Self {
+        self.tracer = tracer;
+        self
+    }
+
+    /// Resolves symbolic links in `path` unless `preserveSymlinks` is enabled.
+    pub(crate) fn real_path(&self, path: PathBuf) -> PathBuf {
+        if self.preserve_symlinks {
+            return path;
+        }
+
+        let real_path = self.fs.canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if real_path != path {
+            self.tracer
+                .trace(|| format!("Resolving real path for '{}', result '{}'.", path.display(), real_path.display()));
+        }
+        real_path
+    }
+
+    /// Returns `true` if `path` is a file. This is used for candidates of the
+    /// result.
+    pub(crate) fn file_exists(&self, path: &Path) -> bool {
+        let exists = self.fs.is_file(path);
+        self.tracer.trace(|| {
+            if exists {
+                format!("File '{}' exists - use it as a name resolution result.", path.display())
+            } else {
+                format!("File '{}' does not exist.", path.display())
+            }
+        });
+        exists
+    }
+
+    fn read_package_json(&self, path: &Path) -> Result<PackageJson, Error> {
+        self.tracer.trace(|| format!("Found 'package.json' at '{}'.", path.display()));
+
+        PackageJson::read(&*self.fs, path)
+    }
+
+    fn extensions(&self) -> impl Iterator<Item = &'static str> {
+        let js: &[&str] = if self.allow_js { ALLOW_JS_EXTENSIONS } else { &[] };
+
+        EXTENSIONS.iter().chain(js).copied()
+    }
+
+    /// Returns `stem` + each of `moduleSuffixes` + `.ext`.
+    fn with_suffixes<'a>(&'a self, stem: &'a str, ext: &'a str) -> impl 'a + Iterator<Item = PathBuf> {
+        self.module_suffixes
+            .iter()
+            .map(move |suffix| PathBuf::from(format!("{}{}.{}", stem, suffix, ext)))
     }
 
     fn wrap(&self, path: PathBuf) -> Result<FileName, Error> {
@@ -34,27 +189,131 @@ impl NodeResolver {
     /// Resolve a path as a file. If `path` refers to a file, it is returned;
     /// otherwise the `path` + each extension is tried.
     pub fn resolve_as_file(&self, path: &Path) -> Result<PathBuf, Error> {
+        // Typescript files are preferred over javascript files.
+        if let Some(ts_path) = self.resolve_js_extension(path) {
+            return Ok(ts_path);
+        }
+
         // 1. If X is a file, load X as JavaScript text.
-        if path.is_file() {
+        if self.file_exists(path) {
             return Ok(path.to_path_buf());
         }
 
-        for ext in EXTENSIONS {
-            let ext_path = path.with_extension(ext);
-            if ext_path.is_file() {
-                return Ok(ext_path);
+        let stem = path.with_extension("");
+        let stem = stem.to_string_lossy();
+        for ext in self.extensions() {
+            for ext_path in self.with_suffixes(&stem, ext) {
+                if self.file_exists(&ext_path) {
+                    return Ok(ext_path);
+                }
             }
         }
 
         bail!("file not found: {}", path.display())
     }
 
+    /// Maps a path to a javascript file to the typescript file for it, like
+    /// `foo.mjs` to `foo.mts`.
+    fn resolve_js_extension(&self, path: &Path) -> Option<PathBuf> {
+        let path_str = path.to_string_lossy();
+
+        for (js_ext, ts_exts) in JS_EXTENSIONS {
+            if let Some(stem) = path_str.strip_suffix(js_ext).and_then(|s| s.strip_suffix('.')) {
+                for ext in *ts_exts {
+                    for ext_path in self.with_suffixes(stem, ext) {
+                        if self.file_exists(&ext_path) {
+                            return Some(ext_path);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Resolve a relative import of an ES module, which should have an
+    /// explicit extension.
+    fn resolve_esm_file(&self, path: &Path) -> Result<PathBuf, Error> {
+        if self.file_exists(path) {
+            return Ok(path.to_path_buf());
+        }
+
+        if let Some(ts_path) = self.resolve_js_extension(path) {
+            return Ok(ts_path);
+        }
+
+        if path.extension().is_none() {
+            bail!(
+                "relative import paths need explicit file extensions in ECMAScript imports: {}",
+                path.display()
+            )
+        }
+
+        bail!("file not found: {}", path.display())
+    }
+
+    /// Returns `true` if `file` is an ES module.
+    fn is_esm_file(&self, file: &Path) -> bool {
+        let file_str = file.to_string_lossy();
+        if file_str.ends_with(".mts") || file_str.ends_with(".mjs") {
+            return true;
+        }
+        if file_str.ends_with(".cts") || file_str.ends_with(".cjs") {
+            return false;
+        }
+
+        // The closest package.json decides.
+        match file.parent().and_then(|dir| self.find_package_json(dir)) {
+            Some(pkg_path) => match PackageJson::read(&*self.fs, &pkg_path) {
+                Ok(pkg) => pkg.ty.as_deref() == Some("module"),
+                Err(..) => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Returns the path of `package.json` in `dir` or the closest ancestor.
+    fn find_package_json(&self, dir: &Path) -> Option<PathBuf> {
+        let mut cur = Some(dir);
+        while let Some(dir) = cur {
+            let pkg_path = dir.join("package.json");
+            if self.fs.is_file(&pkg_path) {
+                return Some(pkg_path);
+            }
+            cur = dir.parent();
+        }
+
+        None
+    }
+
+    /// Returns the resolver to use for imports in `base`.
+    fn for_importer(&self, base: &Path) -> Option<Self> {
+        if !self.node16 {
+            return None;
+        }
+
+        let esm = self.is_esm_file(base);
+        let excluded = if esm { "require" } else { "import" };
+
+        Some(Self {
+            fs: self.fs.clone(),
+            conditions: self.conditions.iter().filter(|c| *c != excluded).cloned().collect(),
+            node16: true,
+            esm,
+            allow_js: self.allow_js,
+            preserve_symlinks: self.preserve_symlinks,
+            module_suffixes: self.module_suffixes.clone(),
+            tracer: self.tracer.clone(),
+        })
+    }
+
     /// Resolve a path as a directory, using the "main" key from a package.json
     /// file if it exists, or resolving to the index.EXT file if it exists.
     pub fn resolve_as_directory(&self, path: &Path) -> Result<PathBuf, Error> {
         // 1. If X/package.json is a file, use it.
         let pkg_path = path.join("package.json");
-        if pkg_path.is_file() {
+        if self.fs.is_file(&pkg_path) {
             let main = self.resolve_using_package_json(&pkg_path);
             if main.is_ok() {
                 return main;
@@ -70,14 +329,34 @@ impl NodeResolver {
         // TODO: how to not always initialize this here?
         let root = PathBuf::from("/");
         let pkg_dir = pkg_path.parent().unwrap_or(&root);
-        let file = File::open(pkg_path)?;
-        let reader = BufReader::new(file);
-        let pkg: PackageJson = serde_json::from_reader(reader).context("failed to deserialize package.json")?;
+        let pkg = self.read_package_json(pkg_path)?;
+
+        if let Some(exports) = &pkg.exports {
+            self.tracer.trace(|| "'package.json' has 'exports' field.".into());
+            return self.resolve_exports(pkg_dir, exports, ".");
+        }
 
         if let Some(target) = &pkg.types {
             let path = pkg_dir.join(target);
+            self.tracer.trace(|| {
+                format!(
+                    "'package.json' has 'types' field '{}' that references '{}'.",
+                    target,
+                    path.display()
+                )
+            });
             return self.resolve_as_file(&path).or_else(|_| self.resolve_as_directory(&path));
         }
+        self.tracer.trace(|| "'package.json' does not have a 'types' field.".into());
+
+        if self.allow_js {
+            if let Some(target) = &pkg.main {
+                let path = pkg_dir.join(target);
+                self.tracer
+                    .trace(|| format!("'package.json' has 'main' field '{}' that references '{}'.", target, path.display()));
+                return self.resolve_as_file(&path).or_else(|_| self.resolve_as_directory(&path));
+            }
+        }
 
         bail!("package.json does not contain a \"main\" string")
     }
@@ -87,41 +366,196 @@ impl NodeResolver {
         // 1. If X/index.js is a file, load X/index.js as JavaScript text.
         // 2. If X/index.json is a file, parse X/index.json to a JavaScript object.
         // 3. If X/index.node is a file, load X/index.node as binary addon.
-        for ext in EXTENSIONS {
-            let ext_path = path.join(format!("index.{}", ext));
-            if ext_path.is_file() {
-                return Ok(ext_path);
+        let stem = path.join("index");
+        let stem = stem.to_string_lossy();
+        for ext in self.extensions() {
+            for ext_path in self.with_suffixes(&stem, ext) {
+                if self.file_exists(&ext_path) {
+                    return Ok(ext_path);
+                }
             }
         }
 
         bail!("index not found: {}", path.display())
     }
 
-    fn try_package(&self, pkg_dir: &Path) -> Result<PathBuf, Error> {
-        self.resolve_as_file(pkg_dir).or_else(|_| self.resolve_as_directory(pkg_dir))
+    /// Resolve a target of `exports` or `imports`, which is a path relative to
+    /// the package directory.
+    ///
+    /// Targets usually point to `.js` files, so we try the declaration files
+    /// next to them first.
+    fn resolve_package_target(&self, pkg_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        if !target.starts_with("./") {
+            bail!("invalid package target `{}`: it should start with `./`", target)
+        }
+
+        let path = pkg_dir.join(target);
+        let path_str = path.to_string_lossy();
+
+        if [".ts", ".tsx", ".mts", ".cts"].iter().any(|ext| path_str.ends_with(ext)) {
+            if self.file_exists(&path) {
+                return Ok(path);
+            }
+            bail!("package target not found: {}", path.display())
+        }
+
+        if let Some(ts_path) = self.resolve_js_extension(&path) {
+            return Ok(ts_path);
+        }
+
+        self.resolve_as_file(&path).or_else(|_| self.resolve_as_directory(&path))
     }
 
-    /// Resolve by walking up node_modules folders.
-    fn resolve_node_modules(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
-        let node_modules = base_dir.join("node_modules");
-        if node_modules.is_dir() {
-            let path = node_modules.join(target);
-            let result = self.try_package(&path);
-            if result.is_ok() {
-                return result;
+    /// Resolve `subpath` using `exports` of a package.
+    fn resolve_exports(&self, pkg_dir: &Path, exports: &Target, subpath: &str) -> Result<PathBuf, Error> {
+        let (target, matched) = match exports.find_subpath(subpath) {
+            Some(v) => v,
+            None => bail!("`{}` is not exported from `{}`", subpath, pkg_dir.display()),
+        };
+
+        self.resolve_targets(pkg_dir, target, matched.as_deref())
+            .with_context(|| format!("failed to resolve `{}` using `exports` of `{}`", subpath, pkg_dir.display()))
+    }
+
+    fn resolve_targets(&self, pkg_dir: &Path, target: &Target, matched: Option<&str>) -> Result<PathBuf, Error> {
+        let candidates = target.candidates(&self.conditions, matched);
+
+        self.tracer.trace(|| {
+            format!(
+                "Targets for conditions {:?} in '{}': {:?}.",
+                self.conditions,
+                pkg_dir.display(),
+                candidates
+            )
+        });
+
+        for candidate in &candidates {
+            if let Ok(path) = self.resolve_package_target(pkg_dir, candidate) {
+                return Ok(path);
             }
+        }
 
-            {
-                let types = node_modules.join("@types").join(target);
+        bail!("none of targets are resolvable: {:?}", candidates)
+    }
 
-                if types.is_dir() {
-                    let result = self.try_package(&types);
+    /// Resolve a `#`-prefixed specifier using `imports` of the closest
+    /// `package.json`.
+    fn resolve_imports(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        let mut cur = Some(base_dir);
+
+        while let Some(dir) = cur {
+            let pkg_path = dir.join("package.json");
+            if self.fs.is_file(&pkg_path) {
+                let pkg = self.read_package_json(&pkg_path)?;
+                let imports = match &pkg.imports {
+                    Some(v) => v,
+                    None => bail!("`{}` does not have `imports`", pkg_path.display()),
+                };
+
+                let (resolved, matched) = match imports.find_subpath(target) {
+                    Some(v) => v,
+                    None => bail!("`{}` is not defined in `imports` of `{}`", target, pkg_path.display()),
+                };
+
+                for candidate in resolved.candidates(&self.conditions, matched.as_deref()) {
+                    // Targets of `imports` can be a package name.
+                    let result = if candidate.starts_with("./") {
+                        self.resolve_package_target(dir, &candidate)
+                    } else {
+                        self.resolve_node_modules(dir, &candidate)
+                    };
 
                     if result.is_ok() {
                         return result;
                     }
                 }
+
+                bail!("failed to resolve `{}` using `imports` of `{}`", target, pkg_path.display())
+            }
+
+            cur = dir.parent();
+        }
+
+        bail!("package.json not found")
+    }
+
+    /// Resolve an import of the package containing the importer, using the
+    /// name of the package. This works only if the package has `exports`.
+    ///
+    /// Returns [None] if `target` is not the name of the package.
+    fn resolve_self_reference(&self, base_dir: &Path, target: &str) -> Option<Result<PathBuf, Error>> {
+        let pkg_path = self.find_package_json(base_dir)?;
+        let pkg = PackageJson::read(&*self.fs, &pkg_path).ok()?;
+
+        let (name, subpath) = split_package_name(target);
+        if pkg.name.as_deref() != Some(name) {
+            return None;
+        }
+        let exports = pkg.exports.as_ref()?;
+        let pkg_dir = pkg_path.parent()?;
+
+        self.tracer.trace(|| {
+            format!(
+                "Resolving '{}' as a self-reference of the package at '{}'.",
+                target,
+                pkg_path.display()
+            )
+        });
+
+        Some(self.resolve_exports(pkg_dir, exports, &subpath))
+    }
+
+    fn try_package(&self, pkg_dir: &Path) -> Result<PathBuf, Error> {
+        self.resolve_as_file(pkg_dir).or_else(|_| self.resolve_as_directory(pkg_dir))
+    }
+
+    /// Resolve a package in `node_modules`, respecting `exports` of it.
+    fn resolve_package(&self, node_modules: &Path, target: &str) -> Result<PathBuf, Error> {
+        let (pkg_name, subpath) = split_package_name(target);
+        let pkg_dir = node_modules.join(pkg_name);
+        let pkg_path = pkg_dir.join("package.json");
+
+        if self.fs.is_file(&pkg_path) {
+            let pkg = self.read_package_json(&pkg_path)?;
+
+            // If `exports` exists, it's the only way to access the package.
+            if let Some(exports) = &pkg.exports {
+                self.tracer.trace(|| "'package.json' has 'exports' field.".into());
+                return self.resolve_exports(&pkg_dir, exports, &subpath);
+            }
+        }
+
+        self.try_package(&node_modules.join(target))
+    }
+
+    /// Resolve by walking up node_modules folders.
+    fn resolve_node_modules(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        let node_modules = base_dir.join("node_modules");
+        if self.fs.is_dir(&node_modules) {
+            self.tracer.trace(|| {
+                format!(
+                    "Loading module '{}' from 'node_modules' folder '{}'.",
+                    target,
+                    node_modules.display()
+                )
+            });
+
+            let result = self.resolve_package(&node_modules, target);
+            if let Ok(path) = result {
+                return Ok(self.real_path(path));
+            }
+
+            // Fall back to DefinitelyTyped packages.
+            let types = node_modules.join("@types");
+            if self.fs.is_dir(&types) {
+                let result = self.resolve_package(&types, &types_package_name(target));
+                if let Ok(path) = result {
+                    return Ok(self.real_path(path));
+                }
             }
+        } else {
+            self.tracer
+                .trace(|| format!("Directory '{}' does not exist, skipping all lookups in it.", node_modules.display()));
         }
 
         match base_dir.parent() {
@@ -129,16 +563,10 @@ impl NodeResolver {
             None => bail!("not found"),
         }
     }
-}
 
-impl Resolve for NodeResolver {
-    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
-        let base = match base {
-            FileName::Real(base) => &**base,
-            _ => {
-                unreachable!("base = {:?}; target = {:?}", base, target)
-            }
-        };
+    /// Resolve `target` imported from `base`, after the mode of the import is
+    /// determined.
+    fn resolve_with_mode(&self, base: &Path, target: &str) -> Result<FileName, Error> {
         // Absolute path
         if target.starts_with('/') {
             let base_dir = &Path::new("/");
@@ -153,8 +581,23 @@ impl Resolve for NodeResolver {
         let cwd = &Path::new(".");
         let base_dir = base.parent().unwrap_or(cwd);
 
+        if target.starts_with('#') {
+            return self
+                .resolve_imports(base_dir, target)
+                .with_context(|| format!("failed to resolve `{}` using `imports` from `{}`", target, base.display()))
+                .and_then(|p| self.wrap(p));
+        }
+
         if target.starts_with("./") || target.starts_with("../") {
             let path = base_dir.join(target);
+
+            if self.esm {
+                return self
+                    .resolve_esm_file(&path)
+                    .with_context(|| format!("failed to resolve `{}` as a file dependency from `{}`", target, base.display()))
+                    .and_then(|p| self.wrap(p));
+            }
+
             return self
                 .resolve_as_file(&path)
                 .with_context(|| format!("failed to resolve `{}` as a file dependency from `{}`", target, base.display()))
@@ -165,8 +608,57 @@ impl Resolve for NodeResolver {
                 .and_then(|p| self.wrap(p));
         }
 
+        if let Some(result) = self.resolve_self_reference(base_dir, target) {
+            return result
+                .with_context(|| format!("failed to resolve `{}` as a self-reference from `{}`", target, base.display()))
+                .and_then(|p| self.wrap(p));
+        }
+
         self.resolve_node_modules(base_dir, target)
             .with_context(|| format!("failed to resolve `{}` as a node module from `{}`", target, base.display()))
             .and_then(|p| self.wrap(p))
     }
 }
+
+/// Returns the name of the `@types` package for `target`, including the
+/// subpath.
+///
+/// Scoped packages are mangled, so `@babel/core` becomes `babel__core`.
+pub fn types_package_name(target: &str) -> String {
+    match target.strip_prefix('@') {
+        Some(scoped) => scoped.replacen('/', "__", 1),
+        None => target.to_string(),
+    }
+}
+
+impl Resolve for NodeResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let base = match base {
+            FileName::Real(base) => &**base,
+            _ => {
+                unreachable!("base = {:?}; target = {:?}", base, target)
+            }
+        };
+
+        self.tracer.start(base, target);
+
+        let result = match self.for_importer(base) {
+            Some(resolver) => {
+                self.tracer.trace(|| {
+                    format!(
+                        "Resolving in {} mode with conditions {:?}.",
+                        if resolver.esm { "ESM" } else { "CJS" },
+                        resolver.conditions
+                    )
+                });
+
+                resolver.resolve_with_mode(base, target)
+            }
+            None => self.resolve_with_mode(base, target),
+        };
+
+        self.tracer.finish(target, &result);
+
+        result
+    }
+}
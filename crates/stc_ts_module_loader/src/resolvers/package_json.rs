@@ -0,0 +1,202 @@
+//! `package.json` parsing and the matching algorithm of `exports` and
+//! `imports`.
+//!
+//! - https://nodejs.org/api/packages.html#package-entry-points
+//! - https://nodejs.org/api/packages.html#subpath-imports
+
+use std::{fmt, path::Path};
+
+use anyhow::{Context, Error};
+use serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::fs::FileSystem;
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct PackageJson {
+    /// Used for imports of the package itself.
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default, alias = "typings")]
+    pub types: Option<String>,
+
+    /// Used only if `allowJs` is enabled.
+    #[serde(default)]
+    pub main: Option<String>,
+
+    #[serde(default)]
+    pub exports: Option<Target>,
+
+    #[serde(default)]
+    pub imports: Option<Target>,
+
+    /// `module` or `commonjs`.
+    #[serde(default, rename = "type")]
+    pub ty: Option<String>,
+}
+
+impl PackageJson {
+    pub fn read(fs: &dyn FileSystem, path: &Path) -> Result<Self, Error> {
+        let content = fs
+            .read_to_string(path)
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+
+        serde_json::from_str(&content).with_context(|| format!("failed to deserialize `{}`", path.display()))
+    }
+}
+
+/// A value of `exports` or `imports`, or a value nested in them.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Target {
+    Null(()),
+    Path(String),
+    Array(Vec<Target>),
+    /// Either conditions or subpaths. Order of keys is preserved because it
+    /// determines the priority of conditions.
+    Map(OrderedMap),
+}
+
+#[derive(Debug)]
+pub(crate) struct OrderedMap(pub Vec<(String, Target)>);
+
+impl<'de> Deserialize<'de> for OrderedMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct OrderedMapVisitor;
+
+        impl<'de> Visitor<'de> for OrderedMapVisitor {
+            type Value = OrderedMap;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = vec![];
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(OrderedMap(entries))
+            }
+        }
+
+        deserializer.deserialize_map(OrderedMapVisitor)
+    }
+}
+
+impl Target {
+    /// Returns `true` if `self` is a map of subpaths, like
+    /// `{ ".": "./index.js", "./foo": "./foo.js" }`.
+    fn is_subpath_map(&self) -> bool {
+        match self {
+            Target::Map(map) => map.0.iter().any(|(key, _)| key.starts_with('.') || key.starts_with('#')),
+            _ => false,
+        }
+    }
+
+    /// Finds the target for `subpath`, which is either `.`, `./foo` (for
+    /// `exports`) or `#foo` (for `imports`).
+    ///
+    /// Returns the target and the string matched by `*`.
+    pub fn find_subpath<'a>(&'a self, subpath: &str) -> Option<(&'a Target, Option<String>)> {
+        if !self.is_subpath_map() {
+            // `"exports": "./index.js"` is a shorthand of `"exports": { ".": "./index.js"
+            // }`
+            return if subpath == "." { Some((self, None)) } else { None };
+        }
+
+        let map = match self {
+            Target::Map(map) => map,
+            _ => unreachable!(),
+        };
+
+        if let Some((_, target)) = map.0.iter().find(|(key, _)| !key.contains('*') && key == subpath) {
+            return Some((target, None));
+        }
+
+        // Pattern with the longest prefix wins.
+        let mut best: Option<(&str, &Target, String)> = None;
+        for (key, target) in &map.0 {
+            let (prefix, suffix) = match key.split_once('*') {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let is_match = subpath.len() >= prefix.len() + suffix.len() && subpath.starts_with(prefix) && subpath.ends_with(suffix);
+            let is_better = best
+                .as_ref()
+                .map(|(best_prefix, ..)| best_prefix.len() < prefix.len())
+                .unwrap_or(true);
+
+            if is_match && is_better {
+                let matched = subpath[prefix.len()..subpath.len() - suffix.len()].to_string();
+                best = Some((prefix, target, matched));
+            }
+        }
+
+        best.map(|(_, target, matched)| (target, Some(matched)))
+    }
+
+    /// Selects target paths using `conditions`, in the order of priority.
+    ///
+    /// `*` in the target is replaced with `matched`.
+    pub fn candidates(&self, conditions: &[String], matched: Option<&str>) -> Vec<String> {
+        let mut buf = vec![];
+        self.collect_candidates(conditions, matched, &mut buf);
+        buf
+    }
+
+    fn collect_candidates(&self, conditions: &[String], matched: Option<&str>, buf: &mut Vec<String>) {
+        match self {
+            Target::Null(()) => {}
+            Target::Path(path) => match matched {
+                Some(matched) => buf.push(path.replace('*', matched)),
+                None => buf.push(path.clone()),
+            },
+            Target::Array(targets) => {
+                for target in targets {
+                    target.collect_candidates(conditions, matched, buf);
+                }
+            }
+            Target::Map(map) => {
+                for (condition, target) in &map.0 {
+                    if condition == "default" || conditions.iter().any(|c| c == condition) {
+                        target.collect_candidates(conditions, matched, buf);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Splits `target` into the package name and the subpath in the form used by
+/// `exports`.
+///
+/// `@scope/pkg/foo` becomes `("@scope/pkg", "./foo")`, and `pkg` becomes
+/// `("pkg", ".")`.
+pub(crate) fn split_package_name(target: &str) -> (&str, String) {
+    let name_len = if target.starts_with('@') {
+        match target.match_indices('/').nth(1) {
+            Some((idx, _)) => idx,
+            None => target.len(),
+        }
+    } else {
+        target.find('/').unwrap_or(target.len())
+    };
+
+    let (name, rest) = target.split_at(name_len);
+    if rest.is_empty() {
+        (name, ".".into())
+    } else {
+        (name, format!(".{}", rest))
+    }
+}
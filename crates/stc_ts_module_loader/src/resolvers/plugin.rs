@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use anyhow::Error;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+/// Custom module resolution, like aliases of bundlers or custom schemes such
+/// as `virtual:foo`.
+///
+/// Modules which don't exist on the disk, like virtual modules or generated
+/// code, can be provided by resolving them to paths of a [FileSystem] given to
+/// the module loader. See [OverlayFs].
+///
+/// [FileSystem]: crate::fs::FileSystem
+/// [OverlayFs]: crate::fs::OverlayFs
+pub trait ResolverPlugin: Send + Sync {
+    /// Returns [None] if `target` is not handled by this plugin, so the next
+    /// plugin is tried.
+    fn resolve(&self, base: &FileName, target: &str) -> Option<Result<FileName, Error>>;
+}
+
+impl<F> ResolverPlugin for F
+where
+    F: Send + Sync + Fn(&FileName, &str) -> Option<Result<FileName, Error>>,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Option<Result<FileName, Error>> {
+        (self)(base, target)
+    }
+}
+
+/// A resolver which tries plugins in the order of registration, and then the
+/// inner resolver.
+#[derive(Clone)]
+pub struct PluginResolver<R>
+where
+    R: Resolve,
+{
+    plugins: Vec<Arc<dyn ResolverPlugin>>,
+    inner: R,
+}
+
+impl<R> PluginResolver<R>
+where
+    R: Resolve,
+{
+    pub fn new(inner: R) -> Self {
+        Self { plugins: vec![], inner }
+    }
+
+    pub fn with_plugin(mut self, plugin: impl 'static + ResolverPlugin) -> Self {
+        self.plugins.push(Arc::new(plugin));
+        self
+    }
+}
+
+impl<R> Resolve for PluginResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        for plugin in &self.plugins {
+            if let Some(result) = plugin.resolve(base, target) {
+                return result;
+            }
+        }
+
+        self.inner.resolve(base, target)
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Error;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+use tracing::debug;
+
+use crate::fs::FileSystem;
+
+/// A resolver which resolves the source files of referenced projects to their
+/// declaration outputs, like `tsc --build`.
+///
+/// Files are resolved by the inner resolver first. If the resolved file is a
+/// typescript file of a referenced project and its declaration output exists,
+/// the declaration output is used instead. Otherwise the source file is used
+/// as is.
+///
+/// https://www.typescriptlang.org/docs/handbook/project-references.html
+pub struct ProjectReferenceResolver<R>
+where
+    R: Resolve,
+{
+    inner: R,
+    fs: Arc<dyn FileSystem>,
+    references: Vec<Reference>,
+}
+
+struct Reference {
+    /// The directory containing the source files.
+    root_dir: PathBuf,
+    /// The directory containing the declaration outputs. The same as
+    /// `root_dir` if declarations are emitted next to the source files.
+    out_dir: PathBuf,
+}
+
+impl<R> ProjectReferenceResolver<R>
+where
+    R: Resolve,
+{
+    /// `fs` should be the file system used by `inner`.
+    pub fn new(inner: R, fs: Arc<dyn FileSystem>) -> Self {
+        Self {
+            inner,
+            fs,
+            references: vec![],
+        }
+    }
+
+    /// Adds a referenced project whose sources are in `root_dir` and whose
+    /// declaration outputs are in `out_dir`, which is `declarationDir` or
+    /// `outDir` of the project. Paths should be absolute.
+    pub fn with_reference(mut self, root_dir: PathBuf, out_dir: Option<PathBuf>) -> Self {
+        self.references.push(Reference {
+            out_dir: out_dir.unwrap_or_else(|| root_dir.clone()),
+            root_dir,
+        });
+        self
+    }
+
+    /// Returns the declaration output of `path` if `path` is a source file of a
+    /// referenced project.
+    fn declaration_output(&self, path: &Path) -> Option<PathBuf> {
+        // The longest root directory containing the path wins, as projects may be
+        // nested.
+        let (reference, rest) = self
+            .references
+            .iter()
+            .filter_map(|reference| Some((reference, path.strip_prefix(&reference.root_dir).ok()?)))
+            .max_by_key(|(reference, _)| reference.root_dir.as_os_str().len())?;
+
+        let name = rest.file_name()?.to_str()?;
+        if name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts") {
+            return None;
+        }
+        let ext = match rest.extension()?.to_str()? {
+            "ts" | "tsx" => "d.ts",
+            "mts" => "d.mts",
+            "cts" => "d.cts",
+            _ => return None,
+        };
+
+        Some(reference.out_dir.join(rest).with_extension(ext))
+    }
+}
+
+impl<R> Resolve for ProjectReferenceResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let resolved = self.inner.resolve(base, target)?;
+
+        if let FileName::Real(path) = &resolved {
+            if let Some(output) = self.declaration_output(path) {
+                if self.fs.is_file(&output) {
+                    debug!("Resolved `{}` as `{}` using project references", target, output.display());
+                    return Ok(FileName::Real(output));
+                }
+            }
+        }
+
+        Ok(resolved)
+    }
+}
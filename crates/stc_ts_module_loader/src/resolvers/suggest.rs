@@ -0,0 +1,121 @@
+//! Suggestions for module specifiers which cannot be resolved.
+
+use std::path::{Path, PathBuf};
+
+use path_clean::PathClean;
+use stc_ts_errors::{ModuleNotFoundHint, ModuleSuggestion};
+use stc_utils::spelling::edit_distance;
+
+use super::{node::types_package_name, package_json::split_package_name};
+use crate::fs::FileSystem;
+
+static EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts"];
+
+/// Returns the paths which would be tried for `specifier` imported from
+/// `base`, and a suggestion to fix the import if any.
+///
+/// This is meant to be called after resolution failed, so it does not check
+/// if `specifier` can be resolved.
+pub fn suggest(fs: &dyn FileSystem, base: &Path, specifier: &str) -> ModuleNotFoundHint {
+    let base_dir = base.parent().unwrap_or_else(|| Path::new("."));
+
+    if specifier.starts_with("./") || specifier.starts_with("../") || specifier.starts_with('/') {
+        let path = base_dir.join(specifier).clean();
+        let probed = candidates(&path);
+
+        let suggestion = similar_file(fs, &path).map(|name| {
+            let dir = specifier.rsplit_once('/').map_or(".", |(dir, _)| dir);
+            ModuleSuggestion::DidYouMean(format!("{}/{}", dir, name))
+        });
+
+        return ModuleNotFoundHint {
+            probed: to_strings(probed),
+            suggestion,
+        };
+    }
+
+    let (name, _) = split_package_name(specifier);
+    let mut probed = vec![];
+    let mut suggestion = None;
+
+    // A file next to the importer, which should be imported with a relative path.
+    let relative = base_dir.join(specifier);
+    if candidates(&relative).iter().any(|path| fs.is_file(path)) {
+        suggestion = Some(ModuleSuggestion::DidYouMean(format!("./{}", specifier)));
+    }
+
+    for dir in base_dir.ancestors() {
+        let node_modules = dir.join("node_modules");
+        let package = node_modules.join(name);
+
+        probed.push(package.join("package.json"));
+        probed.extend(candidates(&node_modules.join(specifier)));
+        probed.extend(candidates(&node_modules.join("@types").join(types_package_name(specifier))));
+
+        // The package is installed, but it does not have types.
+        if suggestion.is_none() && fs.is_file(&package.join("package.json")) {
+            suggestion = Some(ModuleSuggestion::InstallTypes(format!("@types/{}", types_package_name(name))));
+        }
+    }
+
+    ModuleNotFoundHint {
+        probed: to_strings(probed),
+        suggestion,
+    }
+}
+
+/// `path` with each extension, and index files if `path` is a directory.
+fn candidates(path: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![];
+
+    for ext in EXTENSIONS {
+        let mut file = path.as_os_str().to_os_string();
+        file.push(".");
+        file.push(ext);
+        paths.push(PathBuf::from(file));
+    }
+    for ext in EXTENSIONS {
+        paths.push(path.join(format!("index.{}", ext)));
+    }
+
+    paths
+}
+
+/// Finds a typescript file with a name similar to `path` in the same
+/// directory, and returns the name without the extension.
+fn similar_file(fs: &dyn FileSystem, path: &Path) -> Option<String> {
+    let wanted = path.file_name()?.to_string_lossy();
+    let dir = path.parent()?;
+
+    let mut best: Option<(usize, String)> = None;
+    for file in fs.read_dir(dir).ok()? {
+        let file_name = match file.file_name() {
+            Some(file_name) => file_name.to_string_lossy(),
+            None => continue,
+        };
+        let stem = match EXTENSIONS.iter().find_map(|ext| file_name.strip_suffix(&format!(".{}", ext))) {
+            // `foo.d.ts` is `foo`, not `foo.d`.
+            Some(stem) => stem.strip_suffix(".d").unwrap_or(stem),
+            None => continue,
+        };
+
+        let distance = edit_distance(&wanted.to_lowercase(), &stem.to_lowercase());
+        if distance > (wanted.len() / 3).max(1) {
+            continue;
+        }
+
+        let is_better = match &best {
+            Some((best_distance, best)) => distance < *best_distance || (distance == *best_distance && stem < best.as_str()),
+            None => true,
+        };
+        if is_better {
+            best = Some((distance, stem.to_string()));
+        }
+    }
+
+    best.map(|(_, stem)| stem)
+}
+
+fn to_strings(paths: Vec<PathBuf>) -> Vec<String> {
+    paths.into_iter().map(|path| path.display().to_string()).collect()
+}
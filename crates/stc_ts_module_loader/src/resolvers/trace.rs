@@ -0,0 +1,53 @@
+//! Tracing of module resolution, like `traceResolution` of tsc.
+
+use std::{path::Path, sync::Arc};
+
+use anyhow::Error;
+use swc_common::FileName;
+
+/// Receives a message for each step of module resolution, like candidate
+/// paths and fields of `package.json`.
+///
+/// Resolvers don't trace anything by default.
+#[derive(Clone, Default)]
+pub struct Tracer {
+    callback: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+}
+
+impl Tracer {
+    pub fn new(callback: impl 'static + Fn(&str) + Send + Sync) -> Self {
+        Self {
+            callback: Some(Arc::new(callback)),
+        }
+    }
+
+    /// Sends messages to [log] with the target `trace_resolution`.
+    pub fn log() -> Self {
+        Self::new(|msg| log::info!(target: "trace_resolution", "{}", msg))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.callback.is_some()
+    }
+
+    /// `msg` is called only if tracing is enabled.
+    pub(crate) fn trace(&self, msg: impl FnOnce() -> String) {
+        if let Some(callback) = &self.callback {
+            callback(&msg());
+        }
+    }
+
+    pub(crate) fn start(&self, base: &Path, target: &str) {
+        self.trace(|| format!("======== Resolving module '{}' from '{}'. ========", target, base.display()));
+    }
+
+    pub(crate) fn finish(&self, target: &str, result: &Result<FileName, Error>) {
+        self.trace(|| match result {
+            Ok(resolved) => format!(
+                "======== Module name '{}' was successfully resolved to '{}'. ========",
+                target, resolved
+            ),
+            Err(err) => format!("======== Module name '{}' was not resolved: {:#} ========", target, err),
+        });
+    }
+}
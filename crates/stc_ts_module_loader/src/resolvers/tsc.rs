@@ -0,0 +1,326 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Error};
+use path_clean::PathClean;
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+use tracing::debug;
+
+use super::trace::Tracer;
+
+/// A resolver which handles module resolution options of `tsconfig.json`.
+///
+/// Specifiers which are not handled by this resolver are passed to the inner
+/// resolver.
+///
+/// - https://www.typescriptlang.org/tsconfig#baseUrl
+/// - https://www.typescriptlang.org/tsconfig#paths
+/// - https://www.typescriptlang.org/tsconfig#rootDirs
+pub struct TsConfigResolver<R>
+where
+    R: Resolve,
+{
+    inner: R,
+
+    config_dir: PathBuf,
+
+    base_url: Option<PathBuf>,
+
+    /// The directory where the targets of `paths` are resolved from.
+    ///
+    /// This is `baseUrl` if it's specified, and the directory containing
+    /// `tsconfig.json` otherwise.
+    paths_base: PathBuf,
+
+    paths: Vec<(Pattern, Vec<String>)>,
+
+    /// Absolute paths of `rootDirs`.
+    root_dirs: Vec<PathBuf>,
+
+    tracer: Tracer,
+}
+
+impl<R> TsConfigResolver<R>
+where
+    R: Resolve,
+{
+    /// `config_dir` should be the directory containing `tsconfig.json`, and a
+    /// relative `base_url` is resolved from it.
+    ///
+    /// `paths` is the parsed value of `compilerOptions.paths`, in the order of
+    /// declaration.
+    pub fn new(inner: R, config_dir: PathBuf, base_url: Option<PathBuf>, paths: Vec<(String, Vec<String>)>) -> Self {
+        let base_url = base_url.map(|base_url| config_dir.join(base_url));
+        let paths_base = base_url.clone().unwrap_or_else(|| config_dir.clone());
+
+        let paths = paths
+            .into_iter()
+            .map(|(from, to)| {
+                let pattern = Pattern::parse(&from);
+
+                (pattern, to)
+            })
+            .collect();
+
+        Self {
+            inner,
+            config_dir,
+            base_url,
+            paths_base,
+            paths,
+            root_dirs: vec![],
+            tracer: Default::default(),
+        }
+    }
+
+    /// Directories whose contents are merged into one virtual directory for
+    /// relative imports. Relative paths are resolved from the directory
+    /// containing `tsconfig.json`.
+    pub fn with_root_dirs(mut self, root_dirs: Vec<PathBuf>) -> Self {
+        self.root_dirs = root_dirs.into_iter().map(|dir| self.config_dir.join(dir).clean()).collect();
+        self
+    }
+
+    /// Reports how `paths` and `baseUrl` are applied to `tracer`. Note that the
+    /// inner resolver should be configured separately.
+    pub fn with_tracer(mut self, tracer: Tracer) -> Self {
+        self.tracer = tracer;
+        self
+    }
+
+    /// Returns the pattern which should be used for `target`.
+    ///
+    /// An exact match wins, and otherwise the pattern with the longest prefix
+    /// is selected, just like `tsc`.
+    fn find_pattern(&self, target: &str) -> Option<(&Pattern, &[String])> {
+        if let Some((pat, to)) = self.paths.iter().find(|(pat, _)| pat.is_exact(target)) {
+            return Some((pat, &**to));
+        }
+
+        // `rev()` is used because `max_by_key` returns the last element on ties.
+        self.paths
+            .iter()
+            .rev()
+            .filter(|(pat, _)| pat.matches(target).is_some())
+            .max_by_key(|(pat, _)| pat.prefix.len())
+            .map(|(pat, to)| (pat, &**to))
+    }
+
+    fn resolve_paths(&self, base: &FileName, target: &str) -> Result<Option<FileName>, Error> {
+        if self.paths.is_empty() {
+            return Ok(None);
+        }
+
+        self.tracer.trace(|| {
+            format!(
+                "'paths' option is specified, looking for a pattern to match module name '{}'.",
+                target
+            )
+        });
+
+        let (pat, to) = match self.find_pattern(target) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        self.tracer
+            .trace(|| format!("Module name '{}', matched pattern '{}'.", target, pat));
+
+        let matched = pat.matches(target).unwrap_or_default();
+
+        for to in to {
+            let replaced = to.replacen('*', matched, 1);
+            let path = self.paths_base.join(&replaced);
+
+            self.tracer
+                .trace(|| format!("Trying substitution '{}', candidate module location: '{}'.", to, path.display()));
+
+            match self.resolve_path(base, &path) {
+                Ok(v) => {
+                    debug!("Resolved `{}` as `{}` using `paths`", target, path.display());
+                    return Ok(Some(v));
+                }
+                Err(err) => {
+                    debug!("Failed to resolve `{}` as `{}` using `paths`: {:?}", target, path.display(), err);
+                }
+            }
+        }
+
+        bail!(
+            "`{}` matched a pattern in `paths`, but none of the substitutions could be resolved",
+            target
+        )
+    }
+
+    /// Resolves a non-relative specifier against `baseUrl`.
+    fn resolve_base_url(&self, base: &FileName, target: &str) -> Option<FileName> {
+        let base_url = self.base_url.as_ref()?;
+        let path = base_url.join(target);
+
+        self.tracer.trace(|| {
+            format!(
+                "Resolving module name '{}' relative to base url '{}' - '{}'.",
+                target,
+                base_url.display(),
+                path.display()
+            )
+        });
+
+        match self.resolve_path(base, &path) {
+            Ok(v) => {
+                debug!("Resolved `{}` as `{}` using `baseUrl`", target, path.display());
+                Some(v)
+            }
+            Err(err) => {
+                debug!("Failed to resolve `{}` as `{}` using `baseUrl`: {:?}", target, path.display(), err);
+                None
+            }
+        }
+    }
+
+    /// Resolves a relative import as if the directories of `rootDirs` were
+    /// merged.
+    ///
+    /// Returns [None] if the imported path is not in any of `rootDirs` or it's
+    /// not found.
+    fn resolve_root_dirs(&self, base: &FileName, target: &str) -> Option<FileName> {
+        if self.root_dirs.is_empty() || Path::new(target).is_absolute() {
+            return None;
+        }
+
+        let base_dir = match base {
+            FileName::Real(base) => base.parent()?,
+            _ => return None,
+        };
+        let path = base_dir.join(target).clean();
+
+        // The longest root directory containing the path wins.
+        let (matched, rest) = self
+            .root_dirs
+            .iter()
+            .filter_map(|dir| Some((dir, path.strip_prefix(dir).ok()?)))
+            .max_by_key(|(dir, _)| dir.as_os_str().len())?;
+
+        self.tracer.trace(|| {
+            format!(
+                "Module name '{}', matched 'rootDirs' entry '{}', relative part '{}'.",
+                target,
+                matched.display(),
+                rest.display()
+            )
+        });
+
+        let candidates = Some(matched).into_iter().chain(self.root_dirs.iter().filter(|dir| *dir != matched));
+        for dir in candidates {
+            let path = dir.join(rest);
+
+            match self.resolve_path(base, &path) {
+                Ok(v) => {
+                    debug!("Resolved `{}` as `{}` using `rootDirs`", target, path.display());
+                    return Some(v);
+                }
+                Err(err) => {
+                    debug!("Failed to resolve `{}` as `{}` using `rootDirs`: {:?}", target, path.display(), err);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Resolves an absolute path using the inner resolver.
+    fn resolve_path(&self, base: &FileName, path: &Path) -> Result<FileName, Error> {
+        self.inner.resolve(base, &path.to_string_lossy())
+    }
+}
+
+impl<R> Resolve for TsConfigResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        if is_relative(target) {
+            if let Some(resolved) = self.resolve_root_dirs(base, target) {
+                return Ok(resolved);
+            }
+
+            return self.inner.resolve(base, target);
+        }
+
+        if let Some(resolved) = self
+            .resolve_paths(base, target)
+            .with_context(|| format!("failed to resolve `{}` using `paths`", target))?
+        {
+            return Ok(resolved);
+        }
+
+        if let Some(resolved) = self.resolve_base_url(base, target) {
+            return Ok(resolved);
+        }
+
+        self.inner.resolve(base, target)
+    }
+}
+
+fn is_relative(target: &str) -> bool {
+    target == "." || target == ".." || target.starts_with("./") || target.starts_with("../") || Path::new(target).is_absolute()
+}
+
+/// A key of `paths`, which can contain at most one `*`.
+#[derive(Debug)]
+struct Pattern {
+    prefix: String,
+    /// [None] if the pattern does not contain `*`.
+    suffix: Option<String>,
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.suffix {
+            Some(suffix) => write!(f, "{}*{}", self.prefix, suffix),
+            None => write!(f, "{}", self.prefix),
+        }
+    }
+}
+
+impl Pattern {
+    fn parse(s: &str) -> Self {
+        match s.split_once('*') {
+            Some((prefix, suffix)) => Pattern {
+                prefix: prefix.to_string(),
+                suffix: Some(suffix.to_string()),
+            },
+            None => Pattern {
+                prefix: s.to_string(),
+                suffix: None,
+            },
+        }
+    }
+
+    fn is_exact(&self, target: &str) -> bool {
+        self.suffix.is_none() && self.prefix == target
+    }
+
+    /// Returns the part matched by `*`.
+    fn matches<'a>(&self, target: &'a str) -> Option<&'a str> {
+        match &self.suffix {
+            Some(suffix) => {
+                if target.len() >= self.prefix.len() + suffix.len() && target.starts_with(&*self.prefix) && target.ends_with(&**suffix) {
+                    Some(&target[self.prefix.len()..target.len() - suffix.len()])
+                } else {
+                    None
+                }
+            }
+            None => {
+                if self.prefix == target {
+                    Some("")
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Error};
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+use url::Url;
+
+use super::import_map::ImportMap;
+use crate::fs::{FileSystem, RealFs};
+
+/// A resolver for Deno-style projects, which supports `http://` and `https://`
+/// specifiers and import maps.
+///
+/// Remote modules are not downloaded by this resolver. They should be stored
+/// in `cache_dir` using the layout of `<cache_dir>/<scheme>/<host>/<path>`,
+/// and the cached files are used as the source of the remote modules.
+///
+/// Specifiers which are not urls are passed to the inner resolver.
+pub struct UrlResolver<R>
+where
+    R: Resolve,
+{
+    inner: R,
+    fs: Arc<dyn FileSystem>,
+    cache_dir: PathBuf,
+    import_map: Option<ImportMap>,
+}
+
+impl<R> UrlResolver<R>
+where
+    R: Resolve,
+{
+    pub fn new(inner: R, cache_dir: PathBuf, import_map: Option<ImportMap>) -> Self {
+        Self {
+            inner,
+            fs: Arc::new(RealFs),
+            cache_dir,
+            import_map,
+        }
+    }
+
+    /// Use `fs` instead of the file system of the host. The inner resolver is
+    /// not changed.
+    pub fn with_fs(mut self, fs: Arc<dyn FileSystem>) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// Returns the path of the cached file for `url`.
+    pub fn cache_path(&self, url: &Url) -> Result<PathBuf, Error> {
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}_PORT{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => bail!("url without host is not supported: {}", url),
+        };
+
+        let mut path = self.cache_dir.join(url.scheme()).join(host);
+        for segment in url.path_segments().into_iter().flatten() {
+            if segment.is_empty() || segment == "." || segment == ".." {
+                continue;
+            }
+            path.push(segment);
+        }
+
+        Ok(path)
+    }
+
+    /// Returns the url of a cached file.
+    fn url_of_cached_file(&self, path: &Path) -> Option<Url> {
+        let rel = path.strip_prefix(&self.cache_dir).ok()?;
+
+        let mut components = rel.components().filter_map(|c| match c {
+            Component::Normal(s) => s.to_str(),
+            _ => None,
+        });
+
+        let scheme = components.next()?;
+        let host = components.next()?.replacen("_PORT", ":", 1);
+        let path = components.collect::<Vec<_>>().join("/");
+
+        Url::parse(&format!("{}://{}/{}", scheme, host, path)).ok()
+    }
+
+    /// Returns the url of the module which contains the import.
+    fn referrer_url(&self, base: &FileName) -> Option<Url> {
+        match base {
+            FileName::Real(path) => self.url_of_cached_file(path).or_else(|| Url::from_file_path(path).ok()),
+            FileName::Url(url) => Some(url.clone()),
+            _ => None,
+        }
+    }
+
+    fn resolve_url(&self, url: &Url) -> Result<FileName, Error> {
+        match url.scheme() {
+            "file" => {
+                let path = url.to_file_path().map_err(|()| anyhow::anyhow!("invalid file url: {}", url))?;
+                if !self.fs.is_file(&path) {
+                    bail!("file not found: {}", path.display())
+                }
+                Ok(FileName::Real(path))
+            }
+            "http" | "https" => {
+                let path = self.cache_path(url)?;
+                if !self.fs.is_file(&path) {
+                    bail!("remote module `{}` is not cached at `{}`", url, path.display())
+                }
+                Ok(FileName::Real(path))
+            }
+            scheme => bail!("unsupported url scheme `{}`: {}", scheme, url),
+        }
+    }
+}
+
+impl<R> Resolve for UrlResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let referrer = self.referrer_url(base);
+
+        if let (Some(import_map), Some(referrer)) = (&self.import_map, &referrer) {
+            if let Some(mapped) = import_map.resolve(target, referrer) {
+                return self
+                    .resolve_url(&mapped)
+                    .with_context(|| format!("failed to resolve `{}` mapped by the import map to `{}`", target, mapped));
+            }
+        }
+
+        if let Ok(url) = Url::parse(target) {
+            if matches!(url.scheme(), "http" | "https" | "file") {
+                return self.resolve_url(&url);
+            }
+        }
+
+        // Relative imports in remote modules are also remote.
+        if let Some(referrer) = &referrer {
+            let is_remote = matches!(referrer.scheme(), "http" | "https");
+            let is_relative = target.starts_with('/') || target.starts_with("./") || target.starts_with("../");
+
+            if is_remote && is_relative {
+                let url = referrer
+                    .join(target)
+                    .with_context(|| format!("failed to join `{}` to `{}`", target, referrer))?;
+
+                return self.resolve_url(&url);
+            }
+        }
+
+        self.inner.resolve(base, target)
+    }
+}
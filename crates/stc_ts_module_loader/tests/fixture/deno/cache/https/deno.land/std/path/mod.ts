@@ -0,0 +1 @@
+export * from "./_util.ts";
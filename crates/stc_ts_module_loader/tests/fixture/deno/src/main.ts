@@ -0,0 +1,2 @@
+import { sep } from "std/path/mod.ts";
+import { util } from "util";
@@ -0,0 +1 @@
+import { util } from '#internal/util';
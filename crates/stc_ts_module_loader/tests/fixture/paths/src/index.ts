@@ -0,0 +1,2 @@
+import { foo } from '@app/foo';
+import { utils } from 'utils';
@@ -0,0 +1,2 @@
+import { transform } from '@babel/core';
+import { VERSION } from 'lodash';
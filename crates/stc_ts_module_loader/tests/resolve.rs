@@ -0,0 +1,765 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use anyhow::Error;
+use stc_ts_env::ModuleResolution;
+use stc_ts_errors::ModuleSuggestion;
+use stc_ts_module_loader::{
+    fs::{FileSystem, MemoryFs, OverlayFs, RealFs},
+    resolvers::{
+        cache::CachingResolver,
+        classic::ClassicResolver,
+        import_map::ImportMap,
+        node::{self, NodeResolver},
+        plugin::PluginResolver,
+        references::ProjectReferenceResolver,
+        suggest::suggest,
+        trace::Tracer,
+        tsc::TsConfigResolver,
+        url::UrlResolver,
+        ModuleResolver,
+    },
+};
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixture").join(name)
+}
+
+fn paths_resolver(dir: &Path) -> TsConfigResolver<NodeResolver> {
+    TsConfigResolver::new(
+        NodeResolver::new(),
+        dir.to_path_buf(),
+        None,
+        vec![
+            ("@app/*".into(), vec!["src/app/*".into()]),
+            ("utils".into(), vec!["src/lib/utils".into()]),
+        ],
+    )
+}
+
+#[test]
+fn paths_wildcard() {
+    let dir = fixture("paths");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    let resolved = paths_resolver(&dir).resolve(&base, "@app/foo").unwrap();
+
+    assert_eq!(resolved, FileName::Real(dir.join("src").join("app").join("foo.ts")));
+}
+
+#[test]
+fn paths_exact() {
+    let dir = fixture("paths");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    let resolved = paths_resolver(&dir).resolve(&base, "utils").unwrap();
+
+    assert_eq!(resolved, FileName::Real(dir.join("src").join("lib").join("utils").join("index.ts")));
+}
+
+#[test]
+fn paths_fallback_to_node_modules() {
+    let dir = fixture("paths");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    let resolved = paths_resolver(&dir).resolve(&base, "pkg").unwrap();
+
+    assert_eq!(resolved, FileName::Real(dir.join("node_modules").join("pkg").join("index.d.ts")));
+}
+
+#[test]
+fn paths_unresolvable_substitution() {
+    let dir = fixture("paths");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    assert!(paths_resolver(&dir).resolve(&base, "@app/missing").is_err());
+}
+
+#[test]
+fn base_url() {
+    let dir = fixture("base-url");
+    let base = FileName::Real(dir.join("src").join("app").join("index.ts"));
+    let resolver = TsConfigResolver::new(NodeResolver::new(), dir.clone(), Some("src".into()), vec![]);
+
+    let resolved = resolver.resolve(&base, "components/button").unwrap();
+
+    assert_eq!(resolved, FileName::Real(dir.join("src").join("components").join("button.tsx")));
+}
+
+#[test]
+fn base_url_with_paths() {
+    let dir = fixture("base-url");
+    let base = FileName::Real(dir.join("src").join("app").join("index.ts"));
+    let resolver = TsConfigResolver::new(
+        NodeResolver::new(),
+        dir.clone(),
+        Some("src".into()),
+        vec![("~/*".into(), vec!["components/*".into()])],
+    );
+
+    let resolved = resolver.resolve(&base, "~/button").unwrap();
+
+    assert_eq!(resolved, FileName::Real(dir.join("src").join("components").join("button.tsx")));
+}
+
+#[test]
+fn exports_conditions() {
+    let dir = fixture("exports");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    let resolved = NodeResolver::new().resolve(&base, "cond").unwrap();
+
+    assert_eq!(
+        resolved,
+        FileName::Real(dir.join("node_modules").join("cond").join("dist").join("types").join("index.d.ts"))
+    );
+}
+
+#[test]
+fn exports_blocks_unexported_subpath() {
+    let dir = fixture("exports");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    assert!(NodeResolver::new().resolve(&base, "cond/private").is_err());
+}
+
+#[test]
+fn exports_scoped_subpath_pattern() {
+    let dir = fixture("exports");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+    let pkg_dir = dir.join("node_modules").join("@scope").join("pkg");
+
+    let resolver = NodeResolver::new();
+
+    assert_eq!(
+        resolver.resolve(&base, "@scope/pkg").unwrap(),
+        FileName::Real(pkg_dir.join("types").join("index.d.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "@scope/pkg/features/a").unwrap(),
+        FileName::Real(pkg_dir.join("types").join("features").join("a.d.ts"))
+    );
+}
+
+#[test]
+fn imports_field() {
+    let dir = fixture("exports");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    let resolver = NodeResolver::new();
+
+    assert_eq!(
+        resolver.resolve(&base, "#internal/util").unwrap(),
+        FileName::Real(dir.join("src").join("internal").join("util.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "#cond").unwrap(),
+        FileName::Real(dir.join("node_modules").join("cond").join("dist").join("types").join("index.d.ts"))
+    );
+}
+
+#[test]
+fn types_package_fallback() {
+    let dir = fixture("types");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+    let types_dir = dir.join("node_modules").join("@types");
+
+    let resolver = NodeResolver::new();
+
+    assert_eq!(
+        resolver.resolve(&base, "lodash").unwrap(),
+        FileName::Real(types_dir.join("lodash").join("index.d.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "lodash/fp").unwrap(),
+        FileName::Real(types_dir.join("lodash").join("fp.d.ts"))
+    );
+}
+
+#[test]
+fn types_package_fallback_scoped() {
+    let dir = fixture("types");
+    let base = FileName::Real(dir.join("src").join("index.ts"));
+
+    let resolved = NodeResolver::new().resolve(&base, "@babel/core").unwrap();
+
+    assert_eq!(
+        resolved,
+        FileName::Real(dir.join("node_modules").join("@types").join("babel__core").join("index.d.ts"))
+    );
+}
+
+#[test]
+fn types_package_name() {
+    assert_eq!(node::types_package_name("react"), "react");
+    assert_eq!(node::types_package_name("react-dom/server"), "react-dom/server");
+    assert_eq!(node::types_package_name("@babel/core"), "babel__core");
+    assert_eq!(node::types_package_name("@babel/core/lib/x"), "babel__core/lib/x");
+}
+
+fn url_resolver(dir: &Path) -> UrlResolver<NodeResolver> {
+    let import_map = ImportMap::load(&RealFs, &dir.join("import_map.json")).unwrap();
+
+    UrlResolver::new(NodeResolver::new(), dir.join("cache"), Some(import_map))
+}
+
+#[test]
+fn url_remote_module() {
+    let dir = fixture("deno");
+    let base = FileName::Real(dir.join("src").join("main.ts"));
+    let cached_dir = dir.join("cache").join("https").join("deno.land").join("std").join("path");
+
+    let resolver = url_resolver(&dir);
+
+    let resolved = resolver.resolve(&base, "https://deno.land/std/path/mod.ts").unwrap();
+    assert_eq!(resolved, FileName::Real(cached_dir.join("mod.ts")));
+
+    // Relative imports of remote modules are remote.
+    let resolved = resolver.resolve(&resolved, "./_util.ts").unwrap();
+    assert_eq!(resolved, FileName::Real(cached_dir.join("_util.ts")));
+}
+
+#[test]
+fn url_import_map() {
+    let dir = fixture("deno");
+    let base = FileName::Real(dir.join("src").join("main.ts"));
+
+    let resolver = url_resolver(&dir);
+
+    assert_eq!(
+        resolver.resolve(&base, "std/path/mod.ts").unwrap(),
+        FileName::Real(
+            dir.join("cache")
+                .join("https")
+                .join("deno.land")
+                .join("std")
+                .join("path")
+                .join("mod.ts")
+        )
+    );
+    assert_eq!(
+        resolver.resolve(&base, "util").unwrap(),
+        FileName::Real(dir.join("src").join("util.ts"))
+    );
+}
+
+#[test]
+fn url_not_cached() {
+    let dir = fixture("deno");
+    let base = FileName::Real(dir.join("src").join("main.ts"));
+
+    assert!(url_resolver(&dir).resolve(&base, "https://deno.land/x/missing/mod.ts").is_err());
+}
+
+#[test]
+fn url_memory_fs() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(
+        PathBuf::from("/project/import_map.json"),
+        r#"{ "imports": { "std/": "https://deno.land/std/", "util": "./src/util.ts" } }"#,
+    );
+    fs.insert(PathBuf::from("/project/cache/https/deno.land/std/path/mod.ts"), "export {};");
+    fs.insert(PathBuf::from("/project/src/util.ts"), "export {};");
+
+    let import_map = ImportMap::load(&*fs, Path::new("/project/import_map.json")).unwrap();
+    let resolver = UrlResolver::new(
+        NodeResolver::new().with_fs(fs.clone()),
+        PathBuf::from("/project/cache"),
+        Some(import_map),
+    )
+    .with_fs(fs.clone());
+    let base = FileName::Real(PathBuf::from("/project/src/main.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "std/path/mod.ts").unwrap(),
+        FileName::Real(PathBuf::from("/project/cache/https/deno.land/std/path/mod.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "util").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/util.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "file:///project/src/util.ts").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/util.ts"))
+    );
+    // Remote modules which are not in the file system are not cached.
+    assert!(resolver.resolve(&base, "https://deno.land/std/path/_util.ts").is_err());
+    assert!(ImportMap::load(&*fs, Path::new("/project/missing.json")).is_err());
+}
+
+#[test]
+fn memory_fs() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/project/src/index.ts"), "import { foo } from './foo';");
+    fs.insert(PathBuf::from("/project/src/foo.ts"), "export const foo = 1;");
+    fs.insert(
+        PathBuf::from("/project/node_modules/pkg/package.json"),
+        r#"{ "types": "./lib/index.d.ts" }"#,
+    );
+    fs.insert(
+        PathBuf::from("/project/node_modules/pkg/lib/index.d.ts"),
+        "export declare const bar: number;",
+    );
+
+    let resolver = NodeResolver::new().with_fs(fs);
+    let base = FileName::Real(PathBuf::from("/project/src/index.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "./foo").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/foo.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "pkg").unwrap(),
+        FileName::Real(PathBuf::from("/project/node_modules/pkg/lib/index.d.ts"))
+    );
+    assert!(resolver.resolve(&base, "./missing").is_err());
+}
+
+#[test]
+fn trace_resolution() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/project/src/index.ts"), "");
+    fs.insert(
+        PathBuf::from("/project/node_modules/pkg/package.json"),
+        r#"{ "types": "./lib/index.d.ts" }"#,
+    );
+    fs.insert(PathBuf::from("/project/node_modules/pkg/lib/index.d.ts"), "");
+
+    let messages = Arc::new(Mutex::new(vec![]));
+    let tracer = {
+        let messages = messages.clone();
+        Tracer::new(move |msg| messages.lock().unwrap().push(msg.to_string()))
+    };
+    let resolver = NodeResolver::new().with_fs(fs).with_tracer(tracer);
+    let base = FileName::Real(PathBuf::from("/project/src/index.ts"));
+
+    resolver.resolve(&base, "pkg").unwrap();
+    let traced = messages.lock().unwrap().drain(..).collect::<Vec<_>>();
+    for expected in [
+        "======== Resolving module 'pkg' from '/project/src/index.ts'. ========",
+        "Directory '/project/src/node_modules' does not exist, skipping all lookups in it.",
+        "Loading module 'pkg' from 'node_modules' folder '/project/node_modules'.",
+        "Found 'package.json' at '/project/node_modules/pkg/package.json'.",
+        "'package.json' has 'types' field './lib/index.d.ts' that references '/project/node_modules/pkg/./lib/index.d.ts'.",
+        "File '/project/node_modules/pkg/./lib/index.d.ts' exists - use it as a name resolution result.",
+        "======== Module name 'pkg' was successfully resolved to '/project/node_modules/pkg/lib/index.d.ts'. ========",
+    ] {
+        assert!(
+            traced.iter().any(|msg| msg == expected),
+            "{:?} is not traced: {:#?}",
+            expected,
+            traced
+        );
+    }
+
+    resolver.resolve(&base, "./missing").unwrap_err();
+    let traced = messages.lock().unwrap().drain(..).collect::<Vec<_>>();
+    assert!(traced.contains(&"File '/project/src/./missing.ts' does not exist.".to_string()));
+    assert!(traced
+        .last()
+        .unwrap()
+        .starts_with("======== Module name './missing' was not resolved: "));
+}
+
+fn classic_fs() -> Arc<MemoryFs> {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/project/src/app/index.ts"), "");
+    fs.insert(PathBuf::from("/project/src/app/local.ts"), "");
+    fs.insert(PathBuf::from("/project/src/dir/index.ts"), "");
+    fs.insert(PathBuf::from("/project/shared.d.ts"), "");
+    fs.insert(PathBuf::from("/project/node_modules/pkg/index.d.ts"), "");
+    fs.insert(PathBuf::from("/project/node_modules/@types/typed/index.d.ts"), "");
+    fs
+}
+
+#[test]
+fn classic_relative() {
+    let resolver = ClassicResolver::new().with_fs(classic_fs());
+    let base = FileName::Real(PathBuf::from("/project/src/app/index.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "./local").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/app/local.ts"))
+    );
+    // Directories are not resolved by the classic strategy.
+    assert!(resolver.resolve(&base, "../dir").is_err());
+}
+
+#[test]
+fn classic_non_relative() {
+    let resolver = ClassicResolver::new().with_fs(classic_fs());
+    let base = FileName::Real(PathBuf::from("/project/src/app/index.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "shared").unwrap(),
+        FileName::Real(PathBuf::from("/project/shared.d.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "typed").unwrap(),
+        FileName::Real(PathBuf::from("/project/node_modules/@types/typed/index.d.ts"))
+    );
+    // `node_modules` is not used by the classic strategy.
+    assert!(resolver.resolve(&base, "pkg").is_err());
+}
+
+fn node16_fs() -> Arc<MemoryFs> {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/project/package.json"), r#"{ "type": "module" }"#);
+    fs.insert(PathBuf::from("/project/src/index.ts"), "");
+    fs.insert(PathBuf::from("/project/src/util.ts"), "");
+    fs.insert(PathBuf::from("/project/src/esm.mts"), "");
+    fs.insert(PathBuf::from("/project/src/legacy.cts"), "");
+    fs.insert(
+        PathBuf::from("/project/node_modules/dual/package.json"),
+        r#"{ "exports": { ".": { "import": "./esm/index.js", "require": "./cjs/index.js" } } }"#,
+    );
+    fs.insert(PathBuf::from("/project/node_modules/dual/esm/index.d.ts"), "");
+    fs.insert(PathBuf::from("/project/node_modules/dual/cjs/index.d.ts"), "");
+    fs
+}
+
+#[test]
+fn node16_esm() {
+    let resolver = NodeResolver::node16().with_fs(node16_fs());
+    let base = FileName::Real(PathBuf::from("/project/src/index.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "./util.js").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/util.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "./esm.mjs").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/esm.mts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "dual").unwrap(),
+        FileName::Real(PathBuf::from("/project/node_modules/dual/esm/index.d.ts"))
+    );
+    // Extensions are required in ES modules.
+    assert!(resolver.resolve(&base, "./util").is_err());
+}
+
+#[test]
+fn node16_cjs() {
+    let resolver = NodeResolver::node16().with_fs(node16_fs());
+    let base = FileName::Real(PathBuf::from("/project/src/legacy.cts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "./util").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/util.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "dual").unwrap(),
+        FileName::Real(PathBuf::from("/project/node_modules/dual/cjs/index.d.ts"))
+    );
+}
+
+/// Creates a package linked into `node_modules` of two apps, like pnpm does.
+#[cfg(unix)]
+fn symlinked_packages(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("symlinks").join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    std::fs::create_dir_all(dir.join("store").join("pkg")).unwrap();
+    std::fs::write(dir.join("store").join("pkg").join("index.d.ts"), "").unwrap();
+
+    for app in ["app-a", "app-b"] {
+        let node_modules = dir.join(app).join("node_modules");
+        std::fs::create_dir_all(&node_modules).unwrap();
+        std::fs::write(dir.join(app).join("index.ts"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("store").join("pkg"), node_modules.join("pkg")).unwrap();
+    }
+
+    dir
+}
+
+#[test]
+#[cfg(unix)]
+fn symlinks_are_resolved() {
+    let dir = symlinked_packages("resolved");
+    let resolver = NodeResolver::new();
+    let real = std::fs::canonicalize(dir.join("store").join("pkg").join("index.d.ts")).unwrap();
+
+    for app in ["app-a", "app-b"] {
+        let base = FileName::Real(dir.join(app).join("index.ts"));
+        assert_eq!(resolver.resolve(&base, "pkg").unwrap(), FileName::Real(real.clone()));
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn preserve_symlinks() {
+    let dir = symlinked_packages("preserved").join("app-a");
+    let resolver = NodeResolver::new().with_preserve_symlinks(true);
+    let base = FileName::Real(dir.join("index.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "pkg").unwrap(),
+        FileName::Real(dir.join("node_modules").join("pkg").join("index.d.ts"))
+    );
+}
+
+#[test]
+fn plugins() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/project/src/index.ts"), "");
+    fs.insert(PathBuf::from("/project/src/components/button.ts"), "");
+
+    let virtual_modules = Arc::new(MemoryFs::new());
+    virtual_modules.insert(PathBuf::from("/__virtual__/config.ts"), "export const debug = true;");
+
+    let fs: Arc<dyn FileSystem> = Arc::new(OverlayFs::new(virtual_modules, fs));
+    let node = NodeResolver::new().with_fs(fs.clone());
+    let resolver = PluginResolver::new(node.clone())
+        .with_plugin(|_: &FileName, target: &str| -> Option<Result<FileName, anyhow::Error>> {
+            let name = target.strip_prefix("virtual:")?;
+            Some(Ok(FileName::Real(PathBuf::from(format!("/__virtual__/{}.ts", name)))))
+        })
+        .with_plugin(move |base: &FileName, target: &str| {
+            let rest = target.strip_prefix("~/")?;
+            Some(node.resolve(base, &format!("/project/src/{}", rest)))
+        });
+    let base = FileName::Real(PathBuf::from("/project/src/index.ts"));
+
+    let resolved = resolver.resolve(&base, "virtual:config").unwrap();
+    assert_eq!(resolved, FileName::Real(PathBuf::from("/__virtual__/config.ts")));
+    assert!(fs.is_file(Path::new("/__virtual__/config.ts")));
+    assert_eq!(
+        resolver.resolve(&base, "~/components/button").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/components/button.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "./components/button").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/components/button.ts"))
+    );
+}
+
+#[test]
+fn module_suffixes() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/project/index.ts"), "");
+    fs.insert(PathBuf::from("/project/button.ios.ts"), "");
+    fs.insert(PathBuf::from("/project/button.ts"), "");
+    fs.insert(PathBuf::from("/project/input.native.ts"), "");
+    fs.insert(PathBuf::from("/project/input.ts"), "");
+    fs.insert(PathBuf::from("/project/list/index.native.ts"), "");
+    fs.insert(PathBuf::from("/project/label.ts"), "");
+
+    let suffixes = vec![".ios".to_string(), ".native".to_string(), "".to_string()];
+    let base = FileName::Real(PathBuf::from("/project/index.ts"));
+
+    for resolver in [
+        ModuleResolver::new(ModuleResolution::Node).with_module_suffixes(suffixes.clone()),
+        ModuleResolver::new(ModuleResolution::Classic).with_module_suffixes(suffixes.clone()),
+    ] {
+        let resolver = resolver.with_fs(fs.clone());
+
+        assert_eq!(
+            resolver.resolve(&base, "./button").unwrap(),
+            FileName::Real(PathBuf::from("/project/button.ios.ts"))
+        );
+        assert_eq!(
+            resolver.resolve(&base, "./input").unwrap(),
+            FileName::Real(PathBuf::from("/project/input.native.ts"))
+        );
+        assert_eq!(
+            resolver.resolve(&base, "./label").unwrap(),
+            FileName::Real(PathBuf::from("/project/label.ts"))
+        );
+    }
+
+    let resolver = NodeResolver::new().with_fs(fs.clone()).with_module_suffixes(suffixes);
+    assert_eq!(
+        resolver.resolve(&base, "./list").unwrap(),
+        FileName::Real(PathBuf::from("/project/list/index.native.ts"))
+    );
+
+    // Without `moduleSuffixes`, suffixed files are not used.
+    let resolver = NodeResolver::new().with_fs(fs);
+    assert_eq!(
+        resolver.resolve(&base, "./button").unwrap(),
+        FileName::Real(PathBuf::from("/project/button.ts"))
+    );
+    assert!(resolver.resolve(&base, "./list").is_err());
+}
+
+#[test]
+fn root_dirs() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/project/src/views/main.ts"), "");
+    fs.insert(PathBuf::from("/project/src/views/local.ts"), "");
+    fs.insert(PathBuf::from("/project/generated/templates/views/template.ts"), "");
+
+    let resolver = TsConfigResolver::new(NodeResolver::new().with_fs(fs), PathBuf::from("/project"), None, vec![])
+        .with_root_dirs(vec!["src".into(), "generated/templates".into()]);
+    let base = FileName::Real(PathBuf::from("/project/src/views/main.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "./template").unwrap(),
+        FileName::Real(PathBuf::from("/project/generated/templates/views/template.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "./local").unwrap(),
+        FileName::Real(PathBuf::from("/project/src/views/local.ts"))
+    );
+    assert!(resolver.resolve(&base, "./missing").is_err());
+}
+
+#[test]
+fn self_reference() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(
+        PathBuf::from("/lib/package.json"),
+        r#"{ "name": "@scope/lib", "exports": { ".": { "types": "./types/index.d.ts" }, "./utils": "./src/utils.js" } }"#,
+    );
+    fs.insert(PathBuf::from("/lib/types/index.d.ts"), "");
+    fs.insert(PathBuf::from("/lib/src/utils.ts"), "");
+    fs.insert(PathBuf::from("/lib/test/index.test.ts"), "");
+    fs.insert(
+        PathBuf::from("/other/package.json"),
+        r#"{ "name": "other", "types": "./index.d.ts" }"#,
+    );
+    fs.insert(PathBuf::from("/other/index.d.ts"), "");
+    fs.insert(PathBuf::from("/other/test.ts"), "");
+
+    let resolver = NodeResolver::new().with_fs(fs);
+    let base = FileName::Real(PathBuf::from("/lib/test/index.test.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "@scope/lib").unwrap(),
+        FileName::Real(PathBuf::from("/lib/types/index.d.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "@scope/lib/utils").unwrap(),
+        FileName::Real(PathBuf::from("/lib/src/utils.ts"))
+    );
+    assert!(resolver.resolve(&base, "@scope/lib/internal").is_err());
+
+    // Packages without `exports` can't import themselves.
+    let base = FileName::Real(PathBuf::from("/other/test.ts"));
+    assert!(resolver.resolve(&base, "other").is_err());
+}
+
+/// Counts calls to the inner resolver.
+struct CountingResolver {
+    inner: NodeResolver,
+    count: Arc<AtomicUsize>,
+}
+
+impl Resolve for CountingResolver {
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        self.inner.resolve(base, target)
+    }
+}
+
+#[test]
+fn caching_resolver() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/project/main.ts"), "");
+    fs.insert(PathBuf::from("/project/a.ts"), "export {};");
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let resolver = CachingResolver::new(
+        CountingResolver {
+            inner: NodeResolver::new().with_fs(fs.clone()),
+            count: count.clone(),
+        },
+        fs.clone(),
+    );
+    let base = FileName::Real(PathBuf::from("/project/main.ts"));
+    let a = FileName::Real(PathBuf::from("/project/a.ts"));
+
+    assert_eq!(resolver.resolve(&base, "./a").unwrap(), a);
+    assert_eq!(resolver.resolve(&base, "./a").unwrap(), a);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    // Modified files are resolved again.
+    fs.insert(PathBuf::from("/project/a.ts"), "export const a = 1;");
+    assert_eq!(resolver.resolve(&base, "./a").unwrap(), a);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+
+    // Failures are cached until invalidated.
+    assert!(resolver.resolve(&base, "./b").is_err());
+    fs.insert(PathBuf::from("/project/b.ts"), "");
+    assert!(resolver.resolve(&base, "./b").is_err());
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+
+    resolver.invalidate(Path::new("/project/b.ts"));
+    assert_eq!(
+        resolver.resolve(&base, "./b").unwrap(),
+        FileName::Real(PathBuf::from("/project/b.ts"))
+    );
+    assert_eq!(count.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn suggestions() {
+    let fs = MemoryFs::new();
+    fs.insert(PathBuf::from("/project/src/main.ts"), "");
+    fs.insert(PathBuf::from("/project/src/utils.ts"), "");
+    fs.insert(PathBuf::from("/project/src/types.d.ts"), "");
+    fs.insert(PathBuf::from("/project/node_modules/lodash/package.json"), "{}");
+    fs.insert(PathBuf::from("/project/node_modules/lodash/index.js"), "");
+    let base = Path::new("/project/src/main.ts");
+
+    // A similar file name.
+    let hint = suggest(&fs, base, "./util");
+    assert_eq!(hint.suggestion, Some(ModuleSuggestion::DidYouMean("./utils".into())));
+    assert!(hint.probed.contains(&"/project/src/util.ts".to_string()));
+    assert_eq!(
+        suggest(&fs, base, "./typs").suggestion,
+        Some(ModuleSuggestion::DidYouMean("./types".into()))
+    );
+
+    // A relative path.
+    assert_eq!(
+        suggest(&fs, base, "utils").suggestion,
+        Some(ModuleSuggestion::DidYouMean("./utils".into()))
+    );
+
+    // A package without types.
+    let hint = suggest(&fs, base, "lodash");
+    assert_eq!(hint.suggestion, Some(ModuleSuggestion::InstallTypes("@types/lodash".into())));
+    assert!(hint.probed.contains(&"/project/node_modules/@types/lodash/index.d.ts".to_string()));
+
+    assert_eq!(suggest(&fs, base, "./missing").suggestion, None);
+    assert_eq!(suggest(&fs, base, "missing").suggestion, None);
+}
+
+#[test]
+fn project_references() {
+    let fs = Arc::new(MemoryFs::new());
+    fs.insert(PathBuf::from("/repo/app/index.ts"), "");
+    fs.insert(PathBuf::from("/repo/core/src/index.ts"), "");
+    fs.insert(PathBuf::from("/repo/core/src/util.ts"), "");
+    fs.insert(PathBuf::from("/repo/core/lib/index.d.ts"), "");
+    fs.insert(PathBuf::from("/repo/shared/types.ts"), "");
+    fs.insert(PathBuf::from("/repo/shared/types.d.ts"), "");
+
+    let resolver = ProjectReferenceResolver::new(NodeResolver::new().with_fs(fs.clone()), fs)
+        .with_reference(PathBuf::from("/repo/core/src"), Some(PathBuf::from("/repo/core/lib")))
+        .with_reference(PathBuf::from("/repo/shared"), None);
+    let base = FileName::Real(PathBuf::from("/repo/app/index.ts"));
+
+    assert_eq!(
+        resolver.resolve(&base, "../core/src/index").unwrap(),
+        FileName::Real(PathBuf::from("/repo/core/lib/index.d.ts"))
+    );
+    assert_eq!(
+        resolver.resolve(&base, "../shared/types").unwrap(),
+        FileName::Real(PathBuf::from("/repo/shared/types.d.ts"))
+    );
+    // The source file is used if it's not built yet.
+    assert_eq!(
+        resolver.resolve(&base, "../core/src/util").unwrap(),
+        FileName::Real(PathBuf::from("/repo/core/src/util.ts"))
+    );
+}
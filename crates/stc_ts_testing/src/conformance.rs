@@ -6,7 +6,7 @@ use std::path::Path;
 
 use rustc_hash::FxHashSet;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{ModuleConfig, Rule};
+use stc_ts_env::{ModuleConfig, ModuleResolution, Rule};
 use stc_ts_utils::StcComments;
 use swc_common::{input::SourceFileInput, BytePos, Spanned};
 use swc_ecma_ast::{EsVersion, Program};
@@ -21,6 +21,8 @@ pub struct TestSpec {
     pub target: EsVersion,
     pub raw_target: String,
     pub module_config: ModuleConfig,
+    pub module_resolution: ModuleResolution,
+    pub module_suffixes: Vec<String>,
 }
 
 pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
@@ -77,6 +79,8 @@ pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
             ..Default::default()
         };
         let mut module_config = ModuleConfig::None;
+        let mut module_resolution = None;
+        let mut module_suffixes = vec![];
         let ts_config = TsConfig::default();
 
         let mut had_comment = false;
@@ -170,6 +174,22 @@ pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
                 } else if s.starts_with("module:") {
                     let v = s["module:".len()..].trim().to_lowercase().parse().unwrap();
                     module_config = v;
+                } else if s.starts_with("allowJs:") {
+                    let v = s["allowJs:".len()..].trim().parse().unwrap();
+                    rule.allow_js = v;
+                } else if s.starts_with("checkJs:") {
+                    let v = s["checkJs:".len()..].trim().parse().unwrap();
+                    rule.check_js = v;
+                    // `checkJs` implies `allowJs`.
+                    rule.allow_js |= v;
+                } else if s.starts_with("skipLibCheck:") {
+                    let v = s["skipLibCheck:".len()..].trim().parse().unwrap();
+                    rule.skip_lib_check = v;
+                } else if s.starts_with("moduleResolution:") {
+                    let v = s["moduleResolution:".len()..].trim().to_lowercase().parse().unwrap();
+                    module_resolution = Some(v);
+                } else if s.starts_with("moduleSuffixes:") {
+                    module_suffixes = s["moduleSuffixes:".len()..].split(',').map(|v| v.trim().to_string()).collect();
                 } else if s.to_lowercase().starts_with("notypesandsymbols") {
                     // Ignored as we don't generate them.
                 } else if s.to_lowercase().starts_with("usedefineforclassfields") {
@@ -193,6 +213,8 @@ pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
 
         libs.sort();
 
+        let module_resolution = module_resolution.unwrap_or_else(|| module_config.module_resolution());
+
         err_shift_n = err_shift_n.min(first_stmt_line);
         dbg!(err_shift_n);
 
@@ -227,6 +249,8 @@ pub fn parse_conformance_test(file_name: &Path) -> Vec<TestSpec> {
                     target,
                     raw_target,
                     module_config,
+                    module_resolution,
+                    module_suffixes: module_suffixes.clone(),
                 }
             })
             .collect())
@@ -69,12 +69,12 @@ fn run_bench(b: &mut Bencher, path: &Path) {
                 handler.clone(),
                 env.clone(),
                 None,
-                ModuleLoader::new(cm.clone(), env.clone(), NodeResolver),
+                ModuleLoader::new(cm.clone(), env.clone(), NodeResolver::new()),
             );
 
-            let id = checker.check(Arc::new(FileName::Real(path.to_path_buf())));
+            let program = checker.check(Arc::new(FileName::Real(path.to_path_buf())));
             black_box(checker.take_errors());
-            black_box(checker.take_dts(id));
+            black_box(checker.take_dts(program.entry.unwrap()));
         });
 
         Ok(())
@@ -0,0 +1,260 @@
+//! Results of a check which are stored between runs, so that only changed
+//! files and their dependents are checked again.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use fxhash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use stc_ts_errors::{Error, ErrorKind, Report, Severity};
+use stc_ts_module_loader::fs::FileSystem;
+use swc_common::{BytePos, FileName, SourceMap, Span, SyntaxContext};
+
+use crate::{graph::ModuleGraph, loader::LoadModule, program::Diagnostic, Checker};
+
+/// The content of a build info file, like `.stcbuildinfo`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    /// The version of stc which stored the results.
+    pub version: String,
+    /// Describes the options of the check. Results are not reused if the
+    /// options differ.
+    pub options: String,
+    /// The files reachable from the entries of the check.
+    pub graph: ModuleGraph,
+    /// Keyed by the path.
+    pub files: BTreeMap<String, FileInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInfo {
+    /// The hash of the content.
+    pub hash: u64,
+    pub diagnostics: Vec<StoredDiagnostic>,
+}
+
+/// An error reported in a file, which can be reported again without checking
+/// the file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredDiagnostic {
+    /// Like `TS2322`.
+    pub code: String,
+    pub message: String,
+    /// Byte offsets in the file.
+    pub start: u32,
+    pub end: u32,
+    /// See [Error::severity].
+    pub severity: Severity,
+}
+
+impl BuildInfo {
+    /// `options` should describe everything which affects the results of the
+    /// check, like the rules and the builtin libraries.
+    pub fn new(options: String) -> Self {
+        BuildInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            options,
+            ..Default::default()
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+
+    /// Returns `true` if the stored results can be reused by a check with
+    /// `options`.
+    pub fn is_compatible(&self, options: &str) -> bool {
+        self.version == env!("CARGO_PKG_VERSION") && self.options == options
+    }
+
+    /// Returns the files which should be checked again, which are changed
+    /// files, new entries and the files depending on them. The result is
+    /// sorted.
+    ///
+    /// Files with unresolved imports are checked again too, because the
+    /// imported files may be created. Deleted files are not returned, but the
+    /// files depending on them are.
+    pub fn affected_files(&self, fs: &dyn FileSystem, entries: &[PathBuf]) -> Vec<PathBuf> {
+        let mut changed = self
+            .files
+            .iter()
+            .filter(|(path, info)| hash_file(fs, Path::new(path)) != Some(info.hash))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+
+        changed.extend(
+            entries
+                .iter()
+                .map(|entry| entry.display().to_string())
+                .filter(|entry| !self.files.contains_key(entry)),
+        );
+        changed.extend(
+            self.graph
+                .imports
+                .iter()
+                .filter(|import| import.resolved.is_none())
+                .map(|import| import.from.clone()),
+        );
+
+        self.graph
+            .affected_files(&changed.iter().map(|s| &**s).collect::<Vec<_>>())
+            .into_iter()
+            .map(PathBuf::from)
+            .filter(|path| fs.is_file(path))
+            .collect()
+    }
+
+    /// Returns the stored errors of the files which are not in `checked`, to
+    /// be rendered with the errors of the check.
+    pub fn stored_reports(&self, fs: &dyn FileSystem, cm: &SourceMap, checked: &[String]) -> Vec<Report> {
+        self.stored_spans(fs, cm, checked)
+            .into_iter()
+            .map(|(span, diagnostic)| Report {
+                // The file may be loaded into `cm` more than once, so `span` is used as is.
+                span,
+                severity: diagnostic.severity,
+                code: diagnostic.code.trim_start_matches("TS").parse().unwrap_or_default(),
+                message: diagnostic.message.clone(),
+                related: vec![],
+            })
+            .collect()
+    }
+
+    /// Returns the stored errors of the files which are not in `checked`.
+    pub fn stored_diagnostics(&self, fs: &dyn FileSystem, cm: &SourceMap, checked: &[String]) -> Vec<Diagnostic> {
+        self.stored_reports(fs, cm, checked)
+            .iter()
+            .map(|report| Diagnostic::from_report(cm, report))
+            .collect()
+    }
+
+    /// Loads the files with stored errors into `cm`, except for the files in
+    /// `checked`, and returns the errors with their spans.
+    fn stored_spans(&self, fs: &dyn FileSystem, cm: &SourceMap, checked: &[String]) -> Vec<(Span, &StoredDiagnostic)> {
+        let mut stored = vec![];
+
+        for (path, info) in &self.files {
+            if info.diagnostics.is_empty() || checked.contains(path) {
+                continue;
+            }
+
+            let content = match fs.read_to_string(Path::new(path)) {
+                Ok(content) => content,
+                Err(..) => continue,
+            };
+            let fm = cm.new_source_file(FileName::Real(PathBuf::from(path)), content);
+
+            for diagnostic in &info.diagnostics {
+                let span = Span::new(
+                    fm.start_pos + BytePos(diagnostic.start),
+                    fm.start_pos + BytePos(diagnostic.end),
+                    SyntaxContext::empty(),
+                );
+                stored.push((span, diagnostic));
+            }
+        }
+
+        stored
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Stores the results of the files checked by `self` into `info`.
+    ///
+    /// `errors` should be the errors reported by `self`. Files which are not
+    /// reachable from `entries` any more are removed.
+    pub fn update_build_info(&self, info: &mut BuildInfo, errors: &[Error], entries: &[PathBuf]) {
+        let fs = self.module_loader.fs();
+        let graph = self.module_graph();
+        let checked = graph.modules.iter().map(|module| &*module.path).collect::<FxHashSet<_>>();
+
+        for path in &checked {
+            let hash = match hash_file(&*fs, Path::new(path)) {
+                Some(hash) => hash,
+                None => continue,
+            };
+            info.files.insert(path.to_string(), FileInfo { hash, diagnostics: vec![] });
+        }
+        for err in ErrorKind::flatten(errors.to_vec()) {
+            let filename = match self.file_of_error(&err) {
+                Some(filename) => filename.to_string(),
+                None => continue,
+            };
+            if !checked.contains(&*filename) {
+                continue;
+            }
+
+            if let Some(file) = info.files.get_mut(&filename) {
+                let d = self.to_diagnostic(&err);
+                file.diagnostics.push(StoredDiagnostic {
+                    code: format!("TS{}", d.code),
+                    message: d.message,
+                    start: d.start as u32,
+                    end: d.end as u32,
+                    severity: d.severity,
+                });
+            }
+        }
+
+        let mut merged = ModuleGraph {
+            modules: info
+                .graph
+                .modules
+                .iter()
+                .filter(|module| !checked.contains(&*module.path))
+                .chain(&graph.modules)
+                .cloned()
+                .collect(),
+            imports: info
+                .graph
+                .imports
+                .iter()
+                .filter(|import| !checked.contains(&*import.from))
+                .chain(&graph.imports)
+                .cloned()
+                .collect(),
+        };
+
+        // Removes files which are not reachable from the entries.
+        let mut reachable = FxHashSet::default();
+        let mut queue = entries.iter().map(|entry| entry.display().to_string()).collect::<Vec<_>>();
+        while let Some(path) = queue.pop() {
+            if !reachable.insert(path.clone()) {
+                continue;
+            }
+            queue.extend(
+                merged
+                    .imports
+                    .iter()
+                    .filter(|import| import.from == path)
+                    .filter_map(|import| import.resolved.clone()),
+            );
+        }
+        merged.modules.retain(|module| reachable.contains(&module.path));
+        merged.imports.retain(|import| reachable.contains(&import.from));
+        merged.modules.sort_by(|a, b| a.path.cmp(&b.path));
+        merged.imports.sort_by(|a, b| a.from.cmp(&b.from));
+        info.files.retain(|path, _| reachable.contains(path));
+
+        info.graph = merged;
+    }
+}
+
+fn hash_file(fs: &dyn FileSystem, path: &Path) -> Option<u64> {
+    let content = fs.read_to_string(path).ok()?;
+
+    Some(fxhash::hash64(&content))
+}
@@ -0,0 +1,292 @@
+//! Calls from and to functions, like the call hierarchy of editors.
+
+use std::sync::Arc;
+
+use fxhash::FxHashMap;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{FileName, Span, Spanned, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    loader::{LoadModule, ModuleRecord},
+    query::{Definition, ReferenceKind},
+    Checker,
+};
+
+/// A function or another callable declaration, or a file for calls at the
+/// top level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallHierarchyItem {
+    pub name: JsWord,
+    pub kind: CallHierarchyKind,
+    pub file: Arc<FileName>,
+    /// The span of the declared name, or the span of the file for
+    /// [CallHierarchyKind::Module].
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallHierarchyKind {
+    /// Includes variables initialized with function expressions and arrow
+    /// functions.
+    Function,
+    Method,
+    Constructor,
+    /// A class called using `new`.
+    Class,
+    /// The top level of a file.
+    Module,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingCall {
+    /// The caller.
+    pub from: CallHierarchyItem,
+    /// The spans of the callees in `from`.
+    pub spans: Vec<Span>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutgoingCall {
+    /// The callee.
+    pub to: CallHierarchyItem,
+    /// The spans of the callees in the caller.
+    pub spans: Vec<Span>,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the callable declaration of the symbol named by the identifier
+    /// at `pos`, which is a byte offset in `file`.
+    ///
+    /// The symbol is found like [Checker::definition_at]. Returns [None] if
+    /// it's not a function, a method, a constructor or a class.
+    pub fn call_hierarchy_item_at(&self, file: &FileName, pos: usize) -> Option<CallHierarchyItem> {
+        self.definition_at(file, pos).into_iter().find_map(|def| self.callable_item(&def))
+    }
+
+    /// Returns the calls to `item` in the loaded modules, grouped by the
+    /// caller.
+    ///
+    /// Only calls using the name of `item`, like `f()` and `new C()`, are
+    /// included. Calls of properties, like `obj.f()`, are not.
+    pub fn incoming_calls(&self, item: &CallHierarchyItem) -> Vec<IncomingCall> {
+        let definition = Definition {
+            file: item.file.clone(),
+            span: item.span,
+        };
+
+        // The callees and the callable declarations of each file.
+        let mut files = FxHashMap::<Arc<FileName>, (Vec<Span>, Vec<Callable>)>::default();
+        let mut calls: Vec<IncomingCall> = vec![];
+        for reference in self.references(&[definition]) {
+            if reference.kind != ReferenceKind::Read {
+                continue;
+            }
+
+            let record = match self.find_record(&reference.file) {
+                Some(record) => record,
+                None => continue,
+            };
+            let (callees, decls) = files.entry(reference.file.clone()).or_insert_with(|| {
+                let mut collector = CallCollector::default();
+                record.ast.visit_with(&mut collector);
+                (collector.calls.into_iter().map(|(_, span)| span).collect(), callables(&record))
+            });
+            if !callees.contains(&reference.span) {
+                continue;
+            }
+
+            let from = enclosing_item(&record, decls, reference.span);
+            match calls.iter_mut().find(|call| call.from == from) {
+                Some(call) => call.spans.push(reference.span),
+                None => calls.push(IncomingCall {
+                    from,
+                    spans: vec![reference.span],
+                }),
+            }
+        }
+        calls
+    }
+
+    /// Returns the calls in the body of `item`, grouped by the callee.
+    ///
+    /// Calls in nested functions are not included, and only callees which are
+    /// identifiers are resolved.
+    pub fn outgoing_calls(&self, item: &CallHierarchyItem) -> Vec<OutgoingCall> {
+        let record = match self.find_record(&item.file) {
+            Some(record) => record,
+            None => return vec![],
+        };
+
+        let mut collector = CallCollector::default();
+        record.ast.visit_with(&mut collector);
+        let callables = callables(&record);
+
+        let mut calls: Vec<OutgoingCall> = vec![];
+        for (id, span) in collector.calls {
+            if enclosing_item(&record, &callables, span) != *item {
+                continue;
+            }
+
+            let to = match self
+                .definitions_of_local(&record, &id, &mut vec![])
+                .iter()
+                .find_map(|def| self.callable_item(def))
+            {
+                Some(to) => to,
+                None => continue,
+            };
+
+            match calls.iter_mut().find(|call| call.to == to) {
+                Some(call) => call.spans.push(span),
+                None => calls.push(OutgoingCall { to, spans: vec![span] }),
+            }
+        }
+        calls
+    }
+
+    /// Returns the callable declaration named by `def`.
+    fn callable_item(&self, def: &Definition) -> Option<CallHierarchyItem> {
+        let record = self.find_record(&def.file)?;
+        let callable = callables(&record).into_iter().find(|callable| callable.name_span == def.span)?;
+
+        Some(CallHierarchyItem {
+            name: callable.name,
+            kind: callable.kind,
+            file: record.filename.clone(),
+            span: callable.name_span,
+        })
+    }
+}
+
+/// Returns the innermost callable declaration containing `span`, or the file
+/// if there's no such declaration.
+fn enclosing_item(record: &ModuleRecord, callables: &[Callable], span: Span) -> CallHierarchyItem {
+    let callable = callables
+        .iter()
+        .filter(|callable| callable.body_span.lo <= span.lo && span.hi <= callable.body_span.hi)
+        .min_by_key(|callable| callable.body_span.hi - callable.body_span.lo);
+
+    match callable {
+        Some(callable) => CallHierarchyItem {
+            name: callable.name.clone(),
+            kind: callable.kind,
+            file: record.filename.clone(),
+            span: callable.name_span,
+        },
+        None => CallHierarchyItem {
+            name: record.filename.to_string().into(),
+            kind: CallHierarchyKind::Module,
+            file: record.filename.clone(),
+            span: record.ast.span.with_ctxt(SyntaxContext::empty()),
+        },
+    }
+}
+
+fn callables(record: &ModuleRecord) -> Vec<Callable> {
+    let mut collector = CallableCollector { callables: vec![] };
+    record.ast.visit_with(&mut collector);
+    collector.callables
+}
+
+struct Callable {
+    name: JsWord,
+    kind: CallHierarchyKind,
+    name_span: Span,
+    /// The span of the whole declaration.
+    body_span: Span,
+}
+
+struct CallableCollector {
+    callables: Vec<Callable>,
+}
+
+impl CallableCollector {
+    fn add(&mut self, name: &Ident, kind: CallHierarchyKind, body_span: Span) {
+        self.callables.push(Callable {
+            name: name.sym.clone(),
+            kind,
+            name_span: name.span.with_ctxt(SyntaxContext::empty()),
+            body_span,
+        });
+    }
+}
+
+impl Visit for CallableCollector {
+    fn visit_fn_decl(&mut self, f: &FnDecl) {
+        self.add(&f.ident, CallHierarchyKind::Function, f.function.span);
+        f.visit_children_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, f: &FnExpr) {
+        if let Some(ident) = &f.ident {
+            self.add(ident, CallHierarchyKind::Function, f.function.span);
+        }
+        f.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, d: &VarDeclarator) {
+        if let (Pat::Ident(name), Some(init)) = (&d.name, &d.init) {
+            match &**init {
+                Expr::Arrow(a) => self.add(&name.id, CallHierarchyKind::Function, a.span),
+                // Named function expressions are added by `visit_fn_expr`.
+                Expr::Fn(FnExpr { ident: None, function }) => self.add(&name.id, CallHierarchyKind::Function, function.span),
+                _ => {}
+            }
+        }
+        d.visit_children_with(self);
+    }
+
+    fn visit_class_decl(&mut self, c: &ClassDecl) {
+        self.add(&c.ident, CallHierarchyKind::Class, c.class.span);
+        c.visit_children_with(self);
+    }
+
+    fn visit_class_method(&mut self, m: &ClassMethod) {
+        if let PropName::Ident(key) = &m.key {
+            self.add(key, CallHierarchyKind::Method, m.function.span);
+        }
+        m.visit_children_with(self);
+    }
+
+    fn visit_constructor(&mut self, c: &Constructor) {
+        self.add(
+            &Ident::new(js_word!("constructor"), c.key.span()),
+            CallHierarchyKind::Constructor,
+            c.span,
+        );
+        c.visit_children_with(self);
+    }
+}
+
+/// Collects the callees which are identifiers.
+#[derive(Default)]
+struct CallCollector {
+    calls: Vec<(Id, Span)>,
+}
+
+impl CallCollector {
+    fn callee(&mut self, callee: &Expr) {
+        if let Expr::Ident(i) = callee {
+            self.calls.push((i.to_id(), i.span.with_ctxt(SyntaxContext::empty())));
+        }
+    }
+}
+
+impl Visit for CallCollector {
+    fn visit_call_expr(&mut self, e: &CallExpr) {
+        if let Callee::Expr(callee) = &e.callee {
+            self.callee(callee);
+        }
+        e.visit_children_with(self);
+    }
+
+    fn visit_new_expr(&mut self, e: &NewExpr) {
+        self.callee(&e.callee);
+        e.visit_children_with(self);
+    }
+}
@@ -0,0 +1,597 @@
+//! Completion candidates shown by editors while typing names.
+
+use std::sync::Arc;
+
+use fxhash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use stc_ts_ast_rnode::{RExpr, RTsEntityName, RTsEnumMemberId, RTsLit};
+use stc_ts_env::Env;
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_types::{printer::TypePrinter, ClassMember, FnParam, Id, Key, Type, TypeElement, TypeParamDecl};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{BytePos, FileName, Span, Spanned, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    declarations::{declared_type, DeclarationKind},
+    loader::LoadModule,
+    Checker,
+};
+
+/// The maximum depth of references, base interfaces and super classes
+/// followed while collecting members.
+const MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionEntry {
+    pub name: String,
+    pub kind: CompletionKind,
+    /// The printed type, like `(a: number) => string` for a method. [None] if
+    /// the type is not known, and for interfaces and type aliases.
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompletionKind {
+    Property,
+    Method,
+    EnumMember,
+    Var,
+    Let,
+    Const,
+    Function,
+    Class,
+    Interface,
+    TypeAlias,
+    Enum,
+    Namespace,
+    Parameter,
+    Import,
+}
+
+impl From<DeclarationKind> for CompletionKind {
+    fn from(kind: DeclarationKind) -> Self {
+        match kind {
+            DeclarationKind::Var => CompletionKind::Var,
+            DeclarationKind::Let => CompletionKind::Let,
+            DeclarationKind::Const => CompletionKind::Const,
+            DeclarationKind::Function => CompletionKind::Function,
+            DeclarationKind::Class => CompletionKind::Class,
+            DeclarationKind::Interface => CompletionKind::Interface,
+            DeclarationKind::TypeAlias => CompletionKind::TypeAlias,
+            DeclarationKind::Enum => CompletionKind::Enum,
+            DeclarationKind::Namespace => CompletionKind::Namespace,
+            DeclarationKind::Parameter => CompletionKind::Parameter,
+            DeclarationKind::Import => CompletionKind::Import,
+        }
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the completion candidates at `pos`, which is a byte offset in
+    /// `file`, sorted by the name.
+    ///
+    /// If `pos` is in the property of a member expression, like the end of
+    /// `a.b`, the members of the type of the object are returned. Otherwise,
+    /// the declarations of the scopes containing `pos` are returned, without
+    /// the globals declared by libraries.
+    ///
+    /// `file` should be parseable, so editors usually request completions
+    /// with a partially typed name. Returns an empty list if types are not
+    /// recorded (see [Checker::with_type_recorder]), or `file` is not
+    /// checked yet.
+    pub fn completions_at(&self, file: &FileName, pos: usize) -> Vec<CompletionEntry> {
+        let (recorder, fm, record) = match (
+            self.type_recorder.as_ref(),
+            self.source_file(file),
+            self.find_record(&Arc::new(file.clone())),
+        ) {
+            (Some(recorder), Some(fm), Some(record)) => (recorder, fm, record),
+            _ => return vec![],
+        };
+        let pos = fm.start_pos + BytePos(pos as u32);
+
+        let mut finder = MemberFinder { pos, found: None };
+        record.ast.visit_with(&mut finder);
+
+        let mut entries = match finder.found {
+            Some(obj) => {
+                let mut collector = MemberCollector {
+                    env: &self.env,
+                    recorder,
+                    printer: TypePrinter::default(),
+                    entries: vec![],
+                };
+                if let Some(ty) = recorder.type_of(obj) {
+                    collector.collect(&ty, true, 0);
+                }
+                collector.entries
+            }
+            None => {
+                // Inner declarations shadow outer ones.
+                declarations_in_scope(&record.ast, pos)
+                    .into_iter()
+                    .rev()
+                    .map(|(i, kind)| {
+                        let ty = match kind {
+                            DeclarationKind::Interface | DeclarationKind::TypeAlias => None,
+                            _ => declared_type(recorder, &i, kind),
+                        };
+
+                        CompletionEntry {
+                            name: i.sym.to_string(),
+                            kind: kind.into(),
+                            ty: ty.map(|ty| TypePrinter::default().print(&ty)),
+                        }
+                    })
+                    .collect()
+            }
+        };
+
+        let mut seen = FxHashSet::default();
+        entries.retain(|entry| seen.insert(entry.name.clone()));
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+}
+
+/// Returns the declarations of the scopes containing `pos`, sorted from the
+/// outermost scope.
+pub(crate) fn declarations_in_scope(module: &Module, pos: BytePos) -> Vec<(Ident, DeclarationKind)> {
+    let mut collector = ScopeCollector { pos, decls: vec![] };
+    module.visit_with(&mut collector);
+    collector.decls
+}
+
+/// Returns the names of the members of `ty`, which is the type of an
+/// expression.
+pub(crate) fn member_names(env: &Env, recorder: &TypeRecorder, ty: &Type) -> Vec<String> {
+    let mut collector = MemberCollector {
+        env,
+        recorder,
+        printer: TypePrinter::default(),
+        entries: vec![],
+    };
+    collector.collect(ty, true, 0);
+
+    collector.entries.into_iter().map(|entry| entry.name).collect()
+}
+
+struct MemberCollector<'a> {
+    env: &'a Env,
+    recorder: &'a TypeRecorder,
+    printer: TypePrinter,
+    /// May contain duplicates, and the first one is used.
+    entries: Vec<CompletionEntry>,
+}
+
+impl MemberCollector<'_> {
+    /// Collects the members of `ty`.
+    ///
+    /// Class definitions in `ty` are constructors if `is_static` is `true`,
+    /// like for the type of an expression. Otherwise, they are instances,
+    /// like for a type reference.
+    fn collect(&mut self, ty: &Type, is_static: bool, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+
+        match ty.normalize() {
+            Type::Ref(r) => {
+                if let RTsEntityName::Ident(i) = &r.type_name {
+                    if let Some(ty) = self.lookup_type(&i.sym, Id::new(i.sym.clone(), i.span.ctxt)) {
+                        self.collect(&ty, false, depth + 1);
+                    }
+                }
+            }
+            Type::Alias(a) => self.collect(&a.ty, is_static, depth + 1),
+            Type::Interface(i) => {
+                self.type_elements(&i.body);
+                for parent in &i.extends {
+                    if let RExpr::Ident(p) = &*parent.expr {
+                        if let Some(ty) = self.lookup_type(&p.sym, Id::new(p.sym.clone(), p.span.ctxt)) {
+                            self.collect(&ty, false, depth + 1);
+                        }
+                    }
+                }
+            }
+            Type::TypeLit(t) => self.type_elements(&t.members),
+            Type::Class(c) => {
+                self.class_members(&c.def.body, false);
+                if let Some(super_class) = &c.def.super_class {
+                    self.collect(super_class, false, depth + 1);
+                }
+            }
+            Type::ClassDef(c) => {
+                self.class_members(&c.body, is_static);
+                if let Some(super_class) = &c.super_class {
+                    self.collect(super_class, is_static, depth + 1);
+                }
+            }
+            Type::Enum(e) => {
+                for member in &e.members {
+                    let name = match &member.id {
+                        RTsEnumMemberId::Ident(i) => i.sym.clone(),
+                        RTsEnumMemberId::Str(s) => s.value.clone(),
+                    };
+                    self.entries.push(CompletionEntry {
+                        ty: Some(format!("{}.{}", e.id.sym, name)),
+                        name: name.to_string(),
+                        kind: CompletionKind::EnumMember,
+                    });
+                }
+            }
+            Type::Module(m) => {
+                for (name, ty) in &m.exports.vars {
+                    let kind = match ty.normalize() {
+                        Type::Function(..) => CompletionKind::Function,
+                        Type::ClassDef(..) => CompletionKind::Class,
+                        Type::Enum(..) => CompletionKind::Enum,
+                        Type::Module(..) => CompletionKind::Namespace,
+                        _ => CompletionKind::Var,
+                    };
+                    self.entries.push(CompletionEntry {
+                        name: name.to_string(),
+                        kind,
+                        ty: Some(self.printer.print(ty)),
+                    });
+                }
+            }
+            Type::Union(u) => {
+                // Only the members of all types can be accessed.
+                let mut common: Option<Vec<CompletionEntry>> = None;
+                for ty in &u.types {
+                    let entries = self.members_of(ty, is_static, depth + 1);
+                    common = Some(match common {
+                        Some(common) => common
+                            .into_iter()
+                            .filter(|entry| entries.iter().any(|e| e.name == entry.name))
+                            .collect(),
+                        None => entries,
+                    });
+                }
+                self.entries.extend(common.unwrap_or_default());
+            }
+            Type::Intersection(i) => {
+                for ty in &i.types {
+                    self.collect(ty, is_static, depth + 1);
+                }
+            }
+            Type::Keyword(k) => {
+                let name = match k.kind {
+                    TsKeywordTypeKind::TsStringKeyword => js_word!("String"),
+                    TsKeywordTypeKind::TsNumberKeyword => js_word!("Number"),
+                    TsKeywordTypeKind::TsBooleanKeyword => js_word!("Boolean"),
+                    TsKeywordTypeKind::TsSymbolKeyword => js_word!("Symbol"),
+                    TsKeywordTypeKind::TsBigIntKeyword => js_word!("BigInt"),
+                    _ => return,
+                };
+                self.collect_global(&name, depth);
+            }
+            Type::Lit(l) => {
+                let name = match &l.lit {
+                    RTsLit::Str(..) | RTsLit::Tpl(..) => js_word!("String"),
+                    RTsLit::Number(..) => js_word!("Number"),
+                    RTsLit::Bool(..) => js_word!("Boolean"),
+                    RTsLit::BigInt(..) => js_word!("BigInt"),
+                };
+                self.collect_global(&name, depth);
+            }
+            Type::Array(..) => self.collect_global(&js_word!("Array"), depth),
+            Type::Function(..) => self.collect_global(&js_word!("Function"), depth),
+            _ => {}
+        }
+    }
+
+    fn members_of(&self, ty: &Type, is_static: bool, depth: usize) -> Vec<CompletionEntry> {
+        let mut collector = MemberCollector {
+            env: self.env,
+            recorder: self.recorder,
+            printer: self.printer,
+            entries: vec![],
+        };
+        collector.collect(ty, is_static, depth);
+        collector.entries
+    }
+
+    fn collect_global(&mut self, name: &JsWord, depth: usize) {
+        if let Ok(ty) = self.env.get_global_type(DUMMY_SP, name) {
+            self.collect(&ty, false, depth + 1);
+        }
+    }
+
+    /// Returns the type named `name`, which is declared in a checked file or
+    /// globally.
+    fn lookup_type(&self, name: &JsWord, id: Id) -> Option<Type> {
+        self.recorder
+            .type_decl(&id)
+            .or_else(|| self.recorder.var_type(&id))
+            .or_else(|| self.env.get_global_type(DUMMY_SP, name).ok())
+    }
+
+    fn type_elements(&mut self, members: &[TypeElement]) {
+        for member in members {
+            match member {
+                TypeElement::Property(p) => {
+                    if let Key::Normal { sym, .. } = &p.key {
+                        self.entries.push(CompletionEntry {
+                            name: sym.to_string(),
+                            kind: CompletionKind::Property,
+                            ty: Some(p.type_ann.as_deref().map_or_else(|| "any".to_string(), |ty| self.printer.print(ty))),
+                        });
+                    }
+                }
+                TypeElement::Method(m) => {
+                    if let Key::Normal { sym, .. } = &m.key {
+                        self.entries.push(CompletionEntry {
+                            name: sym.to_string(),
+                            kind: CompletionKind::Method,
+                            ty: Some(self.print_signature(m.type_params.as_ref(), &m.params, m.ret_ty.as_deref())),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn class_members(&mut self, members: &[ClassMember], is_static: bool) {
+        for member in members {
+            match member {
+                ClassMember::Property(p) if p.is_static == is_static => {
+                    if let Key::Normal { sym, .. } = &p.key {
+                        self.entries.push(CompletionEntry {
+                            name: sym.to_string(),
+                            kind: CompletionKind::Property,
+                            ty: Some(p.value.as_deref().map_or_else(|| "any".to_string(), |ty| self.printer.print(ty))),
+                        });
+                    }
+                }
+                ClassMember::Method(m) if m.is_static == is_static => {
+                    if let Key::Normal { sym, .. } = &m.key {
+                        self.entries.push(CompletionEntry {
+                            name: sym.to_string(),
+                            kind: CompletionKind::Method,
+                            ty: Some(self.print_signature(m.type_params.as_ref(), &m.params, Some(&m.ret_ty))),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Prints a method as a function type, like `(a: number) => string`.
+    fn print_signature(&self, type_params: Option<&TypeParamDecl>, params: &[FnParam], ret_ty: Option<&Type>) -> String {
+        format!(
+            "{}({}) => {}",
+            self.printer.print_type_params(type_params),
+            params
+                .iter()
+                .map(|param| self.printer.print_param(param))
+                .collect::<Vec<_>>()
+                .join(", "),
+            ret_ty.map_or_else(|| "any".to_string(), |ty| self.printer.print(ty))
+        )
+    }
+}
+
+/// Finds the object of the innermost member expression whose property
+/// contains `pos`.
+struct MemberFinder {
+    pos: BytePos,
+    found: Option<Span>,
+}
+
+impl Visit for MemberFinder {
+    fn visit_member_expr(&mut self, e: &MemberExpr) {
+        if let MemberProp::Ident(prop) = &e.prop {
+            if e.obj.span().hi < self.pos && self.pos <= prop.span.hi {
+                self.found = Some(e.obj.span());
+            }
+        }
+        e.visit_children_with(self);
+    }
+}
+
+/// Collects the declarations of the scopes containing `pos`.
+struct ScopeCollector {
+    pos: BytePos,
+    /// Sorted from the outermost scope.
+    decls: Vec<(Ident, DeclarationKind)>,
+}
+
+impl ScopeCollector {
+    fn contains(&self, span: Span) -> bool {
+        span.lo <= self.pos && self.pos <= span.hi
+    }
+
+    fn decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Class(c) => self.decls.push((c.ident.clone(), DeclarationKind::Class)),
+            Decl::Fn(f) => self.decls.push((f.ident.clone(), DeclarationKind::Function)),
+            Decl::Var(v) => self.var_decl(v),
+            Decl::TsInterface(d) => self.decls.push((d.id.clone(), DeclarationKind::Interface)),
+            Decl::TsTypeAlias(d) => self.decls.push((d.id.clone(), DeclarationKind::TypeAlias)),
+            Decl::TsEnum(d) => self.decls.push((d.id.clone(), DeclarationKind::Enum)),
+            Decl::TsModule(d) => {
+                if let TsModuleName::Ident(i) = &d.id {
+                    self.decls.push((i.clone(), DeclarationKind::Namespace));
+                }
+            }
+        }
+    }
+
+    fn var_decl(&mut self, v: &VarDecl) {
+        let kind = match v.kind {
+            VarDeclKind::Var => DeclarationKind::Var,
+            VarDeclKind::Let => DeclarationKind::Let,
+            VarDeclKind::Const => DeclarationKind::Const,
+        };
+        for decl in &v.decls {
+            self.pat(&decl.name, kind);
+        }
+    }
+
+    fn pat(&mut self, pat: &Pat, kind: DeclarationKind) {
+        let mut collector = BindingCollector { idents: vec![] };
+        pat.visit_with(&mut collector);
+        self.decls.extend(collector.idents.into_iter().map(|i| (i, kind)));
+    }
+
+    fn module_items(&mut self, items: &[ModuleItem]) {
+        for item in items {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => self.decl(decl),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => self.decl(&export.decl),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match &export.decl {
+                    DefaultDecl::Class(ClassExpr { ident: Some(i), .. }) => self.decls.push((i.clone(), DeclarationKind::Class)),
+                    DefaultDecl::Fn(FnExpr { ident: Some(i), .. }) => self.decls.push((i.clone(), DeclarationKind::Function)),
+                    _ => {}
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    for specifier in &import.specifiers {
+                        let local = match specifier {
+                            ImportSpecifier::Named(s) => &s.local,
+                            ImportSpecifier::Default(s) => &s.local,
+                            ImportSpecifier::Namespace(s) => &s.local,
+                        };
+                        self.decls.push((local.clone(), DeclarationKind::Import));
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)) => {
+                    self.decls.push((import.id.clone(), DeclarationKind::Import));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn var_decl_or_pat(&mut self, v: &VarDeclOrPat) {
+        if let VarDeclOrPat::VarDecl(v) = v {
+            self.var_decl(v);
+        }
+    }
+}
+
+impl Visit for ScopeCollector {
+    fn visit_module(&mut self, m: &Module) {
+        self.module_items(&m.body);
+        m.visit_children_with(self);
+    }
+
+    fn visit_ts_module_block(&mut self, b: &TsModuleBlock) {
+        if self.contains(b.span) {
+            self.module_items(&b.body);
+            b.visit_children_with(self);
+        }
+    }
+
+    fn visit_block_stmt(&mut self, b: &BlockStmt) {
+        if self.contains(b.span) {
+            for stmt in &b.stmts {
+                if let Stmt::Decl(decl) = stmt {
+                    self.decl(decl);
+                }
+            }
+            b.visit_children_with(self);
+        }
+    }
+
+    fn visit_fn_expr(&mut self, f: &FnExpr) {
+        if self.contains(f.function.span) {
+            if let Some(i) = &f.ident {
+                self.decls.push((i.clone(), DeclarationKind::Function));
+            }
+            f.function.visit_with(self);
+        }
+    }
+
+    fn visit_function(&mut self, f: &Function) {
+        if self.contains(f.span) {
+            for param in &f.params {
+                self.pat(&param.pat, DeclarationKind::Parameter);
+            }
+            f.visit_children_with(self);
+        }
+    }
+
+    fn visit_arrow_expr(&mut self, a: &ArrowExpr) {
+        if self.contains(a.span) {
+            for param in &a.params {
+                self.pat(param, DeclarationKind::Parameter);
+            }
+            a.visit_children_with(self);
+        }
+    }
+
+    fn visit_constructor(&mut self, c: &Constructor) {
+        if self.contains(c.span) {
+            for param in &c.params {
+                match param {
+                    ParamOrTsParamProp::Param(p) => self.pat(&p.pat, DeclarationKind::Parameter),
+                    ParamOrTsParamProp::TsParamProp(p) => match &p.param {
+                        TsParamPropParam::Ident(i) => self.decls.push((i.id.clone(), DeclarationKind::Parameter)),
+                        TsParamPropParam::Assign(a) => self.pat(&a.left, DeclarationKind::Parameter),
+                    },
+                }
+            }
+            c.visit_children_with(self);
+        }
+    }
+
+    fn visit_catch_clause(&mut self, c: &CatchClause) {
+        if self.contains(c.span) {
+            if let Some(param) = &c.param {
+                self.pat(param, DeclarationKind::Let);
+            }
+            c.visit_children_with(self);
+        }
+    }
+
+    fn visit_for_stmt(&mut self, s: &ForStmt) {
+        if self.contains(s.span) {
+            if let Some(VarDeclOrExpr::VarDecl(v)) = &s.init {
+                self.var_decl(v);
+            }
+            s.visit_children_with(self);
+        }
+    }
+
+    fn visit_for_in_stmt(&mut self, s: &ForInStmt) {
+        if self.contains(s.span) {
+            self.var_decl_or_pat(&s.left);
+            s.visit_children_with(self);
+        }
+    }
+
+    fn visit_for_of_stmt(&mut self, s: &ForOfStmt) {
+        if self.contains(s.span) {
+            self.var_decl_or_pat(&s.left);
+            s.visit_children_with(self);
+        }
+    }
+}
+
+struct BindingCollector {
+    idents: Vec<Ident>,
+}
+
+impl Visit for BindingCollector {
+    fn visit_binding_ident(&mut self, i: &BindingIdent) {
+        self.idents.push(i.id.clone());
+    }
+
+    fn visit_assign_pat_prop(&mut self, p: &AssignPatProp) {
+        self.idents.push(p.key.clone());
+    }
+
+    fn visit_expr(&mut self, _: &Expr) {}
+}
@@ -0,0 +1,438 @@
+//! Type coverage, which is the ratio of identifiers not typed as `any`, like
+//! the `type-coverage` package of npm.
+
+use std::{collections::BTreeMap, fmt::Write, mem::replace, path::Path, sync::Arc};
+
+use fxhash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_types::Type;
+use swc_common::FileName;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{declarations::DeclarationSpan, loader::LoadModule, Checker};
+
+/// The type coverage of the loaded files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeCoverage {
+    /// The sum of the counts of `files`.
+    pub total: CoverageCounts,
+    /// Sorted by the path.
+    pub files: Vec<FileCoverage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileCoverage {
+    pub file: String,
+    pub counts: CoverageCounts,
+    /// The identifiers typed as `any`, sorted by the position.
+    pub anys: Vec<AnyIdent>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageCounts {
+    /// The number of declared and referenced identifiers whose types are
+    /// known.
+    pub total: usize,
+    /// The number of identifiers not typed as `any`.
+    pub typed: usize,
+    /// The number of identifiers typed as `any` by a type annotation.
+    pub explicit_any: usize,
+    /// The number of identifiers typed as `any` by inference, like parameters
+    /// without type annotations.
+    pub implicit_any: usize,
+}
+
+/// The sum of the counts of the files in a directory and its subdirectories.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryCoverage {
+    pub dir: String,
+    pub counts: CoverageCounts,
+}
+
+/// An identifier typed as `any`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnyIdent {
+    pub name: String,
+    pub span: DeclarationSpan,
+    /// `true` for declarations, and `false` for references.
+    pub declaration: bool,
+    /// `true` if the type is not from a type annotation.
+    pub implicit: bool,
+}
+
+impl CoverageCounts {
+    /// Returns the percentage of identifiers not typed as `any`, which is 100
+    /// if there's no identifier.
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+
+        self.typed as f64 * 100.0 / self.total as f64
+    }
+
+    fn add(&mut self, other: &CoverageCounts) {
+        self.total += other.total;
+        self.typed += other.typed;
+        self.explicit_any += other.explicit_any;
+        self.implicit_any += other.implicit_any;
+    }
+}
+
+impl TypeCoverage {
+    /// Sums the counts of the files by directory, including the ancestors of
+    /// the directories up to `root`.
+    ///
+    /// Directories in `root` are relative to it and separated by `/`, and
+    /// `root` itself is `.`. Files outside of `root` are summed only by their
+    /// directory. Sorted by the path.
+    pub fn directories(&self, root: &Path) -> Vec<DirectoryCoverage> {
+        let mut dirs = BTreeMap::<String, CoverageCounts>::new();
+
+        for file in &self.files {
+            let path = Path::new(&file.file);
+            let dir = path.parent().unwrap_or(path);
+
+            match dir.strip_prefix(root) {
+                Ok(relative) => {
+                    dirs.entry(".".into()).or_default().add(&file.counts);
+
+                    let mut name = String::new();
+                    for component in relative.components() {
+                        if !name.is_empty() {
+                            name.push('/');
+                        }
+                        name.push_str(&component.as_os_str().to_string_lossy());
+                        dirs.entry(name.clone()).or_default().add(&file.counts);
+                    }
+                }
+                Err(..) => {
+                    dirs.entry(dir.display().to_string()).or_default().add(&file.counts);
+                }
+            }
+        }
+
+        dirs.into_iter().map(|(dir, counts)| DirectoryCoverage { dir, counts }).collect()
+    }
+
+    /// Returns a standalone HTML page with the coverage of the directories and
+    /// the files, and the identifiers typed as `any` in each file. Paths in
+    /// `root` are relative to it.
+    pub fn to_html(&self, root: &Path) -> String {
+        let mut html = String::new();
+
+        html.push_str(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Type coverage</title>\n<style>\nbody { font-family: \
+             sans-serif; }\ntable { border-collapse: collapse; }\nth, td { padding: 2px 8px; text-align: right; }\nth:first-child, \
+             td:first-child { text-align: left; }\n.low { color: #c00; }\n</style>\n</head>\n<body>\n",
+        );
+        let _ = writeln!(html, "<h1>Type coverage: {}</h1>", format_percentage(&self.total));
+
+        html.push_str("<h2>Directories</h2>\n");
+        table_header(&mut html, "Directory");
+        for dir in self.directories(root) {
+            table_row(&mut html, &escape_html(&dir.dir), &dir.counts);
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Files</h2>\n");
+        table_header(&mut html, "File");
+        for (i, file) in self.files.iter().enumerate() {
+            let path = relative_path(&file.file, root);
+            let name = if file.anys.is_empty() {
+                escape_html(&path)
+            } else {
+                format!("<a href=\"#file-{}\">{}</a>", i, escape_html(&path))
+            };
+            table_row(&mut html, &name, &file.counts);
+        }
+        html.push_str("</table>\n");
+
+        for (i, file) in self.files.iter().enumerate().filter(|(_, file)| !file.anys.is_empty()) {
+            let _ = writeln!(
+                html,
+                "<h3 id=\"file-{}\">{}</h3>\n<ul>",
+                i,
+                escape_html(&relative_path(&file.file, root))
+            );
+            for any in &file.anys {
+                let _ = writeln!(
+                    html,
+                    "<li>{}:{} <code>{}</code> ({} {})</li>",
+                    any.span.line,
+                    any.span.column + 1,
+                    escape_html(&any.name),
+                    if any.implicit { "implicit" } else { "explicit" },
+                    if any.declaration { "declaration" } else { "reference" },
+                );
+            }
+            html.push_str("</ul>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn table_header(html: &mut String, name: &str) {
+    let _ = writeln!(
+        html,
+        "<table>\n<tr><th>{}</th><th>Coverage</th><th>Typed</th><th>Total</th><th>Explicit any</th><th>Implicit any</th></tr>",
+        name
+    );
+}
+
+fn table_row(html: &mut String, name: &str, counts: &CoverageCounts) {
+    let class = if counts.typed < counts.total { " class=\"low\"" } else { "" };
+    let _ = writeln!(
+        html,
+        "<tr><td>{}</td><td{}>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+        name,
+        class,
+        format_percentage(counts),
+        counts.typed,
+        counts.total,
+        counts.explicit_any,
+        counts.implicit_any
+    );
+}
+
+fn format_percentage(counts: &CoverageCounts) -> String {
+    format!("{:.2}%", counts.percentage())
+}
+
+fn relative_path(file: &str, root: &Path) -> String {
+    let path = Path::new(file);
+    path.strip_prefix(root).unwrap_or(path).display().to_string().replace('\\', "/")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the type coverage of `file`.
+    ///
+    /// Identifiers declared by variables and parameters, and identifiers used
+    /// as expressions are counted. A reference typed as `any` is explicit if
+    /// the variable is declared with a type annotation, or if the type is not
+    /// marked as implicit by the analyzer for variables declared in other
+    /// files.
+    ///
+    /// Returns [None] if types are not recorded (see
+    /// [Checker::with_type_recorder]), or `file` is not loaded.
+    pub fn file_coverage(&self, file: &FileName) -> Option<FileCoverage> {
+        let recorder = self.type_recorder.as_ref()?;
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        let mut collector = CoverageCollector {
+            recorder,
+            annotated: None,
+            local: Default::default(),
+            explicit: Default::default(),
+            decls: vec![],
+            refs: vec![],
+        };
+        record.ast.visit_with(&mut collector);
+
+        let mut counts = CoverageCounts::default();
+        let mut anys = vec![];
+        let mut count = |i: &Ident, ty: &Type, declaration: bool, implicit: bool| {
+            counts.total += 1;
+            if !ty.is_any() {
+                counts.typed += 1;
+                return;
+            }
+
+            if implicit {
+                counts.implicit_any += 1;
+            } else {
+                counts.explicit_any += 1;
+            }
+            anys.push(AnyIdent {
+                name: i.sym.to_string(),
+                span: DeclarationSpan::new(&self.cm, i.span),
+                declaration,
+                implicit,
+            });
+        };
+
+        for (i, ty, annotated) in &collector.decls {
+            count(i, ty, true, !annotated);
+        }
+        for (i, ty) in &collector.refs {
+            let implicit = if collector.local.contains(&i.to_id()) {
+                !collector.explicit.contains(&i.to_id())
+            } else {
+                ty.metadata().implicit
+            };
+            count(i, ty, false, implicit);
+        }
+
+        anys.sort_by_key(|any| any.span.start);
+
+        Some(FileCoverage {
+            file: file.to_string(),
+            counts,
+            anys,
+        })
+    }
+
+    /// Returns the type coverage of all loaded files except declaration
+    /// files.
+    pub fn type_coverage(&self) -> TypeCoverage {
+        let files = self
+            .module_graph()
+            .modules
+            .into_iter()
+            .filter(|module| !module.is_dts)
+            .filter_map(|module| self.file_coverage(&FileName::Real(module.path.into())))
+            .collect::<Vec<_>>();
+
+        let mut total = CoverageCounts::default();
+        for file in &files {
+            total.add(&file.counts);
+        }
+
+        TypeCoverage { total, files }
+    }
+}
+
+struct CoverageCollector<'a> {
+    recorder: &'a TypeRecorder,
+    /// `Some(true)` while visiting a pattern with a type annotation, and
+    /// `Some(false)` while visiting a pattern without it. [None] if patterns
+    /// are not declarations.
+    annotated: Option<bool>,
+    /// Variables declared in the file.
+    local: FxHashSet<Id>,
+    /// Variables declared with type annotations.
+    explicit: FxHashSet<Id>,
+    /// Declared identifiers with the types and whether they are annotated.
+    decls: Vec<(Ident, Type, bool)>,
+    refs: Vec<(Ident, Type)>,
+}
+
+impl CoverageCollector<'_> {
+    /// Visits `pat` as a declaration.
+    fn decl(&mut self, pat: &Pat) {
+        let annotated = replace(&mut self.annotated, Some(pat_type_ann(pat).is_some()));
+        pat.visit_with(self);
+        self.annotated = annotated;
+    }
+}
+
+impl Visit for CoverageCollector<'_> {
+    fn visit_var_declarator(&mut self, d: &VarDeclarator) {
+        self.decl(&d.name);
+        d.init.visit_with(self);
+    }
+
+    fn visit_param(&mut self, p: &Param) {
+        self.decl(&p.pat);
+    }
+
+    fn visit_arrow_expr(&mut self, f: &ArrowExpr) {
+        for param in &f.params {
+            self.decl(param);
+        }
+        f.body.visit_with(self);
+    }
+
+    fn visit_ts_param_prop(&mut self, p: &TsParamProp) {
+        match &p.param {
+            TsParamPropParam::Ident(i) => self.decl(&Pat::Ident(i.clone())),
+            TsParamPropParam::Assign(a) => self.decl(&Pat::Assign(a.clone())),
+        }
+    }
+
+    fn visit_catch_clause(&mut self, c: &CatchClause) {
+        if let Some(param) = &c.param {
+            self.decl(param);
+        }
+        c.body.visit_with(self);
+    }
+
+    fn visit_pat(&mut self, pat: &Pat) {
+        // Nested patterns are typed by the annotation of the outermost one.
+        let annotated = self.annotated.map(|annotated| annotated || pat_type_ann(pat).is_some());
+        let old = replace(&mut self.annotated, annotated);
+        pat.visit_children_with(self);
+        self.annotated = old;
+    }
+
+    fn visit_binding_ident(&mut self, i: &BindingIdent) {
+        let annotated = match self.annotated {
+            Some(annotated) => annotated,
+            None => return,
+        };
+
+        self.local.insert(i.id.to_id());
+        if annotated {
+            self.explicit.insert(i.id.to_id());
+        }
+
+        let id = stc_ts_types::Id::new(i.id.sym.clone(), i.id.span.ctxt);
+        if let Some(ty) = self.recorder.var_type(&id) {
+            self.decls.push((i.id.clone(), ty, annotated));
+        }
+    }
+
+    fn visit_assign_pat_prop(&mut self, p: &AssignPatProp) {
+        if let Some(annotated) = self.annotated {
+            self.local.insert(p.key.to_id());
+            if annotated {
+                self.explicit.insert(p.key.to_id());
+            }
+
+            let id = stc_ts_types::Id::new(p.key.sym.clone(), p.key.span.ctxt);
+            if let Some(ty) = self.recorder.var_type(&id) {
+                self.decls.push((p.key.clone(), ty, annotated));
+            }
+        }
+
+        let annotated = self.annotated.take();
+        p.value.visit_with(self);
+        self.annotated = annotated;
+    }
+
+    fn visit_expr(&mut self, e: &Expr) {
+        // Default values in patterns are not declarations.
+        let annotated = self.annotated.take();
+
+        if let Expr::Ident(i) = e {
+            if let Some(ty) = self.recorder.type_of(i.span) {
+                self.refs.push((i.clone(), ty));
+            }
+        }
+        e.visit_children_with(self);
+
+        self.annotated = annotated;
+    }
+
+    fn visit_ts_type(&mut self, _: &TsType) {}
+}
+
+fn pat_type_ann(pat: &Pat) -> Option<&TsTypeAnn> {
+    match pat {
+        Pat::Ident(i) => i.type_ann.as_deref(),
+        Pat::Array(a) => a.type_ann.as_deref(),
+        Pat::Rest(r) => r.type_ann.as_deref(),
+        Pat::Object(o) => o.type_ann.as_deref(),
+        Pat::Assign(a) => a.type_ann.as_deref(),
+        Pat::Invalid(..) | Pat::Expr(..) => None,
+    }
+}
@@ -0,0 +1,361 @@
+//! Inferred types of declarations, like the `.types` baselines of tsc.
+
+use std::{mem::replace, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_types::{printer::print_type, Type};
+use swc_common::{FileName, SourceMap, Span};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{loader::LoadModule, Checker};
+
+/// The declarations of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDeclarations {
+    pub file: String,
+    /// Sorted by the position.
+    pub declarations: Vec<DeclarationType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclarationType {
+    pub name: String,
+    /// The span of the declared name.
+    pub span: DeclarationSpan,
+    /// The inferred type, printed as a typescript type. [None] if the type is
+    /// not recorded, like for declarations in unreachable code.
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+    pub flags: SymbolFlags,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclarationSpan {
+    /// Byte offset in the file.
+    pub start: usize,
+    /// Byte offset in the file.
+    pub end: usize,
+    /// 1-based.
+    pub line: usize,
+    /// 0-based, in characters.
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolFlags {
+    pub kind: DeclarationKind,
+    /// `true` for declarations exported using `export`, including exports of
+    /// namespaces.
+    pub exported: bool,
+    /// `true` for declarations using `declare` and declarations in `.d.ts`
+    /// files.
+    pub ambient: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeclarationKind {
+    Var,
+    Let,
+    Const,
+    Function,
+    Class,
+    Interface,
+    TypeAlias,
+    Enum,
+    Namespace,
+    Parameter,
+    Import,
+}
+
+impl DeclarationSpan {
+    pub(crate) fn new(cm: &SourceMap, span: Span) -> Self {
+        let loc = cm.lookup_char_pos(span.lo);
+        let start = (span.lo - loc.file.start_pos).0 as usize;
+
+        DeclarationSpan {
+            start,
+            end: start + (span.hi - span.lo).0 as usize,
+            line: loc.line,
+            column: loc.col_display,
+        }
+    }
+}
+
+impl FileDeclarations {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the declarations in `file` and their inferred types.
+    ///
+    /// Declarations in nested scopes are included. Returns [None] if types
+    /// are not recorded (see [Checker::with_type_recorder]), or `file` is not
+    /// loaded.
+    pub fn declaration_types(&self, file: &FileName) -> Option<FileDeclarations> {
+        let recorder = self.type_recorder.as_ref()?;
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        let mut collector = DeclCollector {
+            cm: &self.cm,
+            recorder,
+            kind: None,
+            exported: false,
+            ambient: record.is_dts,
+            declarations: vec![],
+        };
+        record.ast.visit_with(&mut collector);
+
+        let mut declarations = collector.declarations;
+        declarations.sort_by_key(|decl| decl.span.start);
+
+        Some(FileDeclarations {
+            file: file.to_string(),
+            declarations,
+        })
+    }
+
+    /// Returns the declarations of all loaded modules, sorted by the path.
+    pub fn all_declaration_types(&self) -> Vec<FileDeclarations> {
+        self.module_graph()
+            .modules
+            .into_iter()
+            .filter_map(|module| self.declaration_types(&FileName::Real(module.path.into())))
+            .collect()
+    }
+}
+
+/// Returns the recorded type of the declaration named `i`.
+pub(crate) fn declared_type(recorder: &TypeRecorder, i: &Ident, kind: DeclarationKind) -> Option<Type> {
+    let id = stc_ts_types::Id::new(i.sym.clone(), i.span.ctxt);
+
+    match kind {
+        DeclarationKind::Interface | DeclarationKind::TypeAlias => recorder.type_decl(&id),
+        DeclarationKind::Namespace | DeclarationKind::Import => recorder.var_type(&id).or_else(|| recorder.type_decl(&id)),
+        _ => recorder.var_type(&id),
+    }
+}
+
+struct DeclCollector<'a> {
+    cm: &'a SourceMap,
+    recorder: &'a TypeRecorder,
+    /// The kind of binding identifiers, which is [None] if they are not
+    /// declarations, like in assignments.
+    kind: Option<DeclarationKind>,
+    exported: bool,
+    ambient: bool,
+    declarations: Vec<DeclarationType>,
+}
+
+impl DeclCollector<'_> {
+    fn add(&mut self, i: &Ident, kind: DeclarationKind) {
+        let ty = declared_type(self.recorder, i, kind);
+
+        self.declarations.push(DeclarationType {
+            name: i.sym.to_string(),
+            span: DeclarationSpan::new(self.cm, i.span),
+            ty: ty.map(|ty| print_type(&ty)),
+            flags: SymbolFlags {
+                kind,
+                exported: self.exported,
+                ambient: self.ambient,
+            },
+        });
+    }
+
+    /// Visits the contents of a declaration, which are not exported.
+    fn nested(&mut self, op: impl FnOnce(&mut Self)) {
+        let exported = replace(&mut self.exported, false);
+        let kind = self.kind.take();
+
+        op(self);
+
+        self.exported = exported;
+        self.kind = kind;
+    }
+
+    /// Visits patterns in `op` as declarations of `kind`.
+    fn with_kind(&mut self, kind: DeclarationKind, op: impl FnOnce(&mut Self)) {
+        let old = replace(&mut self.kind, Some(kind));
+
+        op(self);
+
+        self.kind = old;
+    }
+}
+
+impl Visit for DeclCollector<'_> {
+    fn visit_export_decl(&mut self, e: &ExportDecl) {
+        self.exported = true;
+        e.decl.visit_with(self);
+        self.exported = false;
+    }
+
+    fn visit_export_default_decl(&mut self, e: &ExportDefaultDecl) {
+        self.exported = true;
+        e.decl.visit_with(self);
+        self.exported = false;
+    }
+
+    fn visit_var_decl(&mut self, v: &VarDecl) {
+        let kind = match v.kind {
+            VarDeclKind::Var => DeclarationKind::Var,
+            VarDeclKind::Let => DeclarationKind::Let,
+            VarDeclKind::Const => DeclarationKind::Const,
+        };
+        let ambient = replace(&mut self.ambient, self.ambient || v.declare);
+
+        for decl in &v.decls {
+            self.with_kind(kind, |c| decl.name.visit_with(c));
+            self.nested(|c| decl.init.visit_with(c));
+        }
+
+        self.ambient = ambient;
+    }
+
+    fn visit_binding_ident(&mut self, i: &BindingIdent) {
+        if let Some(kind) = self.kind {
+            self.add(&i.id, kind);
+        }
+        i.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_type_ann(&mut self, t: &TsTypeAnn) {
+        self.nested(|c| t.visit_children_with(c));
+    }
+
+    fn visit_assign_pat_prop(&mut self, p: &AssignPatProp) {
+        if let Some(kind) = self.kind {
+            self.add(&p.key, kind);
+        }
+        self.nested(|c| p.value.visit_with(c));
+    }
+
+    fn visit_assign_pat(&mut self, p: &AssignPat) {
+        p.left.visit_with(self);
+        p.type_ann.visit_with(self);
+        self.nested(|c| p.right.visit_with(c));
+    }
+
+    fn visit_key_value_pat_prop(&mut self, p: &KeyValuePatProp) {
+        self.nested(|c| p.key.visit_with(c));
+        p.value.visit_with(self);
+    }
+
+    fn visit_param(&mut self, p: &Param) {
+        self.nested(|c| p.decorators.visit_with(c));
+        self.with_kind(DeclarationKind::Parameter, |c| p.pat.visit_with(c));
+    }
+
+    fn visit_ts_param_prop(&mut self, p: &TsParamProp) {
+        self.nested(|c| p.decorators.visit_with(c));
+        self.with_kind(DeclarationKind::Parameter, |c| p.param.visit_with(c));
+    }
+
+    fn visit_arrow_expr(&mut self, f: &ArrowExpr) {
+        self.nested(|c| {
+            c.with_kind(DeclarationKind::Parameter, |c| f.params.visit_with(c));
+            f.body.visit_with(c);
+            f.type_params.visit_with(c);
+            f.return_type.visit_with(c);
+        });
+    }
+
+    fn visit_fn_decl(&mut self, f: &FnDecl) {
+        let ambient = replace(&mut self.ambient, self.ambient || f.declare);
+        self.add(&f.ident, DeclarationKind::Function);
+        self.nested(|c| f.function.visit_with(c));
+        self.ambient = ambient;
+    }
+
+    fn visit_fn_expr(&mut self, f: &FnExpr) {
+        if let Some(ident) = &f.ident {
+            self.add(ident, DeclarationKind::Function);
+        }
+        self.nested(|c| f.function.visit_with(c));
+    }
+
+    fn visit_class_decl(&mut self, c: &ClassDecl) {
+        let ambient = replace(&mut self.ambient, self.ambient || c.declare);
+        self.add(&c.ident, DeclarationKind::Class);
+        self.nested(|v| c.class.visit_with(v));
+        self.ambient = ambient;
+    }
+
+    fn visit_class_expr(&mut self, c: &ClassExpr) {
+        if let Some(ident) = &c.ident {
+            self.add(ident, DeclarationKind::Class);
+        }
+        self.nested(|v| c.class.visit_with(v));
+    }
+
+    fn visit_ts_interface_decl(&mut self, d: &TsInterfaceDecl) {
+        let ambient = replace(&mut self.ambient, self.ambient || d.declare);
+        self.add(&d.id, DeclarationKind::Interface);
+        self.nested(|c| d.body.visit_with(c));
+        self.ambient = ambient;
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, d: &TsTypeAliasDecl) {
+        let ambient = replace(&mut self.ambient, self.ambient || d.declare);
+        self.add(&d.id, DeclarationKind::TypeAlias);
+        self.nested(|c| d.type_ann.visit_with(c));
+        self.ambient = ambient;
+    }
+
+    fn visit_ts_enum_decl(&mut self, d: &TsEnumDecl) {
+        let ambient = replace(&mut self.ambient, self.ambient || d.declare);
+        self.add(&d.id, DeclarationKind::Enum);
+        self.nested(|c| d.members.visit_with(c));
+        self.ambient = ambient;
+    }
+
+    fn visit_ts_module_decl(&mut self, d: &TsModuleDecl) {
+        let ambient = replace(&mut self.ambient, self.ambient || d.declare);
+        if let TsModuleName::Ident(id) = &d.id {
+            self.add(id, DeclarationKind::Namespace);
+        }
+        self.nested(|c| d.body.visit_with(c));
+        self.ambient = ambient;
+    }
+
+    fn visit_ts_namespace_decl(&mut self, d: &TsNamespaceDecl) {
+        let ambient = replace(&mut self.ambient, self.ambient || d.declare);
+        self.add(&d.id, DeclarationKind::Namespace);
+        self.nested(|c| d.body.visit_with(c));
+        self.ambient = ambient;
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, d: &TsImportEqualsDecl) {
+        self.exported = d.is_export;
+        self.add(&d.id, DeclarationKind::Import);
+        self.exported = false;
+    }
+
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        for specifier in &import.specifiers {
+            let local = match specifier {
+                ImportSpecifier::Named(named) => &named.local,
+                ImportSpecifier::Default(default) => &default.local,
+                ImportSpecifier::Namespace(ns) => &ns.local,
+            };
+
+            self.add(local, DeclarationKind::Import);
+        }
+    }
+}
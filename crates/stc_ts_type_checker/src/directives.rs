@@ -0,0 +1,170 @@
+//! Comment directives of tsc, which suppress errors.
+//!
+//!  - `// @ts-ignore` suppresses errors on the next line.
+//!  - `// @ts-expect-error` suppresses errors on the next line, and it's
+//!    reported if there's no error to suppress (TS2578).
+//!  - `// @ts-nocheck` before the first statement suppresses all errors of the
+//!    file.
+
+use stc_ts_errors::{Error, ErrorKind};
+use swc_common::{
+    comments::{Comment, CommentKind, SingleThreadedComments},
+    SourceFile, Span, Spanned,
+};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_parser::{lexer::Lexer, EsConfig, StringInput, Syntax, TsConfig};
+
+use crate::loader::ModuleRecord;
+
+#[derive(Debug, Default)]
+pub(crate) struct Directives {
+    no_check: bool,
+    directives: Vec<Directive>,
+}
+
+#[derive(Debug)]
+struct Directive {
+    /// The span of the comment.
+    span: Span,
+    /// 0-based line of the end of the comment. Errors in the lines after it are
+    /// suppressed.
+    line: usize,
+    expect_error: bool,
+}
+
+impl Directives {
+    /// Finds the directives in the comments of `record`, whose source is `fm`.
+    pub fn parse(fm: &SourceFile, record: &ModuleRecord) -> Self {
+        // Comments are collected by lexing the file again, which is not worth it
+        // for most files.
+        if !fm.src.contains("@ts-") {
+            return Default::default();
+        }
+
+        let syntax = if record.is_js {
+            Syntax::Es(EsConfig {
+                jsx: true,
+                ..Default::default()
+            })
+        } else {
+            Syntax::Typescript(TsConfig {
+                tsx: record.filename.to_string().ends_with(".tsx"),
+                dts: record.is_dts,
+                ..Default::default()
+            })
+        };
+
+        let comments = SingleThreadedComments::default();
+        let mut lexer = Lexer::new(syntax, EsVersion::latest(), StringInput::from(fm), Some(&comments));
+        let first_token = lexer.next().map(|token| token.span.lo);
+        lexer.for_each(drop);
+
+        let (leading, trailing) = comments.take_all();
+        let mut found = leading
+            .take()
+            .into_iter()
+            .chain(trailing.take())
+            .flat_map(|(_, comments)| comments)
+            .collect::<Vec<_>>();
+        found.sort_by_key(|comment| comment.span.lo);
+
+        let mut directives = Directives::default();
+        for comment in found {
+            let text = directive_text(&comment);
+
+            if text.starts_with("@ts-nocheck") {
+                if first_token.map_or(true, |first| comment.span.hi <= first) {
+                    directives.no_check = true;
+                }
+                continue;
+            }
+
+            let expect_error = text.starts_with("@ts-expect-error");
+            if expect_error || text.starts_with("@ts-ignore") {
+                if let Some(line) = fm.lookup_line(comment.span.hi) {
+                    directives.directives.push(Directive {
+                        span: comment.span,
+                        line,
+                        expect_error,
+                    });
+                }
+            }
+        }
+
+        directives
+    }
+
+    /// Removes the errors suppressed by the directives from `errors`, which are
+    /// the errors of the file `fm`, and adds the errors of unused
+    /// `@ts-expect-error` directives.
+    pub fn apply(&self, fm: &SourceFile, errors: Vec<Error>) -> Vec<Error> {
+        if self.no_check {
+            return vec![];
+        }
+        if self.directives.is_empty() {
+            return errors;
+        }
+
+        let mut used = vec![false; self.directives.len()];
+        let mut errors = errors
+            .into_iter()
+            .filter(|err| match self.suppressing_directive(fm, err.span()) {
+                Some(idx) => {
+                    used[idx] = true;
+                    false
+                }
+                None => true,
+            })
+            .collect::<Vec<_>>();
+
+        errors.extend(
+            self.directives
+                .iter()
+                .zip(used)
+                .filter(|(directive, used)| directive.expect_error && !used)
+                .map(|(directive, _)| ErrorKind::UnusedTsExpectError { span: directive.span }.into()),
+        );
+
+        errors
+    }
+
+    /// Like tsc, a directive suppresses errors in the next line, but empty
+    /// lines and lines with only comments are skipped.
+    fn suppressing_directive(&self, fm: &SourceFile, span: Span) -> Option<usize> {
+        if span.is_dummy() || span.lo < fm.start_pos || span.lo > fm.end_pos {
+            return None;
+        }
+
+        let mut line = fm.lookup_line(span.lo)?;
+        while line > 0 {
+            line -= 1;
+
+            if let Some(idx) = self.directives.iter().position(|directive| directive.line == line) {
+                return Some(idx);
+            }
+
+            let text = fm.get_line(line)?;
+            let text = text.trim();
+            if !text.is_empty() && !text.starts_with("//") {
+                return None;
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the text of `comment` where a directive may start, which is after
+/// `//` or `///` for line comments, and the last line without leading `*`s
+/// for block comments.
+fn directive_text(comment: &Comment) -> &str {
+    match comment.kind {
+        CommentKind::Line => comment.text.strip_prefix('/').unwrap_or(&comment.text).trim_start(),
+        CommentKind::Block => comment
+            .text
+            .lines()
+            .last()
+            .unwrap_or_default()
+            .trim_start_matches(|c: char| c == '*' || c == '/' || c.is_whitespace()),
+    }
+}
@@ -0,0 +1,88 @@
+//! Types of declaration files shared between checkers.
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use fxhash::FxBuildHasher;
+use parking_lot::RwLock;
+use stc_ts_types::{ModuleId, Type};
+use swc_common::FileName;
+
+use crate::{
+    loader::{LoadModule, ModuleRecord},
+    Checker,
+};
+
+/// Types of analyzed declaration files, which are reused by the checkers
+/// sharing this cache instead of analyzing the files again.
+///
+/// Checkers sharing a cache should use the same module loader, like an
+/// [Arc] of it, and clones of the same [stc_ts_env::Env], because global
+/// declarations are stored in the env. Builtin libraries are already shared
+/// by clones of an env.
+///
+/// Errors in a cached file are reported only by the checker which analyzed
+/// it.
+///
+/// Clones share the cached types.
+#[derive(Clone, Default)]
+pub struct DtsCache {
+    types: Arc<DashMap<Arc<FileName>, Type, FxBuildHasher>>,
+    /// Ambient modules, which may be declared by cached files.
+    pub(crate) declared_modules: Arc<RwLock<Vec<(ModuleId, Type)>>>,
+}
+
+impl DtsCache {
+    /// The number of cached files.
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the type of the entry of `modules`, which are the modules in a
+    /// cycle, if all of them are cached declaration files.
+    pub(crate) fn cached_dts_module(&self, modules: &[Arc<ModuleRecord>], entry: ModuleId) -> Option<Type> {
+        let cache = self.dts_cache.as_ref()?;
+
+        let types = modules
+            .iter()
+            .map(|record| {
+                if !record.is_dts {
+                    return None;
+                }
+
+                cache.types.get(&record.filename).map(|ty| (record.id, ty.value().clone()))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut lock = self.module_types.write();
+        for (id, ty) in &types {
+            let _ = lock.entry(*id).or_default().set(ty.clone());
+        }
+
+        types.into_iter().find(|(id, _)| *id == entry).map(|(_, ty)| ty)
+    }
+
+    /// Stores the types of the declaration files in `modules`, which are
+    /// analyzed.
+    pub(crate) fn cache_dts_modules(&self, modules: &[Arc<ModuleRecord>]) {
+        let cache = match &self.dts_cache {
+            Some(cache) => cache,
+            None => return,
+        };
+
+        for record in modules.iter().filter(|record| record.is_dts) {
+            if let Some(ty) = self.get_types(record.id) {
+                cache.types.insert(record.filename.clone(), ty);
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Transpilation of checked typescript files to javascript.
+
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{Context, Result};
+use rnode::RNode;
+use stc_ts_env::ModuleConfig;
+use stc_ts_utils::StcComments;
+use swc_common::{chain, comments::Comments, FileName};
+use swc_ecma_ast::Program as AstProgram;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+use swc_ecma_transforms::{
+    modules::{amd, common_js, system_js, umd},
+    typescript::strip,
+};
+use swc_ecma_transforms_base::{
+    feature::FeatureFlag,
+    fixer::fixer,
+    helpers::{inject_helpers, Helpers, HELPERS},
+    hygiene::hygiene,
+};
+use swc_ecma_visit::{noop_fold, Fold, FoldWith};
+
+use crate::{
+    loader::{LoadModule, ModuleRecord},
+    program::Program,
+    Checker,
+};
+
+/// A file created from a typescript file by [Checker::emit] or
+/// [Checker::emit_declarations].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedFile {
+    /// The typescript file.
+    pub source: Arc<FileName>,
+    /// The path of the emitted file, which is the path of `source` with the
+    /// extension replaced, like `.js` or `.d.ts` for `.ts`.
+    pub path: PathBuf,
+    pub code: String,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Transpiles the loaded typescript files to javascript, using the module
+    /// format and the target of the [Env](stc_ts_env::Env).
+    ///
+    /// Types are removed, and imports and exports are converted for
+    /// `commonjs`, `amd`, `umd` and `system`. ES modules are emitted for the
+    /// other module formats. Syntax newer than the target is not converted,
+    /// and JSX is preserved, so `.tsx` files are emitted as `.jsx` files.
+    ///
+    /// Declaration files, javascript files and JSON modules are not emitted.
+    /// The result is sorted by the path.
+    pub fn emit(&self) -> Result<Vec<EmittedFile>> {
+        let mut files = vec![];
+
+        for module in self.module_graph().modules {
+            if module.is_dts {
+                continue;
+            }
+
+            let source = PathBuf::from(&module.path);
+            let ext = match source.extension().and_then(|ext| ext.to_str()) {
+                Some("ts") => "js",
+                Some("tsx") => "jsx",
+                Some("mts") => "mjs",
+                Some("cts") => "cjs",
+                _ => continue,
+            };
+
+            let filename = Arc::new(FileName::Real(source.clone()));
+            let records = self
+                .module_loader
+                .load_module(&filename, false)
+                .with_context(|| format!("failed to load `{}`", filename))?;
+            let record = match records.modules.iter().find(|record| record.filename == filename) {
+                Some(record) if !record.is_js => record,
+                _ => continue,
+            };
+
+            let code = self
+                .emit_module(record, &records.comments)
+                .with_context(|| format!("failed to emit `{}`", filename))?;
+
+            files.push(EmittedFile {
+                source: filename,
+                path: source.with_extension(ext),
+                code,
+            });
+        }
+
+        Ok(files)
+    }
+
+    /// Generates declaration files for the checked typescript files, like
+    /// `.d.ts` files for `.ts` files.
+    ///
+    /// The path of each file is the path of the source file with the extension
+    /// replaced. Files which are not checked by `self` are not emitted. The
+    /// result is sorted by the path.
+    pub fn emit_declarations(&self) -> Result<Vec<EmittedFile>> {
+        let mut files = vec![];
+
+        for module in self.module_graph().modules {
+            if module.is_dts {
+                continue;
+            }
+
+            let source = PathBuf::from(&module.path);
+            let ext = match source.extension().and_then(|ext| ext.to_str()) {
+                Some("ts" | "tsx") => "d.ts",
+                Some("mts") => "d.mts",
+                Some("cts") => "d.cts",
+                _ => continue,
+            };
+
+            let filename = Arc::new(FileName::Real(source.clone()));
+            let records = self
+                .module_loader
+                .load_module(&filename, false)
+                .with_context(|| format!("failed to load `{}`", filename))?;
+            let dts = match records.modules.iter().find(|record| record.filename == filename) {
+                Some(record) if !record.is_js => match self.dts_modules.get(&record.id) {
+                    Some(dts) => dts.clone().into_orig(),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let mut buf = vec![];
+            {
+                let mut emitter = Emitter {
+                    cfg: swc_ecma_codegen::Config {
+                        target: self.env.target(),
+                        ..Default::default()
+                    },
+                    cm: self.cm.clone(),
+                    comments: Some(&records.comments),
+                    wr: JsWriter::new(self.cm.clone(), "\n", &mut buf, None),
+                };
+
+                emitter
+                    .emit_module(&dts)
+                    .with_context(|| format!("failed to write declarations of `{}`", filename))?;
+            }
+
+            files.push(EmittedFile {
+                source: filename,
+                path: source.with_extension(ext),
+                code: String::from_utf8(buf).context("emitted code is not valid utf-8")?,
+            });
+        }
+
+        Ok(files)
+    }
+
+    /// Checks `entries` like [Checker::check_all], and transpiles the checked
+    /// files like [Checker::emit] if there's no error.
+    ///
+    /// The emitted files are [None] if an error is found.
+    pub fn check_and_emit(&self, entries: Vec<Arc<FileName>>) -> Result<(Program, Option<Vec<EmittedFile>>)> {
+        let program = self.check_all(entries);
+        if program.errors().next().is_some() {
+            return Ok((program, None));
+        }
+
+        let files = self.emit()?;
+        Ok((program, Some(files)))
+    }
+
+    fn emit_module(&self, record: &ModuleRecord, comments: &StcComments) -> Result<String> {
+        let unresolved_mark = self.env.shared().marks().unresolved_mark();
+        let top_level_mark = record.top_level_ctxt.outer();
+
+        let module: Box<dyn Fold> = match self.env.module() {
+            ModuleConfig::CommonJs => Box::new(common_js(
+                unresolved_mark,
+                Default::default(),
+                FeatureFlag::empty(),
+                Some(comments.clone()),
+            )),
+            ModuleConfig::Amd => Box::new(amd(
+                unresolved_mark,
+                Default::default(),
+                FeatureFlag::empty(),
+                Some(comments.clone()),
+            )),
+            ModuleConfig::Umd => Box::new(umd(
+                self.cm.clone(),
+                unresolved_mark,
+                Default::default(),
+                FeatureFlag::empty(),
+                Some(comments.clone()),
+            )),
+            ModuleConfig::System => Box::new(system_js(unresolved_mark, Default::default())),
+            _ => Box::new(noop_fold()),
+        };
+
+        // Helpers used by the module transforms are inlined.
+        let program = HELPERS.set(&Helpers::new(false), || {
+            AstProgram::Module(record.ast.clone()).fold_with(&mut chain!(
+                strip(top_level_mark),
+                module,
+                inject_helpers(),
+                hygiene(),
+                fixer(Some(comments as &dyn Comments))
+            ))
+        });
+
+        let mut buf = vec![];
+        {
+            let mut emitter = Emitter {
+                cfg: swc_ecma_codegen::Config {
+                    target: self.env.target(),
+                    ..Default::default()
+                },
+                cm: self.cm.clone(),
+                comments: Some(comments),
+                wr: JsWriter::new(self.cm.clone(), "\n", &mut buf, None),
+            };
+
+            emitter.emit_program(&program).context("failed to write javascript")?;
+        }
+
+        String::from_utf8(buf).context("emitted code is not valid utf-8")
+    }
+}
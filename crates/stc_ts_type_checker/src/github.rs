@@ -0,0 +1,45 @@
+//! [Workflow commands](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions)
+//! of GitHub Actions, which annotate pull requests with errors.
+
+use std::path::Path;
+
+use stc_ts_errors::Severity;
+
+use crate::program::Diagnostic;
+
+/// Returns an `::error` command for `diagnostic`, or a `::warning` or a
+/// `::notice` command if it's a warning or an info.
+///
+/// Paths in `root` are relative to it, so `root` should be the root of the
+/// repository.
+pub fn error_command(diagnostic: &Diagnostic, root: &Path) -> String {
+    let mut properties = vec![];
+
+    if let Some(file) = &diagnostic.file {
+        let path = Path::new(file);
+        let path = path.strip_prefix(root).unwrap_or(path);
+
+        properties.push(format!("file={}", escape_property(&path.display().to_string().replace('\\', "/"))));
+        properties.push(format!("line={}", diagnostic.line));
+        properties.push(format!("col={}", diagnostic.column + 1));
+        properties.push(format!("endLine={}", diagnostic.end_line));
+        properties.push(format!("endColumn={}", diagnostic.end_column + 1));
+    }
+    properties.push(format!("title=TS{}", diagnostic.code));
+
+    let command = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice",
+    };
+
+    format!("::{} {}::{}", command, properties.join(","), escape_data(&diagnostic.message))
+}
+
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
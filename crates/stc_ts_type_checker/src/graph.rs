@@ -0,0 +1,324 @@
+//! The module graph, which is built while loading modules.
+
+use std::{collections::VecDeque, fmt::Write};
+
+use fxhash::{FxHashMap, FxHashSet};
+use petgraph::{algo::tarjan_scc, graphmap::DiGraphMap};
+use serde::{Deserialize, Serialize};
+
+/// Files and imports between them.
+///
+/// Files are identified by their names, which is the path for files on the
+/// disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleGraph {
+    /// Sorted by path.
+    pub modules: Vec<ModuleNode>,
+    /// Sorted by the importing file, and then by the order in the file.
+    pub imports: Vec<ImportEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleNode {
+    pub path: String,
+    pub is_dts: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportEdge {
+    /// The importing file.
+    pub from: String,
+    /// The module specifier as written in the importing file.
+    pub specifier: String,
+    /// [None] if the module specifier cannot be resolved.
+    pub resolved: Option<String>,
+    pub kind: ImportKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportKind {
+    /// `import`, `export ... from` or `import foo = require()`.
+    Import,
+    /// `/// <reference path="..." />` or `/// <reference types="..." />`.
+    Reference,
+}
+
+impl ModuleGraph {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+
+    /// Serializes the graph in the DOT language of graphviz.
+    ///
+    /// Unresolved imports are omitted.
+    pub fn to_dot(&self) -> String {
+        let mut buf = String::new();
+
+        buf.push_str("digraph modules {\n");
+        for module in &self.modules {
+            let _ = writeln!(buf, "    {};", quote(&module.path));
+        }
+        for import in &self.imports {
+            if let Some(resolved) = &import.resolved {
+                let style = match import.kind {
+                    ImportKind::Import => "",
+                    ImportKind::Reference => ", style=dashed",
+                };
+                let _ = writeln!(
+                    buf,
+                    "    {} -> {} [label={}{}];",
+                    quote(&import.from),
+                    quote(resolved),
+                    quote(&import.specifier),
+                    style
+                );
+            }
+        }
+        buf.push_str("}\n");
+
+        buf
+    }
+
+    /// Returns the files which depend on any of `changed`, directly or
+    /// indirectly, including `changed` itself. The result is sorted.
+    ///
+    /// This is the set of files which should be checked again after `changed`
+    /// is modified.
+    pub fn affected_files(&self, changed: &[&str]) -> Vec<String> {
+        let mut dependents: FxHashMap<&str, Vec<&str>> = FxHashMap::default();
+        for import in &self.imports {
+            if let Some(resolved) = &import.resolved {
+                dependents.entry(resolved).or_default().push(&import.from);
+            }
+        }
+
+        let mut done = FxHashSet::default();
+        let mut queue = changed.to_vec();
+        while let Some(file) = queue.pop() {
+            if !done.insert(file) {
+                continue;
+            }
+
+            if let Some(files) = dependents.get(file) {
+                queue.extend(files.iter().copied());
+            }
+        }
+
+        let mut files = done.into_iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    /// Returns cycles of imports. References are ignored.
+    ///
+    /// One cycle is returned for each group of files which depend on each
+    /// other, even if the group contains multiple cycles. Each cycle is the
+    /// list of files in it, starting from the smallest path, and the result is
+    /// sorted.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut graph = DiGraphMap::<&str, ()>::new();
+        for import in &self.imports {
+            if let (ImportKind::Import, Some(resolved)) = (import.kind, &import.resolved) {
+                graph.add_edge(import.from.as_str(), resolved.as_str(), ());
+            }
+        }
+
+        let mut cycles = tarjan_scc(&graph)
+            .into_iter()
+            .filter(|scc| scc.len() > 1 || graph.contains_edge(scc[0], scc[0]))
+            .map(|scc| shortest_cycle(&graph, &scc))
+            .collect::<Vec<_>>();
+        cycles.sort();
+        cycles
+    }
+
+    /// Returns the imports which are part of a cycle and the files they
+    /// connect. References are ignored, like [ModuleGraph::cycles].
+    pub fn cycle_graph(&self) -> ModuleGraph {
+        let mut graph = DiGraphMap::<&str, ()>::new();
+        for import in &self.imports {
+            if let (ImportKind::Import, Some(resolved)) = (import.kind, &import.resolved) {
+                graph.add_edge(import.from.as_str(), resolved.as_str(), ());
+            }
+        }
+
+        let mut components = FxHashMap::default();
+        for (i, scc) in tarjan_scc(&graph).into_iter().enumerate() {
+            for file in scc {
+                components.insert(file, i);
+            }
+        }
+
+        // An import is in a cycle if the imported file imports the importing file
+        // directly or indirectly.
+        let in_cycle = |import: &ImportEdge| match (import.kind, &import.resolved) {
+            (ImportKind::Import, Some(resolved)) => components.get(&*import.from) == components.get(&**resolved),
+            _ => false,
+        };
+        let files = self
+            .imports
+            .iter()
+            .filter(|import| in_cycle(import))
+            .map(|import| &*import.from)
+            .collect::<FxHashSet<_>>();
+
+        self.subgraph(&files, in_cycle)
+    }
+
+    /// Returns the files on any path of imports from `from` to `to`, including
+    /// both, and the imports between them. The graph is empty if `to` is not
+    /// reachable from `from`.
+    pub fn paths_between(&self, from: &str, to: &str) -> ModuleGraph {
+        let mut dependencies: FxHashMap<&str, Vec<&str>> = FxHashMap::default();
+        let mut dependents: FxHashMap<&str, Vec<&str>> = FxHashMap::default();
+        for import in &self.imports {
+            if let Some(resolved) = &import.resolved {
+                dependencies.entry(&import.from).or_default().push(resolved);
+                dependents.entry(resolved).or_default().push(&import.from);
+            }
+        }
+
+        let files = reachable(&dependencies, from)
+            .intersection(&reachable(&dependents, to))
+            .copied()
+            .collect::<FxHashSet<_>>();
+
+        self.subgraph(&files, |import| match &import.resolved {
+            Some(resolved) => files.contains(&*import.from) && files.contains(&**resolved),
+            None => false,
+        })
+    }
+
+    /// Prints the files imported by `roots` as a tree, like `cargo tree`.
+    ///
+    /// Files which are already printed are marked with `(*)` and their imports
+    /// are not printed again, so cycles are printed once.
+    pub fn to_tree(&self, roots: &[&str]) -> String {
+        let mut imports: FxHashMap<&str, Vec<&ImportEdge>> = FxHashMap::default();
+        for import in &self.imports {
+            imports.entry(&import.from).or_default().push(import);
+        }
+
+        let mut buf = String::new();
+        let mut printed = FxHashSet::default();
+        for root in roots {
+            if printed.insert(*root) {
+                let _ = writeln!(buf, "{}", root);
+                print_tree(&mut buf, &imports, &mut printed, root, "");
+            } else {
+                let _ = writeln!(buf, "{} (*)", root);
+            }
+        }
+
+        buf
+    }
+
+    fn subgraph(&self, files: &FxHashSet<&str>, keep: impl Fn(&ImportEdge) -> bool) -> ModuleGraph {
+        ModuleGraph {
+            modules: self
+                .modules
+                .iter()
+                .filter(|module| files.contains(&*module.path))
+                .cloned()
+                .collect(),
+            imports: self.imports.iter().filter(|import| keep(import)).cloned().collect(),
+        }
+    }
+}
+
+fn print_tree<'a>(
+    buf: &mut String,
+    imports: &FxHashMap<&str, Vec<&'a ImportEdge>>,
+    printed: &mut FxHashSet<&'a str>,
+    file: &str,
+    prefix: &str,
+) {
+    let children = match imports.get(file) {
+        Some(children) => children,
+        None => return,
+    };
+
+    for (i, import) in children.iter().enumerate() {
+        let last = i == children.len() - 1;
+        let (branch, indent) = if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        let reference = match import.kind {
+            ImportKind::Import => "",
+            ImportKind::Reference => " (reference)",
+        };
+
+        match &import.resolved {
+            Some(resolved) => {
+                let new = printed.insert(resolved);
+                let repeated = if new { "" } else { " (*)" };
+                let _ = writeln!(buf, "{}{}{}{}{}", prefix, branch, resolved, reference, repeated);
+                if new {
+                    print_tree(buf, imports, printed, resolved, &format!("{}{}", prefix, indent));
+                }
+            }
+            None => {
+                let _ = writeln!(buf, "{}{}{}{} (unresolved)", prefix, branch, import.specifier, reference);
+            }
+        }
+    }
+}
+
+/// Returns the files reachable from `start` using `edges`, including `start`.
+fn reachable<'a>(edges: &FxHashMap<&'a str, Vec<&'a str>>, start: &'a str) -> FxHashSet<&'a str> {
+    let mut done = FxHashSet::default();
+    let mut queue = vec![start];
+    while let Some(file) = queue.pop() {
+        if !done.insert(file) {
+            continue;
+        }
+
+        if let Some(files) = edges.get(file) {
+            queue.extend(files.iter().copied());
+        }
+    }
+
+    done
+}
+
+/// Finds the shortest cycle from the smallest path in `scc`, which is a
+/// strongly connected component of `graph`.
+fn shortest_cycle(graph: &DiGraphMap<&str, ()>, scc: &[&str]) -> Vec<String> {
+    let start = *scc.iter().min().unwrap();
+    let scc = scc.iter().copied().collect::<FxHashSet<_>>();
+
+    // Breadth-first search from `start` to `start`.
+    let mut prev = FxHashMap::default();
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for next in graph.neighbors(node) {
+            if next == start {
+                let mut cycle = vec![node.to_string()];
+                let mut cur = node;
+                while cur != start {
+                    cur = prev[cur];
+                    cycle.push(cur.to_string());
+                }
+                cycle.reverse();
+                return cycle;
+            }
+
+            if scc.contains(next) && !prev.contains_key(next) {
+                prev.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    unreachable!("strongly connected component without a cycle: {:?}", scc)
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
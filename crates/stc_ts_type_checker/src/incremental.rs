@@ -0,0 +1,142 @@
+//! Re-checking files after changes.
+
+use std::{
+    mem::{replace, take},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use fxhash::FxHashSet;
+use stc_ts_errors::Error;
+use swc_common::FileName;
+use tracing::warn;
+
+use crate::{graph::ModuleGraph, loader::LoadModule, Checker};
+
+/// Diagnostics of the files re-checked by [Checker::invalidate_file].
+#[derive(Debug, Default)]
+pub struct DiagnosticsDelta {
+    /// Sorted by the path. The errors of each file replace the previous
+    /// errors of the file.
+    pub files: Vec<FileDiagnostics>,
+}
+
+#[derive(Debug)]
+pub struct FileDiagnostics {
+    pub file: Arc<FileName>,
+    pub errors: Vec<Error>,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Drops the cached analysis of the file at `path`, which is changed, and
+    /// re-checks it and the modules which depend on it.
+    ///
+    /// The errors of the re-checked files are returned instead of being
+    /// stored, and their previous errors which are not taken yet are
+    /// discarded. A file which is not loaded yet is not checked.
+    ///
+    /// If the file is created or deleted, cached results of the module
+    /// resolver should be invalidated before calling this.
+    pub fn invalidate_file(&self, path: &Path) -> DiagnosticsDelta {
+        let changed = Arc::new(FileName::Real(path.to_path_buf()));
+        let graph = self.module_graph();
+        if !graph.modules.iter().any(|module| module.path == changed.to_string()) {
+            return Default::default();
+        }
+
+        let affected = dependents(&graph, &changed.to_string())
+            .into_iter()
+            .map(|path| Arc::new(FileName::Real(PathBuf::from(path))))
+            .collect::<Vec<_>>();
+
+        for file in &affected {
+            let id = match self.module_loader.load_module(file, false) {
+                Ok(records) => records.entry.id,
+                Err(err) => {
+                    warn!("Failed to load `{}`: {:?}", file, err);
+                    continue;
+                }
+            };
+
+            self.module_types.write().remove(&id);
+            self.dts_modules.remove(&id);
+            self.started.remove(&id);
+        }
+        self.module_loader.invalidate(&changed);
+
+        let is_affected = |err: &Error| match self.file_of_error(err) {
+            Some(filename) => affected.iter().any(|file| **file == filename),
+            None => false,
+        };
+        let pending = {
+            let mut errors = self.errors.lock();
+            errors.retain(|err| !is_affected(err));
+            take(&mut *errors)
+        };
+
+        // The file may be deleted.
+        let is_loaded = match self.module_loader.load_module(&changed, true) {
+            Ok(..) => true,
+            Err(err) => {
+                warn!("Failed to load `{}`: {:?}", changed, err);
+                false
+            }
+        };
+
+        for file in &affected {
+            if *file != changed || is_loaded {
+                self.analyze_module(None, file.clone());
+            }
+        }
+
+        let new_errors = self.env.severity_overrides().apply(replace(&mut *self.errors.lock(), pending));
+
+        let mut delta = DiagnosticsDelta {
+            files: affected
+                .iter()
+                .map(|file| FileDiagnostics {
+                    file: file.clone(),
+                    errors: vec![],
+                })
+                .collect(),
+        };
+        for err in new_errors {
+            let file = self
+                .file_of_error(&err)
+                .and_then(|filename| delta.files.iter_mut().find(|file| *file.file == filename));
+
+            match file {
+                Some(file) => file.errors.push(err),
+                None => self.errors.lock().push(err),
+            }
+        }
+
+        delta
+    }
+}
+
+/// Returns `path` and the modules which depend on it directly or indirectly,
+/// sorted by the path.
+fn dependents(graph: &ModuleGraph, path: &str) -> Vec<String> {
+    let mut found = FxHashSet::default();
+    let mut queue = vec![path.to_string()];
+
+    while let Some(path) = queue.pop() {
+        if !found.insert(path.clone()) {
+            continue;
+        }
+
+        for import in &graph.imports {
+            if import.resolved.as_deref() == Some(&*path) {
+                queue.push(import.from.clone());
+            }
+        }
+    }
+
+    let mut found = found.into_iter().collect::<Vec<_>>();
+    found.sort();
+    found
+}
@@ -0,0 +1,154 @@
+//! Hints shown by editors in the code, like the inferred types of variables.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use stc_ts_ast_rnode::RPat;
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_types::{printer::print_type, FnParam};
+use swc_common::{FileName, SourceMap, Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{declarations::DeclarationSpan, loader::LoadModule, signature_help::collect_signatures, Checker};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlayHint {
+    pub kind: InlayHintKind,
+    /// The span of the variable name for [InlayHintKind::Type], and the span
+    /// of the argument for [InlayHintKind::Parameter].
+    pub span: DeclarationSpan,
+    /// The text to show, like `: number` after a variable name or `a:` before
+    /// an argument.
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InlayHintKind {
+    /// The inferred type of a variable without a type annotation, shown after
+    /// the name.
+    Type,
+    /// The name of the parameter of an argument, shown before the argument.
+    Parameter,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the inlay hints of `file`, sorted by the position.
+    ///
+    /// Parameter hints are not returned for arguments which are identifiers
+    /// with the same name as the parameter. Returns an empty list if types are
+    /// not recorded (see [Checker::with_type_recorder]), or `file` is not
+    /// checked yet.
+    pub fn inlay_hints(&self, file: &FileName) -> Vec<InlayHint> {
+        let (recorder, record) = match (self.type_recorder.as_ref(), self.find_record(&Arc::new(file.clone()))) {
+            (Some(recorder), Some(record)) => (recorder, record),
+            _ => return vec![],
+        };
+
+        let mut collector = HintCollector {
+            cm: &self.cm,
+            recorder,
+            hints: vec![],
+        };
+        record.ast.visit_with(&mut collector);
+
+        let mut hints = collector.hints;
+        hints.sort_by_key(|hint| hint.span.start);
+        hints
+    }
+}
+
+struct HintCollector<'a> {
+    cm: &'a SourceMap,
+    recorder: &'a TypeRecorder,
+    hints: Vec<InlayHint>,
+}
+
+impl HintCollector<'_> {
+    fn args(&mut self, callee: Span, is_new: bool, args: &[ExprOrSpread]) {
+        let callee_ty = match self.recorder.type_of(callee) {
+            Some(ty) => ty,
+            None => return,
+        };
+
+        let mut signatures = vec![];
+        collect_signatures(&callee_ty, is_new, &mut signatures);
+        let params = match signatures
+            .iter()
+            .find(|sig| sig.params.len() >= args.len() || sig.params.iter().any(|param| matches!(param.pat, RPat::Rest(..))))
+        {
+            Some(sig) => sig.params,
+            None => return,
+        };
+
+        for (arg, param) in args.iter().zip(params) {
+            if arg.spread.is_some() {
+                break;
+            }
+
+            let (name, is_rest) = match param_name(param) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            if !matches!(&*arg.expr, Expr::Ident(ident) if ident.sym == *name) {
+                self.hints.push(InlayHint {
+                    kind: InlayHintKind::Parameter,
+                    span: DeclarationSpan::new(self.cm, arg.expr.span()),
+                    label: format!("{}{}:", if is_rest { "..." } else { "" }, name),
+                });
+            }
+
+            // Only the first argument of a rest parameter is labeled.
+            if is_rest {
+                break;
+            }
+        }
+    }
+}
+
+/// Returns the name of `param`, and `true` if it's a rest parameter.
+fn param_name(param: &FnParam) -> Option<(&str, bool)> {
+    match &param.pat {
+        RPat::Ident(i) => Some((&*i.id.sym, false)),
+        RPat::Rest(r) => match &*r.arg {
+            RPat::Ident(i) => Some((&*i.id.sym, true)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Visit for HintCollector<'_> {
+    fn visit_var_declarator(&mut self, d: &VarDeclarator) {
+        if let Pat::Ident(BindingIdent { id, type_ann: None }) = &d.name {
+            let ty = self.recorder.var_type(&stc_ts_types::Id::new(id.sym.clone(), id.span.ctxt));
+            if let Some(ty) = ty {
+                self.hints.push(InlayHint {
+                    kind: InlayHintKind::Type,
+                    span: DeclarationSpan::new(self.cm, id.span),
+                    label: format!(": {}", print_type(&ty)),
+                });
+            }
+        }
+
+        d.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, e: &CallExpr) {
+        if let Callee::Expr(callee) = &e.callee {
+            self.args(callee.span(), false, &e.args);
+        }
+        e.visit_children_with(self);
+    }
+
+    fn visit_new_expr(&mut self, e: &NewExpr) {
+        self.args(e.callee.span(), true, e.args.as_deref().unwrap_or_default());
+        e.visit_children_with(self);
+    }
+}
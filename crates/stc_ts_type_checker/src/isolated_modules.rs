@@ -0,0 +1,202 @@
+//! Checks for code which can't be transpiled file by file, for
+//! `isolatedModules`.
+
+use std::{path::PathBuf, sync::Arc};
+
+use fxhash::FxHashMap;
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::Type;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{FileName, Span};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    loader::{LoadModule, ModuleRecord},
+    query::Definition,
+    Checker,
+};
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Reports the errors of tsc for `isolatedModules` in the loaded files:
+    ///
+    ///  - files which are not modules (TS1208)
+    ///  - re-exports of types without `export type` (TS1205)
+    ///  - usages of ambient const enums (TS2748)
+    ///
+    /// Declaration files and javascript files are not checked.
+    pub(crate) fn report_isolated_modules_errors(&self) {
+        let mut records = vec![];
+        for module in self.module_graph().modules {
+            let file = Arc::new(FileName::Real(PathBuf::from(&module.path)));
+            if let Some(record) = self.find_record(&file) {
+                records.push(record);
+            }
+        }
+
+        let mut ambient_const_enums = vec![];
+        for record in &records {
+            let mut collector = ConstEnumCollector {
+                ambient: record.is_dts,
+                enums: vec![],
+            };
+            record.ast.visit_with(&mut collector);
+            ambient_const_enums.extend(collector.enums.into_iter().map(|span| Definition {
+                file: record.filename.clone(),
+                span,
+            }));
+        }
+
+        let mut errors = vec![];
+        for record in &records {
+            if record.is_dts || record.is_js {
+                continue;
+            }
+
+            if !record.ast.body.iter().any(|item| matches!(item, ModuleItem::ModuleDecl(..))) {
+                errors.push(
+                    ErrorKind::ScriptWithIsolatedModules {
+                        span: record.ast.span.with_hi(record.ast.span.lo),
+                    }
+                    .into(),
+                );
+            }
+
+            for span in self.type_re_exports(record) {
+                errors.push(ErrorKind::ReExportTypeWithIsolatedModules { span }.into());
+            }
+
+            if !ambient_const_enums.is_empty() {
+                let mut finder = MemberObjFinder { idents: vec![] };
+                record.ast.visit_with(&mut finder);
+
+                for ident in finder.idents {
+                    let defs = self.definitions_of_local(record, &ident.to_id(), &mut vec![]);
+                    if defs.iter().any(|def| ambient_const_enums.contains(def)) {
+                        errors.push(ErrorKind::AmbientConstEnumWithIsolatedModules { span: ident.span }.into());
+                    }
+                }
+            }
+        }
+
+        self.errors.lock().extend(errors);
+    }
+
+    /// Returns the spans of the export specifiers of `record` which re-export
+    /// a type of another module without `export type`.
+    fn type_re_exports(&self, record: &ModuleRecord) -> Vec<Span> {
+        // Names imported without `import type`, by the local identifiers.
+        let mut imports = FxHashMap::default();
+        for item in &record.ast.body {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+                if import.type_only {
+                    continue;
+                }
+
+                for specifier in &import.specifiers {
+                    match specifier {
+                        ImportSpecifier::Named(s) if !s.is_type_only => {
+                            let name = s.imported.as_ref().map_or_else(|| s.local.sym.clone(), module_export_name);
+                            imports.insert(s.local.to_id(), (import.src.value.clone(), name));
+                        }
+                        ImportSpecifier::Default(s) => {
+                            imports.insert(s.local.to_id(), (import.src.value.clone(), js_word!("default")));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut spans = vec![];
+        for item in &record.ast.body {
+            let export = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if !export.type_only => export,
+                _ => continue,
+            };
+
+            for specifier in &export.specifiers {
+                let s = match specifier {
+                    ExportSpecifier::Named(s) if !s.is_type_only => s,
+                    _ => continue,
+                };
+
+                let (src, name) = match (&export.src, &s.orig) {
+                    (Some(src), orig) => (src.value.clone(), module_export_name(orig)),
+                    (None, ModuleExportName::Ident(orig)) => match imports.get(&orig.to_id()) {
+                        Some(import) => import.clone(),
+                        None => continue,
+                    },
+                    (None, ModuleExportName::Str(..)) => continue,
+                };
+
+                if self.is_type_only_export(&record.filename, &src, &name) {
+                    spans.push(s.span);
+                }
+            }
+        }
+
+        spans
+    }
+
+    /// Returns `true` if `name` is exported by the module `src` only as a
+    /// type.
+    fn is_type_only_export(&self, base: &Arc<FileName>, src: &JsWord, name: &JsWord) -> bool {
+        let id = match self.module_loader.load_dep(base, src) {
+            Ok(records) => records.entry.id,
+            Err(..) => return false,
+        };
+
+        match self.get_types(id).as_ref().map(Type::normalize) {
+            Some(Type::Module(module)) => module.exports.types.contains_key(name) && !module.exports.vars.contains_key(name),
+            _ => false,
+        }
+    }
+}
+
+fn module_export_name(name: &ModuleExportName) -> JsWord {
+    match name {
+        ModuleExportName::Ident(i) => i.sym.clone(),
+        ModuleExportName::Str(s) => s.value.clone(),
+    }
+}
+
+/// Collects the spans of the names of ambient const enums.
+struct ConstEnumCollector {
+    /// `true` if the current node is in an ambient context.
+    ambient: bool,
+    enums: Vec<Span>,
+}
+
+impl Visit for ConstEnumCollector {
+    fn visit_ts_enum_decl(&mut self, decl: &TsEnumDecl) {
+        if decl.is_const && (self.ambient || decl.declare) {
+            self.enums.push(decl.id.span);
+        }
+    }
+
+    fn visit_ts_module_decl(&mut self, decl: &TsModuleDecl) {
+        let old = self.ambient;
+        self.ambient |= decl.declare;
+        decl.visit_children_with(self);
+        self.ambient = old;
+    }
+}
+
+/// Collects the identifiers used as the objects of member expressions, like
+/// `E` of `E.A`.
+struct MemberObjFinder {
+    idents: Vec<Ident>,
+}
+
+impl Visit for MemberObjFinder {
+    fn visit_member_expr(&mut self, e: &MemberExpr) {
+        if let Expr::Ident(i) = &*e.obj {
+            self.idents.push(i.clone());
+        }
+        e.visit_children_with(self);
+    }
+}
@@ -0,0 +1,92 @@
+//! Support for javascript files which are not type checked.
+
+use stc_ts_file_analyzer::ModuleTypeData;
+use stc_ts_types::Type;
+use swc_atoms::{js_word, JsWord};
+use swc_common::Span;
+use swc_ecma_ast::{Decl, DefaultDecl, ExportSpecifier, Module, ModuleDecl, ModuleExportName, ModuleItem, ObjectPatProp, Pat};
+
+/// Creates the exports of a javascript file which is not type checked.
+///
+/// All exported items are typed as `any`.
+pub(crate) fn exports_as_any(module: &Module) -> ModuleTypeData {
+    let mut names = vec![];
+
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::ModuleDecl(decl) => decl,
+            ModuleItem::Stmt(..) => continue,
+        };
+
+        match decl {
+            ModuleDecl::ExportDecl(export) => match &export.decl {
+                Decl::Class(c) => names.push(c.ident.sym.clone()),
+                Decl::Fn(f) => names.push(f.ident.sym.clone()),
+                Decl::Var(var) => {
+                    for decl in &var.decls {
+                        collect_pat_names(&decl.name, &mut names);
+                    }
+                }
+                _ => {}
+            },
+            ModuleDecl::ExportNamed(export) => {
+                for specifier in &export.specifiers {
+                    match specifier {
+                        ExportSpecifier::Named(named) => {
+                            names.push(export_name(named.exported.as_ref().unwrap_or(&named.orig)));
+                        }
+                        ExportSpecifier::Default(default) => names.push(default.exported.sym.clone()),
+                        ExportSpecifier::Namespace(ns) => names.push(export_name(&ns.name)),
+                    }
+                }
+            }
+            ModuleDecl::ExportDefaultDecl(export) => {
+                if let DefaultDecl::Class(..) | DefaultDecl::Fn(..) = export.decl {
+                    names.push(js_word!("default"));
+                }
+            }
+            ModuleDecl::ExportDefaultExpr(..) => names.push(js_word!("default")),
+            _ => {}
+        }
+    }
+
+    let mut data = ModuleTypeData::default();
+    for name in names {
+        data.vars.insert(name, any(module.span));
+    }
+    data
+}
+
+fn any(span: Span) -> Type {
+    Type::any(span, Default::default())
+}
+
+pub(crate) fn export_name(name: &ModuleExportName) -> JsWord {
+    match name {
+        ModuleExportName::Ident(i) => i.sym.clone(),
+        ModuleExportName::Str(s) => s.value.clone(),
+    }
+}
+
+pub(crate) fn collect_pat_names(pat: &Pat, names: &mut Vec<JsWord>) {
+    match pat {
+        Pat::Ident(i) => names.push(i.id.sym.clone()),
+        Pat::Array(arr) => {
+            for elem in arr.elems.iter().flatten() {
+                collect_pat_names(elem, names);
+            }
+        }
+        Pat::Object(obj) => {
+            for prop in &obj.props {
+                match prop {
+                    ObjectPatProp::KeyValue(kv) => collect_pat_names(&kv.value, names),
+                    ObjectPatProp::Assign(a) => names.push(a.key.sym.clone()),
+                    ObjectPatProp::Rest(r) => collect_pat_names(&r.arg, names),
+                }
+            }
+        }
+        Pat::Rest(r) => collect_pat_names(&r.arg, names),
+        Pat::Assign(a) => collect_pat_names(&a.left, names),
+        Pat::Invalid(..) | Pat::Expr(..) => {}
+    }
+}
@@ -1,29 +1,64 @@
 //! Full type checker with dependency support.
 #![feature(box_syntax)]
 
-use std::{mem::take, sync::Arc, time::Instant};
+use std::{mem::take, path::PathBuf, sync::Arc};
 
 use dashmap::{DashMap, DashSet, SharedValue};
+use directives::Directives;
+use dts_cache::DtsCache;
 use fxhash::{FxBuildHasher, FxHashMap};
-use loader::LoadModule;
+use graph::ModuleGraph;
+use lint::LintRule;
+use loader::{LoadModule, ModuleRecord};
 use once_cell::sync::OnceCell;
 use parking_lot::{Mutex, RwLock};
+use program::Program;
+use rayon::prelude::*;
 use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_ts_ast_rnode::{RModule, RStr, RTsModuleName};
 use stc_ts_dts::{apply_mutations, cleanup_module_for_dts};
 use stc_ts_env::Env;
-use stc_ts_errors::{debug::debugger::Debugger, Error};
-use stc_ts_file_analyzer::{analyzer::Analyzer, loader::Load, validator::ValidateWith, ModuleTypeData, VResult};
+use stc_ts_errors::{debug::debugger::Debugger, Error, ErrorKind, ModuleNotFoundHint};
+use stc_ts_file_analyzer::{analyzer::Analyzer, loader::Load, recorder::TypeRecorder, validator::ValidateWith, ModuleTypeData, VResult};
+use stc_ts_module_loader::resolvers::suggest::suggest;
 use stc_ts_storage::{ErrorStore, File, Group, Single};
 use stc_ts_types::{ModuleId, Type};
-use stc_utils::{cache::Freeze, early_error};
+use stc_utils::{cache::Freeze, cancel::CancellationToken, early_error, profile::Profiler, time::Instant};
 use swc_atoms::JsWord;
-use swc_common::{errors::Handler, FileName, SourceMap, Spanned, DUMMY_SP};
-use swc_ecma_ast::Module;
+use swc_common::{errors::Handler, FileName, SourceMap, Span, Spanned, DUMMY_SP};
+use swc_ecma_ast::{Module, ModuleDecl, ModuleItem, NamedExport, TsModuleRef};
 use tracing::{info, warn};
 
+pub mod build_info;
+pub mod call_hierarchy;
+pub mod completions;
+pub mod coverage;
+pub mod declarations;
+mod directives;
+pub mod dts_cache;
+pub mod emit;
+pub mod github;
+pub mod graph;
+pub mod incremental;
+pub mod inlay_hints;
+mod isolated_modules;
+mod js;
+pub mod limit;
+pub mod lint;
 pub mod loader;
+pub mod narrowing;
+pub mod outline;
+pub mod program;
+pub mod query;
+pub mod quick_info;
+pub mod rename;
+pub mod sarif;
+pub mod semantic_tokens;
+pub mod signature_help;
+mod suggestions;
+pub mod symbol_index;
 mod typings;
+mod unused_exports;
 
 /// Onc instance per swc::Compiler
 pub struct Checker<L>
@@ -35,7 +70,8 @@ where
     /// Cache
     module_types: RwLock<FxHashMap<ModuleId, Arc<OnceCell<Type>>>>,
 
-    declared_modules: RwLock<Vec<(ModuleId, Type)>>,
+    /// Shared with [DtsCache] if it's used.
+    declared_modules: Arc<RwLock<Vec<(ModuleId, Type)>>>,
 
     /// Information required to generate `.d.ts` files.
     dts_modules: Arc<DashMap<ModuleId, RModule, FxBuildHasher>>,
@@ -50,6 +86,16 @@ where
     env: Env,
 
     debugger: Option<Debugger>,
+
+    type_recorder: Option<TypeRecorder>,
+
+    cancellation_token: Option<CancellationToken>,
+
+    profiler: Option<Profiler>,
+
+    dts_cache: Option<DtsCache>,
+
+    lint_rules: Vec<Box<dyn LintRule>>,
 }
 
 impl<L> Checker<L>
@@ -68,8 +114,56 @@ where
             debugger,
             declared_modules: Default::default(),
             module_loader,
+            type_recorder: None,
+            cancellation_token: None,
+            profiler: None,
+            dts_cache: None,
+            lint_rules: vec![],
         }
     }
+
+    /// Records the types of expressions and declarations, so they can be
+    /// queried using [Checker::type_at] and [Checker::declaration_types] after
+    /// checking.
+    pub fn with_type_recorder(mut self, recorder: TypeRecorder) -> Self {
+        self.type_recorder = Some(recorder);
+        self
+    }
+
+    /// Aborts [Checker::check] once `token` is cancelled, like when the user
+    /// edits a file while it's being checked.
+    ///
+    /// Pass the same token to
+    /// [loader::ModuleLoader::with_cancellation_token] to stop loading
+    /// dependencies too. Results of a cancelled check are incomplete, so the
+    /// checker should be discarded after cancellation.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Records the time taken to check each file and each top-level
+    /// declaration to `profiler`.
+    ///
+    /// Pass the same profiler to [loader::ModuleLoader::with_profiler] to
+    /// record parsing too.
+    pub fn with_profiler(mut self, profiler: Profiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    /// Reuses the types of declaration files analyzed by other checkers
+    /// sharing `cache`, and stores the types of declaration files analyzed by
+    /// this checker.
+    pub fn with_dts_cache(mut self, cache: DtsCache) -> Self {
+        self.declared_modules = cache.declared_modules.clone();
+        self.dts_cache = Some(cache);
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation_token.as_ref().map_or(false, |token| token.is_cancelled())
+    }
 }
 
 impl<L> Checker<L>
@@ -91,23 +185,177 @@ where
         &self.module_loader
     }
 
-    /// After calling this method, you can get errors using `.take_errors()`
-    pub fn check(&self, entry: Arc<FileName>) -> ModuleId {
+    /// Returns the graph of the modules loaded so far.
+    pub fn module_graph(&self) -> ModuleGraph {
+        self.module_loader.module_graph()
+    }
+
+    /// Checks `entry` and its dependencies.
+    ///
+    /// The errors are stored in the returned [Program], grouped by modules.
+    /// They are also stored in `self`, so you can get errors using
+    /// `.take_errors()` after calling this method.
+    ///
+    /// If the check is cancelled, errors which are not taken yet are discarded
+    /// because they may be wrong.
+    pub fn check(&self, entry: Arc<FileName>) -> Program {
+        self.check_all(vec![entry])
+    }
+
+    /// Checks `entries`, like the files of a `tsconfig.json`, and their
+    /// dependencies.
+    ///
+    /// Modules imported by multiple entries are checked only once, and the
+    /// errors of all entries are stored in the returned [Program] like
+    /// [Checker::check].
+    ///
+    /// An entry which can't be loaded, because it doesn't exist or it can't be
+    /// parsed, is reported as [ErrorKind::CannotReadFile] and skipped.
+    pub fn check_all(&self, entries: Vec<Arc<FileName>>) -> Program {
+        if entries.is_empty() {
+            return self.program(vec![], vec![]);
+        }
+
+        let errors_before = self.errors.lock().len();
+        let names = entries.iter().map(|entry| entry.to_string()).collect::<Vec<_>>().join(", ");
         let start = Instant::now();
 
-        let modules = self.module_loader.load_module(&entry, true).expect("failed to load entry");
+        let mut ids = vec![];
+        let mut loaded = vec![];
+        for entry in entries {
+            match self.module_loader.load_module(&entry, true) {
+                Ok(records) => {
+                    ids.push(records.entry.id);
+                    loaded.push(entry);
+                }
+                Err(err) => self.errors.lock().push(
+                    ErrorKind::CannotReadFile {
+                        span: DUMMY_SP,
+                        file: entry.to_string(),
+                        reason: format!("{:#}", err),
+                    }
+                    .into(),
+                ),
+            }
+        }
+        let entries = loaded;
 
         let end = Instant::now();
-        log::debug!("Loading of `{}` and dependencies took {:?}", entry, end - start);
+        log::debug!("Loading of `{}` and dependencies took {:?}", names, end - start);
 
         let start = Instant::now();
 
-        self.analyze_module(None, entry.clone());
+        self.declare_umd_globals();
+        // Entries which don't depend on each other are analyzed in parallel.
+        entries.par_iter().for_each(|entry| {
+            if !self.is_cancelled() {
+                self.analyze_module(None, entry.clone());
+            }
+        });
 
         let end = Instant::now();
-        log::debug!("Analysis of `{}` and dependencies took {:?}", entry, end - start);
+        log::debug!("Analysis of `{}` and dependencies took {:?}", names, end - start);
 
-        modules.entry.id
+        if self.is_cancelled() {
+            log::debug!("Check of `{}` is cancelled", names);
+            self.errors.lock().clear();
+            return self.program(ids, vec![]);
+        }
+
+        if self.env.rule().isolated_modules {
+            self.report_isolated_modules_errors();
+        }
+
+        if self.env.rule().no_circular_imports {
+            self.report_circular_imports();
+        }
+
+        if self.env.rule().no_unused_exports {
+            self.report_unused_exports(&entries);
+        }
+
+        self.run_lint_rules();
+
+        let errors = {
+            let mut lock = self.errors.lock();
+            let new_errors = lock.split_off(errors_before.min(lock.len()));
+            lock.extend(self.env.severity_overrides().apply(new_errors));
+
+            let errors = lock.get_mut(errors_before..).unwrap_or_default();
+            // Modules are analyzed in parallel, so errors are sorted by the file to make
+            // the order independent of the number of threads. Errors in a file are kept
+            // in the order of the analysis.
+            errors.sort_by_cached_key(|err| self.file_of_error(err).map(|file| file.to_string()));
+            errors.to_vec()
+        };
+        self.program(ids, errors)
+    }
+
+    /// Declares a global variable for each `export as namespace Foo` in the
+    /// loaded declaration files, so that UMD libraries can be used without
+    /// imports in files which are not modules.
+    fn declare_umd_globals(&self) {
+        let graph = self.module_graph();
+
+        for module in graph.modules.iter().filter(|module| module.is_dts) {
+            let path = Arc::new(FileName::Real(PathBuf::from(&module.path)));
+            let records = match self.module_loader.load_module(&path, false) {
+                Ok(records) => records,
+                Err(..) => continue,
+            };
+            let record = match records.modules.iter().find(|record| record.filename == path) {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let names = record
+                .ast
+                .body
+                .iter()
+                .filter_map(|item| match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::TsNamespaceExport(export)) => Some(export.id.sym.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if names.is_empty() {
+                continue;
+            }
+
+            let ty = self.analyze_module(None, path.clone());
+            let mut env = self.env.clone();
+            for name in names {
+                env.declare_umd_global(name, ty.clone());
+            }
+        }
+    }
+
+    /// Reports an error for each cycle of imports, at the import of the
+    /// second file in the cycle from the first file.
+    fn report_circular_imports(&self) {
+        let graph = self.module_graph();
+
+        for cycle in graph.cycles() {
+            let next = &cycle[1 % cycle.len()];
+            let specifier = graph
+                .imports
+                .iter()
+                .find(|import| import.from == cycle[0] && import.resolved.as_ref() == Some(next))
+                .map(|import| &*import.specifier);
+
+            let filename = Arc::new(FileName::Real(PathBuf::from(&cycle[0])));
+            let module = match self.module_loader.load_module(&filename, false) {
+                Ok(records) => records.entry,
+                Err(err) => {
+                    warn!("Failed to load `{}`: {:?}", filename, err);
+                    continue;
+                }
+            };
+            let span = specifier
+                .and_then(|specifier| find_import_span(&module.ast, specifier))
+                .unwrap_or(module.ast.span);
+
+            self.errors.lock().push(ErrorKind::CircularImport { span, cycle }.into());
+        }
     }
 
     pub fn take_errors(&mut self) -> Vec<Error> {
@@ -116,12 +364,36 @@ where
 
     /// Analyzes one module.
     fn analyze_module(&self, starter: Option<Arc<FileName>>, path: Arc<FileName>) -> Type {
+        // The result is not cached, so that it is not mistaken for the type of a
+        // fully analyzed module.
+        if self.is_cancelled() {
+            return Type::Module(stc_ts_types::Module {
+                span: DUMMY_SP,
+                name: RTsModuleName::Str(RStr {
+                    span: DUMMY_SP,
+                    value: path.to_string().into(),
+                    raw: None,
+                }),
+                exports: Default::default(),
+                metadata: Default::default(),
+                tracker: Default::default(),
+            })
+            .freezed();
+        }
+
         let modules_in_group = self
             .module_loader
             .load_module(&path, false)
             .expect("failed to load module? (cycle)");
 
         let id = modules_in_group.entry.id;
+
+        if let FileName::Custom(name) = &*path {
+            if let Some(ty) = self.analyze_ambient_module(starter.as_ref(), name, id) {
+                return ty;
+            }
+        }
+
         {
             let lock = self.module_types.read();
             // If a circular chunks are fully analyzed, used them.
@@ -130,6 +402,10 @@ where
             }
         }
 
+        if let Some(ty) = self.cached_dts_module(&modules_in_group.modules, id) {
+            return ty;
+        }
+
         let is_first_run = self.started.insert(id);
 
         if is_first_run && modules_in_group.modules.len() > 1 {
@@ -146,6 +422,7 @@ where
             }
 
             {
+                let start = Instant::now();
                 let mut node_id_gen = NodeIdGenerator::default();
                 let mut storage = Group {
                     parent: None,
@@ -154,7 +431,7 @@ where
                             .modules
                             .iter()
                             .map(|record| File {
-                                id,
+                                id: record.id,
                                 path: record.filename.clone(),
                                 stmt_count: record.ast.body.len(),
                                 top_level_ctxt: record.top_level_ctxt,
@@ -179,10 +456,24 @@ where
                         self,
                         self.debugger.clone(),
                     );
+                    if let Some(recorder) = &self.type_recorder {
+                        a = a.with_type_recorder(recorder.clone());
+                    }
+                    if let Some(token) = &self.cancellation_token {
+                        a = a.with_cancellation_token(token.clone());
+                    }
+                    if let Some(profiler) = &self.profiler {
+                        a = a.with_profiler(profiler.clone());
+                    }
                     let _ = modules.validate_with(&mut a);
                     mutations = a.mutations.unwrap();
                 }
 
+                if let Some(profiler) = &self.profiler {
+                    let files = modules_in_group.modules.iter().map(|record| record.filename.to_string());
+                    profiler.record("check", files.collect::<Vec<_>>().join(", "), start);
+                }
+
                 for (record, mut dts_module) in modules_in_group.modules.iter().zip(modules) {
                     let type_data = storage.info.entry(record.id).or_default();
 
@@ -198,8 +489,24 @@ where
                 }
 
                 {
+                    // Errors in javascript files are reported only if `checkJs` is enabled, and
+                    // errors in declaration files are not reported if `skipLibCheck` is enabled.
+                    // Comment directives like `// @ts-ignore` are applied to the errors of each file.
+                    let mut errors = ErrorKind::flatten(storage.take_errors().into());
+                    let mut checked = vec![];
+                    for record in &modules_in_group.modules {
+                        let (in_module, rest): (Vec<_>, Vec<_>) = errors.into_iter().partition(|err| record.ast.span.contains(err.span()));
+                        errors = rest;
+
+                        if !self.is_unchecked(record) {
+                            let in_module = self.apply_directives(record, in_module);
+                            checked.extend(self.add_spelling_suggestions(record, in_module));
+                        }
+                    }
+
                     let mut lock = self.errors.lock();
-                    lock.extend(storage.take_errors());
+                    lock.extend(checked);
+                    lock.extend(errors);
                 }
                 {
                     let mut lock = self.module_types.write();
@@ -228,6 +535,8 @@ where
                 }
             }
 
+            self.cache_dts_modules(&modules_in_group.modules);
+
             let lock = self.module_types.read();
             return lock.get(&id).and_then(|cell| cell.get().cloned()).unwrap();
         }
@@ -253,7 +562,9 @@ where
                 .clone();
 
             let dur = Instant::now() - start;
-            if !did_work {
+            if did_work {
+                self.cache_dts_modules(&modules_in_group.modules);
+            } else {
                 log::warn!("Waited for {}: {:?}", path, dur);
             }
 
@@ -261,11 +572,81 @@ where
         }
     }
 
+    /// Returns `true` if errors in `record` should not be reported.
+    fn is_unchecked(&self, record: &ModuleRecord) -> bool {
+        (record.is_js && !self.env.rule().check_js) || (record.is_dts && self.env.rule().skip_lib_check)
+    }
+
+    /// Applies the comment directives like `// @ts-ignore` in `record` to
+    /// `errors`, which are the errors of `record`.
+    fn apply_directives(&self, record: &ModuleRecord, errors: Vec<Error>) -> Vec<Error> {
+        // The file may be parsed again after changes.
+        let fm = self.cm.lookup_byte_offset(record.ast.span.lo).sf;
+
+        Directives::parse(&fm, record).apply(&fm, errors)
+    }
+
+    /// Returns the type of an ambient module declared with `declare module
+    /// "name"`, after analyzing the files declaring it.
+    ///
+    /// Returns [None] if there's no declaration.
+    fn analyze_ambient_module(&self, starter: Option<&Arc<FileName>>, name: &str, id: ModuleId) -> Option<Type> {
+        for file in self.module_loader.ambient_module_files(name) {
+            // The declaring file is importing the module, and it's not analyzed yet.
+            if starter == Some(&file) {
+                continue;
+            }
+
+            self.analyze_module(starter.cloned(), file);
+        }
+
+        let types = self
+            .declared_modules
+            .read()
+            .iter()
+            .filter(|(module_id, _)| *module_id == id)
+            .map(|(_, ty)| ty.clone())
+            .collect::<Vec<_>>();
+
+        if types.len() <= 1 {
+            return types.into_iter().next();
+        }
+
+        // Merge all declarations.
+        let mut exports = ModuleTypeData::default();
+        for ty in &types {
+            if let Type::Module(module) = ty.normalize() {
+                exports.vars.extend(module.exports.vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+                for (k, v) in &module.exports.types {
+                    exports.types.entry(k.clone()).or_default().extend(v.iter().cloned());
+                }
+            }
+        }
+
+        Some(
+            Type::Module(stc_ts_types::Module {
+                span: DUMMY_SP,
+                name: RTsModuleName::Str(RStr {
+                    span: DUMMY_SP,
+                    value: name.into(),
+                    raw: None,
+                }),
+                exports: box exports,
+                metadata: Default::default(),
+                tracker: Default::default(),
+            })
+            .freezed(),
+        )
+    }
+
     fn analyze_non_circular_module(&self, module_id: ModuleId, path: Arc<FileName>) -> Type {
         let start = Instant::now();
 
         let is_dts = match &*path {
-            FileName::Real(path) => path.to_string_lossy().ends_with(".d.ts"),
+            FileName::Real(path) => {
+                let path = path.to_string_lossy();
+                path.ends_with(".d.ts") || path.ends_with(".d.mts") || path.ends_with(".d.cts")
+            }
             _ => false,
         };
 
@@ -279,6 +660,21 @@ where
 
         let record = records.modules.into_iter().next().unwrap();
 
+        if record.is_js && !self.env.rule().check_js {
+            return Type::Module(stc_ts_types::Module {
+                span: record.ast.span,
+                name: RTsModuleName::Str(RStr {
+                    span: DUMMY_SP,
+                    value: format!("{:?}", module_id).into(),
+                    raw: None,
+                }),
+                exports: box js::exports_as_any(&record.ast),
+                metadata: Default::default(),
+                tracker: Default::default(),
+            })
+            .freezed();
+        }
+
         let mut module = RModule::from_orig(&mut node_id_gen, record.ast.clone());
 
         let mut storage = Single {
@@ -300,6 +696,15 @@ where
                 self,
                 self.debugger.clone(),
             );
+            if let Some(recorder) = &self.type_recorder {
+                a = a.with_type_recorder(recorder.clone());
+            }
+            if let Some(token) = &self.cancellation_token {
+                a = a.with_cancellation_token(token.clone());
+            }
+            if let Some(profiler) = &self.profiler {
+                a = a.with_profiler(profiler.clone());
+            }
 
             module.visit_with(&mut a);
 
@@ -316,13 +721,18 @@ where
             cleanup_module_for_dts(&mut module.body, &storage.info.exports);
         }
 
-        if early_error() {
-            for err in storage.info.errors {
-                self.handler.struct_span_err(err.span(), &format!("{:?}", err)).emit();
+        // Declaration files are analyzed only for the types if `skipLibCheck` is
+        // enabled.
+        if !self.is_unchecked(&record) {
+            if early_error() {
+                for err in storage.info.errors {
+                    err.emit(&self.handler);
+                }
+            } else {
+                let errors = self.apply_directives(&record, ErrorKind::flatten(storage.info.errors.into()));
+                let errors = self.add_spelling_suggestions(&record, errors);
+                self.errors.lock().extend(errors);
             }
-        } else {
-            let mut errors = self.errors.lock();
-            errors.extend(storage.info.errors);
         }
 
         let type_info = Type::Module(stc_ts_types::Module {
@@ -343,6 +753,10 @@ where
         let dur = Instant::now() - start;
         log::trace!("[Timing] Full analysis of {} took {:?}", path, dur);
 
+        if let Some(profiler) = &self.profiler {
+            profiler.record("check", path.to_string(), start);
+        }
+
         type_info
     }
 }
@@ -394,4 +808,33 @@ where
         info!("Declaring module with type `{}`", name);
         self.declared_modules.write().push((module_id, module));
     }
+
+    fn module_not_found_hint(&self, base: &Arc<FileName>, src: &str) -> Option<Box<ModuleNotFoundHint>> {
+        match &**base {
+            FileName::Real(base) => Some(box suggest(&*self.module_loader.fs(), base, src)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the span of the import of `specifier` in `module`.
+fn find_import_span(module: &Module, specifier: &str) -> Option<Span> {
+    module.body.iter().find_map(|item| {
+        let (span, src) = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => (import.span, &*import.src),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => (export.span, &*export.src),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport { span, src: Some(src), .. })) => (*span, &**src),
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)) => match &import.module_ref {
+                TsModuleRef::TsExternalModuleRef(module_ref) => (import.span, &module_ref.expr),
+                TsModuleRef::TsEntityName(..) => return None,
+            },
+            _ => return None,
+        };
+
+        if &*src.value == specifier {
+            Some(span)
+        } else {
+            None
+        }
+    })
 }
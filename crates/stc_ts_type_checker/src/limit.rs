@@ -0,0 +1,78 @@
+//! Caps on the number of reported errors, so that a file with many errors
+//! does not flood a terminal or an editor.
+
+use std::hash::Hash;
+
+use fxhash::FxHashMap;
+
+/// The maximum numbers of errors reported in a run and in each file. Errors
+/// are reported in order, and the errors over either cap are omitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorLimit {
+    max_errors: Option<usize>,
+    max_errors_per_file: Option<usize>,
+}
+
+impl ErrorLimit {
+    /// Reports at most `max` errors in total, or all errors if [None].
+    pub fn with_max_errors(mut self, max: Option<usize>) -> Self {
+        self.max_errors = max;
+        self
+    }
+
+    /// Reports at most `max` errors of each file, or all errors if [None].
+    pub fn with_max_errors_per_file(mut self, max: Option<usize>) -> Self {
+        self.max_errors_per_file = max;
+        self
+    }
+
+    /// The limit for the errors reported after `reported` errors, which are
+    /// errors of other files.
+    pub fn after(self, reported: usize) -> Self {
+        ErrorLimit {
+            max_errors: self.max_errors.map(|max| max.saturating_sub(reported)),
+            ..self
+        }
+    }
+
+    /// Returns the errors to report among `errors`, and the number of omitted
+    /// errors. `file` returns the file of an error.
+    pub fn truncate<T, K, F>(&self, errors: Vec<T>, file: F) -> (Vec<T>, usize)
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K,
+    {
+        let total = errors.len();
+        let mut per_file = FxHashMap::<K, usize>::default();
+        let mut reported = vec![];
+
+        for err in errors {
+            if self.max_errors.map_or(false, |max| reported.len() >= max) {
+                break;
+            }
+
+            if let Some(max) = self.max_errors_per_file {
+                let count = per_file.entry(file(&err)).or_default();
+                if *count >= max {
+                    continue;
+                }
+                *count += 1;
+            }
+
+            reported.push(err);
+        }
+
+        let omitted = total - reported.len();
+        (reported, omitted)
+    }
+}
+
+/// The line reported after the errors if `omitted` errors are not reported,
+/// like `...and 412 more errors`.
+pub fn truncation_summary(omitted: usize) -> Option<String> {
+    match omitted {
+        0 => None,
+        1 => Some("...and 1 more error".into()),
+        _ => Some(format!("...and {} more errors", omitted)),
+    }
+}
@@ -0,0 +1,137 @@
+//! Type-aware lint rules, which are run on the checked files.
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_errors::{Error, ErrorKind, Fix};
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_types::Type;
+use swc_common::{FileName, Span, Spanned};
+use swc_ecma_ast::{Ident, Module};
+
+use crate::{loader::LoadModule, query::Definition, Checker};
+
+/// A lint rule, which is registered using [Checker::with_lint_rule].
+///
+/// Rules usually implement [swc_ecma_visit::Visit] to find the nodes they are
+/// interested in, and query the types of the nodes using the [LintContext].
+pub trait LintRule: Send + Sync {
+    /// The name of the rule, which is included in the reported errors.
+    fn name(&self) -> &str;
+
+    /// Called for each checked file except declaration files, after the file
+    /// and its dependencies are checked.
+    fn check(&self, cx: &mut LintContext, module: &Module);
+}
+
+/// Provides the results of the type checker to a [LintRule], and collects the
+/// diagnostics reported by it.
+pub struct LintContext<'a> {
+    rule: &'a str,
+    file: &'a Arc<FileName>,
+    recorder: &'a TypeRecorder,
+    definitions: &'a dyn Fn(&Ident) -> Vec<Definition>,
+    errors: Vec<Error>,
+}
+
+impl LintContext<'_> {
+    /// The file being checked.
+    pub fn file(&self) -> &Arc<FileName> {
+        self.file
+    }
+
+    /// Returns the type of an expression.
+    pub fn type_of(&self, node: &dyn Spanned) -> Option<Type> {
+        self.recorder.type_of(node.span())
+    }
+
+    /// Returns the type of the variable named `i`, which includes functions,
+    /// classes and enums.
+    pub fn var_type(&self, i: &Ident) -> Option<Type> {
+        self.recorder.var_type(&stc_ts_types::Id::new(i.sym.clone(), i.span.ctxt))
+    }
+
+    /// Returns the type declared as `i`, like an interface or a type alias.
+    pub fn type_decl(&self, i: &Ident) -> Option<Type> {
+        self.recorder.type_decl(&stc_ts_types::Id::new(i.sym.clone(), i.span.ctxt))
+    }
+
+    /// Returns the declarations of the symbol named `i`, following imports
+    /// like [Checker::definition_at].
+    pub fn definitions(&self, i: &Ident) -> Vec<Definition> {
+        (self.definitions)(i)
+    }
+
+    /// Reports an error of the rule at `span`.
+    pub fn report(&mut self, span: Span, message: impl Into<String>) {
+        self.push(span, message.into(), None);
+    }
+
+    /// Reports an error of the rule at `span`, which can be fixed by `fix`.
+    pub fn report_with_fix(&mut self, span: Span, message: impl Into<String>, fix: Fix) {
+        self.push(span, message.into(), Some(Box::new(fix)));
+    }
+
+    fn push(&mut self, span: Span, message: String, fix: Option<Box<Fix>>) {
+        self.errors.push(
+            ErrorKind::Lint {
+                span,
+                rule: self.rule.to_string(),
+                message,
+                fix,
+            }
+            .into(),
+        );
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Runs `rule` on the checked files after checking them. Errors reported
+    /// by the rule are added to the errors of the check.
+    ///
+    /// This enables recording of types like [Checker::with_type_recorder], as
+    /// rules need them.
+    pub fn with_lint_rule(mut self, rule: impl 'static + LintRule) -> Self {
+        if self.type_recorder.is_none() {
+            self.type_recorder = Some(Default::default());
+        }
+        self.lint_rules.push(Box::new(rule));
+        self
+    }
+
+    /// Runs the registered lint rules on the loaded files.
+    pub(crate) fn run_lint_rules(&self) {
+        let recorder = match &self.type_recorder {
+            Some(recorder) if !self.lint_rules.is_empty() => recorder,
+            _ => return,
+        };
+
+        for module in self.module_graph().modules {
+            if module.is_dts {
+                continue;
+            }
+
+            let file = Arc::new(FileName::Real(PathBuf::from(&module.path)));
+            let record = match self.find_record(&file) {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let definitions = |i: &Ident| self.definitions_of_local(&record, &i.to_id(), &mut vec![]);
+            for rule in &self.lint_rules {
+                let mut cx = LintContext {
+                    rule: rule.name(),
+                    file: &file,
+                    recorder,
+                    definitions: &definitions,
+                    errors: vec![],
+                };
+                rule.check(&mut cx, &record.ast);
+
+                self.errors.lock().extend(cx.errors);
+            }
+        }
+    }
+}
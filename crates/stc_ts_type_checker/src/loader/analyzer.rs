@@ -4,8 +4,11 @@ use swc_common::{comments::Comments, Span, Spanned};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{Visit, VisitWith};
 
-/// Returns `(declared modules, references ,dependencies)`
-pub(crate) fn find_modules_and_deps<C>(comments: &C, m: &Module) -> (Vec<JsWord>, Vec<JsWord>, Vec<JsWord>)
+/// Returns `(declared modules, references ,dependencies, json dependencies)`.
+///
+/// Json dependencies are dependencies imported with `type: "json"`, which are
+/// also included in dependencies.
+pub(crate) fn find_modules_and_deps<C>(comments: &C, m: &Module) -> (Vec<JsWord>, Vec<JsWord>, Vec<JsWord>, Vec<JsWord>)
 where
     C: Comments,
 {
@@ -14,11 +17,12 @@ where
         declared_modules: Default::default(),
         references: Default::default(),
         deps: Default::default(),
+        json_deps: Default::default(),
     };
 
     m.visit_with(&mut v);
 
-    (v.declared_modules, v.references, v.deps)
+    (v.declared_modules, v.references, v.deps, v.json_deps)
 }
 
 struct DepFinder<C>
@@ -29,6 +33,7 @@ where
     declared_modules: Vec<JsWord>,
     references: Vec<JsWord>,
     deps: Vec<JsWord>,
+    json_deps: Vec<JsWord>,
 }
 
 impl<C> DepFinder<C>
@@ -40,6 +45,14 @@ where
 
         self.references.extend(deps.into_iter().map(|i| i.to_path()));
     }
+
+    fn add_dep(&mut self, src: &Str, attrs: Option<&ObjectLit>) {
+        self.deps.push(src.value.clone());
+
+        if attrs.map_or(false, is_json_import) {
+            self.json_deps.push(src.value.clone());
+        }
+    }
 }
 
 impl<C> Visit for DepFinder<C>
@@ -59,16 +72,28 @@ where
     }
 
     fn visit_export_all(&mut self, export: &ExportAll) {
-        self.deps.push(export.src.value.clone());
+        self.add_dep(&export.src, export.asserts.as_deref());
     }
 
     fn visit_import_decl(&mut self, import: &ImportDecl) {
-        self.deps.push(import.src.value.clone());
+        self.add_dep(&import.src, import.asserts.as_deref());
     }
 
     fn visit_named_export(&mut self, export: &NamedExport) {
         if let Some(src) = &export.src {
-            self.deps.push(src.value.clone());
+            self.add_dep(src, export.asserts.as_deref());
+        }
+    }
+
+    /// `import("./foo")`. Specifiers which are not string literals are
+    /// resolved while analyzing.
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        call.visit_children_with(self);
+
+        if let Callee::Import(..) = call.callee {
+            if let Some(Expr::Lit(Lit::Str(src))) = call.args.first().map(|arg| &*arg.expr) {
+                self.deps.push(src.value.clone());
+            }
         }
     }
 
@@ -84,3 +109,22 @@ where
         }
     }
 }
+
+/// Returns `true` for `with { type: "json" }` or `assert { type: "json" }`.
+fn is_json_import(attrs: &ObjectLit) -> bool {
+    attrs.props.iter().any(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(KeyValueProp { key, value }) => {
+                let is_type = match key {
+                    PropName::Ident(key) => &*key.sym == "type",
+                    PropName::Str(key) => &*key.value == "type",
+                    _ => false,
+                };
+
+                is_type && matches!(&**value, Expr::Lit(Lit::Str(value)) if &*value.value == "json")
+            }
+            _ => false,
+        },
+        PropOrSpread::Spread(..) => false,
+    })
+}
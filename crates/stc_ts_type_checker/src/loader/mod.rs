@@ -1,22 +1,29 @@
-use std::sync::{Arc, Mutex, RwLock};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+};
 
 use anyhow::{bail, Context, Result};
 use auto_impl::auto_impl;
 use dashmap::{DashMap, DashSet};
 use fxhash::FxBuildHasher;
-use petgraph::algo::kosaraju_scc;
+use petgraph::{algo::kosaraju_scc, EdgeDirection::Outgoing};
 use rayon::prelude::*;
 use stc_ts_env::Env;
+use stc_ts_module_loader::fs::{FileSystem, RealFs};
 use stc_ts_types::{module_id::ModuleIdGenerator, ModuleId};
 use stc_ts_utils::StcComments;
-use swc_common::{FileName, SourceMap, Span, SyntaxContext, GLOBALS};
-use swc_ecma_ast::{EsVersion, Module};
+use stc_utils::{cancel::CancellationToken, profile::Profiler, time::Instant};
+use swc_atoms::JsWord;
+use swc_common::{FileName, SourceFile, SourceMap, Span, SyntaxContext, GLOBALS};
+use swc_ecma_ast::{EsVersion, ExportDefaultExpr, Module, ModuleDecl, ModuleItem};
 use swc_ecma_loader::resolve::Resolve;
-use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_parser::{lexer::Lexer, EsConfig, Parser, StringInput, Syntax, TsConfig};
 use swc_ecma_visit::VisitMutWith;
 use swc_fast_graph::digraph::FastDiGraphMap;
 
 use self::analyzer::find_modules_and_deps;
+use crate::graph::{ImportEdge, ImportKind, ModuleGraph, ModuleNode};
 
 mod analyzer;
 pub mod store;
@@ -24,6 +31,9 @@ pub mod store;
 pub struct ModuleRecord {
     pub id: ModuleId,
     pub is_dts: bool,
+    /// `true` if the module is a javascript file, which is loaded only if
+    /// `allowJs` is enabled.
+    pub is_js: bool,
     pub filename: Arc<FileName>,
     pub top_level_ctxt: SyntaxContext,
     pub ast: Module,
@@ -53,6 +63,32 @@ pub trait LoadModule: 'static + Send + Sync {
 
     /// Same constraints for [`LoadModule::load_module`] applies.
     fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records>;
+
+    /// Resolves `module_specifier` imported from `base` like
+    /// [`LoadModule::load_dep`], without loading it.
+    fn resolve_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<FileName>;
+
+    /// The file system used to read modules.
+    fn fs(&self) -> Arc<dyn FileSystem> {
+        Arc::new(RealFs)
+    }
+
+    /// Returns the files which contain `declare module "name"` for the
+    /// ambient module `name`.
+    ///
+    /// Imports of ambient modules are resolved to [FileName::Custom].
+    fn ambient_module_files(&self, _name: &str) -> Vec<Arc<FileName>> {
+        vec![]
+    }
+
+    /// Returns the graph of the modules loaded so far.
+    fn module_graph(&self) -> ModuleGraph {
+        Default::default()
+    }
+
+    /// Drops the cached module of `filename`, so that it's read and parsed
+    /// again on the next load.
+    fn invalidate(&self, _filename: &Arc<FileName>) {}
 }
 
 /// A simple implementation of [LoadModule].
@@ -63,6 +99,19 @@ where
     cm: Arc<SourceMap>,
     env: Env,
     resolver: R,
+    fs: Arc<dyn FileSystem>,
+
+    /// Ambient modules (`declare module "foo"` in scripts) and the files
+    /// declaring them. These are preferred over the resolver, except wildcard
+    /// patterns like `*.css`.
+    ambient_modules: DashMap<String, Vec<Arc<FileName>>, FxBuildHasher>,
+
+    /// Imports of each file, used to build [ModuleGraph].
+    imports: DashMap<Arc<FileName>, Vec<ImportEdge>, FxBuildHasher>,
+
+    /// Files imported with `type: "json"`, which are parsed as json modules
+    /// regardless of the extension.
+    json_modules: DashSet<PathBuf, FxBuildHasher>,
 
     /// TODO(kdu1): Split the
     comments: StcComments,
@@ -73,6 +122,9 @@ where
     ids: ModuleIdGenerator,
     parse_cache: DashMap<Arc<FileName>, (Arc<ModuleRecord>, StcComments), FxBuildHasher>,
     parsing_errors: Mutex<Vec<swc_ecma_parser::error::Error>>,
+
+    cancellation_token: Option<CancellationToken>,
+    profiler: Option<Profiler>,
 }
 
 impl<R> ModuleLoader<R>
@@ -80,11 +132,22 @@ where
     R: Resolve,
 {
     pub fn new(cm: Arc<SourceMap>, env: Env, resolver: R) -> Self {
+        Self::with_fs(cm, env, resolver, Arc::new(RealFs))
+    }
+
+    /// Creates a module loader which reads files using `fs`.
+    ///
+    /// Note that `resolver` should use the same file system.
+    pub fn with_fs(cm: Arc<SourceMap>, env: Env, resolver: R, fs: Arc<dyn FileSystem>) -> Self {
         Self {
             cm,
             env,
             resolver,
+            fs,
 
+            ambient_modules: Default::default(),
+            imports: Default::default(),
+            json_modules: Default::default(),
             comments: Default::default(),
             loading_started: Default::default(),
             dep_graph: Default::default(),
@@ -92,9 +155,30 @@ where
             parse_cache: Default::default(),
             ids: Default::default(),
             parsing_errors: Default::default(),
+            cancellation_token: None,
+            profiler: None,
         }
     }
 
+    /// Stops loading dependencies once `token` is cancelled. Files requested
+    /// directly are still loaded, but the loaded modules are incomplete, so the
+    /// loader should not be reused after cancellation.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Records the time taken to parse each file and to resolve the names in
+    /// it to `profiler`.
+    pub fn with_profiler(mut self, profiler: Profiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation_token.as_ref().map_or(false, |token| token.is_cancelled())
+    }
+
     fn load_recursively(&self, filename: &Arc<FileName>, calc_cycles: bool) -> Result<ModuleId> {
         let (id, _) = self.ids.generate(filename);
 
@@ -105,27 +189,98 @@ where
 
         let (entry, comments) = self.parse(filename)?;
 
-        let (_declared_modules, references, deps) = find_modules_and_deps(&comments, &entry.ast);
+        let (declared_modules, references, deps, json_deps) = find_modules_and_deps(&comments, &entry.ast);
 
-        let deps = GLOBALS.with(|globals| {
-            (references.par_iter().map(|v| (v, false)))
-                .chain(deps.par_iter().map(|v| (v, true)))
-                .map(|(dep, is_normal_dep)| {
-                    GLOBALS.set(globals, || {
-                        let dep_path = Arc::new(self.resolver.resolve(filename, dep)?);
+        // `declare module "foo"` in a module is an augmentation, not an ambient module.
+        if !is_module(&entry.ast) {
+            for name in declared_modules {
+                self.ambient_modules.entry(name.to_string()).or_default().push(filename.clone());
+            }
+        }
 
-                        self.load_recursively(&dep_path, false).map(|v| (v, is_normal_dep))
-                    })
+        let deps = GLOBALS.with(|globals| {
+            let load = |dep_path: Option<FileName>| {
+                GLOBALS.set(globals, || {
+                    let dep_path = dep_path.map(Arc::new);
+                    let dep_id = if self.is_cancelled() {
+                        None
+                    } else {
+                        dep_path.as_ref().and_then(|path| self.load_recursively(path, false).ok())
+                    };
+
+                    (dep_path, dep_id)
                 })
+            };
+
+            // References may declare ambient modules used by imports, so imports are
+            // resolved after loading references. Relative imports resolved to files don't
+            // depend on ambient modules, so they are loaded in parallel with references.
+            let (loaded_references, loaded_imports) = rayon::join(
+                || {
+                    references
+                        .par_iter()
+                        .map(|dep| load(self.resolve(filename, dep).ok()))
+                        .collect::<Vec<_>>()
+                },
+                || {
+                    deps.par_iter()
+                        .map(|dep| {
+                            if json_deps.contains(dep) {
+                                self.resolve_json_module(filename, dep).map(|path| load(Some(path)))
+                            } else if is_relative(dep) {
+                                self.resolve_to_file(filename, dep).map(|path| load(Some(path)))
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                },
+            );
+            let loaded_imports = deps
+                .par_iter()
+                .zip(loaded_imports)
+                .map(|(dep, loaded)| loaded.unwrap_or_else(|| load(self.resolve(filename, dep).ok())))
+                .collect::<Vec<_>>();
+
+            references
+                .iter()
+                .zip(loaded_references)
+                .map(|(dep, (dep_path, dep_id))| (dep, false, dep_path, dep_id))
+                .chain(
+                    deps.iter()
+                        .zip(loaded_imports)
+                        .map(|(dep, (dep_path, dep_id))| (dep, true, dep_path, dep_id)),
+                )
                 .collect::<Vec<_>>()
         });
 
+        self.imports.insert(
+            filename.clone(),
+            deps.iter()
+                .map(|(dep, is_normal_dep, dep_path, _)| ImportEdge {
+                    from: filename.to_string(),
+                    specifier: dep.to_string(),
+                    resolved: dep_path.as_ref().map(|path| path.to_string()),
+                    kind: if *is_normal_dep {
+                        ImportKind::Import
+                    } else {
+                        ImportKind::Reference
+                    },
+                })
+                .collect(),
+        );
+
         {
             // Add to the dependency graph
 
             let mut g = self.dep_graph.write().unwrap();
 
-            for (dep, is_normal_dep) in deps.iter().flatten() {
+            for (_, is_normal_dep, _, dep) in &deps {
+                let dep = match dep {
+                    Some(dep) => dep,
+                    None => continue,
+                };
+
                 g.add_edge(id, *dep, ());
 
                 if entry.is_dts && !*is_normal_dep {
@@ -142,9 +297,15 @@ where
             };
 
             let mut cycles = self.cycles.write().unwrap();
-            for cycle in new {
+            for mut cycle in new {
                 if cycle.len() > 1 {
-                    cycles.push(cycle);
+                    // Ids depend on the order of loading, which is not deterministic. Files in a
+                    // cycle are checked together, in this order.
+                    cycle.sort_by_cached_key(|id| self.ids.path(*id).to_string());
+
+                    if !cycles.contains(&cycle) {
+                        cycles.push(cycle);
+                    }
                 }
             }
         }
@@ -152,6 +313,86 @@ where
         Ok(id)
     }
 
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName> {
+        if !is_relative(module_specifier) && self.ambient_modules.contains_key(module_specifier) {
+            return Ok(FileName::Custom(module_specifier.to_string()));
+        }
+
+        let resolved = self.resolver.resolve(base, module_specifier);
+        match &resolved {
+            Ok(FileName::Real(path)) if self.is_loadable(path) => return resolved,
+            Ok(FileName::Real(..)) => {}
+            Ok(..) => return resolved,
+            Err(..) => {}
+        }
+
+        // Wildcard ambient modules like `declare module "*.css"` are used only if
+        // there's no module file.
+        match self.find_pattern_ambient_module(module_specifier) {
+            Some(pattern) => Ok(FileName::Custom(pattern)),
+            None => resolved,
+        }
+    }
+
+    /// Resolves `module_specifier` using only the resolver.
+    ///
+    /// Returns [None] if it's not resolved to a file which can be loaded.
+    fn resolve_to_file(&self, base: &FileName, module_specifier: &str) -> Option<FileName> {
+        match self.resolver.resolve(base, module_specifier) {
+            Ok(FileName::Real(path)) if self.is_loadable(&path) => Some(FileName::Real(path)),
+            _ => None,
+        }
+    }
+
+    /// Resolves `module_specifier` imported with `type: "json"`, and marks the
+    /// file as a json module.
+    fn resolve_json_module(&self, base: &FileName, module_specifier: &str) -> Option<FileName> {
+        match self.resolver.resolve(base, module_specifier) {
+            Ok(FileName::Real(path)) => {
+                self.json_modules.insert(path.clone());
+                Some(FileName::Real(path))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `path` can be parsed as a module.
+    fn is_loadable(&self, path: &Path) -> bool {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ts" | "tsx" | "mts" | "cts") => true,
+            Some("js" | "jsx" | "mjs" | "cjs") => self.env.rule().allow_js,
+            _ => false,
+        }
+    }
+
+    /// Finds a wildcard ambient module matching `module_specifier`. If multiple
+    /// patterns match, the one with the longest prefix wins.
+    fn find_pattern_ambient_module(&self, module_specifier: &str) -> Option<String> {
+        let mut best: Option<(usize, String)> = None;
+
+        for entry in self.ambient_modules.iter() {
+            let pattern = entry.key();
+            let (prefix, suffix) = match pattern.split_once('*') {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let is_match = module_specifier.len() >= prefix.len() + suffix.len()
+                && module_specifier.starts_with(prefix)
+                && module_specifier.ends_with(suffix);
+            let is_better = match &best {
+                Some((len, best)) => *len < prefix.len() || (*len == prefix.len() && best > pattern),
+                None => true,
+            };
+
+            if is_match && is_better {
+                best = Some((prefix.len(), pattern.clone()));
+            }
+        }
+
+        best.map(|(_, pattern)| pattern)
+    }
+
     fn parse(&self, filename: &Arc<FileName>) -> Result<(Arc<ModuleRecord>, StcComments)> {
         if let Some(cached) = self.parse_cache.get(filename).as_deref().cloned() {
             return Ok(cached);
@@ -165,21 +406,78 @@ where
         Ok(record)
     }
 
+    /// Parses a json file as a module with a default export of the content.
+    fn parse_json_module(&self, filename: &Arc<FileName>, fm: &SourceFile) -> Result<(Arc<ModuleRecord>, StcComments)> {
+        let lexer = Lexer::new(Syntax::Es(Default::default()), EsVersion::latest(), StringInput::from(fm), None);
+
+        let mut parser = Parser::new_from(lexer);
+        let expr = match parser.parse_expr() {
+            Ok(v) => v,
+            Err(err) => {
+                let mut errors = self.parsing_errors.lock().unwrap();
+                errors.push(err);
+
+                bail!("Failed to parse {}", filename)
+            }
+        };
+
+        let (id, top_level_mark) = self.ids.generate(filename);
+        let span = Span::new(fm.start_pos, fm.end_pos, Default::default());
+
+        Ok((
+            Arc::new(ModuleRecord {
+                id,
+                filename: filename.clone(),
+                is_dts: false,
+                is_js: false,
+                top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+                ast: Module {
+                    span,
+                    body: vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                        span,
+                        expr,
+                    }))],
+                    shebang: Default::default(),
+                },
+            }),
+            self.comments.clone(),
+        ))
+    }
+
     /// This does not perform caching
     fn parse_inner(&self, filename: &Arc<FileName>) -> Result<(Arc<ModuleRecord>, StcComments)> {
         let comments = self.comments.clone();
 
         let (fm, syntax) = match &**filename {
             FileName::Real(path) => {
-                let fm = self
-                    .cm
-                    .load_file(path)
+                let src = self
+                    .fs
+                    .read_to_string(path)
                     .with_context(|| format!("failed to load module `{}`", path.display()))?;
+                let fm = self.cm.new_source_file((**filename).clone(), src);
+
+                if path.extension().map_or(false, |ext| ext == "json") || self.json_modules.contains(path) {
+                    return self.parse_json_module(filename, &fm);
+                }
 
-                let syntax = TsConfig {
-                    dts: path.as_os_str().to_string_lossy().ends_with(".d.ts"),
-                    tsx: path.extension().map(|v| v == "tsx").unwrap_or(false),
-                    ..Default::default()
+                let path_str = path.as_os_str().to_string_lossy();
+                let is_js = matches!(path.extension().and_then(|v| v.to_str()), Some("js" | "jsx" | "mjs" | "cjs"));
+                if is_js && !self.env.rule().allow_js {
+                    bail!("`{}` is a javascript file, but `allowJs` is not enabled", path.display());
+                }
+
+                let syntax = if is_js {
+                    Syntax::Es(EsConfig {
+                        jsx: true,
+                        import_assertions: true,
+                        ..Default::default()
+                    })
+                } else {
+                    Syntax::Typescript(TsConfig {
+                        dts: path_str.ends_with(".d.ts") || path_str.ends_with(".d.mts") || path_str.ends_with(".d.cts"),
+                        tsx: path.extension().map(|v| v == "tsx").unwrap_or(false),
+                        ..Default::default()
+                    })
                 };
 
                 (fm, syntax)
@@ -195,6 +493,7 @@ where
                         id,
                         filename: filename.clone(),
                         is_dts: false,
+                        is_js: false,
                         top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
                         ast: Module {
                             span: Span::new(fm.start_pos, fm.end_pos, Default::default()),
@@ -211,12 +510,8 @@ where
             }
         };
 
-        let lexer = Lexer::new(
-            Syntax::Typescript(syntax),
-            EsVersion::latest(),
-            StringInput::from(&*fm),
-            Some(&comments),
-        );
+        let start = Instant::now();
+        let lexer = Lexer::new(syntax, EsVersion::latest(), StringInput::from(&*fm), Some(&comments));
 
         let mut parser = Parser::new_from(lexer);
         let result = parser.parse_module();
@@ -236,19 +531,28 @@ where
             errors.extend(extra_errors);
         }
 
+        if let Some(profiler) = &self.profiler {
+            profiler.record("parse", filename.to_string(), start);
+        }
+
         let (id, top_level_mark) = self.ids.generate(filename);
         let top_level_ctxt = SyntaxContext::empty().apply_mark(top_level_mark);
 
+        let start = Instant::now();
         ast.visit_mut_with(&mut swc_ecma_transforms_base::resolver(
             self.env.shared().marks().unresolved_mark(),
             top_level_mark,
             true,
         ));
+        if let Some(profiler) = &self.profiler {
+            profiler.record("bind", filename.to_string(), start);
+        }
 
         Ok((
             Arc::new(ModuleRecord {
                 id,
-                is_dts: syntax.dts,
+                is_dts: syntax.dts(),
+                is_js: !syntax.typescript(),
                 filename: filename.clone(),
                 top_level_ctxt,
                 ast,
@@ -300,10 +604,83 @@ where
 
     fn load_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<Records> {
         let filename = self
-            .resolver
             .resolve(base, module_specifier)
             .with_context(|| format!("failed to resolve `{}` from `{}`", module_specifier, base))?;
 
         self.load_module(&Arc::new(filename), false)
     }
+
+    fn resolve_dep(&self, base: &Arc<FileName>, module_specifier: &str) -> Result<FileName> {
+        self.resolve(base, module_specifier)
+    }
+
+    fn fs(&self) -> Arc<dyn FileSystem> {
+        self.fs.clone()
+    }
+
+    fn ambient_module_files(&self, name: &str) -> Vec<Arc<FileName>> {
+        self.ambient_modules.get(name).map(|files| files.clone()).unwrap_or_default()
+    }
+
+    fn module_graph(&self) -> ModuleGraph {
+        let mut modules = self
+            .parse_cache
+            .iter()
+            .filter(|entry| matches!(&**entry.key(), FileName::Real(..)))
+            .map(|entry| ModuleNode {
+                path: entry.key().to_string(),
+                is_dts: entry.value().0.is_dts,
+            })
+            .collect::<Vec<_>>();
+        modules.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut imports = self
+            .imports
+            .iter()
+            .map(|entry| (entry.key().to_string(), entry.value().clone()))
+            .collect::<Vec<_>>();
+        imports.sort_by(|a, b| a.0.cmp(&b.0));
+
+        ModuleGraph {
+            modules,
+            imports: imports.into_iter().flat_map(|(_, imports)| imports).collect(),
+        }
+    }
+
+    fn invalidate(&self, filename: &Arc<FileName>) {
+        let (id, _) = self.ids.generate(filename);
+
+        self.parse_cache.remove(filename);
+        self.loading_started.remove(filename);
+        self.imports.remove(filename);
+
+        for mut files in self.ambient_modules.iter_mut() {
+            files.retain(|file| file != filename);
+        }
+        self.ambient_modules.retain(|_, files| !files.is_empty());
+
+        {
+            // Imports of the file are added again when it's loaded.
+            let mut g = self.dep_graph.write().unwrap();
+
+            let deps = g.neighbors_directed(id, Outgoing).collect::<Vec<_>>();
+            for dep in deps {
+                g.remove_edge(id, dep);
+            }
+        }
+
+        // Cycles are calculated again when the file is loaded as an entry.
+        self.cycles.write().unwrap().retain(|cycle| !cycle.contains(&id));
+    }
+}
+
+/// Returns `true` if `module_specifier` is relative to the importing file, like
+/// `./foo` or `../foo`.
+fn is_relative(module_specifier: &str) -> bool {
+    module_specifier.starts_with("./") || module_specifier.starts_with("../")
+}
+
+/// Returns `true` if `m` has a top-level import or export.
+fn is_module(m: &Module) -> bool {
+    m.body.iter().any(|item| matches!(item, ModuleItem::ModuleDecl(..)))
 }
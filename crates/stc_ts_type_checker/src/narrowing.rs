@@ -0,0 +1,285 @@
+//! Narrowed types at use sites and the conditions which narrowed them.
+
+use std::sync::Arc;
+
+use stc_ts_types::{printer::print_type, Type};
+use swc_common::{BytePos, FileName, SourceMap, Span, Spanned, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{loader::LoadModule, query::IdentFinder, Checker};
+
+/// The type of an expression at a use site.
+#[derive(Debug, Clone)]
+pub struct NarrowedType {
+    /// The span of the expression.
+    pub span: Span,
+    /// The type after narrowing.
+    pub ty: Type,
+    /// `ty` printed as a typescript type.
+    pub display: String,
+    /// The declared type of the variable, or [None] if the expression is not
+    /// a variable.
+    pub declared: Option<String>,
+    /// Conditions which may have narrowed the type, outermost first.
+    pub guards: Vec<Guard>,
+}
+
+/// A condition which is known to be truthy or falsy at a use site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guard {
+    pub span: Span,
+    /// The source code of the condition.
+    pub text: String,
+    /// `false` if the condition is falsy at the use site, like in an `else`
+    /// branch or after `if (cond) return;`.
+    pub assumed: bool,
+}
+
+impl NarrowedType {
+    /// Returns `true` if the type is narrowed to `never`, which means that the
+    /// use site is unreachable for the values of the declared type.
+    pub fn is_never(&self) -> bool {
+        self.ty.is_never()
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the type of the smallest expression covering `pos`, which is a
+    /// byte offset in `file`, after narrowing by control flow, with the
+    /// conditions which applied.
+    ///
+    /// Guards are found syntactically from the enclosing `if` statements,
+    /// conditional expressions, `&&`, `||`, loops, `switch` cases and
+    /// preceding `if` statements which exit, and only the ones referring to
+    /// the variable of the expression are returned. For `a.b`, the variable
+    /// is `a`.
+    ///
+    /// Returns [None] if types are not recorded (see
+    /// [Checker::with_type_recorder]), or `file` is not checked yet.
+    pub fn narrowed_type_at(&self, file: &FileName, pos: usize) -> Option<NarrowedType> {
+        let recorder = self.type_recorder.as_ref()?;
+        let info = self.type_at(file, pos)?;
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        // The variable of the expression.
+        let mut finder = IdentFinder {
+            pos: info.span.lo,
+            found: None,
+        };
+        record.ast.visit_with(&mut finder);
+        let target = finder.found;
+
+        let declared = target
+            .as_ref()
+            .filter(|(_, span)| span.lo == info.span.lo && span.hi == info.span.hi)
+            .and_then(|(id, _)| recorder.var_type(&stc_ts_types::Id::new(id.0.clone(), id.1)))
+            .map(|ty| print_type(&ty));
+
+        let guards = match target {
+            Some((id, _)) => {
+                let mut finder = GuardFinder {
+                    cm: &self.cm,
+                    pos: info.span.lo,
+                    target: &id,
+                    guards: vec![],
+                    found: None,
+                };
+                record.ast.visit_with(&mut finder);
+                finder.found.unwrap_or_default()
+            }
+            None => vec![],
+        };
+
+        Some(NarrowedType {
+            span: info.span,
+            ty: info.ty,
+            display: info.display,
+            declared,
+            guards,
+        })
+    }
+}
+
+/// Finds the guards applied at `pos`.
+struct GuardFinder<'a> {
+    cm: &'a SourceMap,
+    pos: BytePos,
+    target: &'a Id,
+    /// Guards of the node being visited.
+    guards: Vec<Guard>,
+    found: Option<Vec<Guard>>,
+}
+
+impl GuardFinder<'_> {
+    /// Visits the nodes in `op` with a guard of `cond`, if it refers to the
+    /// target.
+    fn with_guard(&mut self, cond: &Expr, assumed: bool, op: impl FnOnce(&mut Self)) {
+        let pushed = self.push(cond, assumed);
+        op(self);
+        if pushed {
+            self.guards.pop();
+        }
+    }
+
+    /// Pushes a guard of `cond` if it refers to the target, and returns `true`
+    /// if pushed.
+    fn push(&mut self, cond: &Expr, assumed: bool) -> bool {
+        if !self.refers(cond) {
+            return false;
+        }
+
+        self.guards.push(Guard {
+            span: cond.span().with_ctxt(SyntaxContext::empty()),
+            text: self.snippet(cond.span()),
+            assumed,
+        });
+        true
+    }
+
+    fn refers(&self, e: &Expr) -> bool {
+        let mut finder = RefFinder {
+            target: self.target,
+            found: false,
+        };
+        e.visit_with(&mut finder);
+        finder.found
+    }
+
+    fn snippet(&self, span: Span) -> String {
+        self.cm.span_to_snippet(span).unwrap_or_default()
+    }
+}
+
+impl Visit for GuardFinder<'_> {
+    fn visit_ident(&mut self, i: &Ident) {
+        if i.span.lo == self.pos {
+            self.found = Some(self.guards.clone());
+        }
+    }
+
+    fn visit_if_stmt(&mut self, s: &IfStmt) {
+        s.test.visit_with(self);
+        self.with_guard(&s.test, true, |v| s.cons.visit_with(v));
+        self.with_guard(&s.test, false, |v| s.alt.visit_with(v));
+    }
+
+    fn visit_cond_expr(&mut self, e: &CondExpr) {
+        e.test.visit_with(self);
+        self.with_guard(&e.test, true, |v| e.cons.visit_with(v));
+        self.with_guard(&e.test, false, |v| e.alt.visit_with(v));
+    }
+
+    fn visit_bin_expr(&mut self, e: &BinExpr) {
+        e.left.visit_with(self);
+        match e.op {
+            BinaryOp::LogicalAnd => self.with_guard(&e.left, true, |v| e.right.visit_with(v)),
+            BinaryOp::LogicalOr => self.with_guard(&e.left, false, |v| e.right.visit_with(v)),
+            _ => e.right.visit_with(self),
+        }
+    }
+
+    fn visit_while_stmt(&mut self, s: &WhileStmt) {
+        s.test.visit_with(self);
+        self.with_guard(&s.test, true, |v| s.body.visit_with(v));
+    }
+
+    fn visit_for_stmt(&mut self, s: &ForStmt) {
+        s.init.visit_with(self);
+        match &s.test {
+            Some(test) => {
+                test.visit_with(self);
+                self.with_guard(test, true, |v| {
+                    s.update.visit_with(v);
+                    s.body.visit_with(v);
+                });
+            }
+            None => {
+                s.update.visit_with(self);
+                s.body.visit_with(self);
+            }
+        }
+    }
+
+    fn visit_switch_stmt(&mut self, s: &SwitchStmt) {
+        s.discriminant.visit_with(self);
+
+        for case in &s.cases {
+            case.test.visit_with(self);
+
+            let pushed = match &case.test {
+                Some(test) if self.refers(&s.discriminant) => {
+                    self.guards.push(Guard {
+                        span: test.span().with_ctxt(SyntaxContext::empty()),
+                        text: format!("{} === {}", self.snippet(s.discriminant.span()), self.snippet(test.span())),
+                        assumed: true,
+                    });
+                    true
+                }
+                _ => false,
+            };
+
+            case.cons.visit_with(self);
+            if pushed {
+                self.guards.pop();
+            }
+        }
+    }
+
+    fn visit_stmts(&mut self, stmts: &[Stmt]) {
+        let len = self.guards.len();
+
+        for stmt in stmts {
+            stmt.visit_with(self);
+
+            // `if (cond) return;` guards the following statements.
+            if let Stmt::If(s) = stmt {
+                match (exits(&s.cons), s.alt.as_deref().map(exits)) {
+                    (true, None | Some(false)) => {
+                        self.push(&s.test, false);
+                    }
+                    (false, Some(true)) => {
+                        self.push(&s.test, true);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.guards.truncate(len);
+    }
+}
+
+/// Returns `true` if `stmt` always exits the enclosing block.
+fn exits(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Return(..) | Stmt::Throw(..) | Stmt::Break(..) | Stmt::Continue(..) => true,
+        Stmt::Block(b) => b.stmts.last().map_or(false, exits),
+        Stmt::If(s) => exits(&s.cons) && s.alt.as_deref().map_or(false, exits),
+        _ => false,
+    }
+}
+
+/// Finds a reference to `target`.
+struct RefFinder<'a> {
+    target: &'a Id,
+    found: bool,
+}
+
+impl Visit for RefFinder<'_> {
+    fn visit_ident(&mut self, i: &Ident) {
+        if i.to_id() == *self.target {
+            self.found = true;
+        }
+    }
+
+    fn visit_member_prop(&mut self, p: &MemberProp) {
+        // `a.b` doesn't refer to `b`.
+        if let MemberProp::Computed(c) = p {
+            c.visit_with(self);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Outlines of files, for the outline and breadcrumbs of editors.
+
+use std::{
+    mem::{replace, take},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use swc_common::{FileName, SourceMap, Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{declarations::DeclarationSpan, loader::LoadModule, Checker};
+
+/// A declaration in the outline of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The span of the whole declaration.
+    pub span: DeclarationSpan,
+    /// The span of the name.
+    pub selection_span: DeclarationSpan,
+    /// Declarations nested in this one, like methods of a class.
+    pub children: Vec<DocumentSymbol>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymbolKind {
+    /// `declare module 'name'`.
+    Module,
+    Namespace,
+    Class,
+    Constructor,
+    Method,
+    Property,
+    Interface,
+    TypeAlias,
+    Enum,
+    EnumMember,
+    Function,
+    Variable,
+    Constant,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the declarations in `file`, with the declarations nested in
+    /// them as children, sorted by the position.
+    ///
+    /// Returns [None] if `file` is not loaded. Declarations in blocks are
+    /// included in the enclosing declaration.
+    pub fn document_symbols(&self, file: &FileName) -> Option<Vec<DocumentSymbol>> {
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        let mut collector = OutlineCollector {
+            cm: &self.cm,
+            in_pat: false,
+            symbols: vec![],
+        };
+        record.ast.visit_with(&mut collector);
+
+        Some(collector.symbols)
+    }
+}
+
+struct OutlineCollector<'a> {
+    cm: &'a SourceMap,
+    /// `true` while visiting the destructuring pattern of a variable
+    /// declaration.
+    in_pat: bool,
+    symbols: Vec<DocumentSymbol>,
+}
+
+impl OutlineCollector<'_> {
+    /// Adds a symbol with the symbols added by `op` as children.
+    fn add(&mut self, name: String, kind: SymbolKind, span: Span, name_span: Span, op: impl FnOnce(&mut Self)) {
+        let symbols = take(&mut self.symbols);
+        op(self);
+        let children = replace(&mut self.symbols, symbols);
+
+        self.symbols.push(DocumentSymbol {
+            name,
+            kind,
+            span: DeclarationSpan::new(self.cm, span),
+            selection_span: DeclarationSpan::new(self.cm, name_span),
+            children,
+        });
+    }
+
+    fn prop_name(&self, name: &PropName) -> String {
+        match name {
+            PropName::Ident(i) => i.sym.to_string(),
+            PropName::Str(s) => s.value.to_string(),
+            PropName::Num(n) => n.value.to_string(),
+            PropName::BigInt(b) => b.value.to_string(),
+            PropName::Computed(c) => self.cm.span_to_snippet(c.span).unwrap_or_default(),
+        }
+    }
+
+    fn expr_name(&self, key: &Expr) -> String {
+        match key {
+            Expr::Ident(i) => i.sym.to_string(),
+            Expr::Lit(Lit::Str(s)) => s.value.to_string(),
+            _ => format!("[{}]", self.cm.span_to_snippet(key.span()).unwrap_or_default()),
+        }
+    }
+}
+
+impl Visit for OutlineCollector<'_> {
+    fn visit_fn_decl(&mut self, f: &FnDecl) {
+        self.add(f.ident.sym.to_string(), SymbolKind::Function, f.function.span, f.ident.span, |c| {
+            f.function.visit_with(c)
+        });
+    }
+
+    fn visit_class_decl(&mut self, c: &ClassDecl) {
+        self.add(c.ident.sym.to_string(), SymbolKind::Class, c.class.span, c.ident.span, |v| {
+            c.class.visit_with(v)
+        });
+    }
+
+    fn visit_constructor(&mut self, c: &Constructor) {
+        self.add("constructor".into(), SymbolKind::Constructor, c.span, c.key.span(), |v| {
+            c.body.visit_with(v)
+        });
+    }
+
+    fn visit_class_method(&mut self, m: &ClassMethod) {
+        self.add(self.prop_name(&m.key), SymbolKind::Method, m.span, m.key.span(), |v| {
+            m.function.visit_with(v)
+        });
+    }
+
+    fn visit_private_method(&mut self, m: &PrivateMethod) {
+        self.add(format!("#{}", m.key.id.sym), SymbolKind::Method, m.span, m.key.span, |v| {
+            m.function.visit_with(v)
+        });
+    }
+
+    fn visit_class_prop(&mut self, p: &ClassProp) {
+        self.add(self.prop_name(&p.key), SymbolKind::Property, p.span, p.key.span(), |v| {
+            p.value.visit_with(v)
+        });
+    }
+
+    fn visit_private_prop(&mut self, p: &PrivateProp) {
+        self.add(format!("#{}", p.key.id.sym), SymbolKind::Property, p.span, p.key.span, |v| {
+            p.value.visit_with(v)
+        });
+    }
+
+    fn visit_var_declarator(&mut self, d: &VarDeclarator) {
+        // The kind is set by `visit_var_decl`.
+        match &d.name {
+            Pat::Ident(i) => self.add(i.id.sym.to_string(), SymbolKind::Variable, d.span, i.id.span, |c| {
+                d.init.visit_with(c)
+            }),
+            _ => {
+                let in_pat = replace(&mut self.in_pat, true);
+                d.name.visit_with(self);
+                self.in_pat = in_pat;
+
+                d.init.visit_with(self);
+            }
+        }
+    }
+
+    fn visit_binding_ident(&mut self, i: &BindingIdent) {
+        if self.in_pat {
+            self.add(i.id.sym.to_string(), SymbolKind::Variable, i.id.span, i.id.span, |_| {});
+        }
+    }
+
+    fn visit_assign_pat_prop(&mut self, p: &AssignPatProp) {
+        if self.in_pat {
+            self.add(p.key.sym.to_string(), SymbolKind::Variable, p.key.span, p.key.span, |_| {});
+        }
+        p.value.visit_with(self);
+    }
+
+    fn visit_expr(&mut self, e: &Expr) {
+        // Default values in patterns are not declarations.
+        let in_pat = replace(&mut self.in_pat, false);
+        e.visit_children_with(self);
+        self.in_pat = in_pat;
+    }
+
+    fn visit_var_decl(&mut self, v: &VarDecl) {
+        let len = self.symbols.len();
+        v.visit_children_with(self);
+
+        if v.kind == VarDeclKind::Const {
+            for symbol in &mut self.symbols[len..] {
+                if symbol.kind == SymbolKind::Variable {
+                    symbol.kind = SymbolKind::Constant;
+                }
+            }
+        }
+    }
+
+    fn visit_function(&mut self, f: &Function) {
+        // Parameters are not in outlines.
+        f.body.visit_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, f: &ArrowExpr) {
+        f.body.visit_with(self);
+    }
+
+    fn visit_catch_clause(&mut self, c: &CatchClause) {
+        c.body.visit_with(self);
+    }
+
+    fn visit_ts_interface_decl(&mut self, d: &TsInterfaceDecl) {
+        self.add(d.id.sym.to_string(), SymbolKind::Interface, d.span, d.id.span, |c| {
+            d.body.visit_with(c)
+        });
+    }
+
+    fn visit_ts_property_signature(&mut self, p: &TsPropertySignature) {
+        self.add(self.expr_name(&p.key), SymbolKind::Property, p.span, p.key.span(), |_| {});
+    }
+
+    fn visit_ts_method_signature(&mut self, m: &TsMethodSignature) {
+        self.add(self.expr_name(&m.key), SymbolKind::Method, m.span, m.key.span(), |_| {});
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, d: &TsTypeAliasDecl) {
+        self.add(d.id.sym.to_string(), SymbolKind::TypeAlias, d.span, d.id.span, |_| {});
+    }
+
+    fn visit_ts_enum_decl(&mut self, d: &TsEnumDecl) {
+        self.add(d.id.sym.to_string(), SymbolKind::Enum, d.span, d.id.span, |c| {
+            d.members.visit_with(c)
+        });
+    }
+
+    fn visit_ts_enum_member(&mut self, m: &TsEnumMember) {
+        let name = match &m.id {
+            TsEnumMemberId::Ident(i) => i.sym.to_string(),
+            TsEnumMemberId::Str(s) => s.value.to_string(),
+        };
+
+        self.add(name, SymbolKind::EnumMember, m.span, m.id.span(), |_| {});
+    }
+
+    fn visit_ts_module_decl(&mut self, d: &TsModuleDecl) {
+        let (name, kind) = match &d.id {
+            TsModuleName::Ident(i) => (i.sym.to_string(), SymbolKind::Namespace),
+            TsModuleName::Str(s) => (format!("\"{}\"", s.value), SymbolKind::Module),
+        };
+
+        self.add(name, kind, d.span, d.id.span(), |c| d.body.visit_with(c));
+    }
+
+    fn visit_ts_namespace_decl(&mut self, d: &TsNamespaceDecl) {
+        self.add(d.id.sym.to_string(), SymbolKind::Namespace, d.span, d.id.span, |c| {
+            d.body.visit_with(c)
+        });
+    }
+
+    fn visit_ts_type(&mut self, _: &TsType) {}
+}
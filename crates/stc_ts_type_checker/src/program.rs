@@ -0,0 +1,382 @@
+//! Results of [Checker::check] and [Checker::check_all].
+
+use std::{path::PathBuf, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use stc_ts_errors::{Error, ErrorKind, Fix, Report, Severity};
+use stc_ts_types::{ModuleId, Type};
+use swc_common::{BytePos, FileName, SourceMap, Span, Spanned, DUMMY_SP};
+
+use crate::{loader::LoadModule, Checker};
+
+/// The modules checked by [Checker::check] or [Checker::check_all] and their
+/// errors.
+#[derive(Debug)]
+pub struct Program {
+    /// The module of the entry file, or the first entry file for
+    /// [Checker::check_all]. [None] if no entry is loaded.
+    pub entry: Option<ModuleId>,
+    /// The modules of all entry files, in the order passed to
+    /// [Checker::check_all]. Entries which can't be loaded are skipped.
+    pub entries: Vec<ModuleId>,
+    /// All analyzed modules, including modules analyzed by previous checks,
+    /// sorted by the path.
+    pub modules: Vec<CheckedModule>,
+    /// Errors of this check which are not in a file.
+    pub global_errors: Vec<Error>,
+}
+
+#[derive(Debug)]
+pub struct CheckedModule {
+    pub id: ModuleId,
+    pub file: Arc<FileName>,
+    /// The type of the module, which contains the exported variables and
+    /// types.
+    pub exports: Type,
+    /// Errors found in the module by this check. Errors found by previous
+    /// checks are not included.
+    pub errors: Vec<Error>,
+}
+
+/// An error as plain data, for hosts which don't use the types of stc, like
+/// bindings for other languages. The outputs of stc, like SARIF logs and the
+/// diagnostics of the language server, are created from it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    /// [None] if the error is not in a file.
+    pub file: Option<String>,
+    /// Byte offset in the file.
+    pub start: usize,
+    /// Byte offset in the file.
+    pub end: usize,
+    /// 1-based.
+    pub line: usize,
+    /// 0-based, in characters.
+    pub column: usize,
+    /// The line of `end`. 1-based.
+    pub end_line: usize,
+    /// The column of `end`. 0-based, in characters.
+    pub end_column: usize,
+    /// The code of the error, like `2322` for `TS2322`.
+    pub code: usize,
+    pub message: String,
+    /// See [Error::severity].
+    pub severity: Severity,
+    /// Other locations related to the error. See [ErrorKind::related_spans].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedLocation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<DiagnosticTag>,
+    /// See [ErrorKind::fixes].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<DiagnosticFix>,
+}
+
+/// How editors show a [Diagnostic], in addition to its severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticTag {
+    /// The code is unused or unreachable. Editors fade it out.
+    Unnecessary,
+}
+
+impl DiagnosticTag {
+    fn of(err: &ErrorKind) -> Vec<Self> {
+        match err {
+            ErrorKind::UnreachableCode { .. } | ErrorKind::UnusedExport { .. } => vec![DiagnosticTag::Unnecessary],
+            _ => vec![],
+        }
+    }
+}
+
+/// A fix of a [Diagnostic], which is applied by replacing the source code in
+/// all `edits`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticFix {
+    pub message: String,
+    /// Edits in the file of the diagnostic, sorted by the position. The edits
+    /// don't overlap.
+    pub edits: Vec<TextEdit>,
+}
+
+/// Replaces the source code from `start` to `end` with `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+    /// Byte offset in the file.
+    pub start: usize,
+    /// Byte offset in the file. Equal to `start` for an insertion.
+    pub end: usize,
+    pub new_text: String,
+}
+
+/// A location related to a [Diagnostic], like the declaration of a callee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedLocation {
+    pub file: String,
+    /// Byte offset in the file.
+    pub start: usize,
+    /// Byte offset in the file.
+    pub end: usize,
+    /// 1-based.
+    pub line: usize,
+    /// 0-based, in characters.
+    pub column: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Converts `err`, whose spans are in `cm`.
+    pub fn new(cm: &SourceMap, err: &Error) -> Self {
+        let mut diagnostic = diagnostic_at(
+            cm,
+            err.span(),
+            ErrorKind::normalize_error_code(err.code()),
+            err.message_chain(),
+            err.severity(),
+        );
+
+        diagnostic.related = err
+            .related_spans()
+            .into_iter()
+            .filter_map(|(span, message)| {
+                let d = diagnostic_at(cm, span, 0, message, Severity::Info);
+
+                Some(RelatedLocation {
+                    file: d.file?,
+                    start: d.start,
+                    end: d.end,
+                    line: d.line,
+                    column: d.column,
+                    message: d.message,
+                })
+            })
+            .collect();
+        diagnostic.tags = DiagnosticTag::of(err);
+        diagnostic.fixes = err
+            .fixes(cm)
+            .into_iter()
+            .filter_map(|fix| DiagnosticFix::new(cm, err.span(), fix))
+            .collect();
+
+        diagnostic
+    }
+
+    /// Converts `report`, whose spans are in `cm`. The inverse of
+    /// [Diagnostic::report].
+    pub fn from_report(cm: &SourceMap, report: &Report) -> Self {
+        let mut diagnostic = diagnostic_at(cm, report.span, report.code, report.message.clone(), report.severity);
+
+        diagnostic.related = report
+            .related
+            .iter()
+            .filter_map(|(span, message)| {
+                let d = diagnostic_at(cm, *span, 0, message.clone(), Severity::Info);
+
+                Some(RelatedLocation {
+                    file: d.file?,
+                    start: d.start,
+                    end: d.end,
+                    line: d.line,
+                    column: d.column,
+                    message: d.message,
+                })
+            })
+            .collect();
+
+        diagnostic
+    }
+
+    /// Converts the diagnostic back to a [Report], to render it with the
+    /// source code. Locations in files which are not loaded into `cm` are
+    /// dropped.
+    ///
+    /// Files are found by their names, so a file which is loaded into `cm`
+    /// more than once, like a file re-checked by [Checker::invalidate_file],
+    /// is resolved to its first source.
+    pub fn report(&self, cm: &SourceMap) -> Report {
+        Report {
+            span: span_in(cm, self.file.as_deref(), self.start, self.end),
+            severity: self.severity,
+            code: self.code,
+            message: self.message.clone(),
+            related: self
+                .related
+                .iter()
+                .map(|related| {
+                    (
+                        span_in(cm, Some(&related.file), related.start, related.end),
+                        related.message.clone(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The span of `start..end` in `file`, or a dummy span if `file` is not loaded.
+fn span_in(cm: &SourceMap, file: Option<&str>, start: usize, end: usize) -> Span {
+    match file.and_then(|file| cm.get_source_file(&FileName::Real(PathBuf::from(file)))) {
+        Some(fm) => Span::new(
+            fm.start_pos + BytePos(start as u32),
+            fm.start_pos + BytePos(end as u32),
+            Default::default(),
+        ),
+        None => DUMMY_SP,
+    }
+}
+
+impl DiagnosticFix {
+    /// Returns [None] if `fix` edits a file other than the file of `span`.
+    fn new(cm: &SourceMap, span: Span, fix: Fix) -> Option<Self> {
+        if span.is_dummy() {
+            return None;
+        }
+        let file = cm.lookup_byte_offset(span.lo).sf;
+
+        let mut edits = fix
+            .edits
+            .into_iter()
+            .map(|edit| {
+                if edit.span.lo < file.start_pos || edit.span.hi > file.end_pos {
+                    return None;
+                }
+
+                Some(TextEdit {
+                    start: (edit.span.lo - file.start_pos).0 as usize,
+                    end: (edit.span.hi - file.start_pos).0 as usize,
+                    new_text: edit.new_text,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        edits.sort_by_key(|edit| edit.start);
+
+        Some(DiagnosticFix {
+            message: fix.message,
+            edits,
+        })
+    }
+}
+
+impl Program {
+    pub fn module(&self, id: ModuleId) -> Option<&CheckedModule> {
+        self.modules.iter().find(|module| module.id == id)
+    }
+
+    pub fn module_of_file(&self, file: &FileName) -> Option<&CheckedModule> {
+        self.modules.iter().find(|module| *module.file == *file)
+    }
+
+    /// Returns all errors of this check.
+    pub fn errors(&self) -> impl '_ + Iterator<Item = &Error> {
+        self.modules
+            .iter()
+            .flat_map(|module| module.errors.iter())
+            .chain(self.global_errors.iter())
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Creates a [Program] with `errors`, which are the errors of a check.
+    pub(crate) fn program(&self, entries: Vec<ModuleId>, errors: Vec<Error>) -> Program {
+        let mut modules = vec![];
+        for module in self.module_graph().modules {
+            let file = Arc::new(FileName::Real(PathBuf::from(module.path)));
+            let id = match self.module_loader.load_module(&file, false) {
+                Ok(records) => records.entry.id,
+                Err(..) => continue,
+            };
+
+            if let Some(exports) = self.get_types(id) {
+                modules.push(CheckedModule {
+                    id,
+                    file,
+                    exports,
+                    errors: vec![],
+                });
+            }
+        }
+
+        let mut global_errors = vec![];
+        for err in errors {
+            let module = self
+                .file_of_error(&err)
+                .and_then(|file| modules.iter_mut().find(|module| *module.file == file));
+
+            match module {
+                Some(module) => module.errors.push(err),
+                None => global_errors.push(err),
+            }
+        }
+
+        Program {
+            entry: entries.first().copied(),
+            entries,
+            modules,
+            global_errors,
+        }
+    }
+
+    /// Converts `err` to a [Diagnostic].
+    pub fn to_diagnostic(&self, err: &Error) -> Diagnostic {
+        Diagnostic::new(&self.cm, err)
+    }
+
+    /// Returns the file containing the span of `err`, or [None] if the span is
+    /// dummy.
+    pub(crate) fn file_of_error(&self, err: &Error) -> Option<FileName> {
+        let span = err.span();
+        if span.is_dummy() {
+            return None;
+        }
+
+        Some(self.cm.span_to_filename(span))
+    }
+}
+
+/// Creates a [Diagnostic] for an error at `span` of a file in `cm`.
+pub(crate) fn diagnostic_at(cm: &SourceMap, span: Span, code: usize, message: String, severity: Severity) -> Diagnostic {
+    if span.is_dummy() {
+        return Diagnostic {
+            file: None,
+            start: 0,
+            end: 0,
+            line: 0,
+            column: 0,
+            end_line: 0,
+            end_column: 0,
+            code,
+            message,
+            severity,
+            related: vec![],
+            tags: vec![],
+            fixes: vec![],
+        };
+    }
+
+    let loc = cm.lookup_char_pos(span.lo);
+    let end_loc = cm.lookup_char_pos(span.hi);
+    let start = (span.lo - loc.file.start_pos).0 as usize;
+
+    Diagnostic {
+        file: Some(loc.file.name.to_string()),
+        start,
+        end: start + (span.hi - span.lo).0 as usize,
+        line: loc.line,
+        column: loc.col_display,
+        end_line: end_loc.line,
+        end_column: end_loc.col_display,
+        code,
+        message,
+        severity,
+        related: vec![],
+        tags: vec![],
+        fixes: vec![],
+    }
+}
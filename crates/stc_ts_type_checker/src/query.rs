@@ -0,0 +1,651 @@
+//! Queries about checked files, for editors and other tools.
+
+use std::{mem::replace, path::PathBuf, sync::Arc};
+
+use fxhash::{FxHashMap, FxHashSet};
+use stc_ts_types::{printer::print_type, Type};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{BytePos, FileName, SourceFile, Span, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    js::{collect_pat_names, export_name},
+    loader::{LoadModule, ModuleRecord},
+    Checker,
+};
+
+/// The type of a node.
+#[derive(Debug, Clone)]
+pub struct TypeInfo {
+    /// The span of the node.
+    pub span: Span,
+    pub ty: Type,
+    /// `ty` printed as a typescript type.
+    pub display: String,
+}
+
+/// A declaration of a symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    pub file: Arc<FileName>,
+    /// The span of the declared name, or the span of the declaration if it
+    /// does not have a name.
+    pub span: Span,
+}
+
+/// A reference to a symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub file: Arc<FileName>,
+    pub span: Span,
+    pub kind: ReferenceKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// The name of a declaration.
+    Declaration,
+    Read,
+    /// The target of an assignment, or the operand of `++` or `--`.
+    Write,
+}
+
+/// What an exported name refers to.
+enum ExportTarget {
+    Local(Id),
+    /// `name` is [None] for `export * as ns from 'src'`.
+    Reexport {
+        src: JsWord,
+        name: Option<JsWord>,
+    },
+    /// An anonymous default export.
+    Anonymous(Span),
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the type of the smallest expression covering `pos`, which is
+    /// a byte offset in `file`.
+    ///
+    /// Returns [None] if types are not recorded (see
+    /// [Checker::with_type_recorder]), or `file` is not checked yet.
+    pub fn type_at(&self, file: &FileName, pos: usize) -> Option<TypeInfo> {
+        let recorder = self.type_recorder.as_ref()?;
+        let fm = self.source_file(file)?;
+
+        let pos = fm.start_pos + BytePos(pos as u32);
+        if pos >= fm.end_pos {
+            return None;
+        }
+
+        let (span, ty) = recorder.type_at(pos)?;
+
+        Some(TypeInfo {
+            span,
+            display: print_type(&ty),
+            ty,
+        })
+    }
+
+    /// Returns the declarations of the symbol named by the identifier at
+    /// `pos`, which is a byte offset in `file`.
+    ///
+    /// Imports are followed to the declarations in the imported modules, and
+    /// all declarations of merged symbols are returned. Properties are not
+    /// resolved.
+    pub fn definition_at(&self, file: &FileName, pos: usize) -> Vec<Definition> {
+        let filename = Arc::new(file.clone());
+        let (fm, record) = match (self.source_file(file), self.find_record(&filename)) {
+            (Some(fm), Some(record)) => (fm, record),
+            _ => return vec![],
+        };
+
+        let mut finder = IdentFinder {
+            pos: fm.start_pos + BytePos(pos as u32),
+            found: None,
+        };
+        record.ast.visit_with(&mut finder);
+
+        match finder.found {
+            Some((id, _)) => self.definitions_of_local(&record, &id, &mut vec![]),
+            None => vec![],
+        }
+    }
+
+    /// Returns the references to the symbol named by the identifier at `pos`,
+    /// which is a byte offset in `file`.
+    ///
+    /// See [Checker::references].
+    pub fn references_at(&self, file: &FileName, pos: usize) -> Vec<Reference> {
+        let definitions = self.definition_at(file, pos);
+
+        self.references(&definitions)
+    }
+
+    /// Returns the references to the symbol declared by `definitions`, which
+    /// should be a result of [Checker::definition_at], in the loaded modules.
+    ///
+    /// References using imports and re-exports with other names are included,
+    /// but references using properties, like `ns.foo` for `import * as ns`,
+    /// are not.
+    pub fn references(&self, definitions: &[Definition]) -> Vec<Reference> {
+        if definitions.is_empty() {
+            return vec![];
+        }
+
+        let names = definitions
+            .iter()
+            .filter_map(|def| self.cm.span_to_snippet(def.span).ok())
+            .map(JsWord::from)
+            .collect::<FxHashSet<_>>();
+        let is_target = |found: Vec<Definition>| found.iter().any(|def| definitions.contains(def));
+
+        let mut refs = vec![];
+        for module in self.module_graph().modules {
+            let filename = Arc::new(FileName::Real(PathBuf::from(&module.path)));
+            let record = match self.find_record(&filename) {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let mut collector = RefCollector::default();
+            record.ast.visit_with(&mut collector);
+            let RefCollector {
+                idents,
+                imported,
+                reexports,
+                ..
+            } = collector;
+
+            let mut is_ref = FxHashMap::<Id, bool>::default();
+            for (id, span, is_write) in idents {
+                let matched = *is_ref.entry(id.clone()).or_insert_with(|| {
+                    // Other bindings can refer to the symbol only by importing it.
+                    (names.contains(&id.0) || imported.contains(&id)) && is_target(self.definitions_of_local(&record, &id, &mut vec![]))
+                });
+                if !matched {
+                    continue;
+                }
+
+                let file = record.filename.clone();
+                let kind = if definitions.contains(&Definition { file: file.clone(), span }) {
+                    ReferenceKind::Declaration
+                } else if is_write {
+                    ReferenceKind::Write
+                } else {
+                    ReferenceKind::Read
+                };
+                refs.push(Reference { file, span, kind });
+            }
+
+            for (span, src, name) in reexports {
+                if is_target(self.definitions_of_export(&record.filename, &src, Some(&name), &mut vec![])) {
+                    refs.push(Reference {
+                        file: record.filename.clone(),
+                        span,
+                        kind: ReferenceKind::Read,
+                    });
+                }
+            }
+        }
+
+        refs
+    }
+
+    /// Returns the names of the functions and classes declared with `export`
+    /// in `file`, like `export function foo() {}` and `export default class
+    /// Foo {}`, sorted by the position.
+    ///
+    /// Returns [None] if `file` is not loaded.
+    pub fn exported_declarations(&self, file: &FileName) -> Option<Vec<Definition>> {
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        let names = record.ast.body.iter().filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match &export.decl {
+                Decl::Fn(f) => Some(&f.ident),
+                Decl::Class(c) => Some(&c.ident),
+                _ => None,
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match &export.decl {
+                DefaultDecl::Fn(f) => f.ident.as_ref(),
+                DefaultDecl::Class(c) => c.ident.as_ref(),
+                _ => None,
+            },
+            _ => None,
+        });
+
+        Some(
+            names
+                .map(|i| Definition {
+                    file: record.filename.clone(),
+                    span: i.span.with_ctxt(SyntaxContext::empty()),
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the current source of `file`, loading it if it's not loaded
+    /// yet.
+    ///
+    /// Use this instead of [swc_common::SourceMap::get_source_file], which
+    /// returns the first source of `file` even if the file is parsed again by
+    /// [Checker::invalidate_file].
+    pub fn source_file(&self, file: &FileName) -> Option<Arc<SourceFile>> {
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        Some(self.cm.lookup_byte_offset(record.ast.span.lo).sf)
+    }
+
+    pub(crate) fn find_record(&self, filename: &Arc<FileName>) -> Option<Arc<ModuleRecord>> {
+        let records = self.module_loader.load_module(filename, false).ok()?;
+
+        records.modules.into_iter().find(|record| record.filename == *filename)
+    }
+
+    /// Returns the declarations of the binding `id` in `record`.
+    ///
+    /// `visited` contains the modules and names of the exports followed so far,
+    /// to prevent infinite recursion for circular re-exports.
+    pub(crate) fn definitions_of_local(
+        &self,
+        record: &ModuleRecord,
+        id: &Id,
+        visited: &mut Vec<(Arc<FileName>, JsWord)>,
+    ) -> Vec<Definition> {
+        let mut finder = DeclFinder {
+            id,
+            decls: vec![],
+            imports: vec![],
+        };
+        record.ast.visit_with(&mut finder);
+
+        let mut defs = finder
+            .decls
+            .into_iter()
+            .map(|span| Definition {
+                file: record.filename.clone(),
+                span,
+            })
+            .collect::<Vec<_>>();
+
+        for (span, src, name) in finder.imports {
+            let found = self.definitions_of_export(&record.filename, &src, name.as_ref(), visited);
+
+            // Fall back to the import if the declaration cannot be found.
+            if found.is_empty() {
+                defs.push(Definition {
+                    file: record.filename.clone(),
+                    span,
+                });
+            }
+            for def in found {
+                if !defs.contains(&def) {
+                    defs.push(def);
+                }
+            }
+        }
+
+        defs
+    }
+
+    /// Returns the declarations of the export `name` of the module `src`,
+    /// imported from `base`.
+    ///
+    /// If `name` is [None], the module itself is returned.
+    pub(crate) fn definitions_of_export(
+        &self,
+        base: &Arc<FileName>,
+        src: &str,
+        name: Option<&JsWord>,
+        visited: &mut Vec<(Arc<FileName>, JsWord)>,
+    ) -> Vec<Definition> {
+        let record = match self.module_loader.load_dep(base, src) {
+            Ok(records) => records.entry,
+            Err(..) => return vec![],
+        };
+
+        let name = match name {
+            Some(name) => name,
+            None => {
+                return vec![Definition {
+                    file: record.filename.clone(),
+                    span: record.ast.span,
+                }]
+            }
+        };
+
+        if visited.contains(&(record.filename.clone(), name.clone())) {
+            return vec![];
+        }
+        visited.push((record.filename.clone(), name.clone()));
+
+        let mut defs = vec![];
+        for target in find_export(&record, name) {
+            let found = match target {
+                ExportTarget::Local(id) => self.definitions_of_local(&record, &id, visited),
+                ExportTarget::Reexport { src, name } => self.definitions_of_export(&record.filename, &src, name.as_ref(), visited),
+                ExportTarget::Anonymous(span) => vec![Definition {
+                    file: record.filename.clone(),
+                    span,
+                }],
+            };
+
+            for def in found {
+                if !defs.contains(&def) {
+                    defs.push(def);
+                }
+            }
+        }
+
+        defs
+    }
+}
+
+/// Finds the identifier at `pos`.
+///
+/// Names of properties are found too, but they don't match any declaration
+/// because they are not resolved.
+pub(crate) struct IdentFinder {
+    pub pos: BytePos,
+    /// The identifier and its span.
+    pub found: Option<(Id, Span)>,
+}
+
+impl Visit for IdentFinder {
+    fn visit_ident(&mut self, i: &Ident) {
+        if i.span.lo <= self.pos && self.pos < i.span.hi {
+            self.found = Some((i.to_id(), i.span.with_ctxt(SyntaxContext::empty())));
+        }
+    }
+}
+
+/// Finds the declarations of `id`, including imports.
+struct DeclFinder<'a> {
+    id: &'a Id,
+    decls: Vec<Span>,
+    /// The span, source and imported name of imports. The name is [None] for
+    /// namespace imports.
+    imports: Vec<(Span, JsWord, Option<JsWord>)>,
+}
+
+impl DeclFinder<'_> {
+    fn check(&mut self, i: &Ident) {
+        if i.to_id() == *self.id {
+            self.decls.push(i.span.with_ctxt(SyntaxContext::empty()));
+        }
+    }
+}
+
+impl Visit for DeclFinder<'_> {
+    fn visit_binding_ident(&mut self, i: &BindingIdent) {
+        self.check(&i.id);
+        i.visit_children_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, f: &FnDecl) {
+        self.check(&f.ident);
+        f.visit_children_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, f: &FnExpr) {
+        if let Some(ident) = &f.ident {
+            self.check(ident);
+        }
+        f.visit_children_with(self);
+    }
+
+    fn visit_class_decl(&mut self, c: &ClassDecl) {
+        self.check(&c.ident);
+        c.visit_children_with(self);
+    }
+
+    fn visit_class_expr(&mut self, c: &ClassExpr) {
+        if let Some(ident) = &c.ident {
+            self.check(ident);
+        }
+        c.visit_children_with(self);
+    }
+
+    fn visit_ts_interface_decl(&mut self, d: &TsInterfaceDecl) {
+        self.check(&d.id);
+        d.visit_children_with(self);
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, d: &TsTypeAliasDecl) {
+        self.check(&d.id);
+        d.visit_children_with(self);
+    }
+
+    fn visit_ts_enum_decl(&mut self, d: &TsEnumDecl) {
+        self.check(&d.id);
+        d.visit_children_with(self);
+    }
+
+    fn visit_ts_module_decl(&mut self, d: &TsModuleDecl) {
+        if let TsModuleName::Ident(id) = &d.id {
+            self.check(id);
+        }
+        d.visit_children_with(self);
+    }
+
+    fn visit_ts_namespace_decl(&mut self, d: &TsNamespaceDecl) {
+        self.check(&d.id);
+        d.visit_children_with(self);
+    }
+
+    fn visit_ts_type_param(&mut self, p: &TsTypeParam) {
+        self.check(&p.name);
+        p.visit_children_with(self);
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, d: &TsImportEqualsDecl) {
+        self.check(&d.id);
+        d.visit_children_with(self);
+    }
+
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        for specifier in &import.specifiers {
+            let (local, name) = match specifier {
+                ImportSpecifier::Named(named) => (
+                    &named.local,
+                    Some(named.imported.as_ref().map_or_else(|| named.local.sym.clone(), export_name)),
+                ),
+                ImportSpecifier::Default(default) => (&default.local, Some(js_word!("default"))),
+                ImportSpecifier::Namespace(ns) => (&ns.local, None),
+            };
+
+            if local.to_id() == *self.id {
+                self.imports
+                    .push((local.span.with_ctxt(SyntaxContext::empty()), import.src.value.clone(), name));
+            }
+        }
+    }
+}
+
+/// Collects identifiers.
+#[derive(Default)]
+struct RefCollector {
+    in_write: bool,
+    /// Identifiers and whether they are written.
+    idents: Vec<(Id, Span, bool)>,
+    /// Bindings created by imports.
+    imported: FxHashSet<Id>,
+    /// The span, source and original name of names re-exported using
+    /// `export { name } from 'src'`.
+    reexports: Vec<(Span, JsWord, JsWord)>,
+}
+
+impl RefCollector {
+    fn with_write(&mut self, in_write: bool, op: impl FnOnce(&mut Self)) {
+        let prev = replace(&mut self.in_write, in_write);
+        op(self);
+        self.in_write = prev;
+    }
+}
+
+impl Visit for RefCollector {
+    fn visit_ident(&mut self, i: &Ident) {
+        self.idents
+            .push((i.to_id(), i.span.with_ctxt(SyntaxContext::empty()), self.in_write));
+    }
+
+    fn visit_expr(&mut self, e: &Expr) {
+        match e {
+            Expr::Ident(..) | Expr::Paren(..) => e.visit_children_with(self),
+            // The object of `a.b = c` is not written.
+            _ => self.with_write(false, |v| e.visit_children_with(v)),
+        }
+    }
+
+    fn visit_assign_expr(&mut self, e: &AssignExpr) {
+        self.with_write(true, |v| e.left.visit_with(v));
+        e.right.visit_with(self);
+    }
+
+    fn visit_update_expr(&mut self, e: &UpdateExpr) {
+        self.with_write(true, |v| e.arg.visit_with(v));
+    }
+
+    fn visit_for_in_stmt(&mut self, s: &ForInStmt) {
+        self.with_write(true, |v| s.left.visit_with(v));
+        s.right.visit_with(self);
+        s.body.visit_with(self);
+    }
+
+    fn visit_for_of_stmt(&mut self, s: &ForOfStmt) {
+        self.with_write(true, |v| s.left.visit_with(v));
+        s.right.visit_with(self);
+        s.body.visit_with(self);
+    }
+
+    fn visit_assign_pat(&mut self, p: &AssignPat) {
+        p.left.visit_with(self);
+        self.with_write(false, |v| p.right.visit_with(v));
+    }
+
+    fn visit_assign_pat_prop(&mut self, p: &AssignPatProp) {
+        p.key.visit_with(self);
+        self.with_write(false, |v| p.value.visit_with(v));
+    }
+
+    fn visit_prop_name(&mut self, n: &PropName) {
+        self.with_write(false, |v| n.visit_children_with(v));
+    }
+
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        for specifier in &import.specifiers {
+            let local = match specifier {
+                ImportSpecifier::Named(named) => &named.local,
+                ImportSpecifier::Default(default) => &default.local,
+                ImportSpecifier::Namespace(ns) => &ns.local,
+            };
+            self.imported.insert(local.to_id());
+        }
+
+        import.visit_children_with(self);
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        let src = match &export.src {
+            Some(src) => src,
+            None => {
+                export.visit_children_with(self);
+                return;
+            }
+        };
+
+        for specifier in &export.specifiers {
+            if let ExportSpecifier::Named(ExportNamedSpecifier {
+                orig: ModuleExportName::Ident(orig),
+                ..
+            }) = specifier
+            {
+                self.reexports
+                    .push((orig.span.with_ctxt(SyntaxContext::empty()), src.value.clone(), orig.sym.clone()));
+            }
+        }
+    }
+}
+
+/// Returns what the export `name` of `record` refers to.
+fn find_export(record: &ModuleRecord, name: &JsWord) -> Vec<ExportTarget> {
+    let local = |ident: &Ident| ExportTarget::Local(ident.to_id());
+    let mut targets = vec![];
+
+    for item in &record.ast.body {
+        let decl = match item {
+            ModuleItem::ModuleDecl(decl) => decl,
+            ModuleItem::Stmt(..) => continue,
+        };
+
+        match decl {
+            ModuleDecl::ExportDecl(export) => {
+                let mut names = vec![];
+                match &export.decl {
+                    Decl::Class(c) => names.push(c.ident.sym.clone()),
+                    Decl::Fn(f) => names.push(f.ident.sym.clone()),
+                    Decl::Var(var) => {
+                        for decl in &var.decls {
+                            collect_pat_names(&decl.name, &mut names);
+                        }
+                    }
+                    Decl::TsInterface(d) => names.push(d.id.sym.clone()),
+                    Decl::TsTypeAlias(d) => names.push(d.id.sym.clone()),
+                    Decl::TsEnum(d) => names.push(d.id.sym.clone()),
+                    Decl::TsModule(d) => {
+                        if let TsModuleName::Ident(id) = &d.id {
+                            names.push(id.sym.clone());
+                        }
+                    }
+                }
+
+                // Exported declarations are always at the top level.
+                if names.contains(name) {
+                    targets.push(ExportTarget::Local((name.clone(), record.top_level_ctxt)));
+                }
+            }
+            ModuleDecl::ExportNamed(export) => {
+                for specifier in &export.specifiers {
+                    let (exported, orig) = match specifier {
+                        ExportSpecifier::Named(named) => (named.exported.as_ref().unwrap_or(&named.orig), Some(&named.orig)),
+                        ExportSpecifier::Namespace(ns) => (&ns.name, None),
+                        ExportSpecifier::Default(..) => continue,
+                    };
+                    if export_name(exported) != *name {
+                        continue;
+                    }
+
+                    targets.push(match (&export.src, orig) {
+                        (Some(src), orig) => ExportTarget::Reexport {
+                            src: src.value.clone(),
+                            name: orig.map(export_name),
+                        },
+                        (None, Some(ModuleExportName::Ident(orig))) => local(orig),
+                        (None, _) => continue,
+                    });
+                }
+            }
+            ModuleDecl::ExportDefaultDecl(export) if *name == js_word!("default") => targets.push(match &export.decl {
+                DefaultDecl::Class(ClassExpr { ident: Some(ident), .. }) | DefaultDecl::Fn(FnExpr { ident: Some(ident), .. }) => {
+                    local(ident)
+                }
+                DefaultDecl::TsInterfaceDecl(d) => local(&d.id),
+                _ => ExportTarget::Anonymous(export.span),
+            }),
+            ModuleDecl::ExportDefaultExpr(export) if *name == js_word!("default") => targets.push(match &*export.expr {
+                Expr::Ident(ident) => local(ident),
+                _ => ExportTarget::Anonymous(export.span),
+            }),
+            ModuleDecl::ExportAll(export) if *name != js_word!("default") => targets.push(ExportTarget::Reexport {
+                src: export.src.value.clone(),
+                name: Some(name.clone()),
+            }),
+            _ => {}
+        }
+    }
+
+    targets
+}
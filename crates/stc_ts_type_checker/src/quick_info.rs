@@ -0,0 +1,317 @@
+//! Information about symbols shown by editors on hover, like the quickinfo
+//! command of tsserver.
+
+use std::{mem::replace, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use stc_ts_types::{printer::print_type, Type};
+use swc_common::{
+    comments::{Comment, CommentKind, Comments},
+    BytePos, FileName, Span,
+};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    declarations::{declared_type, DeclarationKind},
+    loader::LoadModule,
+    query::IdentFinder,
+    Checker,
+};
+
+/// The body of the response to the quickinfo command of tsserver.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickInfo {
+    /// The kind of the symbol, like `const` or `function`.
+    pub kind: String,
+    /// Comma-separated modifiers of the declaration, like `export,declare`.
+    pub kind_modifiers: String,
+    /// The start of the identifier at the requested position.
+    pub start: Location,
+    /// The end of the identifier at the requested position.
+    pub end: Location,
+    /// The declaration with its type, like `const a: number`.
+    pub display_string: String,
+    /// The text of the JSDoc comment of the declaration, without the tags.
+    pub documentation: String,
+    pub tags: Vec<JsDocTag>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, in characters.
+    pub offset: usize,
+}
+
+/// A tag of a JSDoc comment, like `@param a The first operand`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsDocTag {
+    /// The name without `@`, like `param`.
+    pub name: String,
+    pub text: String,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the information about the symbol named by the identifier at
+    /// `pos`, which is a byte offset in `file`.
+    ///
+    /// The symbol is found like [Checker::definition_at], and the first
+    /// declaration is described. Returns [None] if types are not recorded (see
+    /// [Checker::with_type_recorder]), or there's no such symbol.
+    pub fn quick_info(&self, file: &FileName, pos: usize) -> Option<QuickInfo> {
+        let recorder = self.type_recorder.as_ref()?;
+        let fm = self.source_file(file)?;
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        let mut finder = IdentFinder {
+            pos: fm.start_pos + BytePos(pos as u32),
+            found: None,
+        };
+        record.ast.visit_with(&mut finder);
+        let (_, span) = finder.found?;
+
+        let def = self.definition_at(file, pos).into_iter().next()?;
+        let records = self.module_loader.load_module(&def.file, false).ok()?;
+        let def_record = records.modules.iter().find(|record| record.filename == def.file)?;
+
+        let mut finder = IdentFinder {
+            pos: def.span.lo,
+            found: None,
+        };
+        def_record.ast.visit_with(&mut finder);
+        let (id, _) = finder.found?;
+        let name = Ident::new(id.0, def.span.with_ctxt(id.1));
+
+        let decl = self
+            .declaration_types(&def.file)?
+            .declarations
+            .into_iter()
+            .find(|decl| self.cm.lookup_byte_offset(def.span.lo).pos.0 as usize == decl.span.start)?;
+
+        // Use the narrowed type at the position for variables.
+        let ty = match decl.flags.kind {
+            DeclarationKind::Var | DeclarationKind::Let | DeclarationKind::Const | DeclarationKind::Parameter => recorder
+                .type_at(span.lo)
+                .filter(|(found, _)| found.lo == span.lo && found.hi == span.hi)
+                .map(|(_, ty)| ty),
+            _ => None,
+        }
+        .or_else(|| declared_type(recorder, &name, decl.flags.kind));
+
+        let mut doc_finder = DocFinder {
+            name: def.span.lo,
+            anchors: vec![],
+            export: None,
+            found: None,
+        };
+        def_record.ast.visit_with(&mut doc_finder);
+        let doc = doc_finder
+            .found
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|pos| records.comments.get_leading(pos).unwrap_or_default())
+            .filter(is_jsdoc)
+            .last();
+        let (documentation, tags) = doc.map(|doc| parse_jsdoc(&doc.text)).unwrap_or_default();
+
+        let mut modifiers = vec![];
+        if decl.flags.exported {
+            modifiers.push("export");
+        }
+        if decl.flags.ambient {
+            modifiers.push("declare");
+        }
+
+        Some(QuickInfo {
+            kind: kind_name(decl.flags.kind).to_string(),
+            kind_modifiers: modifiers.join(","),
+            start: self.location(span.lo),
+            end: self.location(span.hi),
+            display_string: display_string(decl.flags.kind, &decl.name, ty.as_ref()),
+            documentation,
+            tags,
+        })
+    }
+
+    fn location(&self, pos: BytePos) -> Location {
+        let loc = self.cm.lookup_char_pos(pos);
+
+        Location {
+            line: loc.line,
+            offset: loc.col_display + 1,
+        }
+    }
+}
+
+/// Returns the name of `kind` used by tsserver.
+fn kind_name(kind: DeclarationKind) -> &'static str {
+    match kind {
+        DeclarationKind::Var => "var",
+        DeclarationKind::Let => "let",
+        DeclarationKind::Const => "const",
+        DeclarationKind::Function => "function",
+        DeclarationKind::Class => "class",
+        DeclarationKind::Interface => "interface",
+        DeclarationKind::TypeAlias => "type",
+        DeclarationKind::Enum => "enum",
+        DeclarationKind::Namespace => "module",
+        DeclarationKind::Parameter => "parameter",
+        DeclarationKind::Import => "alias",
+    }
+}
+
+fn display_string(kind: DeclarationKind, name: &str, ty: Option<&Type>) -> String {
+    let printed = ty.map(print_type);
+    let with_type = |prefix: &str| match &printed {
+        Some(ty) => format!("{}{}: {}", prefix, name, ty),
+        None => format!("{}{}", prefix, name),
+    };
+
+    match kind {
+        DeclarationKind::Var => with_type("var "),
+        DeclarationKind::Let => with_type("let "),
+        DeclarationKind::Const => with_type("const "),
+        DeclarationKind::Parameter => with_type("(parameter) "),
+        DeclarationKind::Function => {
+            // `(a: number) => void` is printed as `function f(a: number): void`.
+            if let (Some(Type::Function(f)), Some(printed)) = (ty.map(Type::normalize), &printed) {
+                let ret = print_type(&f.ret_ty);
+                if let Some(sig) = printed.strip_suffix(&*ret).and_then(|s| s.strip_suffix(" => ")) {
+                    return format!("function {}{}: {}", name, sig, ret);
+                }
+            }
+            with_type("function ")
+        }
+        DeclarationKind::Class => format!("class {}", name),
+        DeclarationKind::Interface => format!("interface {}", name),
+        DeclarationKind::TypeAlias => match ty.map(Type::normalize) {
+            Some(Type::Alias(alias)) => format!("type {} = {}", name, print_type(&alias.ty)),
+            _ => format!("type {}", name),
+        },
+        DeclarationKind::Enum => format!("enum {}", name),
+        DeclarationKind::Namespace => format!("namespace {}", name),
+        DeclarationKind::Import => match &printed {
+            Some(ty) => format!("(alias) {}: {}\nimport {}", name, ty, name),
+            None => format!("import {}", name),
+        },
+    }
+}
+
+/// `/** ... */`
+fn is_jsdoc(c: &Comment) -> bool {
+    c.kind == CommentKind::Block && c.text.starts_with('*') && !c.text.starts_with("**")
+}
+
+/// Splits `text`, which is the text of a JSDoc comment without `/*` and `*/`,
+/// into the documentation and the tags.
+fn parse_jsdoc(text: &str) -> (String, Vec<JsDocTag>) {
+    let mut doc = vec![];
+    let mut tags: Vec<(String, Vec<&str>)> = vec![];
+
+    for line in text[1..].lines() {
+        let line = line.trim_start();
+        let line = line.strip_prefix('*').unwrap_or(line);
+        let line = line.strip_prefix(' ').unwrap_or(line).trim_end();
+
+        if let Some(tag) = line.strip_prefix('@') {
+            let (name, text) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tags.push((name.to_string(), vec![text.trim_start()]));
+        } else if let Some((_, text)) = tags.last_mut() {
+            text.push(line);
+        } else {
+            doc.push(line);
+        }
+    }
+
+    let tags = tags
+        .into_iter()
+        .map(|(name, text)| JsDocTag {
+            name,
+            text: text.join("\n").trim().to_string(),
+        })
+        .collect();
+
+    (doc.join("\n").trim().to_string(), tags)
+}
+
+/// Finds the positions where the doc comment of the declaration named by the
+/// identifier at `name` can be.
+struct DocFinder {
+    name: BytePos,
+    /// Positions of the current declaration.
+    anchors: Vec<BytePos>,
+    /// The position of `export` of the declaration being visited.
+    export: Option<BytePos>,
+    found: Option<Vec<BytePos>>,
+}
+
+impl DocFinder {
+    fn decl(&mut self, lo: BytePos, op: impl FnOnce(&mut Self)) {
+        let mut anchors: Vec<_> = self.export.take().into_iter().collect();
+        anchors.push(lo);
+
+        let old = replace(&mut self.anchors, anchors);
+        op(self);
+        self.anchors = old;
+    }
+
+    fn exported(&mut self, span: Span, op: impl FnOnce(&mut Self)) {
+        self.export = Some(span.lo);
+        op(self);
+        self.export = None;
+    }
+}
+
+impl Visit for DocFinder {
+    fn visit_ident(&mut self, i: &Ident) {
+        if i.span.lo == self.name && self.found.is_none() {
+            self.found = Some(self.anchors.clone());
+        }
+    }
+
+    fn visit_export_decl(&mut self, e: &ExportDecl) {
+        self.exported(e.span, |v| e.decl.visit_with(v));
+    }
+
+    fn visit_export_default_decl(&mut self, e: &ExportDefaultDecl) {
+        self.exported(e.span, |v| e.decl.visit_with(v));
+    }
+
+    fn visit_var_decl(&mut self, d: &VarDecl) {
+        self.decl(d.span.lo, |v| d.visit_children_with(v));
+    }
+
+    fn visit_fn_decl(&mut self, f: &FnDecl) {
+        self.decl(f.function.span.lo, |v| f.visit_children_with(v));
+    }
+
+    fn visit_class_decl(&mut self, c: &ClassDecl) {
+        self.decl(c.class.span.lo, |v| c.visit_children_with(v));
+    }
+
+    fn visit_ts_interface_decl(&mut self, d: &TsInterfaceDecl) {
+        self.decl(d.span.lo, |v| d.visit_children_with(v));
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, d: &TsTypeAliasDecl) {
+        self.decl(d.span.lo, |v| d.visit_children_with(v));
+    }
+
+    fn visit_ts_enum_decl(&mut self, d: &TsEnumDecl) {
+        self.decl(d.span.lo, |v| d.visit_children_with(v));
+    }
+
+    fn visit_ts_module_decl(&mut self, d: &TsModuleDecl) {
+        self.decl(d.span.lo, |v| d.visit_children_with(v));
+    }
+
+    fn visit_param(&mut self, p: &Param) {
+        self.decl(p.span.lo, |v| p.visit_children_with(v));
+    }
+}
@@ -0,0 +1,169 @@
+//! Information required to rename symbols.
+
+use std::{path::PathBuf, sync::Arc};
+
+use swc_atoms::JsWord;
+use swc_common::{BytePos, FileName};
+use swc_ecma_ast::*;
+use swc_ecma_visit::VisitWith;
+
+use crate::{
+    loader::LoadModule,
+    query::{Definition, IdentFinder, Reference, ReferenceKind},
+    Checker,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameInfo {
+    /// The current name of the symbol.
+    pub name: JsWord,
+    /// The declarations of the symbol, like [Checker::definition_at].
+    pub definitions: Vec<Definition>,
+    /// The reason why the symbol can't be renamed, or [None] if it can be
+    /// renamed.
+    pub blocker: Option<RenameBlocker>,
+    /// The spans to replace with the new name, sorted by the file and the
+    /// position. Empty if the symbol can't be renamed.
+    pub locations: Vec<Reference>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameBlocker {
+    /// The symbol is not declared in the loaded files, like the globals
+    /// declared by the default libraries.
+    NotDeclared,
+    /// The symbol is declared in a package in `node_modules`.
+    Dependency,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the information required to rename the symbol named by the
+    /// identifier at `pos`, which is a byte offset in `file`.
+    ///
+    /// The locations are the references of the symbol (see
+    /// [Checker::references]) which use the name at `pos`, including the names
+    /// in imports and exports which rename the symbol, like `a` of
+    /// `import { a as b }`. References using other names, like `b` of the
+    /// import, are kept as they are. Returns [None] if there's no identifier
+    /// at `pos`.
+    pub fn prepare_rename(&self, file: &FileName, pos: usize) -> Option<RenameInfo> {
+        let fm = self.source_file(file)?;
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        let mut finder = IdentFinder {
+            pos: fm.start_pos + BytePos(pos as u32),
+            found: None,
+        };
+        record.ast.visit_with(&mut finder);
+        let (id, _) = finder.found?;
+        let name = id.0;
+
+        let definitions = self.definition_at(file, pos);
+        let blocker = if definitions.is_empty() {
+            Some(RenameBlocker::NotDeclared)
+        } else if definitions.iter().any(|def| is_in_dependency(&def.file)) {
+            Some(RenameBlocker::Dependency)
+        } else {
+            None
+        };
+        if blocker.is_some() {
+            return Some(RenameInfo {
+                name,
+                definitions,
+                blocker,
+                locations: vec![],
+            });
+        }
+
+        let mut locations = self
+            .references(&definitions)
+            .into_iter()
+            .filter(|r| self.cm.span_to_snippet(r.span).map_or(false, |snippet| *snippet == *name))
+            .collect::<Vec<_>>();
+        locations.extend(self.aliases(&name, &definitions));
+
+        locations.sort_by_key(|r| (r.file.to_string(), r.span.lo));
+        locations.dedup();
+
+        Some(RenameInfo {
+            name,
+            definitions,
+            blocker,
+            locations,
+        })
+    }
+
+    /// Returns the names of the symbol declared by `definitions` in imports
+    /// and exports which rename it, like `name` of `import { name as alias }`
+    /// and `export { local as name }`.
+    fn aliases(&self, name: &JsWord, definitions: &[Definition]) -> Vec<Reference> {
+        let mut refs = vec![];
+        for module in self.module_graph().modules {
+            let filename = Arc::new(FileName::Real(PathBuf::from(&module.path)));
+            let record = match self.find_record(&filename) {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let mut aliases = vec![];
+            for item in &record.ast.body {
+                match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                        for specifier in &import.specifiers {
+                            if let ImportSpecifier::Named(ImportNamedSpecifier {
+                                imported: Some(ModuleExportName::Ident(imported)),
+                                ..
+                            }) = specifier
+                            {
+                                if imported.sym == *name {
+                                    let found = self.definitions_of_export(&filename, &import.src.value, Some(name), &mut vec![]);
+                                    aliases.push((imported.span, found));
+                                }
+                            }
+                        }
+                    }
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                        for specifier in &export.specifiers {
+                            if let ExportSpecifier::Named(ExportNamedSpecifier {
+                                orig: ModuleExportName::Ident(orig),
+                                exported: Some(ModuleExportName::Ident(exported)),
+                                ..
+                            }) = specifier
+                            {
+                                if exported.sym == *name {
+                                    let found = match &export.src {
+                                        Some(src) => self.definitions_of_export(&filename, &src.value, Some(&orig.sym), &mut vec![]),
+                                        None => self.definitions_of_local(&record, &orig.to_id(), &mut vec![]),
+                                    };
+                                    aliases.push((exported.span, found));
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            for (span, found) in aliases {
+                if found.iter().any(|def| definitions.contains(def)) {
+                    refs.push(Reference {
+                        file: filename.clone(),
+                        span: span.with_ctxt(Default::default()),
+                        kind: ReferenceKind::Read,
+                    });
+                }
+            }
+        }
+        refs
+    }
+}
+
+fn is_in_dependency(file: &FileName) -> bool {
+    match file {
+        FileName::Real(path) => path.components().any(|c| c.as_os_str() == "node_modules"),
+        _ => false,
+    }
+}
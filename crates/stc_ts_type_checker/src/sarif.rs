@@ -0,0 +1,206 @@
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! logs, which can be uploaded to GitHub code scanning and other tools.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use stc_ts_errors::{
+    codes::{error_code, message_template},
+    Severity,
+};
+
+use crate::program::Diagnostic;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+    pub tool: Tool,
+    /// `unicodeCodePoints`, because columns of [Diagnostic] are in characters.
+    pub column_kind: String,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tool {
+    pub driver: ToolComponent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolComponent {
+    pub name: String,
+    pub version: String,
+    pub information_uri: String,
+    /// One rule for each error code, sorted by the id.
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    /// Like `TS2322`.
+    pub id: String,
+    pub short_description: Message,
+    pub default_configuration: RuleConfiguration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleConfiguration {
+    pub level: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    /// The index of the rule in [ToolComponent::rules].
+    pub rule_index: usize,
+    pub level: String,
+    pub message: Message,
+    /// Empty if the error is not in a file.
+    pub locations: Vec<Location>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    pub region: Region,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+/// Lines and columns are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl SarifLog {
+    /// Creates a log with a run of stc which reported `diagnostics`.
+    ///
+    /// Paths of files in `root` are relative to it, so `root` should be the
+    /// root of the repository for code scanning. Other paths are `file` URIs.
+    pub fn new(diagnostics: &[Diagnostic], root: &Path) -> Self {
+        let mut codes = diagnostics.iter().map(|d| d.code).collect::<Vec<_>>();
+        codes.sort_unstable();
+        codes.dedup();
+
+        let rules = codes
+            .iter()
+            .map(|&code| Rule {
+                id: format!("TS{}", code),
+                short_description: Message {
+                    text: message_template(code)
+                        .or_else(|| Some(error_code(code)?.description))
+                        .map(String::from)
+                        .unwrap_or_else(|| format!("TS{}", code)),
+                },
+                default_configuration: RuleConfiguration {
+                    level: level(diagnostics.iter().find(|d| d.code == code).map_or(Severity::Error, |d| d.severity)).into(),
+                },
+            })
+            .collect();
+
+        let results = diagnostics
+            .iter()
+            .map(|d| SarifResult {
+                rule_id: format!("TS{}", d.code),
+                rule_index: codes.binary_search(&d.code).unwrap(),
+                level: level(d.severity).into(),
+                message: Message { text: d.message.clone() },
+                locations: d
+                    .file
+                    .iter()
+                    .map(|file| Location {
+                        physical_location: PhysicalLocation {
+                            artifact_location: ArtifactLocation { uri: to_uri(file, root) },
+                            region: Region {
+                                start_line: d.line,
+                                start_column: d.column + 1,
+                                end_line: d.end_line,
+                                end_column: d.end_column + 1,
+                            },
+                        },
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        SarifLog {
+            schema: SCHEMA.into(),
+            version: "2.1.0".into(),
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "stc".into(),
+                        version: env!("CARGO_PKG_VERSION").into(),
+                        information_uri: "https://github.com/swc-project/stc".into(),
+                        rules,
+                    },
+                },
+                column_kind: "unicodeCodePoints".into(),
+                results,
+            }],
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+fn to_uri(file: &str, root: &Path) -> String {
+    let path = Path::new(file);
+
+    match path.strip_prefix(root) {
+        Ok(relative) => relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(..) if path.is_absolute() => format!("file://{}", file.replace('\\', "/")),
+        Err(..) => file.replace('\\', "/"),
+    }
+}
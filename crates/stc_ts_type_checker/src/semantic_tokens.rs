@@ -0,0 +1,494 @@
+//! Classification of identifiers for semantic highlighting, like the semantic
+//! tokens of LSP.
+
+use std::{
+    mem::{replace, take},
+    sync::Arc,
+};
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use swc_common::{FileName, Span, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    loader::{LoadModule, ModuleRecord},
+    Checker,
+};
+
+/// A classified identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticToken {
+    /// Byte offset in the file.
+    pub start: usize,
+    /// Byte offset in the file.
+    pub end: usize,
+    pub kind: TokenKind,
+    pub modifiers: TokenModifiers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenKind {
+    Namespace,
+    Class,
+    Enum,
+    Interface,
+    TypeAlias,
+    TypeParameter,
+    Parameter,
+    Variable,
+    Property,
+    EnumMember,
+    Function,
+    Method,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenModifiers {
+    /// The name of a declaration.
+    pub declaration: bool,
+    /// `const` variables, `readonly` properties and parameter properties, and
+    /// enum members.
+    pub readonly: bool,
+    /// `static` members of classes.
+    #[serde(rename = "static")]
+    pub is_static: bool,
+}
+
+/// What a binding declares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Symbol {
+    kind: TokenKind,
+    readonly: bool,
+}
+
+impl Symbol {
+    fn new(kind: TokenKind) -> Self {
+        Symbol { kind, readonly: false }
+    }
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the identifiers in `file` which name a binding or a property,
+    /// sorted by the position.
+    ///
+    /// Bindings are classified by their declarations, and imported bindings
+    /// are classified by the declarations in the imported modules. Names of
+    /// properties are classified by the syntax, because their types are not
+    /// used. Returns [None] if `file` is not loaded.
+    pub fn semantic_tokens(&self, file: &FileName) -> Option<Vec<SemanticToken>> {
+        let fm = self.source_file(file)?;
+        let record = self.find_record(&Arc::new(file.clone()))?;
+        let bindings = self.bindings(&record);
+
+        let mut collector = TokenCollector {
+            bindings: &bindings,
+            tokens: vec![],
+        };
+        record.ast.visit_with(&mut collector);
+
+        let mut tokens = collector
+            .tokens
+            .into_iter()
+            .map(|(span, kind, modifiers)| SemanticToken {
+                start: (span.lo - fm.start_pos).0 as usize,
+                end: (span.hi - fm.start_pos).0 as usize,
+                kind,
+                modifiers,
+            })
+            .collect::<Vec<_>>();
+        tokens.sort_by_key(|token| token.start);
+        tokens.dedup_by_key(|token| token.start);
+
+        Some(tokens)
+    }
+
+    /// Classifies the bindings declared and imported in `record`.
+    fn bindings(&self, record: &ModuleRecord) -> Bindings {
+        let mut bindings = Bindings::collect(record);
+        // The declarations of the imported modules.
+        let mut targets = FxHashMap::<Arc<FileName>, FxHashMap<Span, Symbol>>::default();
+
+        for (id, is_namespace) in take(&mut bindings.imports) {
+            let symbol = match is_namespace {
+                true => Some(Symbol::new(TokenKind::Namespace)),
+                false => self
+                    .definitions_of_local(record, &id, &mut vec![])
+                    .into_iter()
+                    // The import itself is returned if the declaration cannot be found.
+                    .filter(|def| def.file != record.filename)
+                    .find_map(|def| {
+                        if !targets.contains_key(&def.file) {
+                            let target = self.find_record(&def.file)?;
+                            targets.insert(def.file.clone(), Bindings::collect(&target).declarations);
+                        }
+
+                        targets[&def.file].get(&def.span).copied()
+                    }),
+            };
+
+            bindings.symbols.insert(id, symbol.unwrap_or(Symbol::new(TokenKind::Variable)));
+        }
+
+        bindings
+    }
+}
+
+#[derive(Default)]
+struct Bindings {
+    symbols: FxHashMap<Id, Symbol>,
+    /// The declared names, by their spans without the syntax context.
+    declarations: FxHashMap<Span, Symbol>,
+    /// Imported bindings, and whether they are namespace imports.
+    imports: Vec<(Id, bool)>,
+}
+
+impl Bindings {
+    fn collect(record: &ModuleRecord) -> Self {
+        let mut collector = BindingCollector {
+            bindings: Default::default(),
+            pat: None,
+        };
+        record.ast.visit_with(&mut collector);
+
+        collector.bindings
+    }
+}
+
+struct BindingCollector {
+    bindings: Bindings,
+    /// What the binding identifiers declare while visiting a pattern.
+    pat: Option<Symbol>,
+}
+
+impl BindingCollector {
+    fn declare(&mut self, i: &Ident, symbol: Symbol) {
+        self.bindings.symbols.insert(i.to_id(), symbol);
+        self.bindings.declarations.insert(i.span.with_ctxt(SyntaxContext::empty()), symbol);
+    }
+
+    fn with_pat(&mut self, symbol: Option<Symbol>, op: impl FnOnce(&mut Self)) {
+        let pat = replace(&mut self.pat, symbol);
+        op(self);
+        self.pat = pat;
+    }
+}
+
+impl Visit for BindingCollector {
+    fn visit_var_decl(&mut self, v: &VarDecl) {
+        let symbol = Symbol {
+            kind: TokenKind::Variable,
+            readonly: v.kind == VarDeclKind::Const,
+        };
+
+        for decl in &v.decls {
+            self.with_pat(Some(symbol), |c| decl.name.visit_with(c));
+            decl.init.visit_with(self);
+        }
+    }
+
+    fn visit_binding_ident(&mut self, i: &BindingIdent) {
+        if let Some(symbol) = self.pat {
+            self.declare(&i.id, symbol);
+        }
+        self.with_pat(None, |c| i.type_ann.visit_with(c));
+    }
+
+    fn visit_assign_pat_prop(&mut self, p: &AssignPatProp) {
+        if let Some(symbol) = self.pat {
+            self.declare(&p.key, symbol);
+        }
+        p.value.visit_with(self);
+    }
+
+    fn visit_expr(&mut self, e: &Expr) {
+        // Default values in patterns are not declarations.
+        self.with_pat(None, |c| e.visit_children_with(c));
+    }
+
+    fn visit_param(&mut self, p: &Param) {
+        p.decorators.visit_with(self);
+        self.with_pat(Some(Symbol::new(TokenKind::Parameter)), |c| p.pat.visit_with(c));
+    }
+
+    fn visit_ts_param_prop(&mut self, p: &TsParamProp) {
+        let symbol = Symbol {
+            kind: TokenKind::Parameter,
+            readonly: p.readonly,
+        };
+
+        p.decorators.visit_with(self);
+        self.with_pat(Some(symbol), |c| p.param.visit_with(c));
+    }
+
+    fn visit_arrow_expr(&mut self, f: &ArrowExpr) {
+        self.with_pat(Some(Symbol::new(TokenKind::Parameter)), |c| f.params.visit_with(c));
+        self.with_pat(None, |c| {
+            f.body.visit_with(c);
+            f.type_params.visit_with(c);
+            f.return_type.visit_with(c);
+        });
+    }
+
+    fn visit_ts_fn_param(&mut self, p: &TsFnParam) {
+        self.with_pat(Some(Symbol::new(TokenKind::Parameter)), |c| p.visit_children_with(c));
+    }
+
+    fn visit_catch_clause(&mut self, c: &CatchClause) {
+        self.with_pat(Some(Symbol::new(TokenKind::Variable)), |v| c.param.visit_with(v));
+        c.body.visit_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, f: &FnDecl) {
+        self.declare(&f.ident, Symbol::new(TokenKind::Function));
+        f.function.visit_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, f: &FnExpr) {
+        if let Some(ident) = &f.ident {
+            self.declare(ident, Symbol::new(TokenKind::Function));
+        }
+        f.function.visit_with(self);
+    }
+
+    fn visit_class_decl(&mut self, c: &ClassDecl) {
+        self.declare(&c.ident, Symbol::new(TokenKind::Class));
+        c.class.visit_with(self);
+    }
+
+    fn visit_class_expr(&mut self, c: &ClassExpr) {
+        if let Some(ident) = &c.ident {
+            self.declare(ident, Symbol::new(TokenKind::Class));
+        }
+        c.class.visit_with(self);
+    }
+
+    fn visit_ts_interface_decl(&mut self, d: &TsInterfaceDecl) {
+        self.declare(&d.id, Symbol::new(TokenKind::Interface));
+        d.visit_children_with(self);
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, d: &TsTypeAliasDecl) {
+        self.declare(&d.id, Symbol::new(TokenKind::TypeAlias));
+        d.visit_children_with(self);
+    }
+
+    fn visit_ts_enum_decl(&mut self, d: &TsEnumDecl) {
+        self.declare(&d.id, Symbol::new(TokenKind::Enum));
+        d.visit_children_with(self);
+    }
+
+    fn visit_ts_module_decl(&mut self, d: &TsModuleDecl) {
+        if let TsModuleName::Ident(id) = &d.id {
+            self.declare(id, Symbol::new(TokenKind::Namespace));
+        }
+        d.body.visit_with(self);
+    }
+
+    fn visit_ts_namespace_decl(&mut self, d: &TsNamespaceDecl) {
+        self.declare(&d.id, Symbol::new(TokenKind::Namespace));
+        d.body.visit_with(self);
+    }
+
+    fn visit_ts_type_param(&mut self, p: &TsTypeParam) {
+        self.declare(&p.name, Symbol::new(TokenKind::TypeParameter));
+        p.visit_children_with(self);
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, d: &TsImportEqualsDecl) {
+        self.declare(&d.id, Symbol::new(TokenKind::Namespace));
+    }
+
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        for specifier in &import.specifiers {
+            let (local, is_namespace) = match specifier {
+                ImportSpecifier::Named(named) => (&named.local, false),
+                ImportSpecifier::Default(default) => (&default.local, false),
+                ImportSpecifier::Namespace(ns) => (&ns.local, true),
+            };
+
+            self.bindings.imports.push((local.to_id(), is_namespace));
+        }
+    }
+}
+
+struct TokenCollector<'a> {
+    bindings: &'a Bindings,
+    tokens: Vec<(Span, TokenKind, TokenModifiers)>,
+}
+
+impl TokenCollector<'_> {
+    fn add(&mut self, span: Span, kind: TokenKind, modifiers: TokenModifiers) {
+        self.tokens.push((span, kind, modifiers));
+    }
+
+    /// Adds the name of a property or a method.
+    fn add_member(&mut self, key: &PropName, kind: TokenKind, modifiers: TokenModifiers) {
+        match key {
+            PropName::Ident(i) => self.add(i.span, kind, modifiers),
+            PropName::Computed(c) => c.visit_with(self),
+            _ => {}
+        }
+    }
+}
+
+impl Visit for TokenCollector<'_> {
+    fn visit_ident(&mut self, i: &Ident) {
+        // Names of properties are not resolved, so they don't match any binding.
+        if let Some(symbol) = self.bindings.symbols.get(&i.to_id()) {
+            let modifiers = TokenModifiers {
+                declaration: self.bindings.declarations.contains_key(&i.span.with_ctxt(SyntaxContext::empty())),
+                readonly: symbol.readonly,
+                is_static: false,
+            };
+
+            self.add(i.span, symbol.kind, modifiers);
+        }
+    }
+
+    fn visit_prop_name(&mut self, n: &PropName) {
+        self.add_member(n, TokenKind::Property, Default::default());
+    }
+
+    fn visit_member_expr(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        match &e.prop {
+            MemberProp::Ident(i) => {
+                let is_enum = match &*e.obj {
+                    Expr::Ident(obj) => self.bindings.symbols.get(&obj.to_id()).map(|symbol| symbol.kind) == Some(TokenKind::Enum),
+                    _ => false,
+                };
+
+                if is_enum {
+                    let modifiers = TokenModifiers {
+                        readonly: true,
+                        ..Default::default()
+                    };
+                    self.add(i.span, TokenKind::EnumMember, modifiers);
+                } else {
+                    self.add(i.span, TokenKind::Property, Default::default());
+                }
+            }
+            MemberProp::PrivateName(p) => self.add(p.span, TokenKind::Property, Default::default()),
+            MemberProp::Computed(c) => c.visit_with(self),
+        }
+    }
+
+    fn visit_class_prop(&mut self, p: &ClassProp) {
+        let modifiers = TokenModifiers {
+            declaration: true,
+            readonly: p.readonly,
+            is_static: p.is_static,
+        };
+
+        p.decorators.visit_with(self);
+        self.add_member(&p.key, TokenKind::Property, modifiers);
+        p.type_ann.visit_with(self);
+        p.value.visit_with(self);
+    }
+
+    fn visit_private_prop(&mut self, p: &PrivateProp) {
+        let modifiers = TokenModifiers {
+            declaration: true,
+            readonly: p.readonly,
+            is_static: p.is_static,
+        };
+
+        p.decorators.visit_with(self);
+        self.add(p.key.span, TokenKind::Property, modifiers);
+        p.type_ann.visit_with(self);
+        p.value.visit_with(self);
+    }
+
+    fn visit_constructor(&mut self, c: &Constructor) {
+        // `constructor` is a keyword.
+        c.params.visit_with(self);
+        c.body.visit_with(self);
+    }
+
+    fn visit_class_method(&mut self, m: &ClassMethod) {
+        let kind = match m.kind {
+            MethodKind::Method => TokenKind::Method,
+            MethodKind::Getter | MethodKind::Setter => TokenKind::Property,
+        };
+        let modifiers = TokenModifiers {
+            declaration: true,
+            is_static: m.is_static,
+            ..Default::default()
+        };
+
+        self.add_member(&m.key, kind, modifiers);
+        m.function.visit_with(self);
+    }
+
+    fn visit_private_method(&mut self, m: &PrivateMethod) {
+        let kind = match m.kind {
+            MethodKind::Method => TokenKind::Method,
+            MethodKind::Getter | MethodKind::Setter => TokenKind::Property,
+        };
+        let modifiers = TokenModifiers {
+            declaration: true,
+            is_static: m.is_static,
+            ..Default::default()
+        };
+
+        self.add(m.key.span, kind, modifiers);
+        m.function.visit_with(self);
+    }
+
+    fn visit_method_prop(&mut self, m: &MethodProp) {
+        self.add_member(&m.key, TokenKind::Method, Default::default());
+        m.function.visit_with(self);
+    }
+
+    fn visit_ts_property_signature(&mut self, p: &TsPropertySignature) {
+        let modifiers = TokenModifiers {
+            declaration: true,
+            readonly: p.readonly,
+            ..Default::default()
+        };
+
+        match &*p.key {
+            Expr::Ident(i) if !p.computed => self.add(i.span, TokenKind::Property, modifiers),
+            key => key.visit_with(self),
+        }
+        p.params.visit_with(self);
+        p.type_ann.visit_with(self);
+        p.type_params.visit_with(self);
+    }
+
+    fn visit_ts_method_signature(&mut self, m: &TsMethodSignature) {
+        let modifiers = TokenModifiers {
+            declaration: true,
+            ..Default::default()
+        };
+
+        match &*m.key {
+            Expr::Ident(i) if !m.computed => self.add(i.span, TokenKind::Method, modifiers),
+            key => key.visit_with(self),
+        }
+        m.params.visit_with(self);
+        m.type_ann.visit_with(self);
+        m.type_params.visit_with(self);
+    }
+
+    fn visit_ts_enum_member(&mut self, m: &TsEnumMember) {
+        if let TsEnumMemberId::Ident(i) = &m.id {
+            let modifiers = TokenModifiers {
+                declaration: true,
+                readonly: true,
+                ..Default::default()
+            };
+            self.add(i.span, TokenKind::EnumMember, modifiers);
+        }
+        m.init.visit_with(self);
+    }
+}
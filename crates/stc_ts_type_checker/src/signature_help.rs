@@ -0,0 +1,270 @@
+//! Signatures of the function being called, shown by editors while typing
+//! arguments.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use stc_ts_ast_rnode::RPat;
+use stc_ts_types::{printer::TypePrinter, ClassMember, FnParam, Type, TypeElement, TypeParamDecl};
+use swc_common::{BytePos, FileName, SourceMap, Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{loader::LoadModule, Checker};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureHelp {
+    /// The candidate signatures, including all overloads.
+    pub signatures: Vec<SignatureInfo>,
+    /// The index of the signature which is likely to be used.
+    pub active_signature: usize,
+    /// The index of the argument at the position.
+    pub active_parameter: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureInfo {
+    /// The whole signature, like `add(a: number, b: number): number`.
+    pub label: String,
+    pub parameters: Vec<ParameterInfo>,
+    /// The printed return type.
+    pub return_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParameterInfo {
+    /// The parameter with its type, like `b?: string`.
+    pub label: String,
+    /// The printed type.
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// A signature found in the type of a callee.
+pub(crate) struct Signature<'a> {
+    pub type_params: Option<&'a TypeParamDecl>,
+    pub params: &'a [FnParam],
+    pub ret_ty: Option<&'a Type>,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Returns the signatures of the innermost call or `new` expression whose
+    /// arguments contain `pos`, which is a byte offset in `file`.
+    ///
+    /// Returns [None] if types are not recorded (see
+    /// [Checker::with_type_recorder]), there's no such call, or the callee
+    /// is not callable. Callees typed as references to interfaces or type
+    /// aliases are not expanded.
+    pub fn signature_help(&self, file: &FileName, pos: usize) -> Option<SignatureHelp> {
+        let recorder = self.type_recorder.as_ref()?;
+        let fm = self.source_file(file)?;
+        let record = self.find_record(&Arc::new(file.clone()))?;
+
+        let mut finder = CallFinder {
+            pos: fm.start_pos + BytePos(pos as u32),
+            found: None,
+        };
+        record.ast.visit_with(&mut finder);
+        let call = finder.found?;
+
+        let callee_ty = recorder.type_of(call.callee)?;
+        let mut signatures = vec![];
+        collect_signatures(&callee_ty, call.is_new, &mut signatures);
+        if signatures.is_empty() {
+            return None;
+        }
+
+        let active_parameter = active_parameter(&self.cm, &call.args, finder.pos);
+        let active_signature = signatures
+            .iter()
+            .position(|sig| sig.params.len() > active_parameter || sig.params.iter().any(|param| matches!(param.pat, RPat::Rest(..))))
+            .unwrap_or(0);
+
+        let printer = TypePrinter::default();
+        let signatures = signatures
+            .into_iter()
+            .map(|sig| {
+                let parameters = sig
+                    .params
+                    .iter()
+                    .map(|param| ParameterInfo {
+                        label: printer.print_param(param),
+                        ty: printer.print(&param.ty),
+                    })
+                    .collect::<Vec<_>>();
+                let return_type = match sig.ret_ty {
+                    Some(ty) => printer.print(ty),
+                    // Constructors of classes create instances of them.
+                    None if call.is_new => call.name.clone(),
+                    None => "any".to_string(),
+                };
+
+                SignatureInfo {
+                    label: format!(
+                        "{}{}({}): {}",
+                        call.name,
+                        printer.print_type_params(sig.type_params),
+                        parameters.iter().map(|param| &*param.label).collect::<Vec<_>>().join(", "),
+                        return_type
+                    ),
+                    parameters,
+                    return_type,
+                }
+            })
+            .collect();
+
+        Some(SignatureHelp {
+            signatures,
+            active_signature,
+            active_parameter,
+        })
+    }
+}
+
+/// Collects the call signatures of `ty`, or the construct signatures if
+/// `is_new` is `true`.
+pub(crate) fn collect_signatures<'a>(ty: &'a Type, is_new: bool, signatures: &mut Vec<Signature<'a>>) {
+    let members = match ty.normalize() {
+        // Overloaded functions are unions.
+        Type::Union(u) => {
+            u.types.iter().for_each(|ty| collect_signatures(ty, is_new, signatures));
+            return;
+        }
+        Type::Intersection(i) => {
+            i.types.iter().for_each(|ty| collect_signatures(ty, is_new, signatures));
+            return;
+        }
+        Type::Function(f) if !is_new => {
+            signatures.push(Signature {
+                type_params: f.type_params.as_ref(),
+                params: &f.params,
+                ret_ty: Some(&*f.ret_ty),
+            });
+            return;
+        }
+        Type::Constructor(c) if is_new => {
+            signatures.push(Signature {
+                type_params: c.type_params.as_ref(),
+                params: &c.params,
+                ret_ty: Some(&*c.type_ann),
+            });
+            return;
+        }
+        Type::ClassDef(c) if is_new => {
+            let len = signatures.len();
+            for member in &c.body {
+                if let ClassMember::Constructor(c) = member {
+                    signatures.push(Signature {
+                        type_params: c.type_params.as_ref(),
+                        params: &c.params,
+                        ret_ty: c.ret_ty.as_deref(),
+                    });
+                }
+            }
+            // The implicit constructor.
+            if signatures.len() == len {
+                signatures.push(Signature {
+                    type_params: None,
+                    params: &[],
+                    ret_ty: None,
+                });
+            }
+            return;
+        }
+        Type::Interface(i) => &i.body,
+        Type::TypeLit(t) => &t.members,
+        _ => return,
+    };
+
+    for member in members {
+        match member {
+            TypeElement::Call(c) if !is_new => signatures.push(Signature {
+                type_params: c.type_params.as_ref(),
+                params: &c.params,
+                ret_ty: c.ret_ty.as_deref(),
+            }),
+            TypeElement::Constructor(c) if is_new => signatures.push(Signature {
+                type_params: c.type_params.as_ref(),
+                params: &c.params,
+                ret_ty: c.ret_ty.as_deref(),
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the index of the argument at `pos`, which is the number of commas
+/// between the arguments before `pos`.
+fn active_parameter(cm: &SourceMap, args: &[Span], pos: BytePos) -> usize {
+    let mut index = 0;
+    for (i, arg) in args.iter().enumerate() {
+        if arg.hi > pos {
+            break;
+        }
+
+        let end = args.get(i + 1).map_or(pos, |next| next.lo.min(pos));
+        if let Ok(between) = cm.span_to_snippet(Span::new(arg.hi, end, Default::default())) {
+            if between.contains(',') {
+                index = i + 1;
+            }
+        }
+    }
+    index
+}
+
+struct Call {
+    is_new: bool,
+    callee: Span,
+    /// The name of the callee, or an empty string if it's not a name.
+    name: String,
+    args: Vec<Span>,
+}
+
+/// Finds the innermost call whose arguments contain `pos`.
+struct CallFinder {
+    pos: BytePos,
+    found: Option<Call>,
+}
+
+impl CallFinder {
+    fn check(&mut self, span: Span, is_new: bool, callee: &Expr, args: &[ExprOrSpread]) {
+        // Inside of the parentheses.
+        if callee.span().hi < self.pos && self.pos < span.hi {
+            let name = match callee {
+                Expr::Ident(i) => i.sym.to_string(),
+                Expr::Member(MemberExpr {
+                    prop: MemberProp::Ident(i),
+                    ..
+                }) => i.sym.to_string(),
+                _ => String::new(),
+            };
+
+            self.found = Some(Call {
+                is_new,
+                callee: callee.span(),
+                name,
+                args: args.iter().map(|arg| arg.span()).collect(),
+            });
+        }
+    }
+}
+
+impl Visit for CallFinder {
+    fn visit_call_expr(&mut self, e: &CallExpr) {
+        if let Callee::Expr(callee) = &e.callee {
+            self.check(e.span, false, callee, &e.args);
+        }
+        e.visit_children_with(self);
+    }
+
+    fn visit_new_expr(&mut self, e: &NewExpr) {
+        self.check(e.span, true, &e.callee, e.args.as_deref().unwrap_or_default());
+        e.visit_children_with(self);
+    }
+}
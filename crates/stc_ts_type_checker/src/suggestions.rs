@@ -0,0 +1,155 @@
+//! "Did you mean" suggestions for names and properties which are not found,
+//! like tsc.
+
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_types::{Id, Key};
+use stc_utils::spelling::spelling_suggestion;
+use swc_atoms::JsWord;
+use swc_common::{Span, DUMMY_SP};
+
+use crate::{
+    completions::{declarations_in_scope, member_names},
+    declarations::DeclarationKind,
+    loader::{LoadModule, ModuleRecord},
+    Checker,
+};
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Replaces the errors of names and properties which are not found in
+    /// `record` with the errors suggesting a similar name, if any.
+    ///
+    /// Names are compared with the declarations in scope and the globals.
+    /// Properties are compared with the members of the object, which may be
+    /// incomplete if types are not recorded (see
+    /// [Checker::with_type_recorder]).
+    pub(crate) fn add_spelling_suggestions(&self, record: &ModuleRecord, errors: Vec<Error>) -> Vec<Error> {
+        if !errors.iter().any(|err| is_suggestable(err)) {
+            return errors;
+        }
+
+        let empty_recorder;
+        let recorder = match &self.type_recorder {
+            Some(recorder) => recorder,
+            None => {
+                empty_recorder = TypeRecorder::default();
+                &empty_recorder
+            }
+        };
+
+        errors
+            .into_iter()
+            .map(|err| match self.spelling_suggestion(record, recorder, &err) {
+                Some(kind) => kind.into(),
+                None => err,
+            })
+            .collect()
+    }
+
+    fn spelling_suggestion(&self, record: &ModuleRecord, recorder: &TypeRecorder, err: &ErrorKind) -> Option<ErrorKind> {
+        match err {
+            ErrorKind::NoSuchVar { span, name } => {
+                let (suggestion, declared) = self.similar_name(record, *span, name, false)?;
+
+                Some(ErrorKind::NoSuchVarWithSuggestion {
+                    span: *span,
+                    name: name.clone(),
+                    suggestion,
+                    declared,
+                })
+            }
+            ErrorKind::NoSuchType { span, name } => {
+                let (suggestion, declared) = self.similar_name(record, *span, name, true)?;
+
+                Some(ErrorKind::NoSuchTypeWithSuggestion {
+                    span: *span,
+                    name: name.clone(),
+                    suggestion,
+                    declared,
+                })
+            }
+            ErrorKind::NoSuchProperty {
+                span,
+                obj: Some(obj),
+                prop: Some(prop),
+            } => {
+                let name = match &**prop {
+                    Key::Normal { sym, .. } => sym,
+                    _ => return None,
+                };
+                let members = member_names(&self.env, recorder, obj);
+                let suggestion = spelling_suggestion(name, members.iter().map(|member| &**member))?;
+
+                Some(ErrorKind::NoSuchPropertyWithSuggestion {
+                    span: *span,
+                    obj: obj.clone(),
+                    prop: prop.clone(),
+                    suggestion: suggestion.into(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the name of a declaration in scope or a global similar to
+    /// `name`, and the span of the declaration. The span is dummy for globals.
+    ///
+    /// Inner declarations are preferred over outer ones and globals.
+    fn similar_name(&self, record: &ModuleRecord, span: Span, name: &Id, is_type: bool) -> Option<(JsWord, Span)> {
+        let decls = declarations_in_scope(&record.ast, span.lo)
+            .into_iter()
+            .rev()
+            .filter(|(_, kind)| if is_type { kind.is_type() } else { kind.is_value() })
+            .collect::<Vec<_>>();
+        let globals = if is_type {
+            self.env.global_type_names()
+        } else {
+            self.env.global_var_names()
+        };
+
+        let suggestion = spelling_suggestion(
+            name.sym(),
+            decls.iter().map(|(i, _)| &*i.sym).chain(globals.iter().map(|name| &**name)),
+        )?;
+        let declared = decls.iter().find(|(i, _)| &*i.sym == suggestion).map_or(DUMMY_SP, |(i, _)| i.span);
+
+        Some((suggestion.into(), declared))
+    }
+}
+
+fn is_suggestable(err: &ErrorKind) -> bool {
+    matches!(
+        err,
+        ErrorKind::NoSuchVar { .. }
+            | ErrorKind::NoSuchType { .. }
+            | ErrorKind::NoSuchProperty {
+                obj: Some(..),
+                prop: Some(..),
+                ..
+            }
+    )
+}
+
+impl DeclarationKind {
+    /// Returns `true` if the declaration declares a value, which can be used
+    /// as an expression.
+    fn is_value(self) -> bool {
+        !matches!(self, DeclarationKind::Interface | DeclarationKind::TypeAlias)
+    }
+
+    /// Returns `true` if the declaration may declare a type.
+    fn is_type(self) -> bool {
+        matches!(
+            self,
+            DeclarationKind::Class
+                | DeclarationKind::Interface
+                | DeclarationKind::TypeAlias
+                | DeclarationKind::Enum
+                | DeclarationKind::Namespace
+                | DeclarationKind::Import
+        )
+    }
+}
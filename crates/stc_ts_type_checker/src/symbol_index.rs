@@ -0,0 +1,129 @@
+//! An index of the top-level declarations of the checked files, to search
+//! symbols by name in the whole project like `workspace/symbol` of editors.
+
+use std::{cmp::Reverse, path::PathBuf, sync::Arc};
+
+use swc_common::FileName;
+
+use crate::{declarations::DeclarationSpan, loader::LoadModule, outline::SymbolKind, Checker};
+
+/// A top-level declaration of a checked file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub file: Arc<FileName>,
+    /// The span of the name.
+    pub span: DeclarationSpan,
+}
+
+/// Created by [Checker::symbol_index]. It is not updated when files change,
+/// so it should be created again after checking files.
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    symbols: Vec<WorkspaceSymbol>,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Indexes the top-level declarations of the loaded files. Files in
+    /// `node_modules` are not indexed.
+    pub fn symbol_index(&self) -> SymbolIndex {
+        let mut symbols = vec![];
+
+        for module in self.module_graph().modules {
+            if module.path.split(['/', '\\']).any(|component| component == "node_modules") {
+                continue;
+            }
+
+            let file = Arc::new(FileName::Real(PathBuf::from(&module.path)));
+            let found = match self.document_symbols(&file) {
+                Some(found) => found,
+                None => continue,
+            };
+
+            symbols.extend(found.into_iter().map(|symbol| WorkspaceSymbol {
+                name: symbol.name,
+                kind: symbol.kind,
+                file: file.clone(),
+                span: symbol.selection_span,
+            }));
+        }
+
+        SymbolIndex { symbols }
+    }
+}
+
+impl SymbolIndex {
+    /// Returns at most `limit` symbols whose names match `query`, with the
+    /// best matches first.
+    ///
+    /// A name matches if it contains the characters of `query` in order,
+    /// ignoring the case, so `gUN` matches `getUserName`. Exact matches,
+    /// prefixes and characters at the start of words rank higher. All symbols
+    /// match an empty query.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&WorkspaceSymbol> {
+        let query = query.chars().flat_map(char::to_lowercase).collect::<Vec<_>>();
+
+        let mut found = self
+            .symbols
+            .iter()
+            .filter_map(|symbol| Some((fuzzy_score(&query, &symbol.name)?, symbol)))
+            .collect::<Vec<_>>();
+        found.sort_by(|(a_score, a), (b_score, b)| {
+            (Reverse(a_score), a.name.len(), &a.name).cmp(&(Reverse(b_score), b.name.len(), &b.name))
+        });
+
+        found.into_iter().take(limit).map(|(_, symbol)| symbol).collect()
+    }
+}
+
+/// Returns [None] if `name` does not contain the characters of `query`, which
+/// is lowercase, in order.
+///
+/// Characters are matched greedily, so the score of a name is not always the
+/// score of its best match.
+fn fuzzy_score(query: &[char], name: &str) -> Option<u32> {
+    let mut score = 0;
+    let mut matched = 0;
+    let mut prev_char = None::<char>;
+    let mut prev_matched = false;
+
+    for c in name.chars() {
+        if matched == query.len() {
+            break;
+        }
+
+        let is_match = c.to_lowercase().eq([query[matched]]);
+        if is_match {
+            let is_word_start = match prev_char {
+                None => true,
+                Some(prev) => prev == '_' || prev == '$' || (prev.is_lowercase() && c.is_uppercase()),
+            };
+
+            score += 1;
+            if is_word_start {
+                score += 3;
+            }
+            if prev_matched {
+                score += 2;
+            }
+            matched += 1;
+        }
+
+        prev_matched = is_match;
+        prev_char = Some(c);
+    }
+
+    if matched < query.len() {
+        return None;
+    }
+
+    if name.chars().flat_map(char::to_lowercase).eq(query.iter().copied()) {
+        score += 10;
+    }
+
+    Some(score)
+}
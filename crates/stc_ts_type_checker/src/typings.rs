@@ -1,77 +1,159 @@
 use std::{
-    fs::read_dir,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Instant,
 };
 
+use fxhash::FxHashSet;
 use rayon::prelude::*;
-use stc_ts_module_loader::resolvers::node::NodeResolver;
-use swc_common::FileName;
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_module_loader::{fs::FileSystem, resolvers::node::types_package_name};
+use stc_utils::time::Instant;
+use swc_common::{FileName, DUMMY_SP};
 
 use crate::{loader::LoadModule, Checker};
 
+/// Typings are resolved as if they are imported by this file in the type root,
+/// or in the base directory. The file doesn't need to exist.
+const INFERRED_TYPES_FILE: &str = "__inferred type names__.ts";
+
 impl<L> Checker<L>
 where
     L: LoadModule,
 {
-    fn try_loading_typing_of_one_package(&self, dir: &Path) {
-        if !dir.is_dir() {
-            return;
+    /// Resolves the entry of the typing package `name`.
+    ///
+    /// The type roots are searched first, in order. Scoped packages like
+    /// `@scope/pkg` are also found by their mangled names like `scope__pkg`.
+    /// Packages which are not in the type roots are resolved like an import of
+    /// `name` from `base`, which finds packages in `node_modules`.
+    fn resolve_typing_package(&self, fs: &Arc<dyn FileSystem>, base: &Path, type_roots: &[PathBuf], name: &str) -> Option<FileName> {
+        let mut names = vec![name.to_string()];
+        if name.starts_with('@') {
+            names.push(types_package_name(name));
         }
 
-        let result = NodeResolver
-            .resolve_as_file(dir)
-            .or_else(|_| NodeResolver.resolve_as_directory(dir));
+        let resolve = |dir: &Path, module_specifier: &str| match self
+            .module_loader
+            .resolve_dep(&Arc::new(FileName::Real(dir.join(INFERRED_TYPES_FILE))), module_specifier)
+        {
+            Ok(file @ FileName::Real(..)) => Some(file),
+            _ => None,
+        };
+
+        type_roots
+            .iter()
+            .filter(|type_root| fs.is_dir(type_root))
+            .find_map(|type_root| names.iter().find_map(|name| resolve(type_root, &format!("./{}", name))))
+            .or_else(|| resolve(base, name))
+    }
 
-        if let Ok(entry) = result {
-            let entry = Arc::new(FileName::Real(entry));
-            let start = Instant::now();
+    /// Load typings from node_modules.
+    ///
+    /// If `type_roots` is [None], `node_modules/@types` of `base` and all of
+    /// its ancestors are used.
+    ///
+    /// If `types` is [Some], only the listed packages are loaded. Returns the
+    /// errors of the listed packages which can't be found.
+    ///
+    /// - https://www.typescriptlang.org/tsconfig#typeRoots
+    /// - https://www.typescriptlang.org/tsconfig#types
+    pub fn load_typings(&self, base: &Path, type_roots: Option<&[PathBuf]>, types: Option<&[String]>) -> Vec<Error> {
+        let type_roots = match type_roots {
+            Some(type_roots) => type_roots.to_vec(),
+            None => default_type_roots(base),
+        };
+
+        let fs = self.module_loader.fs();
+        let names = match types {
+            Some(types) => types.to_vec(),
+            None => find_typing_packages(&*fs, &type_roots),
+        };
+
+        // Errors are collected in the order of `types`.
+        names
+            .into_par_iter()
+            .filter_map(|name| {
+                let entry = match self.resolve_typing_package(&fs, base, &type_roots, &name) {
+                    Some(entry) => Arc::new(entry),
+                    None => return Some(ErrorKind::CannotFindTypeDefinitionFile { span: DUMMY_SP, name }.into()),
+                };
+                let start = Instant::now();
+
+                self.analyze_module(None, entry);
+
+                let end = Instant::now();
+                log::debug!("Loading typings of `{}` took {:?}", name, end - start);
+
+                None
+            })
+            .collect()
+    }
+}
 
-            self.analyze_module(None, entry);
+/// Returns `node_modules/@types` of `base` and its ancestors, from the
+/// nearest one.
+fn default_type_roots(base: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![];
 
-            let end = Instant::now();
-            log::debug!("Loading typings at `{}` took {:?}", dir.display(), end - start);
-        }
+    let mut cur = Some(base);
+    while let Some(c) = cur {
+        dirs.push(c.join("node_modules").join("@types"));
+        cur = c.parent();
     }
 
-    fn load_typings_from_dir(&self, dir: &Path, types: Option<&[String]>) {
-        let types_dir = dir.join("node_modules").join("@types");
+    dirs
+}
 
-        if !types_dir.is_dir() {
-            return Default::default();
+/// Returns the names of the packages in the type roots, like `node` or
+/// `@scope/pkg`.
+///
+/// If multiple type roots contain a package with the same name, the name is
+/// returned once.
+fn find_typing_packages(fs: &dyn FileSystem, type_roots: &[PathBuf]) -> Vec<String> {
+    let mut done = FxHashSet::default();
+    let mut packages = vec![];
+
+    for type_root in type_roots {
+        if !fs.is_dir(type_root) {
+            continue;
         }
 
-        let dirs = types.map(|s| s.iter().map(|s| PathBuf::from(s.clone())).collect()).or_else(|| {
-            let pkgs = read_dir(&types_dir).ok()?;
-
-            let f = pkgs.into_iter().filter_map(Result::ok).map(|e| e.path()).collect::<Vec<_>>();
-
-            Some(f)
-        });
-
-        if let Some(dirs) = dirs {
-            dirs.into_par_iter().for_each(|dir| {
-                self.try_loading_typing_of_one_package(&types_dir.join(dir));
-            });
+        let mut names = package_dirs(fs, type_root)
+            .into_iter()
+            .flat_map(|name| match name.starts_with('@') {
+                // The packages of a scope.
+                true => package_dirs(fs, &type_root.join(&name))
+                    .into_iter()
+                    .map(|pkg| format!("{}/{}", name, pkg))
+                    .collect(),
+                false => vec![name],
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+
+        for name in names {
+            if done.insert(name.clone()) {
+                packages.push(name);
+            }
         }
     }
 
-    /// Load typings from node_modules.
-    ///
-    /// - https://www.typescriptlang.org/tsconfig#typeRoots
-    /// - https://www.typescriptlang.org/tsconfig#types
-    pub fn load_typings(&self, base: &Path, _type_roots: Option<&[PathBuf]>, types: Option<&[String]>) {
-        let mut dirs = vec![];
+    packages
+}
 
-        let mut cur = Some(base);
-        while let Some(c) = cur {
-            dirs.push(c.to_path_buf());
-            cur = c.parent();
+/// Returns the names of the directories in `dir`, except hidden ones.
+fn package_dirs(fs: &dyn FileSystem, dir: &Path) -> Vec<String> {
+    match fs.read_dir(dir) {
+        Ok(entries) => entries
+            .into_iter()
+            .filter(|path| fs.is_dir(path))
+            .filter_map(|path| path.file_name().and_then(|s| s.to_str()).map(|s| s.to_string()))
+            // Hidden directories are not packages.
+            .filter(|name| !name.starts_with('.'))
+            .collect(),
+        Err(err) => {
+            log::warn!("Failed to read `{}`: {:?}", dir.display(), err);
+            vec![]
         }
-
-        dirs.into_par_iter().for_each(|dir| {
-            self.load_typings_from_dir(&dir, types);
-        });
     }
 }
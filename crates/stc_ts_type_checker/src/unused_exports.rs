@@ -0,0 +1,211 @@
+//! Detection of exports which are not imported by any checked file, for
+//! `noUnusedExports`.
+
+use std::{path::PathBuf, sync::Arc};
+
+use fxhash::{FxHashMap, FxHashSet};
+use stc_ts_errors::ErrorKind;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{FileName, Span};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{loader::LoadModule, Checker};
+
+/// Names imported from a file.
+#[derive(Default)]
+struct Usage {
+    /// `true` if all exports may be used, like for `import * as ns`.
+    all: bool,
+    names: FxHashSet<JsWord>,
+}
+
+impl<L> Checker<L>
+where
+    L: LoadModule,
+{
+    /// Reports an error for each export of the loaded files which is not
+    /// imported by any loaded file. Exports of `entries` and declaration
+    /// files are not reported.
+    pub(crate) fn report_unused_exports(&self, entries: &[Arc<FileName>]) {
+        let graph = self.module_graph();
+
+        // The resolved file of each module specifier.
+        let resolved = graph
+            .imports
+            .iter()
+            .filter_map(|import| Some(((&*import.from, &*import.specifier), import.resolved.as_deref()?)))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut usages = FxHashMap::<String, Usage>::default();
+        let mut exports = vec![];
+
+        for module in &graph.modules {
+            let file = Arc::new(FileName::Real(PathBuf::from(&module.path)));
+            let record = match self.find_record(&file) {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let mut collector = ImportCollector { imports: vec![] };
+            record.ast.visit_with(&mut collector);
+            for (specifier, name) in collector.imports {
+                let target = match resolved.get(&(&*module.path, &*specifier)) {
+                    Some(target) => target.to_string(),
+                    None => continue,
+                };
+
+                let usage = usages.entry(target).or_default();
+                match name {
+                    Some(name) => {
+                        usage.names.insert(name);
+                    }
+                    None => usage.all = true,
+                }
+            }
+
+            if module.is_dts || entries.contains(&file) {
+                continue;
+            }
+
+            for item in &record.ast.body {
+                if let ModuleItem::ModuleDecl(decl) = item {
+                    exports.extend(
+                        exported_names(decl)
+                            .into_iter()
+                            .map(|(name, span)| (module.path.clone(), name, span)),
+                    );
+                }
+            }
+        }
+
+        let mut errors = self.errors.lock();
+        for (path, name, span) in exports {
+            let used = usages.get(&path).map_or(false, |usage| usage.all || usage.names.contains(&name));
+            if !used {
+                errors.push(ErrorKind::UnusedExport { span, name }.into());
+            }
+        }
+    }
+}
+
+/// Returns the names exported by `decl` and their spans.
+///
+/// Names exported by `export *` are not included, because they are exports of
+/// another file.
+fn exported_names(decl: &ModuleDecl) -> Vec<(JsWord, Span)> {
+    match decl {
+        ModuleDecl::ExportDecl(export) => match &export.decl {
+            Decl::Class(c) => vec![(c.ident.sym.clone(), c.ident.span)],
+            Decl::Fn(f) => vec![(f.ident.sym.clone(), f.ident.span)],
+            Decl::Var(v) => {
+                let mut collector = BindingCollector { names: vec![] };
+                v.decls.iter().for_each(|decl| decl.name.visit_with(&mut collector));
+                collector.names
+            }
+            Decl::TsInterface(d) => vec![(d.id.sym.clone(), d.id.span)],
+            Decl::TsTypeAlias(d) => vec![(d.id.sym.clone(), d.id.span)],
+            Decl::TsEnum(d) => vec![(d.id.sym.clone(), d.id.span)],
+            Decl::TsModule(d) => match &d.id {
+                TsModuleName::Ident(i) => vec![(i.sym.clone(), i.span)],
+                TsModuleName::Str(..) => vec![],
+            },
+        },
+        ModuleDecl::ExportNamed(export) => export
+            .specifiers
+            .iter()
+            .map(|specifier| match specifier {
+                ExportSpecifier::Namespace(s) => (module_export_name(&s.name), s.span),
+                ExportSpecifier::Default(s) => (js_word!("default"), s.exported.span),
+                ExportSpecifier::Named(s) => (module_export_name(s.exported.as_ref().unwrap_or(&s.orig)), s.span),
+            })
+            .collect(),
+        ModuleDecl::ExportDefaultDecl(export) => vec![(js_word!("default"), export.span)],
+        ModuleDecl::ExportDefaultExpr(export) => vec![(js_word!("default"), export.span)],
+        _ => vec![],
+    }
+}
+
+fn module_export_name(name: &ModuleExportName) -> JsWord {
+    match name {
+        ModuleExportName::Ident(i) => i.sym.clone(),
+        ModuleExportName::Str(s) => s.value.clone(),
+    }
+}
+
+/// Collects the module specifiers of a file with the imported names, which
+/// are [None] if all exports may be used.
+struct ImportCollector {
+    imports: Vec<(JsWord, Option<JsWord>)>,
+}
+
+impl Visit for ImportCollector {
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        for specifier in &import.specifiers {
+            let name = match specifier {
+                ImportSpecifier::Named(s) => Some(s.imported.as_ref().map_or_else(|| s.local.sym.clone(), module_export_name)),
+                ImportSpecifier::Default(..) => Some(js_word!("default")),
+                ImportSpecifier::Namespace(..) => None,
+            };
+            self.imports.push((import.src.value.clone(), name));
+        }
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        let src = match &export.src {
+            Some(src) => &src.value,
+            None => return,
+        };
+
+        for specifier in &export.specifiers {
+            let name = match specifier {
+                ExportSpecifier::Named(s) => Some(module_export_name(&s.orig)),
+                ExportSpecifier::Default(..) => Some(js_word!("default")),
+                ExportSpecifier::Namespace(..) => None,
+            };
+            self.imports.push((src.clone(), name));
+        }
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        self.imports.push((export.src.value.clone(), None));
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, import: &TsImportEqualsDecl) {
+        if let TsModuleRef::TsExternalModuleRef(r) = &import.module_ref {
+            self.imports.push((r.expr.value.clone(), None));
+        }
+    }
+
+    fn visit_call_expr(&mut self, e: &CallExpr) {
+        // `import('./foo')`
+        if let (Callee::Import(..), Some(ExprOrSpread { expr, spread: None })) = (&e.callee, e.args.first()) {
+            if let Expr::Lit(Lit::Str(src)) = &**expr {
+                self.imports.push((src.value.clone(), None));
+            }
+        }
+        e.visit_children_with(self);
+    }
+
+    fn visit_ts_import_type(&mut self, t: &TsImportType) {
+        // `import('./foo').Bar`
+        self.imports.push((t.arg.value.clone(), None));
+        t.visit_children_with(self);
+    }
+}
+
+struct BindingCollector {
+    names: Vec<(JsWord, Span)>,
+}
+
+impl Visit for BindingCollector {
+    fn visit_binding_ident(&mut self, i: &BindingIdent) {
+        self.names.push((i.id.sym.clone(), i.id.span));
+    }
+
+    fn visit_assign_pat_prop(&mut self, p: &AssignPatProp) {
+        self.names.push((p.key.sym.clone(), p.key.span));
+    }
+
+    fn visit_expr(&mut self, _: &Expr) {}
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use stc_ts_env::Rule;
+
+use self::common::TestProject;
+
+fn check(rule: Rule) -> Vec<usize> {
+    TestProject::new(&[
+        ("/project/main.ts", "import { foo } from './lib.js';\nconst v: number = foo.bar;\n"),
+        ("/project/lib.js", "export const foo = {};\nconst num = 1;\nnum();\n"),
+    ])
+    .with_rule(rule)
+    .error_codes()
+}
+
+#[test]
+fn js_is_not_loaded_without_allow_js() {
+    // Cannot find module './lib.js'
+    assert_eq!(check(Default::default()), vec![2307]);
+}
+
+#[test]
+fn js_exports_are_any() {
+    let rule = Rule {
+        allow_js: true,
+        ..Default::default()
+    };
+
+    assert_eq!(check(rule), Vec::<usize>::new());
+}
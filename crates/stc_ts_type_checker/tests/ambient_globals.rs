@@ -0,0 +1,66 @@
+mod common;
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{BuiltIn, Env, ModuleConfig};
+use stc_ts_file_analyzer::env::{AmbientSourceError, BuiltInGen, EnvFactory};
+use swc_ecma_ast::EsVersion;
+
+use self::common::TestProject;
+
+const GLOBALS: &str = "declare const __DEV__: boolean;
+declare function describe(name: string, f: () => void): void;
+declare namespace expect {
+    function assertions(count: number): void;
+}
+";
+
+/// Checks `main` with the globals declared by `globals`.
+fn check(globals: Option<&'static str>, main: &str) -> Vec<usize> {
+    let project = TestProject::new(&[("/project/main.ts", main)]);
+
+    match globals {
+        Some(globals) => project
+            .with_env(move |mut env| {
+                let globals = BuiltIn::from_source(env.shared(), globals).unwrap();
+                env.declare_globals(&globals);
+                env
+            })
+            .error_codes(),
+        None => project.error_codes(),
+    }
+}
+
+#[test]
+fn global_var() {
+    assert_eq!(check(Some(GLOBALS), "const dev: boolean = __DEV__;\n"), Vec::<usize>::new());
+    assert_eq!(check(Some(GLOBALS), "const dev: string = __DEV__;\n"), vec![2322]);
+}
+
+#[test]
+fn global_var_is_not_declared_by_default() {
+    assert_eq!(check(None, "const dev: boolean = __DEV__;\n"), vec![2304]);
+}
+
+#[test]
+fn global_function_and_namespace() {
+    assert_eq!(
+        check(Some(GLOBALS), "describe('a', () => {\n    expect.assertions(1);\n});\n"),
+        Vec::<usize>::new()
+    );
+    assert_eq!(check(Some(GLOBALS), "describe(1, () => {});\n"), vec![2345]);
+}
+
+#[test]
+fn unsupported_statement() {
+    testing::run_test2(false, |_, _| {
+        let env = Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5"));
+
+        match BuiltIn::from_source(env.shared(), "declare const a: number;\nexport const b = 1;\n") {
+            Err(AmbientSourceError::Unsupported { start, end }) => assert_eq!((start, end), (25, 44)),
+            res => panic!("unexpected result: {:?}", res.map(|_| ())),
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
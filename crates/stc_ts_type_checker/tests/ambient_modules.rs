@@ -0,0 +1,55 @@
+mod common;
+
+use self::common::TestProject;
+
+const TYPES: &str = "declare module \"untyped\" {
+    export const value: number;
+}
+declare module \"*.svg\" {
+    export const url: string;
+}
+";
+
+/// Checks `main` next to `/project/types.d.ts`, which declares ambient
+/// modules.
+fn check(main: &str) -> Vec<usize> {
+    TestProject::new(&[("/project/main.ts", main), ("/project/types.d.ts", TYPES)]).error_codes()
+}
+
+#[test]
+fn ambient_module_is_resolved() {
+    let codes = check("/// <reference path=\"types.d.ts\" />\nimport { value } from \"untyped\";\nconst v: number = value;\n");
+
+    assert_eq!(codes, Vec::<usize>::new());
+}
+
+#[test]
+fn ambient_module_is_typed() {
+    let codes = check("/// <reference path=\"types.d.ts\" />\nimport { value } from \"untyped\";\nconst v: string = value;\n");
+
+    // Type 'number' is not assignable to type 'string'.
+    assert_eq!(codes, vec![2322]);
+}
+
+#[test]
+fn undeclared_module() {
+    let codes = check("import { value } from \"untyped\";\n");
+
+    // Cannot find module 'untyped'.
+    assert_eq!(codes, vec![2307]);
+}
+
+#[test]
+fn wildcard_ambient_module() {
+    let codes = check("/// <reference path=\"types.d.ts\" />\nimport { url } from \"./logo.svg\";\nconst v: string = url;\n");
+
+    assert_eq!(codes, Vec::<usize>::new());
+}
+
+#[test]
+fn wildcard_ambient_module_is_typed() {
+    let codes = check("/// <reference path=\"types.d.ts\" />\nimport { url } from \"./logo.svg\";\nconst v: number = url;\n");
+
+    // Type 'string' is not assignable to type 'number'.
+    assert_eq!(codes, vec![2322]);
+}
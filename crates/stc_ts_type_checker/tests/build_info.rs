@@ -0,0 +1,117 @@
+mod common;
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_errors::Severity;
+use stc_ts_type_checker::build_info::BuildInfo;
+use swc_common::{FileName, SourceMap};
+
+use self::common::TestProject;
+
+/// Checks `files` using a new checker, and stores the results into `info`.
+fn check(project: &TestProject, info: &mut BuildInfo, files: &[PathBuf], entries: &[PathBuf]) {
+    project.run(|_, mut checker| {
+        checker.check_all(files.iter().map(|path| Arc::new(FileName::Real(path.clone()))).collect());
+        let errors = checker.take_errors();
+        checker.update_build_info(info, &errors, entries);
+    });
+}
+
+#[test]
+fn affected_files() {
+    let project = TestProject::new(&[
+        (
+            "/project/main.ts",
+            "import { a } from './a';\nimport { b } from './b';\nconst s: string = a;\n",
+        ),
+        ("/project/a.ts", "export const a = 1;\n"),
+        ("/project/b.ts", "export const b = 1;\n"),
+        ("/project/other.ts", "export const c = 1;\n"),
+    ]);
+    let fs = project.fs.clone();
+
+    let main = PathBuf::from("/project/main.ts");
+    let entries = vec![main.clone(), PathBuf::from("/project/other.ts")];
+
+    let mut info = BuildInfo::new("es5".into());
+    check(&project, &mut info, &entries, &entries);
+
+    assert_eq!(info.files.len(), 4);
+    assert_eq!(info.files["/project/main.ts"].diagnostics.len(), 1);
+    assert_eq!(info.files["/project/main.ts"].diagnostics[0].code, "TS2322");
+    assert!(info.files["/project/a.ts"].diagnostics.is_empty());
+
+    // The stored results survive a round trip.
+    let mut info = BuildInfo::from_json(&info.to_json().unwrap()).unwrap();
+    assert!(info.is_compatible("es5"));
+    assert!(!info.is_compatible("es2015"));
+
+    assert!(info.affected_files(&*fs, &entries).is_empty());
+
+    fs.insert(PathBuf::from("/project/a.ts"), "export const a = '';\n");
+    let affected = info.affected_files(&*fs, &entries);
+    assert_eq!(affected, vec![PathBuf::from("/project/a.ts"), main.clone()]);
+
+    check(&project, &mut info, &affected, &entries);
+    assert!(info.files["/project/main.ts"].diagnostics.is_empty());
+    assert!(info.affected_files(&*fs, &entries).is_empty());
+
+    // Files which are not reachable from the entries are removed.
+    fs.insert(main.clone(), "import { a } from './a';\n");
+    let affected = info.affected_files(&*fs, &entries);
+    assert_eq!(affected, vec![main.clone()]);
+
+    check(&project, &mut info, &affected, &entries);
+    assert!(!info.files.contains_key("/project/b.ts"));
+    assert!(info.files.contains_key("/project/other.ts"));
+}
+
+#[test]
+fn unresolved_imports_are_rechecked() {
+    let project = TestProject::new(&[("/project/main.ts", "import { a } from './a';\n")]);
+    let fs = project.fs.clone();
+
+    let entries = vec![PathBuf::from("/project/main.ts")];
+
+    let mut info = BuildInfo::new(String::new());
+    check(&project, &mut info, &entries, &entries);
+    assert_eq!(info.files["/project/main.ts"].diagnostics.len(), 1);
+
+    fs.insert(PathBuf::from("/project/a.ts"), "export const a = 1;\n");
+    assert_eq!(info.affected_files(&*fs, &entries), entries);
+}
+
+#[test]
+fn stored_reports() {
+    let project = TestProject::new(&[
+        ("/project/main.ts", "import { a } from './a';\nconst s: string = a;\n"),
+        ("/project/a.ts", "export const a = 1;\n"),
+    ]);
+    let fs = project.fs.clone();
+
+    let entries = vec![PathBuf::from("/project/main.ts")];
+
+    let mut info = BuildInfo::new(String::new());
+    check(&project, &mut info, &entries, &entries);
+
+    let cm = SourceMap::default();
+    let reports = info.stored_reports(&*fs, &cm, &[]);
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].code, 2322);
+    assert_eq!(reports[0].severity, Severity::Error);
+    assert_eq!(cm.lookup_char_pos(reports[0].span.lo).line, 2);
+
+    let diagnostics = info.stored_diagnostics(&*fs, &SourceMap::default(), &[]);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].file.as_deref(), Some("/project/main.ts"));
+    let stored = &info.files["/project/main.ts"].diagnostics[0];
+    assert_eq!(
+        (diagnostics[0].start, diagnostics[0].end),
+        (stored.start as usize, stored.end as usize)
+    );
+    assert_eq!(diagnostics[0].line, 2);
+    assert_eq!(diagnostics[0].message, reports[0].message);
+
+    // The errors of checked files are reported by the check.
+    assert!(info.stored_reports(&*fs, &cm, &["/project/main.ts".into()]).is_empty());
+}
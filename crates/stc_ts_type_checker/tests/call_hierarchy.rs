@@ -0,0 +1,75 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_type_checker::call_hierarchy::{CallHierarchyItem, CallHierarchyKind};
+use swc_common::SourceMap;
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { log } from './log';
+function helper() {
+    log();
+}
+const run = () => {
+    helper();
+    helper();
+    [1].forEach(function inner() {
+        log();
+    });
+};
+run();
+const count = [1].length;
+";
+
+fn describe(cm: &SourceMap, item: &CallHierarchyItem) -> (String, CallHierarchyKind, String) {
+    (item.name.to_string(), item.kind, cm.span_to_filename(item.span).to_string())
+}
+
+#[test]
+fn call_hierarchy() {
+    let project = TestProject::new(&[("/project/main.ts", MAIN), ("/project/log.ts", "export function log() {}\n")]);
+
+    project.run(|cm, checker| {
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        // `log` is declared in `log.ts`.
+        let log = checker.call_hierarchy_item_at(&file, MAIN.find("log();").unwrap()).unwrap();
+        assert_eq!(
+            describe(&cm, &log),
+            ("log".into(), CallHierarchyKind::Function, "/project/log.ts".into())
+        );
+
+        let incoming = checker.incoming_calls(&log);
+        assert_eq!(
+            incoming
+                .iter()
+                .map(|call| (describe(&cm, &call.from), call.spans.len()))
+                .collect::<Vec<_>>(),
+            vec![
+                (("helper".into(), CallHierarchyKind::Function, "/project/main.ts".into()), 1),
+                (("inner".into(), CallHierarchyKind::Function, "/project/main.ts".into()), 1),
+            ]
+        );
+
+        // Calls in `inner` are not calls of `run`.
+        let run = checker.call_hierarchy_item_at(&file, MAIN.find("run = ").unwrap()).unwrap();
+        let outgoing = checker.outgoing_calls(&run);
+        assert_eq!(
+            outgoing
+                .iter()
+                .map(|call| (describe(&cm, &call.to), call.spans.len()))
+                .collect::<Vec<_>>(),
+            vec![(("helper".into(), CallHierarchyKind::Function, "/project/main.ts".into()), 2)]
+        );
+
+        // Calls at the top level are calls from the file.
+        let incoming = checker.incoming_calls(&run);
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0].from.kind, CallHierarchyKind::Module);
+
+        // Variables which are not functions are not callable.
+        assert_eq!(checker.call_hierarchy_item_at(&file, MAIN.find("count").unwrap()), None);
+    });
+}
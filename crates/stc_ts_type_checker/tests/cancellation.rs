@@ -0,0 +1,48 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_errors::ErrorKind;
+use stc_utils::cancel::CancellationToken;
+
+use self::common::{file_name, TestProject};
+
+/// Checks `/project/main.ts` and returns the loaded files and the error codes.
+fn check(token: CancellationToken) -> (Vec<String>, Vec<usize>) {
+    let project = TestProject::new(&[
+        ("/project/main.ts", "import { a } from './a';\nconst b: string = a;\n"),
+        ("/project/a.ts", "export const a = 1;\n"),
+    ]);
+
+    project.run_with_loader(
+        |loader| loader.with_cancellation_token(token.clone()),
+        |_, checker| {
+            let mut checker = checker.with_cancellation_token(token.clone());
+            checker.check(Arc::new(file_name("/project/main.ts")));
+
+            let files = checker.module_graph().modules.into_iter().map(|module| module.path).collect();
+            let mut codes = ErrorKind::flatten(checker.take_errors())
+                .into_iter()
+                .map(|err| err.code())
+                .collect::<Vec<_>>();
+            codes.sort_unstable();
+            (files, codes)
+        },
+    )
+}
+
+#[test]
+fn not_cancelled() {
+    assert_eq!(
+        check(CancellationToken::new()),
+        (vec!["/project/a.ts".to_string(), "/project/main.ts".to_string()], vec![2322])
+    );
+}
+
+#[test]
+fn cancelled() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    assert_eq!(check(token), (vec!["/project/main.ts".to_string()], vec![]));
+}
@@ -1,78 +1,139 @@
-use std::{
-    fs::File,
-    io::Read,
-    path::{Path, PathBuf},
-};
-
-use test::{DynTestFn, ShouldPanic::No, TestDesc, TestDescAndFn, TestName, TestType};
-use walkdir::WalkDir;
-
-pub fn load_fixtures<F>(dir_name: &str, op: F) -> Vec<TestDescAndFn>
-where
-    F: Fn(PathBuf) -> Option<Box<dyn FnOnce() + Send + Sync>>,
-{
-    let root = {
-        let mut root = Path::new(env!("CARGO_MANIFEST_DIR")).to_path_buf();
-        root.push("tests");
-        root.push(dir_name);
-
-        root
-    };
-
-    eprintln!("Loading tests from {}", root.display());
-
-    let mut tests = vec![];
-
-    for entry in WalkDir::new(&root).into_iter() {
-        let entry = entry.unwrap();
-        let is_ts = entry.file_name().to_string_lossy().ends_with(".ts") || entry.file_name().to_string_lossy().ends_with(".tsx");
-        if entry.file_type().is_dir() || !is_ts {
-            continue;
+//! Projects in memory, which are checked by the tests of [Checker].
+
+// Each test uses a part of the helpers.
+#![allow(dead_code)]
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{fs::MemoryFs, resolvers::node::NodeResolver};
+use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use swc_common::{FileName, SourceMap};
+use swc_ecma_ast::EsVersion;
+
+pub type TestLoader = ModuleLoader<NodeResolver>;
+
+pub type TestChecker = Checker<TestLoader>;
+
+/// Files in a [MemoryFs], and the options to check them with.
+///
+/// By default, files are checked with the default [Rule], the latest target,
+/// no module system and the `es5` library.
+pub struct TestProject {
+    /// Files can be changed between checks.
+    pub fs: Arc<MemoryFs>,
+    rule: Rule,
+    target: EsVersion,
+    module: ModuleConfig,
+    lib: &'static str,
+    env: Option<Box<dyn Send + Sync + Fn(Env) -> Env>>,
+}
+
+impl TestProject {
+    /// `files` are pairs of an absolute path and the content.
+    pub fn new(files: &[(&str, &str)]) -> Self {
+        let fs = Arc::new(MemoryFs::new());
+        for (path, content) in files {
+            fs.insert(PathBuf::from(*path), *content);
+        }
+
+        TestProject {
+            fs,
+            rule: Default::default(),
+            target: EsVersion::latest(),
+            module: ModuleConfig::None,
+            lib: "es5",
+            env: None,
         }
+    }
 
-        let file_name = entry
-            .path()
-            .strip_prefix(&root)
-            .expect("failed to strip prefix")
-            .to_str()
-            .unwrap()
-            .to_string();
-
-        eprintln!("Test: {}", entry.path().display());
-
-        let input = {
-            let mut buf = String::new();
-            if File::open(entry.path()).unwrap().read_to_string(&mut buf).is_err() {
-                continue;
-            }
-            buf
-        };
-
-        let test_name = format!("{}::{}", dir_name, file_name.replace('/', "::"));
-        let test_fn = op(entry.path().to_path_buf());
-        let (test_fn, ignore) = match test_fn {
-            Some(v) => (v, false),
-            None => ((box || {}) as Box<dyn FnOnce() + Send + Sync>, true),
-        };
-        let ignore = ignore || test_name.starts_with('.') || test_name.contains("::.");
-
-        tests.push(TestDescAndFn {
-            desc: TestDesc {
-                test_type: TestType::UnitTest,
-                name: TestName::DynTestName(test_name),
-                ignore,
-                should_panic: No,
-                compile_fail: Default::default(),
-                no_run: Default::default(),
-                ignore_message: Default::default(),
-            },
-            testfn: DynTestFn(box move || {
-                eprintln!("\n\n========== Running test {}\nSource:\n{}\n", file_name, input);
-
-                test_fn()
-            }),
-        });
+    /// `allow_js` is applied to the resolver too.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
     }
 
-    tests
+    pub fn with_target(mut self, target: EsVersion) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_module(mut self, module: ModuleConfig) -> Self {
+        self.module = module;
+        self
+    }
+
+    pub fn with_lib(mut self, lib: &'static str) -> Self {
+        self.lib = lib;
+        self
+    }
+
+    /// Changes the [Env] before checking, like declaring globals.
+    pub fn with_env(mut self, op: impl 'static + Send + Sync + Fn(Env) -> Env) -> Self {
+        self.env = Some(Box::new(op));
+        self
+    }
+
+    pub fn env(&self) -> Env {
+        let env = Env::simple(self.rule, self.target, self.module, &Lib::load(self.lib));
+
+        match &self.env {
+            Some(op) => op(env),
+            None => env,
+        }
+    }
+
+    /// Creates a module loader which reads the files of the project.
+    pub fn loader(&self, cm: Arc<SourceMap>, env: Env) -> TestLoader {
+        let resolver = NodeResolver::new().with_fs(self.fs.clone()).with_allow_js(self.rule.allow_js);
+
+        ModuleLoader::with_fs(cm, env, resolver, self.fs.clone())
+    }
+
+    /// Creates a checker of the project and calls `op` with it.
+    pub fn run<F, R>(&self, op: F) -> R
+    where
+        F: FnOnce(Arc<SourceMap>, TestChecker) -> R,
+    {
+        self.run_with_loader(|loader| loader, op)
+    }
+
+    /// Like [TestProject::run], but the module loader is configured by
+    /// `loader` first.
+    pub fn run_with_loader<L, F, R>(&self, loader: L, op: F) -> R
+    where
+        L: FnOnce(TestLoader) -> TestLoader,
+        F: FnOnce(Arc<SourceMap>, TestChecker) -> R,
+    {
+        testing::run_test2(false, |cm, handler| {
+            let env = self.env();
+            let loader = loader(self.loader(cm.clone(), env.clone()));
+            let checker = Checker::new(cm.clone(), Arc::new(handler), env, None, loader);
+
+            Ok(op(cm, checker))
+        })
+        .unwrap()
+    }
+
+    /// Returns the sorted error codes reported while checking
+    /// `/project/main.ts`.
+    pub fn error_codes(&self) -> Vec<usize> {
+        self.run(|_, mut checker| {
+            checker.check(Arc::new(file_name("/project/main.ts")));
+
+            let mut codes = ErrorKind::flatten(checker.take_errors())
+                .into_iter()
+                .map(|err| err.code())
+                .collect::<Vec<_>>();
+            codes.sort_unstable();
+            codes
+        })
+    }
+}
+
+pub fn file_name(path: &str) -> FileName {
+    FileName::Real(PathBuf::from(path))
 }
@@ -0,0 +1,104 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_type_checker::completions::{CompletionEntry, CompletionKind};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { Shape } from './shape';
+interface Named {
+    name: string;
+}
+interface Point extends Named {
+    x: number;
+    move(dx: number): Point;
+}
+class Counter {
+    static create(): Counter {
+        return new Counter();
+    }
+    count: number = 0;
+}
+declare const p: Point;
+const c = Counter.create();
+function f(a: number) {
+    const inner = p.x;
+    return a.toFixed(2) + inner + c.count;
+}
+";
+
+/// Checks `/project/main.ts` and returns the completions at the end of
+/// `needle` in it.
+fn completions(needle: &str) -> Vec<CompletionEntry> {
+    let project = TestProject::new(&[("/project/main.ts", MAIN), ("/project/shape.ts", "export type Shape = 'circle';\n")]);
+
+    project.run(|_, checker| {
+        let checker = checker.with_type_recorder(TypeRecorder::default());
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        checker.completions_at(&file, MAIN.find(needle).unwrap() + needle.len())
+    })
+}
+
+fn entry(name: &str, kind: CompletionKind, ty: Option<&str>) -> CompletionEntry {
+    CompletionEntry {
+        name: name.into(),
+        kind,
+        ty: ty.map(From::from),
+    }
+}
+
+#[test]
+fn interface_members() {
+    assert_eq!(
+        completions("p.x"),
+        vec![
+            entry("move", CompletionKind::Method, Some("(dx: number) => Point")),
+            entry("name", CompletionKind::Property, Some("string")),
+            entry("x", CompletionKind::Property, Some("number")),
+        ]
+    );
+}
+
+#[test]
+fn class_members() {
+    assert_eq!(
+        completions("c.count"),
+        vec![entry("count", CompletionKind::Property, Some("number"))]
+    );
+    assert_eq!(
+        completions("Counter.create"),
+        vec![entry("create", CompletionKind::Method, Some("() => Counter"))]
+    );
+}
+
+#[test]
+fn members_of_global_types() {
+    let names = completions("a.toFixed").into_iter().map(|entry| entry.name).collect::<Vec<_>>();
+
+    assert!(names.contains(&"toFixed".to_string()), "{:?}", names);
+}
+
+#[test]
+fn scope() {
+    let entries = completions("+ inner");
+
+    assert_eq!(
+        entries.iter().map(|entry| (&*entry.name, entry.kind)).collect::<Vec<_>>(),
+        vec![
+            ("Counter", CompletionKind::Class),
+            ("Named", CompletionKind::Interface),
+            ("Point", CompletionKind::Interface),
+            ("Shape", CompletionKind::Import),
+            ("a", CompletionKind::Parameter),
+            ("c", CompletionKind::Const),
+            ("f", CompletionKind::Function),
+            ("inner", CompletionKind::Const),
+            ("p", CompletionKind::Const),
+        ]
+    );
+    assert_eq!(entries[7].ty.as_deref(), Some("number"));
+}
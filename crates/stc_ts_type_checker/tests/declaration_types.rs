@@ -0,0 +1,70 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_type_checker::declarations::{DeclarationKind::*, FileDeclarations};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { count } from './a';
+export interface Point {
+    x: number;
+}
+let n: number = count;
+export function add(a: number, b: number): number {
+    const sum: number = a + b;
+    return sum;
+}
+";
+
+fn declaration_types() -> FileDeclarations {
+    let project = TestProject::new(&[("/project/main.ts", MAIN), ("/project/a.ts", "export let count: number = 0;\n")]);
+
+    project.run(|_, checker| {
+        let checker = checker.with_type_recorder(TypeRecorder::default());
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        assert_eq!(checker.all_declaration_types().len(), 2);
+        checker.declaration_types(&file).unwrap()
+    })
+}
+
+#[test]
+fn declarations() {
+    let decls = declaration_types();
+    assert_eq!(decls.file, "/project/main.ts");
+
+    let found = decls
+        .declarations
+        .iter()
+        .map(|d| (&*d.name, d.span.line, d.flags.kind, d.flags.exported, d.ty.as_deref()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        found,
+        vec![
+            ("count", 1, Import, false, Some("number")),
+            ("Point", 2, Interface, true, Some("Point")),
+            ("n", 5, Let, false, Some("number")),
+            ("add", 6, Function, true, Some("(a: number, b: number) => number")),
+            ("a", 6, Parameter, false, Some("number")),
+            ("b", 6, Parameter, false, Some("number")),
+            ("sum", 7, Const, false, Some("number")),
+        ]
+    );
+
+    let point = &decls.declarations[1];
+    assert_eq!(&MAIN[point.span.start..point.span.end], "Point");
+    assert_eq!(point.span.column, 17);
+}
+
+#[test]
+fn json() {
+    let decls = declaration_types();
+
+    let json = decls.to_json().unwrap();
+    assert!(json.contains("\"type\": \"(a: number, b: number) => number\""));
+    assert_eq!(FileDeclarations::from_json(&json).unwrap(), decls);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use std::sync::Arc;
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { create, Foo } from './b';\nconst x: Foo = create();\nexport const y = x;\n";
+
+/// Checks `/project/main.ts` and returns the definitions of the identifier at
+/// the first occurrence of `needle`, as pairs of the file name and the source
+/// text.
+fn definitions_at(needle: &str) -> Vec<(String, String)> {
+    let project = TestProject::new(&[
+        ("/project/main.ts", MAIN),
+        (
+            "/project/a.ts",
+            "export interface Foo { a: number }\nexport interface Foo { b: string }\nexport function make(): Foo { return null as any }\n",
+        ),
+        ("/project/b.ts", "export { make as create } from './a';\nexport * from './a';\n"),
+    ]);
+
+    project.run(|cm, checker| {
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        checker
+            .definition_at(&file, MAIN.find(needle).unwrap())
+            .into_iter()
+            .map(|def| (def.file.to_string(), cm.span_to_snippet(def.span).unwrap()))
+            .collect()
+    })
+}
+
+#[test]
+fn local() {
+    assert_eq!(definitions_at("x;"), vec![("/project/main.ts".to_string(), "x".to_string())]);
+}
+
+#[test]
+fn merged_declarations() {
+    assert_eq!(
+        definitions_at("Foo ="),
+        vec![
+            ("/project/a.ts".to_string(), "Foo".to_string()),
+            ("/project/a.ts".to_string(), "Foo".to_string())
+        ]
+    );
+}
+
+#[test]
+fn renamed_reexport() {
+    assert_eq!(definitions_at("create()"), vec![("/project/a.ts".to_string(), "make".to_string())]);
+}
+
+#[test]
+fn no_identifier() {
+    assert_eq!(definitions_at("= create"), vec![]);
+}
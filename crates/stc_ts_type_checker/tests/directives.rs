@@ -0,0 +1,55 @@
+mod common;
+
+use std::sync::Arc;
+
+use self::common::{file_name, TestProject};
+
+/// Returns the codes and lines of the errors of `/project/main.ts`.
+fn check(src: &str) -> Vec<(usize, usize)> {
+    TestProject::new(&[("/project/main.ts", src)]).run(|_, checker| {
+        let program = checker.check(Arc::new(file_name("/project/main.ts")));
+
+        let mut errors = program
+            .errors()
+            .map(|err| {
+                let d = checker.to_diagnostic(err);
+                (d.code, d.line)
+            })
+            .collect::<Vec<_>>();
+        errors.sort();
+        errors
+    })
+}
+
+#[test]
+fn ts_ignore() {
+    let errors = check(
+        "// @ts-ignore\nconst a: string = 1;\n\n/* @ts-ignore */\n\n// A comment is skipped.\nconst b: string = 1;\nconst c: string = 1;\n",
+    );
+
+    assert_eq!(errors, vec![(2322, 8)]);
+}
+
+#[test]
+fn ts_expect_error() {
+    let errors = check("// @ts-expect-error\nconst a: string = 1;\nconst b: string = 1;\n");
+
+    assert_eq!(errors, vec![(2322, 3)]);
+}
+
+#[test]
+fn unused_ts_expect_error() {
+    let errors =
+        check("// @ts-expect-error\nconst a: string = 1;\n/** @ts-expect-error */\nconst b: string = '';\n// @ts-ignore\nconst c = 1;\n");
+
+    // Only unused `@ts-expect-error` directives are reported.
+    assert_eq!(errors, vec![(2578, 3)]);
+}
+
+#[test]
+fn ts_nocheck() {
+    assert_eq!(check("// @ts-nocheck\nconst a: string = 1;\nfoo;\n"), vec![]);
+
+    // `@ts-nocheck` is used only before the first statement.
+    assert_eq!(check("const a: string = 1;\n// @ts-nocheck\n"), vec![(2322, 1)]);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_type_checker::outline::{DocumentSymbol, SymbolKind};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "export class Point {
+    x = 0;
+    constructor() {}
+    move(dx: number) {
+        const next = this.x + dx;
+    }
+}
+
+interface Shape {
+    area(): number;
+    name: string;
+}
+
+namespace Geometry {
+    export function origin() {
+        return new Point();
+    }
+}
+
+enum Color {
+    Red,
+    Green,
+}
+
+type Id = string;
+
+let { a, b: [c] } = { a: 1, b: [2] };
+";
+
+/// Returns the names and kinds of the symbols, with the children of each
+/// symbol after it.
+fn flatten(symbols: &[DocumentSymbol], depth: usize, out: &mut Vec<(usize, String, SymbolKind)>) {
+    for symbol in symbols {
+        out.push((depth, symbol.name.clone(), symbol.kind));
+        flatten(&symbol.children, depth + 1, out);
+    }
+}
+
+#[test]
+fn document_symbols() {
+    let symbols = TestProject::new(&[("/project/main.ts", MAIN)]).run(|_, checker| {
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+        checker.document_symbols(&file).unwrap()
+    });
+
+    let mut flat = vec![];
+    flatten(&symbols, 0, &mut flat);
+    let expected = [
+        (0, "Point", SymbolKind::Class),
+        (1, "x", SymbolKind::Property),
+        (1, "constructor", SymbolKind::Constructor),
+        (1, "move", SymbolKind::Method),
+        (2, "next", SymbolKind::Constant),
+        (0, "Shape", SymbolKind::Interface),
+        (1, "area", SymbolKind::Method),
+        (1, "name", SymbolKind::Property),
+        (0, "Geometry", SymbolKind::Namespace),
+        (1, "origin", SymbolKind::Function),
+        (0, "Color", SymbolKind::Enum),
+        (1, "Red", SymbolKind::EnumMember),
+        (1, "Green", SymbolKind::EnumMember),
+        (0, "Id", SymbolKind::TypeAlias),
+        (0, "a", SymbolKind::Variable),
+        (0, "c", SymbolKind::Variable),
+    ];
+    assert_eq!(
+        flat,
+        expected
+            .iter()
+            .map(|(depth, name, kind)| (*depth, name.to_string(), *kind))
+            .collect::<Vec<_>>()
+    );
+
+    let point = &symbols[0];
+    assert_eq!((point.selection_span.line, point.selection_span.column), (1, 13));
+    assert_eq!((point.span.line, point.span.column), (1, 7));
+}
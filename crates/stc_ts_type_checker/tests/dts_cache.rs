@@ -0,0 +1,51 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_errors::ErrorKind;
+use stc_ts_type_checker::{dts_cache::DtsCache, Checker};
+
+use self::common::{file_name, TestProject};
+
+#[test]
+fn share_dts_between_checkers() {
+    let project = TestProject::new(&[
+        (
+            "/project/lib.d.ts",
+            "export declare const x: number;\nexport declare let bad: Unknown;\n",
+        ),
+        ("/project/a.ts", "import { x } from './lib';\nconst s: string = x;\n"),
+        (
+            "/project/b.ts",
+            "import { x } from './lib';\nconst n: number = x;\nconst b: boolean = x;\n",
+        ),
+    ]);
+
+    testing::run_test2(false, |cm, handler| {
+        let handler = Arc::new(handler);
+        let env = project.env();
+        let loader = Arc::new(project.loader(cm.clone(), env.clone()));
+        let cache = DtsCache::default();
+
+        let check = |file: &str| {
+            let mut checker = Checker::new(cm.clone(), handler.clone(), env.clone(), None, loader.clone()).with_dts_cache(cache.clone());
+            checker.check(Arc::new(file_name(file)));
+
+            let mut codes = ErrorKind::flatten(checker.take_errors())
+                .into_iter()
+                .map(|err| err.code())
+                .collect::<Vec<_>>();
+            codes.sort_unstable();
+            codes
+        };
+
+        assert_eq!(check("/project/a.ts"), vec![2304, 2322]);
+        assert_eq!(cache.len(), 1);
+
+        // `lib.d.ts` is not analyzed again, so the error in it is not reported.
+        assert_eq!(check("/project/b.ts"), vec![2322]);
+
+        Ok(())
+    })
+    .unwrap();
+}
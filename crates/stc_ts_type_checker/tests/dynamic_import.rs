@@ -0,0 +1,32 @@
+mod common;
+
+use self::common::TestProject;
+
+/// Checks `main` next to `/project/a.ts`.
+fn check(main: &str) -> Vec<usize> {
+    TestProject::new(&[("/project/main.ts", main), ("/project/a.ts", "export const value = 1;\n")])
+        .with_lib("es2015")
+        .error_codes()
+}
+
+#[test]
+fn dynamic_import() {
+    // `number` is not assignable to `string`.
+    assert_eq!(
+        check("export async function f() {\n    const a = await import('./a');\n    const v: string = a.value;\n}\n"),
+        vec![2322]
+    );
+}
+
+#[test]
+fn dynamic_import_of_string_literal_type() {
+    assert_eq!(
+        check("const path = './a';\nexport async function f() {\n    const a = await import(path);\n    const v: string = a.value;\n}\n"),
+        vec![2322]
+    );
+}
+
+#[test]
+fn dynamic_import_of_missing_module() {
+    assert_eq!(check("export const a = import('./missing');\n"), vec![2307]);
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_env::ModuleConfig;
+use stc_ts_type_checker::emit::EmittedFile;
+use swc_ecma_ast::EsVersion;
+
+use self::common::{file_name, TestProject};
+
+/// A project of `/project/main.ts` with the content `main`, which can import
+/// `/project/a.ts`.
+fn project(module: ModuleConfig, main: &str) -> TestProject {
+    TestProject::new(&[
+        ("/project/main.ts", main),
+        (
+            "/project/a.ts",
+            "export interface Point { x: number }\nexport const origin: Point = { x: 0 };\n",
+        ),
+        ("/project/types.d.ts", "declare const VERSION: string;\n"),
+    ])
+    .with_target(EsVersion::Es2020)
+    .with_module(module)
+}
+
+/// Checks `/project/main.ts` and returns the emitted files.
+fn check_and_emit(module: ModuleConfig, main: &str) -> Option<Vec<EmittedFile>> {
+    project(module, main).run(|_, checker| {
+        let (_, files) = checker.check_and_emit(vec![Arc::new(file_name("/project/main.ts"))]).unwrap();
+        files
+    })
+}
+
+const MAIN: &str = "/// <reference path=\"./types.d.ts\" />
+import { origin, Point } from './a';
+export function move(p: Point, dx: number): Point {
+    return { x: p.x + dx };
+}
+export const moved = move(origin, 1) as Point;
+";
+
+#[test]
+fn emit_es_modules() {
+    let files = check_and_emit(ModuleConfig::EsNext, MAIN).unwrap();
+
+    assert_eq!(
+        files.iter().map(|file| file.path.clone()).collect::<Vec<_>>(),
+        vec![PathBuf::from("/project/a.js"), PathBuf::from("/project/main.js")]
+    );
+
+    let main = &files[1].code;
+    assert!(main.contains("import { origin } from './a';"), "{}", main);
+    assert!(main.contains("export function move(p, dx) {"), "{}", main);
+    assert!(!main.contains("Point"), "{}", main);
+    assert!(!files[0].code.contains("interface"), "{}", files[0].code);
+}
+
+#[test]
+fn emit_commonjs() {
+    let files = check_and_emit(ModuleConfig::CommonJs, MAIN).unwrap();
+
+    let main = &files[1].code;
+    assert!(main.contains("require(\"./a\")"), "{}", main);
+    assert!(!main.contains("import "), "{}", main);
+}
+
+#[test]
+fn no_emit_on_errors() {
+    assert_eq!(check_and_emit(ModuleConfig::EsNext, "const a: string = 1;\n"), None);
+}
+
+#[test]
+fn emit_declarations() {
+    project(ModuleConfig::EsNext, MAIN).run(|_, mut checker| {
+        checker.check(Arc::new(file_name("/project/main.ts")));
+        assert!(checker.take_errors().is_empty());
+
+        let files = checker.emit_declarations().unwrap();
+        assert_eq!(
+            files.iter().map(|file| file.path.clone()).collect::<Vec<_>>(),
+            vec![PathBuf::from("/project/a.d.ts"), PathBuf::from("/project/main.d.ts")]
+        );
+
+        let main = &files[1].code;
+        assert!(main.contains("move(p: Point, dx: number): Point"), "{}", main);
+        assert!(!main.contains("return"), "{}", main);
+        assert!(files[0].code.contains("interface Point"), "{}", files[0].code);
+    });
+}
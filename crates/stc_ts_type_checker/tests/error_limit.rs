@@ -0,0 +1,48 @@
+use stc_ts_type_checker::limit::{truncation_summary, ErrorLimit};
+
+/// Errors as `(file, index)`.
+fn errors() -> Vec<(&'static str, usize)> {
+    vec![("a.ts", 0), ("a.ts", 1), ("a.ts", 2), ("b.ts", 0), ("a.ts", 3), ("b.ts", 1)]
+}
+
+#[test]
+fn unlimited() {
+    let (reported, omitted) = ErrorLimit::default().truncate(errors(), |err| err.0);
+
+    assert_eq!(reported, errors());
+    assert_eq!(omitted, 0);
+    assert_eq!(truncation_summary(omitted), None);
+}
+
+#[test]
+fn max_errors() {
+    let limit = ErrorLimit::default().with_max_errors(Some(4));
+
+    let (reported, omitted) = limit.truncate(errors(), |err| err.0);
+    assert_eq!(reported, vec![("a.ts", 0), ("a.ts", 1), ("a.ts", 2), ("b.ts", 0)]);
+    assert_eq!(omitted, 2);
+
+    // Errors reported before use the limit.
+    let (reported, omitted) = limit.after(3).truncate(errors(), |err| err.0);
+    assert_eq!(reported, vec![("a.ts", 0)]);
+    assert_eq!(omitted, 5);
+}
+
+#[test]
+fn max_errors_per_file() {
+    let limit = ErrorLimit::default().with_max_errors_per_file(Some(2));
+
+    let (reported, omitted) = limit.truncate(errors(), |err| err.0);
+    assert_eq!(reported, vec![("a.ts", 0), ("a.ts", 1), ("b.ts", 0), ("b.ts", 1)]);
+    assert_eq!(omitted, 2);
+
+    let (reported, omitted) = limit.with_max_errors(Some(3)).truncate(errors(), |err| err.0);
+    assert_eq!(reported, vec![("a.ts", 0), ("a.ts", 1), ("b.ts", 0)]);
+    assert_eq!(omitted, 3);
+}
+
+#[test]
+fn summary() {
+    assert_eq!(truncation_summary(1).as_deref(), Some("...and 1 more error"));
+    assert_eq!(truncation_summary(412).as_deref(), Some("...and 412 more errors"));
+}
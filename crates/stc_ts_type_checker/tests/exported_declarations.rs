@@ -0,0 +1,25 @@
+mod common;
+
+use std::sync::Arc;
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str =
+    "export function foo() {}\nfunction bar() {}\nexport const baz = 1;\nexport class Qux {}\nexport default function () {}\n";
+
+#[test]
+fn exported_functions_and_classes() {
+    TestProject::new(&[("/project/main.ts", MAIN)]).run(|cm, checker| {
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        // Variables and anonymous declarations are not included.
+        let names = checker
+            .exported_declarations(&file)
+            .unwrap()
+            .into_iter()
+            .map(|def| cm.span_to_snippet(def.span).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["foo", "Qux"]);
+    });
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use stc_ts_errors::Severity;
+use stc_ts_type_checker::{github::error_command, program::Diagnostic};
+
+fn diagnostic(file: Option<&str>, message: &str, severity: Severity) -> Diagnostic {
+    Diagnostic {
+        file: file.map(String::from),
+        start: 24,
+        end: 25,
+        line: 2,
+        column: 6,
+        end_line: 2,
+        end_column: 7,
+        code: 2322,
+        message: message.into(),
+        severity,
+        related: vec![],
+        tags: vec![],
+        fixes: vec![],
+    }
+}
+
+#[test]
+fn error_in_file() {
+    assert_eq!(
+        error_command(
+            &diagnostic(Some("/repo/src/main.ts"), "AssignFailed", Severity::Error),
+            Path::new("/repo")
+        ),
+        "::error file=src/main.ts,line=2,col=7,endLine=2,endColumn=8,title=TS2322::AssignFailed"
+    );
+}
+
+#[test]
+fn escape() {
+    assert_eq!(
+        error_command(
+            &diagnostic(Some("/other/a,b.ts"), "100%\nnot assignable", Severity::Error),
+            Path::new("/repo")
+        ),
+        "::error file=/other/a%2Cb.ts,line=2,col=7,endLine=2,endColumn=8,title=TS2322::100%25%0Anot assignable"
+    );
+}
+
+#[test]
+fn global_error() {
+    assert_eq!(
+        error_command(&diagnostic(None, "error", Severity::Error), Path::new("/repo")),
+        "::error title=TS2322::error"
+    );
+}
+
+#[test]
+fn warning() {
+    assert_eq!(
+        error_command(&diagnostic(None, "unused", Severity::Warning), Path::new("/repo")),
+        "::warning title=TS2322::unused"
+    );
+}
+
+#[test]
+fn info() {
+    assert_eq!(
+        error_command(&diagnostic(None, "unused", Severity::Info), Path::new("/repo")),
+        "::notice title=TS2322::unused"
+    );
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use self::common::TestProject;
+
+/// Checks `main` next to json files.
+fn check(main: &str) -> Vec<usize> {
+    TestProject::new(&[
+        ("/project/main.ts", main),
+        ("/project/data.json", "{ \"version\": 1 }\n"),
+        ("/project/data.txt", "{ \"version\": 1 }\n"),
+    ])
+    .error_codes()
+}
+
+#[test]
+fn json_module() {
+    // `number` is not assignable to `string`.
+    assert_eq!(
+        check("import data from './data.json' assert { type: 'json' };\nexport const v: string = data.version;\n"),
+        vec![2322]
+    );
+}
+
+#[test]
+fn json_module_without_json_extension() {
+    assert_eq!(
+        check("import data from './data.txt' assert { type: 'json' };\nexport const v: string = data.version;\n"),
+        vec![2322]
+    );
+}
+
+#[test]
+fn attribute_value_must_be_string() {
+    assert_eq!(
+        check("import data from './data.json' assert { type: 1 };\nexport const v: number = data.version;\n"),
+        vec![2837]
+    );
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_type_checker::inlay_hints::InlayHintKind::{self, *};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "function add(a: number, b: number): number {
+    return a + b;
+}
+function sum(...values: number[]): number {
+    return 0;
+}
+const b = 2;
+const total = add(1, b);
+const annotated: number = sum(1, 2, 3);
+";
+
+#[test]
+fn inlay_hints() {
+    TestProject::new(&[("/project/main.ts", MAIN)]).run(|_, checker| {
+        let checker = checker.with_type_recorder(TypeRecorder::default());
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        let hints = checker
+            .inlay_hints(&file)
+            .into_iter()
+            .map(|hint| (hint.kind, &MAIN[hint.span.start..hint.span.end], hint.label))
+            .collect::<Vec<_>>();
+
+        // `b` is passed as `b`, and `annotated` has a type annotation.
+        let expected: Vec<(InlayHintKind, &str, String)> = vec![
+            (Type, "b", ": 2".into()),
+            (Type, "total", ": number".into()),
+            (Parameter, "1", "a:".into()),
+            (Parameter, "1", "...values:".into()),
+        ];
+        assert_eq!(hints, expected);
+    });
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_errors::ErrorKind;
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_type_checker::incremental::DiagnosticsDelta;
+
+use self::common::{file_name, TestProject};
+
+/// Returns the paths and the error codes of the files in `delta`.
+fn codes(delta: DiagnosticsDelta) -> Vec<(String, Vec<usize>)> {
+    delta
+        .files
+        .into_iter()
+        .map(|file| {
+            let mut codes = ErrorKind::flatten(file.errors)
+                .into_iter()
+                .map(|err| err.code())
+                .collect::<Vec<_>>();
+            codes.sort_unstable();
+            (file.file.to_string(), codes)
+        })
+        .collect()
+}
+
+#[test]
+fn recheck_dependents() {
+    let project = TestProject::new(&[
+        (
+            "/project/main.ts",
+            "import { a } from './a';\nimport { b } from './b';\nconst s: string = a;\n",
+        ),
+        ("/project/a.ts", "export const a = 1;\n"),
+        ("/project/b.ts", "export const b: number = '';\n"),
+    ]);
+    let fs = &project.fs;
+
+    project.run(|_, mut checker| {
+        checker.check(Arc::new(file_name("/project/main.ts")));
+        assert_eq!(ErrorKind::flatten(checker.take_errors()).len(), 2);
+
+        fs.insert(PathBuf::from("/project/a.ts"), "export const a = '';\n");
+        assert_eq!(
+            codes(checker.invalidate_file(&PathBuf::from("/project/a.ts"))),
+            vec![("/project/a.ts".to_string(), vec![]), ("/project/main.ts".to_string(), vec![])]
+        );
+
+        fs.insert(
+            PathBuf::from("/project/main.ts"),
+            "import { a } from './a';\nconst n: number = a;\n",
+        );
+        assert_eq!(
+            codes(checker.invalidate_file(&PathBuf::from("/project/main.ts"))),
+            vec![("/project/main.ts".to_string(), vec![2322])]
+        );
+
+        assert!(checker.take_errors().is_empty());
+        assert!(checker.invalidate_file(&PathBuf::from("/project/unknown.ts")).files.is_empty());
+    });
+}
+
+#[test]
+fn queries_after_changes() {
+    let project = TestProject::new(&[("/project/main.ts", "const a = 1;\n")]);
+    let fs = &project.fs;
+    let path = PathBuf::from("/project/main.ts");
+
+    project.run(|_, checker| {
+        let mut checker = checker.with_type_recorder(TypeRecorder::default());
+        checker.check(Arc::new(file_name("/project/main.ts")));
+        assert!(checker.take_errors().is_empty());
+
+        // Everything after the first line is past the end of the old file.
+        let src = "const a = 1;\n// @ts-ignore\nconst n: number = '';\nconst s: string = n;\nexport const longerName = s;\n";
+        fs.insert(path.clone(), src);
+        assert_eq!(
+            codes(checker.invalidate_file(&path)),
+            vec![("/project/main.ts".to_string(), vec![2322])]
+        );
+
+        let file = file_name("/project/main.ts");
+        let info = checker.quick_info(&file, src.find("longerName").unwrap()).unwrap();
+        assert_eq!(info.display_string, "const longerName: string");
+        assert_eq!(checker.source_file(&file).unwrap().src.as_str(), src);
+    });
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_env::{ModuleConfig, Rule};
+use stc_ts_errors::ErrorKind;
+use swc_common::Spanned;
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "/// <reference path=\"./enums.d.ts\" />
+import { Point, origin } from './a';
+import './script';
+export { Point };
+export { Shape, area } from './a';
+export type { Shape as S } from './a';
+export const p: Point = origin;
+export const d = Direction.Up;
+";
+
+/// Checks `/project/main.ts` and returns the codes of the errors with the
+/// snippets of their spans.
+fn isolated_modules_errors(isolated_modules: bool) -> Vec<(usize, String)> {
+    let project = TestProject::new(&[
+        ("/project/main.ts", MAIN),
+        (
+            "/project/a.ts",
+            "export interface Point { x: number }
+export type Shape = 'circle';
+export const origin: Point = { x: 0 };
+export function area(s: Shape): number { return 0; }
+",
+        ),
+        ("/project/script.ts", "/// <reference path=\"./enums.d.ts\" />\nvar global = 1;\n"),
+        ("/project/enums.d.ts", "declare const enum Direction { Up, Down }\n"),
+    ])
+    .with_rule(Rule {
+        isolated_modules,
+        ..Default::default()
+    })
+    .with_module(ModuleConfig::EsNext);
+
+    project.run(|cm, mut checker| {
+        checker.check(Arc::new(file_name("/project/main.ts")));
+
+        let mut result = vec![];
+        for err in ErrorKind::flatten(checker.take_errors()) {
+            let span = err.span();
+            let file = cm.span_to_filename(span).to_string();
+            let snippet = cm.span_to_snippet(span).unwrap();
+            result.push((err.code(), format!("{}: {}", file, snippet)));
+        }
+        result.sort();
+        result
+    })
+}
+
+#[test]
+fn isolated_modules_is_disabled_by_default() {
+    assert_eq!(isolated_modules_errors(false), vec![]);
+}
+
+#[test]
+fn isolated_modules() {
+    assert_eq!(
+        isolated_modules_errors(true),
+        vec![
+            (1205, "/project/main.ts: Point".to_string()),
+            (1205, "/project/main.ts: Shape".to_string()),
+            (1208, "/project/script.ts: ".to_string()),
+            (2748, "/project/main.ts: Direction".to_string()),
+        ]
+    );
+}
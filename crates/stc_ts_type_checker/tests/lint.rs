@@ -0,0 +1,90 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_env::ModuleConfig;
+use stc_ts_errors::ErrorKind;
+use stc_ts_type_checker::lint::{LintContext, LintRule};
+use stc_ts_types::printer::print_type;
+use swc_common::Spanned;
+use swc_ecma_ast::{ExprStmt, Module};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use self::common::{file_name, TestProject};
+
+/// Reports promises which are neither awaited nor used.
+struct NoFloatingPromises;
+
+impl LintRule for NoFloatingPromises {
+    fn name(&self) -> &str {
+        "no-floating-promises"
+    }
+
+    fn check(&self, cx: &mut LintContext, module: &Module) {
+        module.visit_with(&mut FloatingPromiseFinder { cx });
+    }
+}
+
+struct FloatingPromiseFinder<'a, 'b> {
+    cx: &'a mut LintContext<'b>,
+}
+
+impl Visit for FloatingPromiseFinder<'_, '_> {
+    fn visit_expr_stmt(&mut self, s: &ExprStmt) {
+        if let Some(ty) = self.cx.type_of(&*s.expr) {
+            if print_type(&ty).starts_with("Promise<") {
+                self.cx.report(s.expr.span(), "promises must be awaited");
+            }
+        }
+    }
+}
+
+const MAIN: &str = "import { load } from './a';
+declare function wait(ms: number): Promise<void>;
+async function main() {
+    load();
+    await load();
+    wait(1);
+    void wait(1);
+}
+";
+
+#[test]
+fn lint_rule() {
+    let project = TestProject::new(&[
+        ("/project/main.ts", MAIN),
+        ("/project/a.ts", "export declare function load(): Promise<string>;\nload();\n"),
+    ])
+    .with_module(ModuleConfig::EsNext)
+    .with_lib("es2015");
+
+    let errors = project.run(|cm, checker| {
+        let mut checker = checker.with_lint_rule(NoFloatingPromises);
+        checker.check(Arc::new(file_name("/project/main.ts")));
+
+        let mut errors = vec![];
+        for err in ErrorKind::flatten(checker.take_errors()) {
+            match &*err {
+                ErrorKind::Lint { span, rule, message, .. } => {
+                    assert_eq!(rule, "no-floating-promises");
+                    assert_eq!(message, "promises must be awaited");
+
+                    let loc = cm.lookup_char_pos(span.lo);
+                    errors.push((loc.file.name.to_string(), loc.line, cm.span_to_snippet(*span).unwrap()));
+                }
+                err => panic!("unexpected error: {:?}", err),
+            }
+        }
+        errors.sort();
+        errors
+    });
+
+    assert_eq!(
+        errors,
+        vec![
+            ("/project/a.ts".to_string(), 2, "load()".to_string()),
+            ("/project/main.ts".to_string(), 4, "load()".to_string()),
+            ("/project/main.ts".to_string(), 6, "wait(1)".to_string()),
+        ]
+    );
+}
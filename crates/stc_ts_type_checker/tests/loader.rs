@@ -0,0 +1,80 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_type_checker::{graph::ModuleGraph, loader::LoadModule};
+use swc_common::FileName;
+
+use self::common::{file_name, TestProject};
+
+/// Loads `/project/main.ts` and returns the files checked together with it,
+/// and the module graph.
+fn load(project: &TestProject) -> (Vec<String>, ModuleGraph) {
+    testing::run_test2(false, |cm, _| {
+        let loader = project.loader(cm, project.env());
+
+        let records = loader.load_module(&Arc::new(file_name("/project/main.ts")), true).unwrap();
+        let files = records.modules.iter().map(|record| record.filename.to_string()).collect();
+
+        Ok((files, loader.module_graph()))
+    })
+    .unwrap()
+}
+
+#[test]
+fn order_of_cycle_is_deterministic() {
+    let project = TestProject::new(&[
+        ("/project/main.ts", "import './d';\nimport './c';\nimport './b';\nimport './a';\n"),
+        ("/project/a.ts", "import './b';\nexport {};\n"),
+        ("/project/b.ts", "import './c';\nexport {};\n"),
+        ("/project/c.ts", "import './d';\nexport {};\n"),
+        ("/project/d.ts", "import './main';\nexport {};\n"),
+    ]);
+
+    for _ in 0..10 {
+        let (files, _) = load(&project);
+
+        assert_eq!(
+            files,
+            vec![
+                "/project/a.ts",
+                "/project/b.ts",
+                "/project/c.ts",
+                "/project/d.ts",
+                "/project/main.ts"
+            ]
+        );
+    }
+}
+
+#[test]
+fn relative_import_of_wildcard_ambient_module() {
+    let project = TestProject::new(&[
+        (
+            "/project/main.ts",
+            "/// <reference path=\"./types.d.ts\" />\nimport { url } from './logo.svg';\nimport { a } from './a';\n",
+        ),
+        (
+            "/project/types.d.ts",
+            "declare module \"*.svg\" {\n    export const url: string;\n}\n",
+        ),
+        ("/project/a.ts", "export const a = 1;\n"),
+    ]);
+
+    let (_, graph) = load(&project);
+    let svg = FileName::Custom("*.svg".into()).to_string();
+
+    let resolved = graph
+        .imports
+        .iter()
+        .map(|import| (&*import.specifier, import.resolved.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        resolved,
+        vec![
+            ("./types.d.ts", Some("/project/types.d.ts")),
+            ("./logo.svg", Some(&*svg)),
+            ("./a", Some("/project/a.ts")),
+        ]
+    );
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_env::Rule;
+use stc_ts_errors::ErrorKind;
+use stc_ts_type_checker::graph::{ImportEdge, ImportKind, ModuleGraph};
+
+use self::common::{file_name, TestProject};
+
+fn graph() -> ModuleGraph {
+    check(
+        &[
+            (
+                "/project/main.ts",
+                "import { a } from './a';\nimport { b } from './b';\nimport { c } from './missing';\n",
+            ),
+            ("/project/a.ts", "import { b } from './b';\nexport const a = b;\n"),
+            ("/project/b.ts", "export const b = 1;\n"),
+        ],
+        Default::default(),
+    )
+    .0
+}
+
+/// Checks `/project/main.ts` and returns the module graph and the errors.
+fn check(files: &[(&str, &str)], rule: Rule) -> (ModuleGraph, Vec<ErrorKind>) {
+    TestProject::new(files).with_rule(rule).run(|_, mut checker| {
+        checker.check(Arc::new(file_name("/project/main.ts")));
+
+        let graph = checker.module_graph();
+        let errors = ErrorKind::flatten(checker.take_errors())
+            .into_iter()
+            .map(|err| (*err).clone())
+            .collect();
+        (graph, errors)
+    })
+}
+
+fn import(from: &str, specifier: &str, resolved: Option<&str>) -> ImportEdge {
+    ImportEdge {
+        from: from.into(),
+        specifier: specifier.into(),
+        resolved: resolved.map(From::from),
+        kind: ImportKind::Import,
+    }
+}
+
+#[test]
+fn module_graph() {
+    let graph = graph();
+
+    assert_eq!(
+        graph.modules.iter().map(|m| &*m.path).collect::<Vec<_>>(),
+        vec!["/project/a.ts", "/project/b.ts", "/project/main.ts"]
+    );
+    assert_eq!(
+        graph.imports,
+        vec![
+            import("/project/a.ts", "./b", Some("/project/b.ts")),
+            import("/project/main.ts", "./a", Some("/project/a.ts")),
+            import("/project/main.ts", "./b", Some("/project/b.ts")),
+            import("/project/main.ts", "./missing", None),
+        ]
+    );
+}
+
+#[test]
+fn json_roundtrip() {
+    let graph = graph();
+
+    assert_eq!(ModuleGraph::from_json(&graph.to_json().unwrap()).unwrap(), graph);
+}
+
+#[test]
+fn dot() {
+    let dot = graph().to_dot();
+
+    assert!(dot.starts_with("digraph modules {\n"));
+    assert!(dot.contains("    \"/project/main.ts\" -> \"/project/a.ts\" [label=\"./a\"];\n"));
+    assert!(!dot.contains("./missing"));
+}
+
+#[test]
+fn affected_files() {
+    let graph = graph();
+
+    assert_eq!(
+        graph.affected_files(&["/project/b.ts"]),
+        vec!["/project/a.ts", "/project/b.ts", "/project/main.ts"]
+    );
+    assert_eq!(graph.affected_files(&["/project/a.ts"]), vec!["/project/a.ts", "/project/main.ts"]);
+}
+
+const CYCLIC: &[(&str, &str)] = &[
+    ("/project/main.ts", "import { a } from './a';\nexport const main = a;\n"),
+    ("/project/a.ts", "import { b } from './b';\nexport const a = 1;\n"),
+    ("/project/b.ts", "import { a } from './a';\nexport const b = 2;\n"),
+];
+
+#[test]
+fn cycles() {
+    let (graph, _) = check(CYCLIC, Default::default());
+
+    assert_eq!(graph.cycles(), vec![vec!["/project/a.ts".to_string(), "/project/b.ts".to_string()]]);
+}
+
+#[test]
+fn no_cycles() {
+    assert_eq!(graph().cycles(), Vec::<Vec<String>>::new());
+}
+
+#[test]
+fn circular_imports_are_allowed_by_default() {
+    let (_, errors) = check(CYCLIC, Default::default());
+
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn no_circular_imports() {
+    let (_, errors) = check(
+        CYCLIC,
+        Rule {
+            no_circular_imports: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(errors.len(), 1);
+    match &errors[0] {
+        ErrorKind::CircularImport { cycle, .. } => {
+            assert_eq!(cycle, &vec!["/project/a.ts".to_string(), "/project/b.ts".to_string()]);
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+fn cycle_graph() {
+    let (graph, _) = check(CYCLIC, Default::default());
+    let cycles = graph.cycle_graph();
+
+    assert_eq!(
+        cycles.modules.iter().map(|m| &*m.path).collect::<Vec<_>>(),
+        vec!["/project/a.ts", "/project/b.ts"]
+    );
+    assert_eq!(
+        cycles.imports,
+        vec![
+            import("/project/a.ts", "./b", Some("/project/b.ts")),
+            import("/project/b.ts", "./a", Some("/project/a.ts")),
+        ]
+    );
+
+    assert_eq!(graph().cycle_graph(), ModuleGraph::default());
+}
+
+#[test]
+fn paths_between() {
+    let graph = graph();
+
+    let paths = graph.paths_between("/project/main.ts", "/project/b.ts");
+    assert_eq!(paths.modules.len(), 3);
+    assert_eq!(
+        paths.imports,
+        vec![
+            import("/project/a.ts", "./b", Some("/project/b.ts")),
+            import("/project/main.ts", "./a", Some("/project/a.ts")),
+            import("/project/main.ts", "./b", Some("/project/b.ts")),
+        ]
+    );
+
+    let paths = graph.paths_between("/project/a.ts", "/project/main.ts");
+    assert_eq!(paths, ModuleGraph::default());
+}
+
+#[test]
+fn tree() {
+    assert_eq!(
+        graph().to_tree(&["/project/main.ts"]),
+        "/project/main.ts
+├── /project/a.ts
+│   └── /project/b.ts
+├── /project/b.ts (*)
+└── ./missing (unresolved)
+"
+    );
+
+    let (graph, _) = check(CYCLIC, Default::default());
+    assert_eq!(
+        graph.to_tree(&["/project/main.ts", "/project/b.ts"]),
+        "/project/main.ts
+└── /project/a.ts
+    └── /project/b.ts
+        └── /project/a.ts (*)
+/project/b.ts (*)
+"
+    );
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "declare const x: string | number;
+if (typeof x === \"string\") {
+    x;
+} else {
+    x;
+}
+
+function f(y: string | undefined) {
+    if (!y) return;
+    y;
+}
+";
+
+/// The narrowed type, the declared type and the guards at the `nth`
+/// occurrence of `needle`.
+type Narrowed = (String, Option<String>, Vec<(String, bool)>);
+
+fn narrowed_type_at(needle: &str, nth: usize) -> Narrowed {
+    TestProject::new(&[("/project/main.ts", MAIN)]).run(|_, checker| {
+        let checker = checker.with_type_recorder(TypeRecorder::default());
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        let (pos, _) = MAIN.match_indices(needle).nth(nth).unwrap();
+        let info = checker.narrowed_type_at(&file, pos + needle.len() - 2).unwrap();
+        (
+            info.display,
+            info.declared,
+            info.guards.into_iter().map(|guard| (guard.text, guard.assumed)).collect(),
+        )
+    })
+}
+
+#[test]
+fn narrowed_by_if() {
+    assert_eq!(
+        narrowed_type_at("    x;", 0),
+        (
+            "string".into(),
+            Some("string | number".into()),
+            vec![("typeof x === \"string\"".into(), true)]
+        )
+    );
+}
+
+#[test]
+fn narrowed_by_else() {
+    assert_eq!(
+        narrowed_type_at("    x;", 1),
+        (
+            "number".into(),
+            Some("string | number".into()),
+            vec![("typeof x === \"string\"".into(), false)]
+        )
+    );
+}
+
+#[test]
+fn narrowed_by_early_return() {
+    let (_, _, guards) = narrowed_type_at("    y;", 0);
+
+    assert_eq!(guards, vec![("!y".into(), false)]);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_errors::ErrorKind;
+
+use self::common::{file_name, TestProject};
+
+/// Checks the entries using `threads` threads, and returns the files of the
+/// errors in the reported order.
+fn check(threads: usize) -> Vec<String> {
+    let src = "import { shared } from './shared';\nconst x: string = shared;\nconst y: number = '';\n";
+    let project = TestProject::new(&[
+        ("/project/a.ts", src),
+        ("/project/b.ts", src),
+        ("/project/c.ts", src),
+        ("/project/d.ts", src),
+        ("/project/shared.ts", "export const shared = 1;\nconst z: boolean = 0;\n"),
+    ]);
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+    pool.install(|| {
+        project.run(|_, mut checker| {
+            checker.check_all(
+                ["d", "c", "b", "a"]
+                    .iter()
+                    .map(|name| Arc::new(file_name(&format!("/project/{}.ts", name))))
+                    .collect(),
+            );
+            ErrorKind::flatten(checker.take_errors())
+                .iter()
+                .map(|err| checker.to_diagnostic(err).file.unwrap())
+                .collect()
+        })
+    })
+}
+
+#[test]
+fn errors_are_sorted_by_file() {
+    let files = check(1);
+
+    let mut expected = vec![];
+    for name in ["a", "b", "c", "d"] {
+        expected.push(format!("/project/{}.ts", name));
+        expected.push(format!("/project/{}.ts", name));
+    }
+    expected.push("/project/shared.ts".to_string());
+    assert_eq!(files, expected);
+
+    for threads in [2, 4] {
+        assert_eq!(check(threads), files);
+    }
+}
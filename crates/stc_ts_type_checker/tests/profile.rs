@@ -0,0 +1,53 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_utils::profile::Profiler;
+
+use self::common::{file_name, TestProject};
+
+#[test]
+fn phases_of_check() {
+    let project = TestProject::new(&[
+        (
+            "/project/main.ts",
+            "import { a } from './a';\nfunction f() {\n    return a;\n}\nf();\n",
+        ),
+        ("/project/a.ts", "export const a = 1;\n"),
+    ]);
+
+    let profiler = Profiler::new();
+
+    project.run_with_loader(
+        |loader| loader.with_profiler(profiler.clone()),
+        |_, checker| {
+            let checker = checker.with_profiler(profiler.clone());
+            checker.check(Arc::new(file_name("/project/main.ts")));
+        },
+    );
+
+    let events = profiler.events();
+    let names = |cat: &str| {
+        let mut names = events
+            .iter()
+            .filter(|event| event.cat == cat)
+            .map(|event| &*event.name)
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    assert_eq!(names("parse"), vec!["/project/a.ts", "/project/main.ts"]);
+    assert_eq!(names("bind"), vec!["/project/a.ts", "/project/main.ts"]);
+    assert_eq!(names("check"), vec!["/project/a.ts", "/project/main.ts"]);
+    let declarations = names("declaration");
+    assert!(declarations.contains(&"/project/a.ts: a"));
+    assert!(declarations.contains(&"/project/main.ts: f"));
+    // `f();`
+    assert!(declarations.contains(&"/project/main.ts: (statement)"));
+
+    let trace: serde_json::Value = serde_json::from_str(&profiler.to_chrome_trace()).unwrap();
+    let trace_events = trace["traceEvents"].as_array().unwrap();
+    assert_eq!(trace_events.len(), events.len());
+    assert_eq!(trace_events[0]["ph"], "X");
+}
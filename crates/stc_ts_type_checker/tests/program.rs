@@ -0,0 +1,221 @@
+mod common;
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_errors::{Edit, Error, ErrorKind, Fix, Report};
+use stc_ts_type_checker::program::{Diagnostic, DiagnosticFix, DiagnosticTag, TextEdit};
+use stc_ts_types::{Id, Type};
+use swc_atoms::JsWord;
+use swc_common::{BytePos, FileName, SourceMap, Span, SyntaxContext, DUMMY_SP};
+
+use self::common::{file_name, TestProject};
+
+#[test]
+fn modules_and_errors() {
+    let project = TestProject::new(&[
+        ("/project/main.ts", "import { a } from './a';\nconst s: string = a;\n"),
+        (
+            "/project/a.ts",
+            "export const a = 1;\nexport interface A {}\nconst b: number = '';\n",
+        ),
+    ]);
+
+    project.run(|_, mut checker| {
+        let main = file_name("/project/main.ts");
+        let program = checker.check(Arc::new(main.clone()));
+
+        assert_eq!(
+            program
+                .modules
+                .iter()
+                .map(|module| (
+                    module.file.to_string(),
+                    module.errors.iter().map(|err| err.code()).collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/project/a.ts".to_string(), vec![2322]),
+                ("/project/main.ts".to_string(), vec![2322]),
+            ]
+        );
+        assert!(program.global_errors.is_empty());
+        assert_eq!(Some(program.module_of_file(&main).unwrap().id), program.entry);
+
+        let a = program.module_of_file(&file_name("/project/a.ts")).unwrap();
+        match a.exports.normalize() {
+            Type::Module(module) => {
+                assert!(module.exports.vars.contains_key(&JsWord::from("a")));
+                assert!(module.exports.types.contains_key(&JsWord::from("A")));
+                assert!(!module.exports.vars.contains_key(&JsWord::from("b")));
+            }
+            ty => panic!("expected a module, got {:?}", ty),
+        }
+
+        // Errors are stored in the checker too.
+        assert_eq!(checker.take_errors().len(), program.errors().count());
+    });
+}
+
+#[test]
+fn multiple_entries() {
+    let project = TestProject::new(&[
+        ("/project/a.ts", "import { c } from './common';\nexport const a: string = c;\n"),
+        ("/project/b.ts", "import { c } from './common';\nexport const b: number = c;\n"),
+        ("/project/common.ts", "export const c = 1;\nconst d: string = c;\n"),
+    ]);
+
+    project.run(|_, checker| {
+        let a = file_name("/project/a.ts");
+        let b = file_name("/project/b.ts");
+        let program = checker.check_all(vec![Arc::new(a.clone()), Arc::new(b.clone())]);
+
+        assert_eq!(
+            program.entries,
+            vec![program.module_of_file(&a).unwrap().id, program.module_of_file(&b).unwrap().id]
+        );
+        assert_eq!(program.entry, Some(program.entries[0]));
+
+        // The error in the common dependency is reported once.
+        assert_eq!(
+            program
+                .modules
+                .iter()
+                .map(|module| (
+                    module.file.to_string(),
+                    module.errors.iter().map(|err| err.code()).collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/project/a.ts".to_string(), vec![2322]),
+                ("/project/b.ts".to_string(), vec![]),
+                ("/project/common.ts".to_string(), vec![2322]),
+            ]
+        );
+    });
+}
+
+#[test]
+fn no_entries() {
+    TestProject::new(&[]).run(|_, mut checker| {
+        let program = checker.check_all(vec![]);
+
+        assert_eq!(program.entry, None);
+        assert!(program.entries.is_empty());
+        assert!(program.modules.is_empty());
+        assert_eq!(program.errors().count(), 0);
+        assert!(checker.take_errors().is_empty());
+    });
+}
+
+#[test]
+fn entries_which_cannot_be_loaded() {
+    let project = TestProject::new(&[
+        ("/project/main.ts", "const a: string = 1;\n"),
+        ("/project/invalid.ts", "const = ;\n"),
+    ]);
+
+    project.run(|_, checker| {
+        let main = file_name("/project/main.ts");
+        let program = checker.check_all(vec![
+            Arc::new(file_name("/project/missing.ts")),
+            Arc::new(main.clone()),
+            Arc::new(file_name("/project/invalid.ts")),
+        ]);
+
+        // Other entries are still checked.
+        assert_eq!(program.entries, vec![program.module_of_file(&main).unwrap().id]);
+        assert_eq!(program.module_of_file(&main).unwrap().errors.len(), 1);
+
+        let files = program
+            .global_errors
+            .iter()
+            .map(|err| match &**err {
+                ErrorKind::CannotReadFile { file, .. } => {
+                    assert_eq!(err.code(), 5012);
+                    let message = err.message();
+                    assert!(message.starts_with(&format!("Cannot read file '{}': ", file)), "{}", message);
+                    file.clone()
+                }
+                err => panic!("unexpected error: {:?}", err),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(files, vec!["/project/missing.ts", "/project/invalid.ts"]);
+    });
+}
+
+#[test]
+fn diagnostic() {
+    let cm = SourceMap::default();
+    let fm = cm.new_source_file(FileName::Real(PathBuf::from("/project/main.ts")), "return;\nfoo();\n".into());
+    let span = Span::new(fm.start_pos + BytePos(8), fm.start_pos + BytePos(14), SyntaxContext::empty());
+
+    let d = Diagnostic::new(&cm, &ErrorKind::UnreachableCode { span }.into());
+    assert_eq!(d.file.as_deref(), Some("/project/main.ts"));
+    assert_eq!((d.start, d.end, d.line, d.column, d.end_line, d.end_column), (8, 14, 2, 0, 2, 6));
+    assert_eq!(d.code, 7027);
+    assert_eq!(d.message, "Unreachable code detected.");
+    assert_eq!(d.tags, vec![DiagnosticTag::Unnecessary]);
+
+    let json = serde_json::to_value(&d).unwrap();
+    assert_eq!(json["endLine"], 2);
+    assert_eq!(json["tags"], serde_json::json!(["unnecessary"]));
+    assert_eq!(serde_json::from_value::<Diagnostic>(json).unwrap(), d);
+}
+
+#[test]
+fn diagnostic_fix() {
+    let cm = SourceMap::default();
+    let fm = cm.new_source_file(FileName::Real(PathBuf::from("/project/main.ts")), "a == b;\n".into());
+    let span = |lo: u32, hi: u32| Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(hi), SyntaxContext::empty());
+
+    let err = ErrorKind::Lint {
+        span: span(0, 6),
+        rule: "eqeqeq".into(),
+        message: "Use '===' instead of '=='".into(),
+        fix: Some(Box::new(Fix {
+            message: "Use '==='".into(),
+            edits: vec![Edit::replace(span(2, 4), "===")],
+        })),
+    };
+
+    let d = Diagnostic::new(&cm, &err.into());
+    assert_eq!(
+        d.fixes,
+        vec![DiagnosticFix {
+            message: "Use '==='".into(),
+            edits: vec![TextEdit {
+                start: 2,
+                end: 4,
+                new_text: "===".into(),
+            }],
+        }]
+    );
+
+    let json = serde_json::to_value(&d).unwrap();
+    assert_eq!(json["fixes"][0]["edits"][0]["newText"], "===");
+}
+
+#[test]
+fn diagnostic_report() {
+    let cm = SourceMap::default();
+    let fm = cm.new_source_file(
+        FileName::Real(PathBuf::from("/project/main.ts")),
+        "const count = 1;\ncoutn;\n".into(),
+    );
+    let span = |lo: u32, hi: u32| Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(hi), SyntaxContext::empty());
+
+    let err: Error = ErrorKind::NoSuchVarWithSuggestion {
+        span: span(17, 22),
+        name: Id::word("coutn".into()),
+        suggestion: "count".into(),
+        declared: span(6, 11),
+    }
+    .into();
+    let d = Diagnostic::new(&cm, &err);
+    assert_eq!(d.report(&cm), Report::new(&err));
+
+    // The file is not loaded.
+    let report = d.report(&SourceMap::default());
+    assert_eq!(report.span, DUMMY_SP);
+    assert_eq!(report.related, vec![(DUMMY_SP, "'count' is declared here.".to_string())]);
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_type_checker::quick_info::{JsDocTag, Location, QuickInfo};
+use swc_common::FileName;
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { add } from './math';
+/**
+ * The answer.
+ */
+export const answer = add(40, 2);
+";
+
+const MATH: &str = "/**
+ * Adds two numbers.
+ *
+ * @param a The first operand
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+    return a + b;
+}
+";
+
+/// Checks `/project/main.ts` and returns the quick info at the first
+/// occurrence of `needle` in `file`.
+fn quick_info(file: &str, needle: &str) -> Option<QuickInfo> {
+    let project = TestProject::new(&[("/project/main.ts", MAIN), ("/project/math.ts", MATH)]);
+
+    project.run(|_, checker| {
+        let checker = checker.with_type_recorder(TypeRecorder::default());
+        checker.check(Arc::new(file_name("/project/main.ts")));
+
+        let path = PathBuf::from(file);
+        let pos = project.fs.get(&path).unwrap().find(needle).unwrap();
+        checker.quick_info(&FileName::Real(path), pos)
+    })
+}
+
+#[test]
+fn exported_const() {
+    assert_eq!(
+        quick_info("/project/main.ts", "answer"),
+        Some(QuickInfo {
+            kind: "const".into(),
+            kind_modifiers: "export".into(),
+            start: Location { line: 5, offset: 14 },
+            end: Location { line: 5, offset: 20 },
+            display_string: "const answer: number".into(),
+            documentation: "The answer.".into(),
+            tags: vec![],
+        })
+    );
+}
+
+#[test]
+fn imported_function() {
+    let info = quick_info("/project/main.ts", "add(").unwrap();
+
+    assert_eq!(info.kind, "function");
+    assert_eq!(info.display_string, "function add(a: number, b: number): number");
+    assert_eq!(info.documentation, "Adds two numbers.");
+    assert_eq!(
+        info.tags,
+        vec![
+            JsDocTag {
+                name: "param".into(),
+                text: "a The first operand".into(),
+            },
+            JsDocTag {
+                name: "returns".into(),
+                text: "The sum".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn parameter() {
+    let info = quick_info("/project/math.ts", "b;").unwrap();
+
+    assert_eq!(info.kind, "parameter");
+    assert_eq!(info.display_string, "(parameter) b: number");
+    assert_eq!(info.documentation, "");
+}
+
+#[test]
+fn no_symbol() {
+    assert_eq!(quick_info("/project/main.ts", "40"), None);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_type_checker::query::ReferenceKind::{self, *};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { total } from './b';\nimport { count } from './a';\nexport const x = total + count;\n";
+
+/// Checks `/project/main.ts` and returns the references to the symbol at the
+/// first occurrence of `needle`, sorted by the position.
+fn references_at(needle: &str) -> Vec<(String, String, ReferenceKind)> {
+    let project = TestProject::new(&[
+        ("/project/main.ts", MAIN),
+        ("/project/a.ts", "export let count = 0;\nexport function inc() { count++; }\n"),
+        ("/project/b.ts", "export { count as total } from './a';\n"),
+    ]);
+
+    project.run(|cm, checker| {
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        let mut found = checker.references_at(&file, MAIN.find(needle).unwrap());
+        found.sort_by_key(|r| (r.file.to_string(), r.span.lo));
+
+        found
+            .into_iter()
+            .map(|r| (r.file.to_string(), cm.span_to_snippet(r.span).unwrap(), r.kind))
+            .collect()
+    })
+}
+
+#[test]
+fn references_through_aliases() {
+    let expected = vec![
+        ("/project/a.ts".to_string(), "count".to_string(), Declaration),
+        ("/project/a.ts".to_string(), "count".to_string(), Write),
+        ("/project/b.ts".to_string(), "count".to_string(), Read),
+        ("/project/main.ts".to_string(), "total".to_string(), Read),
+        ("/project/main.ts".to_string(), "count".to_string(), Read),
+        ("/project/main.ts".to_string(), "total".to_string(), Read),
+        ("/project/main.ts".to_string(), "count".to_string(), Read),
+    ];
+
+    assert_eq!(references_at("total +"), expected);
+    assert_eq!(references_at("count;"), expected);
+}
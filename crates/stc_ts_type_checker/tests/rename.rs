@@ -0,0 +1,91 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_type_checker::rename::{RenameBlocker, RenameInfo};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { total } from './b';
+import { count as c } from './a';
+import { value } from 'pkg';
+export const x = total + c + value + parseInt('1');
+";
+
+const A: &str = "export let count = 0;\nexport function inc() { count++; }\n";
+
+/// Checks `/project/main.ts` and returns the rename information of the symbol
+/// at the first occurrence of `needle` in `file`, with the file and the text
+/// of the locations.
+fn prepare_rename(file: &str, needle: &str) -> (RenameInfo, Vec<(String, String)>) {
+    let project = TestProject::new(&[
+        ("/project/main.ts", MAIN),
+        ("/project/a.ts", A),
+        ("/project/b.ts", "export { count as total } from './a';\n"),
+        ("/project/node_modules/pkg/index.d.ts", "export declare const value: number;\n"),
+    ]);
+
+    project.run(|cm, checker| {
+        checker.check(Arc::new(file_name("/project/main.ts")));
+
+        let src = if file == "a.ts" { A } else { MAIN };
+        let info = checker
+            .prepare_rename(&file_name(&format!("/project/{}", file)), src.find(needle).unwrap())
+            .unwrap();
+        let locations = info
+            .locations
+            .iter()
+            .map(|r| (r.file.to_string(), cm.span_to_snippet(r.span).unwrap()))
+            .collect();
+        (info, locations)
+    })
+}
+
+fn loc(file: &str, text: &str) -> (String, String) {
+    (format!("/project/{}", file), text.to_string())
+}
+
+#[test]
+fn rename_declaration() {
+    // `c` is kept, as the import renames `count`.
+    let (info, locations) = prepare_rename("a.ts", "count");
+
+    assert_eq!(&*info.name, "count");
+    assert_eq!(info.blocker, None);
+    assert_eq!(
+        locations,
+        vec![
+            loc("a.ts", "count"),
+            loc("a.ts", "count"),
+            loc("b.ts", "count"),
+            loc("main.ts", "count"),
+        ]
+    );
+}
+
+#[test]
+fn rename_alias() {
+    let (info, locations) = prepare_rename("main.ts", "total");
+
+    assert_eq!(info.blocker, None);
+    assert_eq!(
+        locations,
+        vec![loc("b.ts", "total"), loc("main.ts", "total"), loc("main.ts", "total")]
+    );
+}
+
+#[test]
+fn dependency_is_not_renameable() {
+    let (info, locations) = prepare_rename("main.ts", "value");
+
+    assert_eq!(info.blocker, Some(RenameBlocker::Dependency));
+    assert_eq!(info.definitions.len(), 1);
+    assert_eq!(locations, vec![]);
+}
+
+#[test]
+fn global_is_not_renameable() {
+    let (info, _) = prepare_rename("main.ts", "parseInt");
+
+    assert_eq!(info.blocker, Some(RenameBlocker::NotDeclared));
+}
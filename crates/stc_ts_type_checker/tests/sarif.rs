@@ -0,0 +1,92 @@
+mod common;
+
+use std::{path::Path, sync::Arc};
+
+use stc_ts_errors::Severity;
+use stc_ts_type_checker::{program::Diagnostic, sarif::SarifLog};
+
+use self::common::{file_name, TestProject};
+
+#[test]
+fn errors_of_check() {
+    let project = TestProject::new(&[
+        ("/project/src/main.ts", "import { a } from './a';\nconst s: string = a;\n"),
+        ("/project/src/a.ts", "export const a = 1;\nfoo;\n"),
+    ]);
+
+    let diagnostics = project.run(|_, checker| {
+        let program = checker.check(Arc::new(file_name("/project/src/main.ts")));
+
+        program.errors().map(|err| checker.to_diagnostic(err)).collect::<Vec<_>>()
+    });
+
+    let log = SarifLog::new(&diagnostics, Path::new("/project"));
+    assert_eq!(log.version, "2.1.0");
+
+    let run = &log.runs[0];
+    let rules = &run.tool.driver.rules;
+    assert_eq!(rules.iter().map(|rule| &*rule.id).collect::<Vec<_>>(), vec!["TS2304", "TS2322"]);
+    assert_eq!(rules[1].short_description.text, "Type '{0}' is not assignable to type '{1}'.");
+
+    let mut results = run
+        .results
+        .iter()
+        .map(|result| {
+            let location = &result.locations[0].physical_location;
+            (
+                &*result.rule_id,
+                result.rule_index,
+                &*location.artifact_location.uri,
+                location.region.start_line,
+            )
+        })
+        .collect::<Vec<_>>();
+    results.sort();
+    assert_eq!(results, vec![("TS2304", 0, "src/a.ts", 2), ("TS2322", 1, "src/main.ts", 2)]);
+
+    let json: serde_json::Value = serde_json::from_str(&log.to_json().unwrap()).unwrap();
+    assert_eq!(json["$schema"], "https://json.schemastore.org/sarif-2.1.0.json");
+    assert_eq!(
+        json["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+        2
+    );
+}
+
+#[test]
+fn locations() {
+    let diagnostic = |file: Option<&str>, severity: Severity| Diagnostic {
+        file: file.map(String::from),
+        start: 4,
+        end: 7,
+        line: 1,
+        column: 4,
+        end_line: 1,
+        end_column: 7,
+        code: 99999,
+        message: "error".into(),
+        severity,
+        related: vec![],
+        tags: vec![],
+        fixes: vec![],
+    };
+
+    let log = SarifLog::new(
+        &[
+            diagnostic(None, Severity::Error),
+            diagnostic(Some("/other/a.ts"), Severity::Warning),
+        ],
+        Path::new("/project"),
+    );
+    let run = &log.runs[0];
+
+    // Codes without a known message are described by the code.
+    assert_eq!(run.tool.driver.rules[0].short_description.text, "TS99999");
+    assert!(run.results[0].locations.is_empty());
+    assert_eq!(run.results[0].level, "error");
+    assert_eq!(run.results[1].level, "warning");
+
+    // Columns are 1-based, and files outside of the root are absolute.
+    let location = &run.results[1].locations[0].physical_location;
+    assert_eq!(location.artifact_location.uri, "file:///other/a.ts");
+    assert_eq!((location.region.start_column, location.region.end_column), (5, 8));
+}
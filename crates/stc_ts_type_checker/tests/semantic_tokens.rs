@@ -0,0 +1,112 @@
+mod common;
+
+use std::{path::PathBuf, sync::Arc};
+
+use stc_ts_type_checker::semantic_tokens::{SemanticToken, TokenKind};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { Color, Id } from './types';
+
+class Point {
+    static origin = 0;
+    readonly x: number = 1;
+    constructor(private readonly y: number) {}
+    move(dx: number): number {
+        const next = this.x + dx;
+        return next;
+    }
+}
+
+function pick<T>(value: T, id: Id): Color {
+    return Color.Red;
+}
+";
+
+const TYPES: &str = "export enum Color {
+    Red,
+    Green,
+}
+
+export type Id = string;
+";
+
+/// Checks `/project/main.ts` and returns the tokens of `file` as the text,
+/// the kind and the modifiers.
+fn semantic_tokens(file: &str) -> Vec<(String, TokenKind, String)> {
+    let project = TestProject::new(&[("/project/main.ts", MAIN), ("/project/types.ts", TYPES)]);
+
+    let tokens = project.run(|_, checker| {
+        checker.check(Arc::new(file_name("/project/main.ts")));
+        checker.semantic_tokens(&file_name(file)).unwrap()
+    });
+
+    let text = project.fs.get(&PathBuf::from(file)).unwrap();
+    tokens.iter().map(|token| describe(&text, token)).collect()
+}
+
+fn describe(text: &str, token: &SemanticToken) -> (String, TokenKind, String) {
+    let mut modifiers = vec![];
+    if token.modifiers.declaration {
+        modifiers.push("declaration");
+    }
+    if token.modifiers.readonly {
+        modifiers.push("readonly");
+    }
+    if token.modifiers.is_static {
+        modifiers.push("static");
+    }
+
+    (text[token.start..token.end].to_string(), token.kind, modifiers.join(" "))
+}
+
+fn expected(tokens: &[(&str, TokenKind, &str)]) -> Vec<(String, TokenKind, String)> {
+    tokens
+        .iter()
+        .map(|(text, kind, modifiers)| (text.to_string(), *kind, modifiers.to_string()))
+        .collect()
+}
+
+#[test]
+fn bindings_and_members() {
+    assert_eq!(
+        semantic_tokens("/project/main.ts"),
+        expected(&[
+            // Imports are classified by the declarations in `types.ts`.
+            ("Color", TokenKind::Enum, ""),
+            ("Id", TokenKind::TypeAlias, ""),
+            ("Point", TokenKind::Class, "declaration"),
+            ("origin", TokenKind::Property, "declaration static"),
+            ("x", TokenKind::Property, "declaration readonly"),
+            ("y", TokenKind::Parameter, "declaration readonly"),
+            ("move", TokenKind::Method, "declaration"),
+            ("dx", TokenKind::Parameter, "declaration"),
+            ("next", TokenKind::Variable, "declaration readonly"),
+            ("x", TokenKind::Property, ""),
+            ("dx", TokenKind::Parameter, ""),
+            ("next", TokenKind::Variable, "readonly"),
+            ("pick", TokenKind::Function, "declaration"),
+            ("T", TokenKind::TypeParameter, "declaration"),
+            ("value", TokenKind::Parameter, "declaration"),
+            ("T", TokenKind::TypeParameter, ""),
+            ("id", TokenKind::Parameter, "declaration"),
+            ("Id", TokenKind::TypeAlias, ""),
+            ("Color", TokenKind::Enum, ""),
+            ("Color", TokenKind::Enum, ""),
+            ("Red", TokenKind::EnumMember, "readonly"),
+        ])
+    );
+}
+
+#[test]
+fn enum_members() {
+    assert_eq!(
+        semantic_tokens("/project/types.ts"),
+        expected(&[
+            ("Color", TokenKind::Enum, "declaration"),
+            ("Red", TokenKind::EnumMember, "declaration readonly"),
+            ("Green", TokenKind::EnumMember, "declaration readonly"),
+            ("Id", TokenKind::TypeAlias, "declaration"),
+        ])
+    );
+}
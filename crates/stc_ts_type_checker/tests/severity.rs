@@ -0,0 +1,31 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_errors::{Severity, SeverityOverrides};
+
+use self::common::{file_name, TestProject};
+
+#[test]
+fn severity_overrides() {
+    let overrides = SeverityOverrides::default()
+        .with_code(2322, Some(Severity::Info))
+        .with_code(2304, None);
+
+    let project = TestProject::new(&[("/project/main.ts", "const s: string = 1;\nfoo;\nbar();\n")])
+        .with_env(move |env| env.with_severity_overrides(overrides.clone()));
+
+    let (diagnostics, remaining) = project.run(|_, mut checker| {
+        let program = checker.check(Arc::new(file_name("/project/main.ts")));
+        let diagnostics = program.errors().map(|err| checker.to_diagnostic(err)).collect::<Vec<_>>();
+
+        (diagnostics, checker.take_errors().len())
+    });
+
+    // Errors of `TS2304` are turned off.
+    assert_eq!(
+        diagnostics.iter().map(|d| (d.code, d.severity)).collect::<Vec<_>>(),
+        vec![(2322, Severity::Info)]
+    );
+    assert_eq!(remaining, 1);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_type_checker::signature_help::{ParameterInfo, SignatureHelp, SignatureInfo};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "function add(a: number, b: number): number {
+    return a + b;
+}
+add(1, 2);
+
+class Point {
+    constructor(x: number, y: number) {}
+}
+new Point(1, 2);
+
+declare const counter: { add(by: number): number };
+counter.add(1);
+";
+
+/// Checks `/project/main.ts` and returns the signature help at the end of
+/// `needle` in it.
+fn signature_help(needle: &str) -> Option<SignatureHelp> {
+    TestProject::new(&[("/project/main.ts", MAIN)]).run(|_, checker| {
+        let checker = checker.with_type_recorder(TypeRecorder::default());
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        checker.signature_help(&file, MAIN.find(needle).unwrap() + needle.len())
+    })
+}
+
+fn param(name: &str) -> ParameterInfo {
+    ParameterInfo {
+        label: format!("{}: number", name),
+        ty: "number".into(),
+    }
+}
+
+#[test]
+fn function_call() {
+    assert_eq!(
+        signature_help("add(1, "),
+        Some(SignatureHelp {
+            signatures: vec![SignatureInfo {
+                label: "add(a: number, b: number): number".into(),
+                parameters: vec![param("a"), param("b")],
+                return_type: "number".into(),
+            }],
+            active_signature: 0,
+            active_parameter: 1,
+        })
+    );
+}
+
+#[test]
+fn first_argument() {
+    assert_eq!(signature_help("}\nadd(").unwrap().active_parameter, 0);
+}
+
+#[test]
+fn method_call() {
+    assert_eq!(
+        signature_help("counter.add("),
+        Some(SignatureHelp {
+            signatures: vec![SignatureInfo {
+                label: "add(by: number): number".into(),
+                parameters: vec![param("by")],
+                return_type: "number".into(),
+            }],
+            active_signature: 0,
+            active_parameter: 0,
+        })
+    );
+}
+
+#[test]
+fn constructor() {
+    let help = signature_help("new Point(").unwrap();
+
+    assert_eq!(help.signatures.len(), 1);
+    assert_eq!(help.signatures[0].label, "Point(x: number, y: number): Point");
+    assert_eq!(help.active_parameter, 0);
+}
+
+#[test]
+fn outside_of_call() {
+    assert_eq!(signature_help("add(1, 2)"), None);
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use stc_ts_env::Rule;
+
+use self::common::TestProject;
+
+fn check(rule: Rule) -> Vec<usize> {
+    TestProject::new(&[
+        ("/project/main.ts", "import { value } from 'pkg';\nconst v: string = value;\n"),
+        (
+            "/project/node_modules/pkg/index.d.ts",
+            "export declare const value: number;\nexport declare function broken(): Missing;\n",
+        ),
+    ])
+    .with_rule(rule)
+    .error_codes()
+}
+
+#[test]
+fn errors_in_dts_are_reported() {
+    // Cannot find name 'Missing', and `number` is not assignable to `string`.
+    assert_eq!(check(Default::default()), vec![2304, 2322]);
+}
+
+#[test]
+fn skip_lib_check() {
+    let rule = Rule {
+        skip_lib_check: true,
+        ..Default::default()
+    };
+
+    // Types of the declaration file are still used.
+    assert_eq!(check(rule), vec![2322]);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_utils::spelling::spelling_suggestion;
+
+use self::common::{file_name, TestProject};
+
+/// Returns the codes, messages and related messages of the errors of
+/// `/project/main.ts`.
+fn check(src: &str) -> Vec<(usize, String, Vec<String>)> {
+    TestProject::new(&[("/project/main.ts", src)]).run(|_, checker| {
+        let program = checker.check(Arc::new(file_name("/project/main.ts")));
+
+        program
+            .errors()
+            .map(|err| {
+                let related = err.related_spans().into_iter().map(|(_, message)| message).collect();
+                (err.code(), err.message(), related)
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn spelling() {
+    assert_eq!(spelling_suggestion("countr", ["count", "counter", "Counter"]), Some("counter"));
+    // A change of the case is cheaper.
+    assert_eq!(spelling_suggestion("counTer", ["counted", "counter"]), Some("counter"));
+    assert_eq!(spelling_suggestion("x", ["y", "z"]), None);
+    assert_eq!(spelling_suggestion("value", ["value", "other"]), None);
+}
+
+#[test]
+fn names_in_scope() {
+    let errors = check(
+        "const counter = 1;
+function run() {
+    return countr;
+}
+interface Point {
+    x: number;
+}
+let p: Piont;
+",
+    );
+
+    assert_eq!(
+        errors,
+        vec![
+            (
+                2552,
+                "Cannot find name 'countr'. Did you mean 'counter'?".to_string(),
+                vec!["'counter' is declared here.".to_string()]
+            ),
+            (
+                2552,
+                "Cannot find name 'Piont'. Did you mean 'Point'?".to_string(),
+                vec!["'Point' is declared here.".to_string()]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn globals() {
+    let errors = check("Mathh.floor(1.5);\nMath.flor(1.5);\n");
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, 2552);
+    assert_eq!(errors[0].1, "Cannot find name 'Mathh'. Did you mean 'Math'?");
+    // Globals don't have a declaration in the file.
+    assert_eq!(errors[0].2, Vec::<String>::new());
+
+    assert_eq!(errors[1].0, 2551);
+    assert!(errors[1].1.ends_with("Did you mean 'floor'?"), "{}", errors[1].1);
+}
+
+#[test]
+fn no_similar_name() {
+    let errors = check("unrelatedName;\n");
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, 2304);
+}
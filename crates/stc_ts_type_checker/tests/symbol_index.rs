@@ -0,0 +1,61 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_type_checker::outline::SymbolKind;
+
+use self::common::{file_name, TestProject};
+
+/// Checks `/project/main.ts` and returns the names, kinds and files of the
+/// symbols matching `query`.
+fn search(query: &str) -> Vec<(String, SymbolKind, String)> {
+    let project = TestProject::new(&[
+        (
+            "/project/main.ts",
+            "import { User } from './user';\nexport function getUserName(user: User) { return user.name; }\nconst user = 1;\n",
+        ),
+        (
+            "/project/user.ts",
+            "export class User { name = ''; getName() { return this.name; } }\nexport type UserId = string;\n",
+        ),
+    ]);
+
+    project.run(|_, checker| {
+        checker.check(Arc::new(file_name("/project/main.ts")));
+
+        checker
+            .symbol_index()
+            .search(query, 10)
+            .into_iter()
+            .map(|symbol| (symbol.name.clone(), symbol.kind, symbol.file.to_string()))
+            .collect()
+    })
+}
+
+#[test]
+fn fuzzy_match() {
+    assert_eq!(
+        search("gUN"),
+        vec![("getUserName".into(), SymbolKind::Function, "/project/main.ts".into())]
+    );
+}
+
+#[test]
+fn exact_match_first() {
+    // Members of classes are not top-level symbols, and names of the same
+    // score are sorted by the length.
+    assert_eq!(
+        search("user"),
+        vec![
+            ("User".into(), SymbolKind::Class, "/project/user.ts".into()),
+            ("user".into(), SymbolKind::Constant, "/project/main.ts".into()),
+            ("UserId".into(), SymbolKind::TypeAlias, "/project/user.ts".into()),
+            ("getUserName".into(), SymbolKind::Function, "/project/main.ts".into()),
+        ]
+    );
+}
+
+#[test]
+fn no_match() {
+    assert_eq!(search("xyz"), vec![]);
+}
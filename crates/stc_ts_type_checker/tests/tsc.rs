@@ -7,8 +7,8 @@
 
 extern crate test;
 
-#[path = "common/mod.rs"]
-mod common;
+#[path = "common/fixtures.rs"]
+mod fixtures;
 
 use std::{
     env, fs,
@@ -25,21 +25,18 @@ use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use serde::Deserialize;
 use stc_ts_env::Env;
+use stc_ts_errors::{FrameRenderer, Report};
 use stc_ts_file_analyzer::env::EnvFactory;
-use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_module_loader::resolvers::ModuleResolver;
 use stc_ts_testing::conformance::{parse_conformance_test, TestSpec};
 use stc_ts_type_checker::{loader::ModuleLoader, Checker};
-use swc_common::{
-    errors::{DiagnosticBuilder, DiagnosticId},
-    input::SourceFileInput,
-    FileName, SourceMap, Span,
-};
+use swc_common::{errors::DiagnosticId, input::SourceFileInput, FileName, SourceMap, Span};
 use swc_ecma_parser::{Parser, Syntax, TsConfig};
 use swc_ecma_visit::Fold;
 use test::test_main;
 use testing::{StdErr, Tester};
 
-use self::common::load_fixtures;
+use self::fixtures::load_fixtures;
 
 struct RecordOnPanic {
     stats_file_name: PathBuf,
@@ -60,8 +57,54 @@ impl Drop for RecordOnPanic {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, PartialOrd, Ord)]
 struct RefError {
     pub line: usize,
+    /// 1-based, in UTF-16 code units.
     pub column: usize,
+    /// The length of the span in UTF-16 code units. If specified, the column
+    /// and the length of the actual error should match too.
+    #[serde(default)]
+    pub length: Option<usize>,
+    pub code: String,
+    /// The message of `tsc`. If specified, the message of the actual error
+    /// should be the same. Messages with a single line are compared with the
+    /// first line of the message chain of the actual error.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl RefError {
+    fn matches(&self, actual: &ActualError) -> bool {
+        if self.code != actual.code {
+            return false;
+        }
+        // This error use special span.
+        if self.line == 0 {
+            return true;
+        }
+
+        if let Some(message) = &self.message {
+            let actual_message = if message.contains('\n') {
+                &*actual.message
+            } else {
+                actual.message.lines().next().unwrap_or_default()
+            };
+
+            if message != actual_message {
+                return false;
+            }
+        }
+
+        self.line == actual.line && self.length.map_or(true, |length| (self.column, length) == (actual.column, actual.length))
+    }
+}
+
+/// An error reported by stc, located like [RefError].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct ActualError {
+    pub line: usize,
     pub code: String,
+    pub column: usize,
+    pub length: usize,
+    pub message: String,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -306,8 +349,6 @@ fn load_expected_errors(ts_file: &Path, spec: Option<&TestSpec>) -> (String, Vec
             }
         }
 
-        // TODO(kdy1): Match column and message
-
         errors
     };
 
@@ -329,6 +370,8 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
         rule,
         target,
         module_config,
+        module_resolution,
+        module_suffixes,
         raw_target: _,
         ..
     } = spec;
@@ -373,6 +416,7 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
     let full_ref_err_cnt = full_ref_errors.len();
 
     let tester = Tester::new();
+    let mut reports = vec![];
     let diagnostics = tester
         .errors(|cm, handler| {
             let handler = Arc::new(handler);
@@ -382,7 +426,13 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
                 handler.clone(),
                 env.clone(),
                 None,
-                ModuleLoader::new(cm, env, NodeResolver),
+                ModuleLoader::new(
+                    cm,
+                    env,
+                    ModuleResolver::new(module_resolution)
+                        .with_allow_js(rule.allow_js)
+                        .with_module_suffixes(module_suffixes.clone()),
+                ),
             );
 
             // Install a logger
@@ -399,6 +449,7 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
             let errors = ::stc_ts_errors::ErrorKind::flatten(checker.take_errors());
 
             for e in errors {
+                reports.push(Report::new(&e));
                 e.emit(&handler);
             }
 
@@ -432,8 +483,6 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
     let mut extra_errors = diagnostics
         .iter()
         .map(|d| {
-            let span = d.span.primary_span().unwrap();
-            let cp = tester.cm.lookup_char_pos(span.lo());
             let code = d
                 .code
                 .clone()
@@ -445,26 +494,19 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
                 }
             };
 
-            (cp.line, code)
+            actual_error(&tester.cm, d.span.primary_span().unwrap(), code, d.message())
         })
         .collect::<Vec<_>>();
     extra_errors.sort();
 
     let full_actual_errors = extra_errors.clone();
 
-    for (line, error_code) in full_actual_errors.clone() {
-        if let Some(idx) = expected_errors
-            .iter()
-            .position(|err| (err.line == line || err.line == 0) && err.code == error_code)
-        {
+    for actual in full_actual_errors.clone() {
+        if let Some(idx) = expected_errors.iter().position(|err| err.matches(&actual)) {
             stats.matched_error += 1;
 
-            let is_zero_line = expected_errors[idx].line == 0;
             expected_errors.remove(idx);
-            if let Some(idx) = extra_errors
-                .iter()
-                .position(|(r_line, r_code)| (line == *r_line || is_zero_line) && error_code == *r_code)
-            {
+            if let Some(idx) = extra_errors.iter().position(|err| *err == actual) {
                 extra_errors.remove(idx);
             }
         }
@@ -475,22 +517,22 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
     //      - Actual errors does not remain
     let success = expected_errors.is_empty() && extra_errors.is_empty();
 
-    let res: Result<(), _> = tester.print_errors(|_, handler| {
-        // If we failed, we only emit errors which has wrong line.
-
-        for (d, line_col) in diagnostics.into_iter().zip(full_actual_errors.clone()) {
-            if success || env::var("PRINT_ALL").unwrap_or_default() == "1" || extra_errors.contains(&line_col) {
-                DiagnosticBuilder::new_diagnostic(&handler, d).emit();
-            }
-        }
-
-        Err(())
-    });
-
-    let err = match res {
-        Ok(_) => StdErr::from(String::from("")),
-        Err(err) => err,
-    };
+    // If we failed, we only print errors which are not expected.
+    let renderer = FrameRenderer::default();
+    let frames = reports
+        .iter()
+        .filter(|report| {
+            success
+                || env::var("PRINT_ALL").unwrap_or_default() == "1"
+                || extra_errors.contains(&actual_error(
+                    &tester.cm,
+                    report.span,
+                    format!("TS{}", report.code),
+                    report.message.clone(),
+                ))
+        })
+        .map(|report| renderer.render(&tester.cm, report))
+        .collect::<String>();
 
     let extra_err_count = extra_errors.len();
     stats.required_error += expected_errors.len();
@@ -513,7 +555,7 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
 
     if extra_errors.len() == expected_errors.len() {
         let expected_lines = expected_errors.iter().map(|v| v.line).collect::<Vec<_>>();
-        let extra_lines = extra_errors.iter().map(|(v, _)| *v).collect::<Vec<_>>();
+        let extra_lines = extra_errors.iter().map(|v| v.line).collect::<Vec<_>>();
 
         if expected_lines == extra_lines {
             println!("[ERROR_CODE_ONLY]{}", file_name.display());
@@ -522,10 +564,10 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
 
     if print_matched_errors() {
         eprintln!(
-            "\n============================================================\n{:?}
+            "\n============================================================\n{}
 ============================================================\n{} unmatched errors out of {} errors. Got {} extra errors.\nWanted: \
              {:?}\nUnwanted: {:?}\n\nAll required errors: {:?}\nAll actual errors: {:?}",
-            err,
+            frames,
             expected_errors.len(),
             full_ref_err_cnt,
             extra_err_count,
@@ -536,10 +578,10 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
         );
     } else {
         eprintln!(
-            "\n============================================================\n{:?}
+            "\n============================================================\n{}
 ============================================================\n{} unmatched errors out of {} errors. Got {} extra errors.\nWanted: \
              {:?}\nUnwanted: {:?}",
-            err,
+            frames,
             expected_errors.len(),
             full_ref_err_cnt,
             extra_err_count,
@@ -554,6 +596,25 @@ fn do_test(file_name: &Path, spec: TestSpec, use_target: bool) -> Result<(), Std
     Ok(())
 }
 
+/// Creates an [ActualError] for an error at `span`, with columns and lengths
+/// in UTF-16 code units like `tsc`.
+fn actual_error(cm: &SourceMap, span: Span, code: String, message: String) -> ActualError {
+    let cp = cm.lookup_char_pos(span.lo());
+    let column = cp
+        .file
+        .get_line(cp.line - 1)
+        .map_or(cp.col.0, |line| line.chars().take(cp.col.0).map(char::len_utf16).sum());
+    let length = cm.span_to_snippet(span).map(|s| s.encode_utf16().count()).unwrap_or_default();
+
+    ActualError {
+        line: cp.line,
+        code,
+        column: column + 1,
+        length,
+        message,
+    }
+}
+
 fn print_per_test_stat(stats_file_name: &Path, stats: &Stats) {
     if env::var("CI").unwrap_or_default() == "1" {
         let stat_string = fs::read_to_string(stats_file_name).expect("failed to read test stats file");
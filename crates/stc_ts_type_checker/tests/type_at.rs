@@ -0,0 +1,32 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "declare const s: string;\nexport const n = s.length;\n";
+
+/// Checks `/project/main.ts` and returns the printed types at the first
+/// occurrence of each of `needles` in it.
+fn types_at(needles: &[&str]) -> Vec<Option<String>> {
+    TestProject::new(&[("/project/main.ts", MAIN)]).run(|_, checker| {
+        let checker = checker.with_type_recorder(TypeRecorder::default());
+        let file = file_name("/project/main.ts");
+        checker.check(Arc::new(file.clone()));
+
+        needles
+            .iter()
+            .map(|needle| checker.type_at(&file, MAIN.find(needle).unwrap()).map(|info| info.display))
+            .collect()
+    })
+}
+
+#[test]
+fn type_at() {
+    assert_eq!(
+        types_at(&["s.length", "length", "declare"]),
+        vec![Some("string".into()), Some("number".into()), None]
+    );
+}
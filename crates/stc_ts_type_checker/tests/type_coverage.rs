@@ -0,0 +1,124 @@
+mod common;
+
+use std::{path::Path, sync::Arc};
+
+use stc_ts_file_analyzer::recorder::TypeRecorder;
+use stc_ts_type_checker::{
+    coverage::{AnyIdent, CoverageCounts, FileCoverage, TypeCoverage},
+    declarations::DeclarationSpan,
+};
+
+use self::common::{file_name, TestProject};
+
+const MAIN: &str = "import { typed } from './typed';
+declare const e: any;
+function f(a, b: number) {
+    return a;
+}
+const x = f(typed, 2);
+const y = e;
+";
+
+#[test]
+fn type_coverage() {
+    let project = TestProject::new(&[
+        ("/project/main.ts", MAIN),
+        ("/project/typed.ts", "export const typed: number = 1;\n"),
+    ]);
+
+    project.run(|_, checker| {
+        let checker = checker.with_type_recorder(TypeRecorder::default());
+        let main = file_name("/project/main.ts");
+        checker.check(Arc::new(main.clone()));
+
+        let coverage = checker.file_coverage(&main).unwrap();
+        assert_eq!(
+            coverage
+                .anys
+                .iter()
+                .map(|any| (&*any.name, any.span.line, any.declaration, any.implicit))
+                .collect::<Vec<_>>(),
+            vec![
+                ("e", 2, true, false),
+                ("a", 3, true, true),
+                ("a", 4, false, true),
+                ("x", 6, true, true),
+                ("y", 7, true, true),
+                ("e", 7, false, false),
+            ]
+        );
+        assert_eq!(coverage.counts.explicit_any, 2);
+        assert_eq!(coverage.counts.implicit_any, 4);
+        assert_eq!(coverage.counts.typed, coverage.counts.total - 6);
+        assert!(coverage.counts.typed > 0);
+
+        // `typed.ts` doesn't use `any`.
+        let project = checker.type_coverage();
+        assert_eq!(
+            project.files.iter().map(|file| &*file.file).collect::<Vec<_>>(),
+            vec!["/project/main.ts", "/project/typed.ts"]
+        );
+        assert_eq!(project.files[1].counts.implicit_any + project.files[1].counts.explicit_any, 0);
+        assert_eq!(project.files[1].counts.percentage(), 100.0);
+        assert_eq!(project.total.total, coverage.counts.total + project.files[1].counts.total);
+    });
+}
+
+fn file(file: &str, typed: usize, implicit_any: usize) -> FileCoverage {
+    FileCoverage {
+        file: file.into(),
+        counts: CoverageCounts {
+            total: typed + implicit_any,
+            typed,
+            explicit_any: 0,
+            implicit_any,
+        },
+        anys: vec![],
+    }
+}
+
+#[test]
+fn directories() {
+    let coverage = TypeCoverage {
+        total: Default::default(),
+        files: vec![
+            file("/other/c.ts", 1, 1),
+            file("/project/main.ts", 4, 0),
+            file("/project/src/a/a.ts", 1, 1),
+            file("/project/src/b.ts", 2, 2),
+        ],
+    };
+
+    let dirs = coverage.directories(Path::new("/project"));
+    assert_eq!(
+        dirs.iter()
+            .map(|dir| (&*dir.dir, dir.counts.typed, dir.counts.total))
+            .collect::<Vec<_>>(),
+        vec![(".", 7, 10), ("/other", 1, 2), ("src", 3, 6), ("src/a", 1, 2)]
+    );
+}
+
+#[test]
+fn html() {
+    let mut main = file("/project/<main>.ts", 1, 1);
+    main.anys.push(AnyIdent {
+        name: "a".into(),
+        span: DeclarationSpan {
+            start: 10,
+            end: 11,
+            line: 2,
+            column: 4,
+        },
+        declaration: true,
+        implicit: true,
+    });
+    let coverage = TypeCoverage {
+        total: main.counts,
+        files: vec![main],
+    };
+
+    let html = coverage.to_html(Path::new("/project"));
+    assert!(html.contains("<h1>Type coverage: 50.00%</h1>"));
+    assert!(html.contains("<a href=\"#file-0\">&lt;main&gt;.ts</a>"));
+    assert!(html.contains("<li>2:5 <code>a</code> (implicit declaration)</li>"));
+}
@@ -41,7 +41,7 @@ fn run_tests_for_types_pkg(module_specifier: &str) {
             handler.clone(),
             env.clone(),
             None,
-            ModuleLoader::new(cm, env, NodeResolver),
+            ModuleLoader::new(cm, env, NodeResolver::new()),
         );
 
         checker.check(Arc::new(path));
@@ -0,0 +1,124 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use stc_ts_errors::ErrorKind;
+
+use self::common::TestProject;
+
+/// Loads the typings of `/project/src` and returns the loaded files, with the
+/// names of the typings which are not found.
+fn load(files: &[(&str, &str)], type_roots: Option<&[&str]>, types: Option<&[&str]>) -> (Vec<String>, Vec<String>) {
+    let type_roots = type_roots.map(|type_roots| type_roots.iter().map(PathBuf::from).collect::<Vec<_>>());
+    let types = types.map(|types| types.iter().map(|name| name.to_string()).collect::<Vec<_>>());
+
+    TestProject::new(files).run(|_, checker| {
+        let errors = checker.load_typings(Path::new("/project/src"), type_roots.as_deref(), types.as_deref());
+
+        let missing = errors
+            .iter()
+            .map(|err| match &**err {
+                ErrorKind::CannotFindTypeDefinitionFile { name, .. } => {
+                    assert_eq!(err.code(), 2688);
+                    assert_eq!(err.message(), format!("Cannot find type definition file for '{}'.", name));
+                    name.clone()
+                }
+                err => panic!("unexpected error: {:?}", err),
+            })
+            .collect();
+        let loaded = checker.module_graph().modules.into_iter().map(|module| module.path).collect();
+
+        (loaded, missing)
+    })
+}
+
+const TYPING: &str = "export declare const a: number;\n";
+
+#[test]
+fn default_type_roots() {
+    let (loaded, missing) = load(
+        &[
+            ("/project/src/node_modules/@types/a/index.d.ts", TYPING),
+            ("/project/node_modules/@types/b/index.d.ts", TYPING),
+            ("/node_modules/@types/c/index.d.ts", TYPING),
+            // The nearest type root wins.
+            ("/project/node_modules/@types/a/index.d.ts", TYPING),
+            // Hidden directories are not packages.
+            ("/project/node_modules/@types/.cache/index.d.ts", TYPING),
+        ],
+        None,
+        None,
+    );
+
+    assert_eq!(
+        loaded,
+        vec![
+            "/node_modules/@types/c/index.d.ts",
+            "/project/node_modules/@types/b/index.d.ts",
+            "/project/src/node_modules/@types/a/index.d.ts",
+        ]
+    );
+    assert!(missing.is_empty());
+}
+
+#[test]
+fn explicit_type_roots() {
+    let (loaded, missing) = load(
+        &[
+            ("/project/typings/a/index.d.ts", TYPING),
+            ("/project/typings/@scope/b/index.d.ts", TYPING),
+            // Default type roots are not used.
+            ("/project/node_modules/@types/d/index.d.ts", TYPING),
+        ],
+        Some(&["/project/typings", "/project/missing"]),
+        None,
+    );
+
+    assert_eq!(
+        loaded,
+        vec!["/project/typings/@scope/b/index.d.ts", "/project/typings/a/index.d.ts"]
+    );
+    assert!(missing.is_empty());
+}
+
+#[test]
+fn types_filter() {
+    let (loaded, missing) = load(
+        &[
+            ("/project/node_modules/@types/a/index.d.ts", TYPING),
+            ("/project/node_modules/@types/b/index.d.ts", TYPING),
+            ("/project/node_modules/@types/scope__c/index.d.ts", TYPING),
+            // Packages in `node_modules` are found like imports.
+            ("/project/node_modules/d/package.json", r#"{ "types": "lib/d.d.ts" }"#),
+            ("/project/node_modules/d/lib/d.d.ts", TYPING),
+        ],
+        None,
+        Some(&["a", "@scope/c", "d"]),
+    );
+
+    assert_eq!(
+        loaded,
+        vec![
+            "/project/node_modules/@types/a/index.d.ts",
+            "/project/node_modules/@types/scope__c/index.d.ts",
+            "/project/node_modules/d/lib/d.d.ts",
+        ]
+    );
+    assert!(missing.is_empty());
+}
+
+#[test]
+fn missing_types() {
+    let (loaded, missing) = load(
+        &[
+            ("/project/node_modules/@types/a/index.d.ts", TYPING),
+            // A package without typings.
+            ("/project/node_modules/@types/b/README.md", ""),
+        ],
+        None,
+        Some(&["missing", "a", "@scope/missing", "b"]),
+    );
+
+    assert_eq!(loaded, vec!["/project/node_modules/@types/a/index.d.ts"]);
+    assert_eq!(missing, vec!["missing", "@scope/missing", "b"]);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use self::common::TestProject;
+
+/// Checks `main` next to the UMD library `/project/lib.d.ts`.
+fn check(main: &str) -> Vec<usize> {
+    TestProject::new(&[
+        ("/project/main.ts", main),
+        (
+            "/project/lib.d.ts",
+            "export declare const value: number;\nexport as namespace Lib;\n",
+        ),
+    ])
+    .error_codes()
+}
+
+#[test]
+fn umd_global_in_script() {
+    // `number` is not assignable to `string`.
+    assert_eq!(
+        check("/// <reference path=\"./lib.d.ts\" />\nconst v: string = Lib.value;\n"),
+        vec![2322]
+    );
+}
+
+#[test]
+fn umd_global_in_module() {
+    assert_eq!(
+        check("/// <reference path=\"./lib.d.ts\" />\nexport const v: number = Lib.value;\n"),
+        vec![2686]
+    );
+}
+
+#[test]
+fn umd_library_is_importable() {
+    assert_eq!(
+        check("import * as lib from './lib';\nexport const v: number = lib.value;\n"),
+        Vec::<usize>::new()
+    );
+}
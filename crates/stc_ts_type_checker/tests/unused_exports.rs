@@ -0,0 +1,61 @@
+mod common;
+
+use std::sync::Arc;
+
+use stc_ts_env::Rule;
+use stc_ts_errors::ErrorKind;
+
+use self::common::{file_name, TestProject};
+
+/// Checks `/project/main.ts` and returns the names of unused exports with
+/// their files.
+fn unused_exports(no_unused_exports: bool) -> Vec<(String, String)> {
+    let project = TestProject::new(&[
+        (
+            "/project/main.ts",
+            "import { used, Used } from './a';\nimport * as b from './b';\nexport const entry = used;\nlet u: Used;\n",
+        ),
+        (
+            "/project/a.ts",
+            "export const used = 1;\nexport const unused = 2;\nexport interface Used {}\nexport { reexported } from './c';\n",
+        ),
+        ("/project/b.ts", "export const all = 1;\n"),
+        ("/project/c.ts", "export const reexported = 1;\nexport function other() {}\n"),
+    ])
+    .with_rule(Rule {
+        no_unused_exports,
+        ..Default::default()
+    });
+
+    project.run(|cm, mut checker| {
+        checker.check(Arc::new(file_name("/project/main.ts")));
+
+        let mut result = vec![];
+        for err in ErrorKind::flatten(checker.take_errors()) {
+            match &*err {
+                ErrorKind::UnusedExport { span, name } => result.push((cm.span_to_filename(*span).to_string(), name.to_string())),
+                err => panic!("unexpected error: {:?}", err),
+            }
+        }
+        result.sort();
+        result
+    })
+}
+
+#[test]
+fn unused_exports_are_allowed_by_default() {
+    assert_eq!(unused_exports(false), vec![]);
+}
+
+#[test]
+fn no_unused_exports() {
+    // Exports of the entry and files imported as namespaces are used.
+    assert_eq!(
+        unused_exports(true),
+        vec![
+            ("/project/a.ts".to_string(), "reexported".to_string()),
+            ("/project/a.ts".to_string(), "unused".to_string()),
+            ("/project/c.ts".to_string(), "other".to_string()),
+        ]
+    );
+}
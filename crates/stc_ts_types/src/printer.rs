@@ -0,0 +1,592 @@
+//! Printing types as typescript types.
+
+use std::fmt::Write;
+
+use stc_ts_ast_rnode::{RExpr, RMemberProp, RPat, RTsEntityName, RTsLit, RTsModuleName, RTsThisTypeOrIdent};
+use swc_ecma_ast::{TruePlusMinus, TsKeywordTypeKind, TsTypeOperatorOp};
+
+use crate::{
+    FnParam, IntrinsicKind, Key, QueryExpr, TplType, TupleElement, Type, TypeElement, TypeParam, TypeParamDecl, TypeParamInstantiation,
+};
+
+/// The default of [TypePrinter::with_max_len], which is the default of
+/// `defaultMaximumTruncationLength` of tsc.
+const DEFAULT_MAX_LEN: usize = 160;
+
+/// Prints types as typescript types, like tsc does in error messages.
+///
+/// References to type aliases, interfaces and classes are printed by name, and
+/// aliases are not expanded.
+#[derive(Debug, Clone, Copy)]
+pub struct TypePrinter {
+    max_len: Option<usize>,
+}
+
+impl Default for TypePrinter {
+    fn default() -> Self {
+        Self {
+            max_len: Some(DEFAULT_MAX_LEN),
+        }
+    }
+}
+
+impl TypePrinter {
+    /// If the output gets longer than `max_len`, remaining members of unions,
+    /// intersections, tuples and type literals are elided as `... N more ...`.
+    /// The output is truncated with `...` if it's still longer than twice of
+    /// `max_len`, like tsc.
+    ///
+    /// [None] disables truncation.
+    pub fn with_max_len(mut self, max_len: Option<usize>) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    pub fn print(&self, ty: &Type) -> String {
+        let mut p = Printer {
+            max_len: self.max_len,
+            out: String::new(),
+        };
+        p.ty(ty, Prec::Top);
+
+        let mut out = p.out;
+        if let Some(max_len) = self.max_len.map(|max_len| max_len * 2) {
+            if out.len() > max_len {
+                let mut end = max_len.saturating_sub(3);
+                while !out.is_char_boundary(end) {
+                    end -= 1;
+                }
+                out.truncate(end);
+                out.push_str("...");
+            }
+        }
+        out
+    }
+
+    /// Prints a parameter of a function, like `a?: string`.
+    pub fn print_param(&self, param: &FnParam) -> String {
+        let mut p = Printer {
+            max_len: self.max_len,
+            out: String::new(),
+        };
+        p.param(param);
+        p.out
+    }
+
+    /// Prints type parameters, like `<T extends string>`. Returns an empty
+    /// string for [None].
+    pub fn print_type_params(&self, decl: Option<&TypeParamDecl>) -> String {
+        let mut p = Printer {
+            max_len: self.max_len,
+            out: String::new(),
+        };
+        p.type_params(decl);
+        p.out
+    }
+}
+
+/// Prints `ty` with the default options.
+pub fn print_type(ty: &Type) -> String {
+    TypePrinter::default().print(ty)
+}
+
+/// The context of a type, which decides if the type needs parens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Prec {
+    Top,
+    Union,
+    Intersection,
+    Operator,
+    /// The element of an array type or the object of an indexed access type.
+    Postfix,
+}
+
+fn prec_of(ty: &Type) -> Prec {
+    match ty.normalize() {
+        Type::Function(..) | Type::Constructor(..) | Type::Conditional(..) => Prec::Top,
+        Type::Union(u) if u.types.len() > 1 => Prec::Union,
+        Type::Intersection(i) if i.types.len() > 1 => Prec::Intersection,
+        Type::Operator(..) | Type::Infer(..) => Prec::Operator,
+        _ => Prec::Postfix,
+    }
+}
+
+struct Printer {
+    max_len: Option<usize>,
+    out: String,
+}
+
+impl Printer {
+    fn is_too_long(&self) -> bool {
+        matches!(self.max_len, Some(max_len) if self.out.len() > max_len)
+    }
+
+    /// Prints `items` separated by `sep`, eliding the remaining items if the
+    /// output gets too long.
+    fn list<T>(&mut self, items: &[T], sep: &str, mut op: impl FnMut(&mut Self, &T)) {
+        for (i, item) in items.iter().enumerate() {
+            if i != 0 {
+                self.out.push_str(sep);
+
+                if self.is_too_long() {
+                    write!(self.out, "... {} more ...", items.len() - i).unwrap();
+                    return;
+                }
+            }
+
+            op(self, item);
+        }
+    }
+
+    fn ty(&mut self, ty: &Type, prec: Prec) {
+        let ty = ty.normalize();
+
+        if prec_of(ty) < prec {
+            self.out.push('(');
+            self.ty(ty, Prec::Top);
+            self.out.push(')');
+            return;
+        }
+
+        match ty {
+            Type::Instance(t) => self.ty(&t.ty, prec),
+            Type::StaticThis(..) | Type::This(..) => self.out.push_str("this"),
+            Type::Lit(t) => self.lit(&t.lit),
+            Type::Query(t) => {
+                self.out.push_str("typeof ");
+                match &*t.expr {
+                    QueryExpr::TsEntityName(name) => self.entity_name(name),
+                    QueryExpr::Import(import) => self.import(&import.arg.value, import.qualifier.as_ref(), import.type_params.as_deref()),
+                }
+            }
+            Type::Infer(t) => {
+                self.out.push_str("infer ");
+                self.type_param(&t.type_param);
+            }
+            Type::Import(t) => self.import(&t.arg.value, t.qualifier.as_ref(), t.type_params.as_deref()),
+            Type::Predicate(t) => {
+                if t.asserts {
+                    self.out.push_str("asserts ");
+                }
+                match &t.param_name {
+                    RTsThisTypeOrIdent::TsThisType(..) => self.out.push_str("this"),
+                    RTsThisTypeOrIdent::Ident(i) => self.out.push_str(&i.sym),
+                }
+                if let Some(ty) = &t.ty {
+                    self.out.push_str(" is ");
+                    self.ty(ty, Prec::Top);
+                }
+            }
+            Type::IndexedAccessType(t) => {
+                self.ty(&t.obj_type, Prec::Postfix);
+                self.out.push('[');
+                self.ty(&t.index_type, Prec::Top);
+                self.out.push(']');
+            }
+            Type::Ref(t) => {
+                self.entity_name(&t.type_name);
+                if let Some(args) = &t.type_args {
+                    self.type_args(args);
+                }
+            }
+            Type::TypeLit(t) => self.members(&t.members),
+            Type::Keyword(t) => self.out.push_str(keyword(t.kind)),
+            Type::Conditional(t) => {
+                self.ty(&t.check_type, Prec::Union);
+                self.out.push_str(" extends ");
+                self.ty(&t.extends_type, Prec::Union);
+                self.out.push_str(" ? ");
+                self.ty(&t.true_type, Prec::Top);
+                self.out.push_str(" : ");
+                self.ty(&t.false_type, Prec::Top);
+            }
+            Type::Tuple(t) => {
+                self.out.push('[');
+                self.list(&t.elems, ", ", |p, elem| p.tuple_element(elem));
+                self.out.push(']');
+            }
+            Type::Array(t) => {
+                self.ty(&t.elem_type, Prec::Postfix);
+                self.out.push_str("[]");
+            }
+            Type::Union(t) => match &*t.types {
+                [] => self.out.push_str("never"),
+                [ty] => self.ty(ty, prec),
+                types => self.list(types, " | ", |p, ty| p.ty(ty, Prec::Union)),
+            },
+            Type::Intersection(t) => match &*t.types {
+                [] => self.out.push_str("unknown"),
+                [ty] => self.ty(ty, prec),
+                types => self.list(types, " & ", |p, ty| p.ty(ty, Prec::Intersection)),
+            },
+            Type::Function(t) => {
+                self.type_params(t.type_params.as_ref());
+                self.params(&t.params);
+                self.out.push_str(" => ");
+                self.ty(&t.ret_ty, Prec::Top);
+            }
+            Type::Constructor(t) => {
+                if t.is_abstract {
+                    self.out.push_str("abstract ");
+                }
+                self.out.push_str("new ");
+                self.type_params(t.type_params.as_ref());
+                self.params(&t.params);
+                self.out.push_str(" => ");
+                self.ty(&t.type_ann, Prec::Top);
+            }
+            Type::Operator(t) => {
+                self.out.push_str(match t.op {
+                    TsTypeOperatorOp::KeyOf => "keyof ",
+                    TsTypeOperatorOp::Unique => "unique ",
+                    TsTypeOperatorOp::ReadOnly => "readonly ",
+                });
+                self.ty(&t.ty, Prec::Operator);
+            }
+            Type::Param(t) => self.out.push_str(t.name.sym()),
+            Type::EnumVariant(t) => {
+                self.out.push_str(t.enum_name.sym());
+                if let Some(name) = &t.name {
+                    write!(self.out, ".{}", name).unwrap();
+                }
+            }
+            Type::Interface(t) => self.out.push_str(t.name.sym()),
+            Type::Enum(t) => self.out.push_str(&t.id.sym),
+            Type::Mapped(t) => {
+                self.out.push_str("{ ");
+                match t.readonly {
+                    Some(TruePlusMinus::True) => self.out.push_str("readonly "),
+                    Some(TruePlusMinus::Plus) => self.out.push_str("+readonly "),
+                    Some(TruePlusMinus::Minus) => self.out.push_str("-readonly "),
+                    None => {}
+                }
+                write!(self.out, "[{} in ", t.type_param.name.sym()).unwrap();
+                match &t.type_param.constraint {
+                    Some(constraint) => self.ty(constraint, Prec::Top),
+                    None => self.out.push_str("string"),
+                }
+                if let Some(name_type) = &t.name_type {
+                    self.out.push_str(" as ");
+                    self.ty(name_type, Prec::Top);
+                }
+                self.out.push(']');
+                match t.optional {
+                    Some(TruePlusMinus::True) => self.out.push('?'),
+                    Some(TruePlusMinus::Plus) => self.out.push_str("+?"),
+                    Some(TruePlusMinus::Minus) => self.out.push_str("-?"),
+                    None => {}
+                }
+                self.out.push_str(": ");
+                match &t.ty {
+                    Some(ty) => self.ty(ty, Prec::Top),
+                    None => self.out.push_str("any"),
+                }
+                self.out.push_str("; }");
+            }
+            Type::Alias(t) => self.ty(&t.ty, prec),
+            Type::Namespace(t) => write!(self.out, "typeof {}", t.name.sym()).unwrap(),
+            Type::Module(t) => match &t.name {
+                RTsModuleName::Ident(i) => write!(self.out, "typeof {}", i.sym).unwrap(),
+                RTsModuleName::Str(s) => {
+                    self.out.push_str("typeof ");
+                    self.import(&s.value, None, None);
+                }
+            },
+            Type::Class(t) => match &t.def.name {
+                Some(name) => self.out.push_str(name.sym()),
+                None => self.out.push_str("(Anonymous class)"),
+            },
+            Type::ClassDef(t) => match &t.name {
+                Some(name) => write!(self.out, "typeof {}", name.sym()).unwrap(),
+                None => self.out.push_str("typeof (Anonymous class)"),
+            },
+            Type::Arc(t) => self.ty(&t.ty, prec),
+            Type::Rest(t) => {
+                self.out.push_str("...");
+                self.ty(&t.ty, Prec::Postfix);
+            }
+            Type::Optional(t) => {
+                self.ty(&t.ty, Prec::Postfix);
+                self.out.push('?');
+            }
+            Type::Symbol(..) => self.out.push_str("unique symbol"),
+            Type::Tpl(t) => self.tpl(t),
+            Type::StringMapping(t) => {
+                self.out.push_str(match t.kind {
+                    IntrinsicKind::Uppercase => "Uppercase",
+                    IntrinsicKind::Lowercase => "Lowercase",
+                    IntrinsicKind::Capitalize => "Capitalize",
+                    IntrinsicKind::Uncapitalize => "Uncapitalize",
+                });
+                self.type_args(&t.type_args);
+            }
+        }
+    }
+
+    fn lit(&mut self, lit: &RTsLit) {
+        match lit {
+            RTsLit::BigInt(v) => write!(self.out, "{}n", v.value).unwrap(),
+            RTsLit::Number(v) => self.out.push_str(&number(v.value)),
+            RTsLit::Str(v) => self.str(&v.value),
+            RTsLit::Bool(v) => write!(self.out, "{}", v.value).unwrap(),
+            // Template literal types are represented as [TplType].
+            RTsLit::Tpl(..) => self.out.push_str("string"),
+        }
+    }
+
+    fn str(&mut self, s: &str) {
+        self.out.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                _ => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+
+    fn tpl(&mut self, t: &TplType) {
+        self.out.push('`');
+        for (i, quasi) in t.quasis.iter().enumerate() {
+            self.out.push_str(&quasi.value);
+            if let Some(ty) = t.types.get(i) {
+                self.out.push_str("${");
+                self.ty(ty, Prec::Top);
+                self.out.push('}');
+            }
+        }
+        self.out.push('`');
+    }
+
+    fn entity_name(&mut self, name: &RTsEntityName) {
+        match name {
+            RTsEntityName::Ident(i) => self.out.push_str(&i.sym),
+            RTsEntityName::TsQualifiedName(q) => {
+                self.entity_name(&q.left);
+                write!(self.out, ".{}", q.right.sym).unwrap();
+            }
+        }
+    }
+
+    fn import(&mut self, src: &str, qualifier: Option<&RTsEntityName>, type_args: Option<&TypeParamInstantiation>) {
+        self.out.push_str("import(");
+        self.str(src);
+        self.out.push(')');
+        if let Some(qualifier) = qualifier {
+            self.out.push('.');
+            self.entity_name(qualifier);
+        }
+        if let Some(args) = type_args {
+            self.type_args(args);
+        }
+    }
+
+    fn type_args(&mut self, args: &TypeParamInstantiation) {
+        self.out.push('<');
+        self.list(&args.params, ", ", |p, ty| p.ty(ty, Prec::Top));
+        self.out.push('>');
+    }
+
+    fn type_params(&mut self, decl: Option<&TypeParamDecl>) {
+        if let Some(decl) = decl {
+            self.out.push('<');
+            self.list(&decl.params, ", ", |p, param| p.type_param(param));
+            self.out.push('>');
+        }
+    }
+
+    fn type_param(&mut self, param: &TypeParam) {
+        self.out.push_str(param.name.sym());
+        if let Some(constraint) = &param.constraint {
+            self.out.push_str(" extends ");
+            self.ty(constraint, Prec::Top);
+        }
+        if let Some(default) = &param.default {
+            self.out.push_str(" = ");
+            self.ty(default, Prec::Top);
+        }
+    }
+
+    fn params(&mut self, params: &[FnParam]) {
+        self.out.push('(');
+        self.list(params, ", ", |p, param| p.param(param));
+        self.out.push(')');
+    }
+
+    fn param(&mut self, param: &FnParam) {
+        fn name(pat: &RPat) -> Option<&str> {
+            match pat {
+                RPat::Ident(i) => Some(&*i.id.sym),
+                RPat::Assign(a) => name(&a.left),
+                _ => None,
+            }
+        }
+
+        match &param.pat {
+            RPat::Rest(rest) => {
+                write!(self.out, "...{}", name(&rest.arg).unwrap_or("args")).unwrap();
+            }
+            pat => {
+                // tsc uses the same name for destructuring patterns.
+                self.out.push_str(name(pat).unwrap_or("__0"));
+                if !param.required {
+                    self.out.push('?');
+                }
+            }
+        }
+
+        self.out.push_str(": ");
+        self.ty(&param.ty, Prec::Top);
+    }
+
+    fn tuple_element(&mut self, elem: &TupleElement) {
+        let label = match &elem.label {
+            Some(RPat::Ident(i)) => &i.id.sym,
+            _ => return self.ty(&elem.ty, Prec::Top),
+        };
+
+        match elem.ty.normalize() {
+            Type::Optional(t) => {
+                write!(self.out, "{}?: ", label).unwrap();
+                self.ty(&t.ty, Prec::Top);
+            }
+            Type::Rest(t) => {
+                write!(self.out, "...{}: ", label).unwrap();
+                self.ty(&t.ty, Prec::Top);
+            }
+            ty => {
+                write!(self.out, "{}: ", label).unwrap();
+                self.ty(ty, Prec::Top);
+            }
+        }
+    }
+
+    fn members(&mut self, members: &[TypeElement]) {
+        if members.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.out.push_str("{ ");
+        self.list(members, " ", |p, member| {
+            p.member(member);
+            p.out.push(';');
+        });
+        if self.out.ends_with("...") {
+            self.out.push(';');
+        }
+        self.out.push_str(" }");
+    }
+
+    fn member(&mut self, member: &TypeElement) {
+        match member {
+            TypeElement::Call(m) => {
+                self.type_params(m.type_params.as_ref());
+                self.params(&m.params);
+                self.ret_ty(m.ret_ty.as_deref());
+            }
+            TypeElement::Constructor(m) => {
+                self.out.push_str("new ");
+                self.type_params(m.type_params.as_ref());
+                self.params(&m.params);
+                self.ret_ty(m.ret_ty.as_deref());
+            }
+            TypeElement::Property(m) => {
+                if m.readonly {
+                    self.out.push_str("readonly ");
+                }
+                self.key(&m.key);
+                if m.optional {
+                    self.out.push('?');
+                }
+                self.ret_ty(m.type_ann.as_deref());
+            }
+            TypeElement::Method(m) => {
+                if m.readonly {
+                    self.out.push_str("readonly ");
+                }
+                self.key(&m.key);
+                if m.optional {
+                    self.out.push('?');
+                }
+                self.type_params(m.type_params.as_ref());
+                self.params(&m.params);
+                self.ret_ty(m.ret_ty.as_deref());
+            }
+            TypeElement::Index(m) => {
+                if m.readonly {
+                    self.out.push_str("readonly ");
+                }
+                self.out.push('[');
+                self.list(&m.params, ", ", |p, param| p.param(param));
+                self.out.push(']');
+                self.ret_ty(m.type_ann.as_deref());
+            }
+        }
+    }
+
+    /// Prints `: any` if `ty` is [None], like tsc.
+    fn ret_ty(&mut self, ty: Option<&Type>) {
+        self.out.push_str(": ");
+        match ty {
+            Some(ty) => self.ty(ty, Prec::Top),
+            None => self.out.push_str("any"),
+        }
+    }
+
+    fn key(&mut self, key: &Key) {
+        match key {
+            Key::Normal { sym, .. } => self.out.push_str(sym),
+            Key::Num(n) => self.out.push_str(&number(n.value)),
+            Key::BigInt(n) => write!(self.out, "{}", n.value).unwrap(),
+            Key::Private(name) => write!(self.out, "#{}", name.id.sym()).unwrap(),
+            Key::Computed(key) => {
+                self.out.push('[');
+                match &*key.expr {
+                    RExpr::Ident(i) => self.out.push_str(&i.sym),
+                    RExpr::Member(m) => match (&*m.obj, &m.prop) {
+                        (RExpr::Ident(obj), RMemberProp::Ident(prop)) => write!(self.out, "{}.{}", obj.sym, prop.sym).unwrap(),
+                        _ => self.ty(&key.ty, Prec::Top),
+                    },
+                    _ => self.ty(&key.ty, Prec::Top),
+                }
+                self.out.push(']');
+            }
+        }
+    }
+}
+
+fn keyword(kind: TsKeywordTypeKind) -> &'static str {
+    match kind {
+        TsKeywordTypeKind::TsAnyKeyword => "any",
+        TsKeywordTypeKind::TsUnknownKeyword => "unknown",
+        TsKeywordTypeKind::TsNumberKeyword => "number",
+        TsKeywordTypeKind::TsObjectKeyword => "object",
+        TsKeywordTypeKind::TsBooleanKeyword => "boolean",
+        TsKeywordTypeKind::TsBigIntKeyword => "bigint",
+        TsKeywordTypeKind::TsStringKeyword => "string",
+        TsKeywordTypeKind::TsSymbolKeyword => "symbol",
+        TsKeywordTypeKind::TsVoidKeyword => "void",
+        TsKeywordTypeKind::TsUndefinedKeyword => "undefined",
+        TsKeywordTypeKind::TsNullKeyword => "null",
+        TsKeywordTypeKind::TsNeverKeyword => "never",
+        TsKeywordTypeKind::TsIntrinsicKeyword => "intrinsic",
+    }
+}
+
+/// Formats `v` like javascript.
+fn number(v: f64) -> String {
+    if v.is_nan() {
+        "NaN".into()
+    } else if v == f64::INFINITY {
+        "Infinity".into()
+    } else if v == f64::NEG_INFINITY {
+        "-Infinity".into()
+    } else {
+        v.to_string()
+    }
+}
@@ -0,0 +1,79 @@
+use stc_ts_ast_rnode::{RStr, RTsLit};
+use stc_ts_types::{
+    printer::{print_type, TypePrinter},
+    Array, Function, KeywordType, LitType, Type, Union,
+};
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::TsKeywordTypeKind;
+
+fn kwd(kind: TsKeywordTypeKind) -> Type {
+    Type::Keyword(KeywordType {
+        span: DUMMY_SP,
+        kind,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+fn str_lit(value: &str) -> Type {
+    Type::Lit(LitType {
+        span: DUMMY_SP,
+        lit: RTsLit::Str(RStr {
+            span: DUMMY_SP,
+            value: value.into(),
+            raw: None,
+        }),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+fn union(types: Vec<Type>) -> Type {
+    Type::Union(Union {
+        span: DUMMY_SP,
+        types,
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+fn array(elem_type: Type) -> Type {
+    Type::Array(Array {
+        span: DUMMY_SP,
+        elem_type: Box::new(elem_type),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    })
+}
+
+#[test]
+fn parens() {
+    let func = Type::Function(Function {
+        span: DUMMY_SP,
+        type_params: None,
+        params: vec![],
+        ret_ty: Box::new(kwd(TsKeywordTypeKind::TsNumberKeyword)),
+        metadata: Default::default(),
+        tracker: Default::default(),
+    });
+
+    assert_eq!(print_type(&array(func)), "(() => number)[]");
+    assert_eq!(
+        print_type(&array(union(vec![kwd(TsKeywordTypeKind::TsStringKeyword), str_lit("a\"b")]))),
+        "(string | \"a\\\"b\")[]"
+    );
+}
+
+#[test]
+fn elision() {
+    let ty = union((0..10).map(|i| str_lit(&format!("a{}", i))).collect());
+
+    assert_eq!(
+        TypePrinter::default().with_max_len(Some(20)).print(&ty),
+        "\"a0\" | \"a1\" | \"a2\" | ... 7 more ..."
+    );
+    assert_eq!(
+        TypePrinter::default().with_max_len(None).print(&ty),
+        (0..10).map(|i| format!("\"a{}\"", i)).collect::<Vec<_>>().join(" | ")
+    );
+}
@@ -13,10 +13,14 @@ use once_cell::sync::Lazy;
 use swc_common::SyntaxContext;
 
 pub mod cache;
+pub mod cancel;
 pub mod error;
 pub mod ext;
 pub mod panic_context;
+pub mod profile;
+pub mod spelling;
 pub mod stack;
+pub mod time;
 
 pub type ABuilderHasher = ahash::RandomState;
 
@@ -0,0 +1,113 @@
+//! Recording of the time taken by each phase of a check, which can be written
+//! as a trace for `chrome://tracing` or Perfetto.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use serde_json::json;
+
+use crate::time::Instant;
+
+/// Collects [TraceEvent]s. Cloned profilers share the events.
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    start: Instant,
+    events: Mutex<Vec<TraceEvent>>,
+}
+
+/// A phase of the check, like parsing of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEvent {
+    /// The kind of the phase, like `parse` or `check`.
+    pub cat: &'static str,
+    /// Usually the file or the declaration the phase worked on.
+    pub name: String,
+    /// Relative to the creation of the [Profiler].
+    pub start: Duration,
+    pub duration: Duration,
+    /// A number identifying the thread which ran the phase.
+    pub thread: u64,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Profiler {
+            inner: Arc::new(Inner {
+                start: Instant::now(),
+                events: Default::default(),
+            }),
+        }
+    }
+
+    /// Records a phase which started at `start` and ends now.
+    pub fn record(&self, cat: &'static str, name: impl Into<String>, start: Instant) {
+        let event = TraceEvent {
+            cat,
+            name: name.into(),
+            start: start.saturating_duration_since(self.inner.start),
+            duration: start.elapsed(),
+            thread: current_thread(),
+        };
+
+        self.inner.events.lock().unwrap().push(event);
+    }
+
+    /// Returns the recorded events, sorted by the start.
+    pub fn events(&self) -> Vec<TraceEvent> {
+        let mut events = self.inner.events.lock().unwrap().clone();
+        events.sort_by_key(|event| event.start);
+        events
+    }
+
+    /// Returns the events in the trace event format of `chrome://tracing`.
+    pub fn to_chrome_trace(&self) -> String {
+        let events = self
+            .events()
+            .into_iter()
+            .map(|event| {
+                json!({
+                    "name": event.name,
+                    "cat": event.cat,
+                    "ph": "X",
+                    "ts": event.start.as_secs_f64() * 1e6,
+                    "dur": event.duration.as_secs_f64() * 1e6,
+                    "pid": 1,
+                    "tid": event.thread,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+        .to_string()
+    }
+}
+
+/// [std::thread::ThreadId] can't be converted to a number on stable.
+fn current_thread() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    thread_local! {
+        static THREAD: u64 = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    THREAD.with(|thread| *thread)
+}
@@ -0,0 +1,64 @@
+//! Suggestions for misspelled names.
+
+/// The Levenshtein distance between `a` and `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    weighted_distance(a, b, 1, 1, 1)
+}
+
+/// Returns the candidate most similar to `name`, if it's similar enough to be
+/// a misspelling of `name`. Candidates equal to `name` are ignored, and the
+/// first one wins ties.
+///
+/// This follows `getSpellingSuggestion` of tsc, so a change of the case is
+/// cheaper than other changes, and short candidates are suggested only if
+/// they differ in the case.
+pub fn spelling_suggestion<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let len = name.chars().count();
+    let max_length_difference = (len * 34 / 100).max(2);
+    // Distances are multiplied by 10, as a change of the case costs 0.1.
+    let mut best_distance = (len * 4 / 10 + 1) * 10;
+    let mut best = None;
+
+    for candidate in candidates {
+        let candidate_len = candidate.chars().count();
+        if candidate == name || candidate_len.abs_diff(len) > max_length_difference {
+            continue;
+        }
+        if candidate_len < 3 && candidate.to_lowercase() != name.to_lowercase() {
+            continue;
+        }
+
+        let distance = weighted_distance(name, candidate, 10, 20, 1);
+        if distance < best_distance {
+            best_distance = distance;
+            best = Some(candidate);
+        }
+    }
+
+    best
+}
+
+/// The Levenshtein distance where inserting or deleting a character costs
+/// `indel`, and replacing it costs `case` if only the case is changed, or
+/// `substitution` otherwise.
+fn weighted_distance(a: &str, b: &str, indel: usize, substitution: usize, case: usize) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).map(|j| j * indel).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![(i + 1) * indel];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb {
+                0
+            } else if ca.to_lowercase().eq(cb.to_lowercase()) {
+                case
+            } else {
+                substitution
+            };
+            cur.push((prev[j] + cost).min(prev[j + 1] + indel).min(cur[j] + indel));
+        }
+        prev = cur;
+    }
+
+    prev[b.len()]
+}
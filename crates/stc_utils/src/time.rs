@@ -0,0 +1,8 @@
+//! [std::time::Instant::now] panics on `wasm32-unknown-unknown`, so use
+//! [Instant] of this module instead.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+
+#[cfg(target_arch = "wasm32")]
+pub use instant::Instant;
@@ -0,0 +1,94 @@
+//! WebAssembly binding of stc, which allows running the checker without a
+//! backend, like in a playground in the browser.
+//!
+//! Files are read from a virtual file system which contains only the files
+//! passed to [check], and the builtin libraries are bundled.
+
+use std::{collections::HashMap, io, path::PathBuf, sync::Arc};
+
+use serde::Deserialize;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{fs::MemoryFs, resolvers::node::NodeResolver};
+use stc_ts_type_checker::{loader::ModuleLoader, program::Diagnostic, Checker};
+use swc_common::{
+    errors::{EmitterWriter, Handler},
+    FileName, Globals, SourceMap, GLOBALS,
+};
+use swc_ecma_ast::EsVersion;
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CheckOptions {
+    /// The path of `source`. Defaults to `/input.ts`.
+    pub file_name: Option<String>,
+    /// Other files which can be imported by `source`, keyed by the absolute
+    /// path.
+    pub files: HashMap<String, String>,
+    /// The builtin libraries to load. Defaults to `es5`.
+    pub libs: Option<Vec<String>>,
+    pub strict_null_checks: bool,
+    pub no_implicit_any: bool,
+}
+
+/// Checks `source` and returns the errors as an array of [Diagnostic].
+///
+/// `options` is an object like [CheckOptions], and can be omitted.
+#[wasm_bindgen]
+pub fn check(source: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options: CheckOptions = if options.is_undefined() || options.is_null() {
+        Default::default()
+    } else {
+        serde_wasm_bindgen::from_value(options)?
+    };
+
+    let diagnostics = check_source(source, options);
+
+    Ok(serde_wasm_bindgen::to_value(&diagnostics)?)
+}
+
+/// [check] for rust callers.
+pub fn check_source(source: &str, options: CheckOptions) -> Vec<Diagnostic> {
+    GLOBALS.set(&Globals::new(), || check_in_globals(source, options))
+}
+
+fn check_in_globals(source: &str, options: CheckOptions) -> Vec<Diagnostic> {
+    let file_name = PathBuf::from(options.file_name.as_deref().unwrap_or("/input.ts"));
+
+    let fs = Arc::new(MemoryFs::new());
+    for (path, content) in options.files {
+        fs.insert(PathBuf::from(path), content);
+    }
+    fs.insert(file_name.clone(), source);
+
+    let cm = Arc::new(SourceMap::default());
+    // Errors are returned instead of being printed.
+    let handler = Arc::new(Handler::with_emitter(
+        false,
+        false,
+        Box::new(EmitterWriter::new(Box::new(io::sink()), Some(cm.clone()), false, false)),
+    ));
+
+    let mut libs = match &options.libs {
+        Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+        None => Lib::load("es5"),
+    };
+    libs.sort();
+    libs.dedup();
+
+    let rule = Rule {
+        strict_null_checks: options.strict_null_checks,
+        no_implicit_any: options.no_implicit_any,
+        ..Default::default()
+    };
+    let env = Env::simple(rule, EsVersion::latest(), ModuleConfig::None, &libs);
+    let resolver = NodeResolver::new().with_fs(fs.clone());
+
+    let checker = Checker::new(cm.clone(), handler, env.clone(), None, ModuleLoader::with_fs(cm, env, resolver, fs));
+
+    let program = checker.check(Arc::new(FileName::Real(file_name)));
+
+    program.errors().map(|err| checker.to_diagnostic(err)).collect()
+}
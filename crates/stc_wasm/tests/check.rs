@@ -0,0 +1,22 @@
+use stc_wasm::{check_source, CheckOptions};
+
+#[test]
+fn reports_errors_of_source() {
+    let diagnostics = check_source("const a: string = 1;\n", Default::default());
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].file.as_deref(), Some("/input.ts"));
+    assert_eq!(diagnostics[0].code, 2322);
+    assert_eq!(diagnostics[0].line, 1);
+}
+
+#[test]
+fn resolves_virtual_files() {
+    let options = CheckOptions {
+        files: [("/a.ts".to_string(), "export const a = 1;\n".to_string())].into_iter().collect(),
+        ..Default::default()
+    };
+    let diagnostics = check_source("import { a } from './a';\nconst b: string = a;\n", options);
+
+    assert_eq!(diagnostics.iter().map(|d| (d.code, d.line)).collect::<Vec<_>>(), vec![(2322, 2)]);
+}
@@ -0,0 +1,197 @@
+use std::{
+    io,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Error};
+use clap::Args;
+use stc_ts_config::{Project, TsConfig};
+use stc_ts_module_loader::{
+    fs::{FileSystem, RealFs},
+    resolvers::{cache::CachingResolver, Resolve},
+};
+use stc_ts_type_checker::{graph::ModuleNode, loader::ModuleLoader, Checker};
+use stc_utils::profile::Profiler;
+use swc_common::{
+    errors::{EmitterWriter, Handler},
+    FileName, SourceMap,
+};
+
+/// Phases reported by `stc bench`, which are recorded by [Profiler].
+const PHASES: &[&str] = &["parse", "bind", "check"];
+
+/// Check the files in a directory repeatedly, and print the time taken by
+/// each phase, the number of lines checked per second and the peak memory
+/// usage.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct BenchCommand {
+    /// The directory to check. The root files and the options of its
+    /// `tsconfig.json` are used if it exists, and all files in it are checked
+    /// otherwise.
+    pub dir: PathBuf,
+
+    /// The number of measured checks.
+    #[clap(long, default_value_t = 5)]
+    pub iterations: usize,
+
+    /// The number of checks run before the measured checks, which are not
+    /// measured.
+    #[clap(long, default_value_t = 1)]
+    pub warmup: usize,
+}
+
+/// The result of a check.
+struct Run {
+    time: Duration,
+    /// The sum of the durations of each phase, in the order of [PHASES].
+    /// Phases run in parallel are added up.
+    phases: Vec<Duration>,
+    files: Vec<ModuleNode>,
+    errors: usize,
+}
+
+impl BenchCommand {
+    pub fn run(self) -> Result<(), Error> {
+        if self.iterations == 0 {
+            bail!("`--iterations` should be at least 1");
+        }
+
+        let fs: Arc<dyn FileSystem> = Arc::new(RealFs);
+        let project = if fs.is_file(&self.dir.join("tsconfig.json")) {
+            Project::load(&*fs, &self.dir)?
+        } else {
+            Project {
+                path: self.dir.join("tsconfig.json"),
+                config: TsConfig::default(),
+                case_sensitive: !cfg!(any(windows, target_os = "macos")),
+            }
+        };
+        let entries = project.root_files(&*fs);
+        if entries.is_empty() {
+            bail!("no inputs were found in `{}`", self.dir.display());
+        }
+
+        for i in 0..self.warmup {
+            let run = check(&project, &fs, &entries)?;
+            eprintln!("Warmup {}: {:.2?}", i + 1, run.time);
+        }
+
+        let mut runs = vec![];
+        for i in 0..self.iterations {
+            let run = check(&project, &fs, &entries)?;
+            eprintln!("Iteration {}: {:.2?}", i + 1, run.time);
+            runs.push(run);
+        }
+
+        // Declaration files are not counted, like `lib.d.ts` and packages.
+        let lines = runs[0]
+            .files
+            .iter()
+            .filter(|file| !file.is_dts)
+            .filter_map(|file| std::fs::read_to_string(&file.path).ok())
+            .map(|content| content.lines().count())
+            .sum::<usize>();
+
+        let times = runs.iter().map(|run| run.time).collect::<Vec<_>>();
+        let mean = times.iter().sum::<Duration>() / runs.len() as u32;
+
+        println!(
+            "Checked {} files ({} lines, {} errors) {} times after {} warmup runs",
+            runs[0].files.len(),
+            lines,
+            runs[0].errors,
+            runs.len(),
+            self.warmup
+        );
+        println!(
+            "  time:        mean {:.2?}, min {:.2?}, max {:.2?}",
+            mean,
+            times.iter().min().unwrap(),
+            times.iter().max().unwrap()
+        );
+        println!("  throughput:  {:.0} lines/sec", lines as f64 / mean.as_secs_f64());
+        for (i, phase) in PHASES.iter().enumerate() {
+            let total = runs.iter().map(|run| run.phases[i]).sum::<Duration>();
+            println!("  {:<12} {:.2?}", format!("{}:", phase), total / runs.len() as u32);
+        }
+        match peak_memory() {
+            Some(bytes) => println!("  peak memory: {:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+            None => println!("  peak memory: unknown"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks `entries` with a new checker, so that nothing is cached.
+fn check(project: &Project, fs: &Arc<dyn FileSystem>, entries: &[PathBuf]) -> Result<Run, Error> {
+    let start = Instant::now();
+
+    let cm = Arc::new(SourceMap::default());
+    // Errors are counted instead of being printed.
+    let handler = Arc::new(Handler::with_emitter(
+        false,
+        false,
+        Box::new(EmitterWriter::new(Box::new(io::sink()), Some(cm.clone()), false, false)),
+    ));
+    let profiler = Profiler::new();
+
+    let env = project.env()?;
+    let options = project.options();
+    let resolver: Arc<dyn Resolve> = Arc::new(project.resolver(project.module_resolver(fs.clone())?));
+    let resolver = CachingResolver::new(resolver, fs.clone());
+
+    Checker::new(
+        cm.clone(),
+        handler.clone(),
+        env.clone(),
+        None,
+        ModuleLoader::new(cm.clone(), env.clone(), resolver.clone()).with_profiler(profiler.clone()),
+    )
+    .load_typings(project.dir(), options.type_roots.as_deref(), options.types.as_deref());
+
+    let mut checker = Checker::new(
+        cm.clone(),
+        handler,
+        env.clone(),
+        None,
+        ModuleLoader::new(cm, env, resolver).with_profiler(profiler.clone()),
+    )
+    .with_profiler(profiler.clone());
+    checker.check_all(entries.iter().map(|path| Arc::new(FileName::Real(path.clone()))).collect());
+    let errors = checker.take_errors().len();
+
+    let time = start.elapsed();
+
+    let events = profiler.events();
+    let phases = PHASES
+        .iter()
+        .map(|phase| {
+            events
+                .iter()
+                .filter(|event| event.cat == *phase)
+                .map(|event| event.duration)
+                .sum::<Duration>()
+        })
+        .collect();
+
+    Ok(Run {
+        time,
+        phases,
+        files: checker.module_graph().modules,
+        errors,
+    })
+}
+
+/// Returns the peak resident set size of the process in bytes. Only supported
+/// on Linux.
+fn peak_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kb = line.trim_start_matches("VmHWM:").trim().trim_end_matches("kB").trim();
+
+    kb.parse::<u64>().ok().map(|kb| kb * 1024)
+}
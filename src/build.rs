@@ -0,0 +1,147 @@
+//! `--build`, which checks a project and the projects it references in order,
+//! skipping projects which are up to date.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Error};
+use stc_ts_config::{build_order, Project};
+use stc_ts_errors::{FrameRenderer, Report};
+use stc_ts_module_loader::{
+    fs::{FileSystem, RealFs},
+    resolvers::{cache::CachingResolver, Resolve},
+};
+use stc_ts_type_checker::{build_info::BuildInfo, loader::ModuleLoader, Checker};
+use swc_common::{errors::Handler, FileName, SourceMap};
+
+use crate::emit::{emit, EmitOptions};
+
+/// Builds the project at `path` and the projects it references, and returns
+/// the number of errors.
+///
+/// Projects are checked after the projects they reference, and imports of
+/// referenced projects are resolved to their declaration outputs. If a
+/// project has no error, its outputs are written like [emit].
+///
+/// A project is up to date if none of the files it checked are modified since
+/// the last build and the last build had no error. Projects depending on a
+/// project with errors are skipped.
+pub fn build(cm: &Arc<SourceMap>, handler: &Arc<Handler>, renderer: &FrameRenderer, path: &Path) -> Result<usize, Error> {
+    let fs: Arc<dyn FileSystem> = Arc::new(RealFs);
+
+    let mut errors = 0;
+    // Projects with errors, including skipped projects.
+    let mut failed: Vec<PathBuf> = vec![];
+
+    for project in build_order(&*fs, path)? {
+        let references = project
+            .references()
+            .iter()
+            .map(|path| Project::load(&*fs, path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(reference) = references.iter().find(|reference| failed.contains(&reference.path)) {
+            log::warn!(
+                "Skipping build of `{}` because its dependency `{}` has errors",
+                project.path.display(),
+                reference.path.display()
+            );
+            failed.push(project.path.clone());
+            continue;
+        }
+
+        let entries = project.root_files(&*fs);
+        if entries.is_empty() {
+            // A config which only lists references, like the root of a monorepo.
+            continue;
+        }
+
+        let options = project.options();
+        for option in options.unsupported_options() {
+            eprintln!("warning: {}: {}", project.path.display(), option);
+        }
+        let env = project.env()?;
+        let libs = options.libs()?;
+        let build_info_options = format!(
+            "{:?}",
+            (
+                env.rule(),
+                env.target(),
+                env.module().to_string(),
+                &libs,
+                &options.type_roots,
+                &options.types
+            )
+        );
+
+        let emit_options = EmitOptions::new(options, project.dir());
+
+        let build_info_path = project.build_info_path();
+        let previous = std::fs::read_to_string(&build_info_path)
+            .ok()
+            .and_then(|s| BuildInfo::from_json(&s).ok())
+            .filter(|info| info.is_compatible(&build_info_options));
+        if let Some(previous) = previous {
+            let has_errors = previous.files.values().any(|file| !file.diagnostics.is_empty());
+            let outputs_exist = emit_options.no_emit
+                || !emit_options.declaration
+                || entries
+                    .iter()
+                    .filter_map(|entry| project.declaration_output(entry))
+                    .all(|output| fs.is_file(&output));
+
+            if !has_errors && outputs_exist && previous.affected_files(&*fs, &entries).is_empty() {
+                log::info!("Project `{}` is up to date", project.path.display());
+                continue;
+            }
+        }
+
+        log::info!("Building project `{}`", project.path.display());
+
+        let resolver: Arc<dyn Resolve> =
+            Arc::new(project.reference_resolver(project.resolver(project.module_resolver(fs.clone())?), fs.clone(), &references));
+        // Checkers for typings and the entries share the cache.
+        let resolver = CachingResolver::new(resolver, fs.clone());
+
+        let typing_errors = Checker::new(
+            cm.clone(),
+            handler.clone(),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm.clone(), env.clone(), resolver.clone()),
+        )
+        .load_typings(project.dir(), options.type_roots.as_deref(), options.types.as_deref());
+
+        let checker = Checker::new(
+            cm.clone(),
+            handler.clone(),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm.clone(), env, resolver),
+        );
+        checker.check_all(entries.iter().map(|path| Arc::new(FileName::Real(path.clone()))).collect());
+
+        let mut project_errors = typing_errors;
+        project_errors.extend(checker.take_errors());
+        for err in &project_errors {
+            eprint!("{}", renderer.render(cm, &Report::new(err)));
+        }
+        errors += project_errors.len();
+
+        // Outputs of a project with errors are not written, so that projects depending
+        // on it are not built with them.
+        if project_errors.is_empty() {
+            emit(&checker, &emit_options, false)?;
+        } else {
+            failed.push(project.path.clone());
+        }
+
+        let mut info = BuildInfo::new(build_info_options);
+        checker.update_build_info(&mut info, &project_errors, &entries);
+        std::fs::write(&build_info_path, info.to_json()?).with_context(|| format!("failed to write `{}`", build_info_path.display()))?;
+    }
+
+    Ok(errors)
+}
@@ -0,0 +1,76 @@
+//! `--changed-only`, which checks only the files affected by the changes in the
+//! git working tree.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Error};
+use stc_ts_type_checker::loader::LoadModule;
+use swc_common::FileName;
+
+/// Returns the files modified since `HEAD` in the repository containing `dir`,
+/// including staged and untracked files. Deleted files are not returned.
+pub fn changed_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let root = git(dir, &["rev-parse", "--show-toplevel"])?;
+    let root = PathBuf::from(root.trim());
+
+    let modified = git(dir, &["diff", "--name-only", "HEAD"])?;
+    let untracked = git(dir, &["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+
+    let mut files = modified
+        .lines()
+        .chain(untracked.lines())
+        .filter_map(|path| root.join(path).canonicalize().ok())
+        .collect::<Vec<_>>();
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+/// Returns the entries which are changed or import any of `changed`, using the
+/// module graph of `entries`. Modules are loaded but not checked.
+pub fn affected_entries<L>(loader: &L, entries: &[PathBuf], changed: &[PathBuf]) -> Result<Vec<PathBuf>, Error>
+where
+    L: LoadModule,
+{
+    // Paths in the graph are compared with the paths reported by git, which are
+    // canonical.
+    let canonical = entries.iter().map(|path| path.canonicalize()).collect::<Result<Vec<_>, _>>()?;
+    for entry in &canonical {
+        loader.load_module(&Arc::new(FileName::Real(entry.clone())), true)?;
+    }
+
+    let changed = changed.iter().map(|path| path.display().to_string()).collect::<Vec<_>>();
+    let affected = loader
+        .module_graph()
+        .affected_files(&changed.iter().map(|path| &**path).collect::<Vec<_>>());
+
+    Ok(entries
+        .iter()
+        .zip(&canonical)
+        .filter(|(_, canonical)| affected.binary_search(&canonical.display().to_string()).is_ok())
+        .map(|(entry, _)| entry.clone())
+        .collect())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("failed to run git, which is required by `--changed-only`")?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed in `{}`: {}",
+            args.join(" "),
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
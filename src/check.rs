@@ -1,18 +1,199 @@
-use clap::Args;
+use std::path::PathBuf;
 
-/// Perform type checking, but this command is not public api and is only used
-/// for testing.
+use clap::{Args, ValueEnum};
+
+/// Check a file or a project, and report the errors. Also available as
+/// `stc check`.
 #[derive(Debug, Args)]
 #[clap(rename_all = "camel-case")]
 pub struct TestCommand {
-    #[clap(name = "file")]
-    pub file: String,
+    #[clap(name = "file", required_unless_present_any = ["project", "build", "stdin"])]
+    pub file: Option<String>,
+
+    /// Check the root files of a `tsconfig.json`, or `tsconfig.json` in a
+    /// directory, instead of a file. Options given as flags are applied on top
+    /// of its `compilerOptions`.
+    #[clap(short, long)]
+    pub project: Option<PathBuf>,
+
+    /// Check the project at the path and the projects it references using
+    /// `references`, and write their declaration files. Projects which are not
+    /// changed since the last build are skipped. The options of each project
+    /// are used, and other flags are ignored.
+    #[clap(short, long, conflicts_with_all = ["project", "watch"])]
+    pub build: Option<PathBuf>,
+
+    /// Check the content of stdin as the file at `--stdinFilepath`, like an
+    /// unsaved buffer of an editor. Imports are resolved relative to the path,
+    /// and the file on the disk is ignored.
+    #[clap(long, requires = "stdin_filepath", conflicts_with_all = ["file", "build", "watch", "incremental"])]
+    pub stdin: bool,
 
-    /// The builtin libraries to load. Defaults to `es5`.
+    /// The path of the file read from stdin. It doesn't need to exist.
+    #[clap(long, alias = "stdin-filepath", value_name = "PATH", requires = "stdin")]
+    pub stdin_filepath: Option<PathBuf>,
+
+    /// Check only the root files matching the glob, like `src/auth/**`, and
+    /// the files they import. A directory matches the files under it.
+    /// Relative globs are resolved from the current directory. Can be given
+    /// multiple times.
+    #[clap(long, value_name = "GLOB", conflicts_with_all = ["build", "stdin"])]
+    pub filter: Option<Vec<String>>,
+
+    /// Check only the root files modified since the last commit according to
+    /// git, including staged and untracked files, and the root files which
+    /// import them directly or indirectly.
+    #[clap(long, conflicts_with_all = ["build", "stdin", "watch", "incremental"])]
+    pub changed_only: bool,
+
+    /// The builtin libraries to load. Defaults to `es5`, or the libraries of
+    /// the project.
     #[clap(long)]
     pub libs: Option<Vec<String>>,
 
-    /// Directory name of typings to load.
+    /// Directories to load typings from. Defaults to `typeRoots` of the
+    /// project, or `node_modules/@types` of the directory of the file and its
+    /// ancestors.
+    #[clap(long)]
+    pub type_roots: Option<Vec<PathBuf>>,
+
+    /// Directory name of typings to load. Defaults to `types` of the project.
     #[clap(long)]
     pub types: Option<Vec<String>>,
+
+    /// Load javascript files.
+    #[clap(long)]
+    pub allow_js: bool,
+
+    /// Report errors in javascript files. Implies `--allowJs`.
+    #[clap(long)]
+    pub check_js: bool,
+
+    /// Don't report errors in declaration files.
+    #[clap(long)]
+    pub skip_lib_check: bool,
+
+    /// Do not resolve symbolic links of packages to their real paths.
+    #[clap(long)]
+    pub preserve_symlinks: bool,
+
+    /// Suffixes tried when resolving modules, like `.ios`. Use an empty string
+    /// for files without a suffix.
+    #[clap(long)]
+    pub module_suffixes: Option<Vec<String>>,
+
+    /// Print each step of module resolution to stderr.
+    #[clap(long)]
+    pub trace_resolution: bool,
+
+    /// Report code which can't be transpiled file by file, like re-exports of
+    /// types without `export type`.
+    #[clap(long)]
+    pub isolated_modules: bool,
+
+    /// Report imports which form a cycle.
+    #[clap(long)]
+    pub no_circular_imports: bool,
+
+    /// Report exports which are not imported by any checked file, except the
+    /// exports of the entry.
+    #[clap(long)]
+    pub no_unused_exports: bool,
+
+    /// The number of threads used to parse and check files. Files which don't
+    /// depend on each other are checked in parallel. Defaults to the number of
+    /// CPUs. The order of errors doesn't depend on it.
+    #[clap(short, long, value_name = "N")]
+    pub jobs: Option<usize>,
+
+    /// How errors are reported. `sarif` prints a SARIF log to stdout instead,
+    /// which can be uploaded to GitHub code scanning, and `github` prints
+    /// workflow commands which annotate pull requests in GitHub Actions. Paths
+    /// are relative to the current directory.
+    #[clap(long, value_enum, default_value_t = Format::Human, conflicts_with_all = ["build", "watch"])]
+    pub format: Format,
+
+    /// Report at most N errors and warnings. The rest are still counted.
+    /// Defaults to `maxErrors` of the `stc` section of the project.
+    #[clap(long, value_name = "N")]
+    pub max_errors: Option<usize>,
+
+    /// Report at most N errors and warnings of each file. The rest are still
+    /// counted. Defaults to `maxErrorsPerFile` of the `stc` section of the
+    /// project.
+    #[clap(long, value_name = "N")]
+    pub max_errors_per_file: Option<usize>,
+
+    /// Fail if a warning is reported, like the errors of `--noUnusedExports`.
+    #[clap(long)]
+    pub error_on_warning: bool,
+
+    /// Succeed if the number of errors does not exceed N, to adopt stc
+    /// gradually. Defaults to `errorBaseline` of the `stc` section of the
+    /// project, or 0.
+    #[clap(long, value_name = "N")]
+    pub baseline: Option<usize>,
+
+    /// Write `trace.json` to the directory, which shows the time taken to
+    /// parse, bind and check each file and each top-level declaration. It can
+    /// be opened with `chrome://tracing` or Perfetto.
+    #[clap(long, value_name = "DIR", conflicts_with = "build")]
+    pub trace: Option<PathBuf>,
+
+    /// Print the declarations of each checked file and their inferred types
+    /// as JSON.
+    #[clap(long)]
+    pub emit_types: bool,
+
+    /// Write the transpiled javascript files to the directory, keeping the
+    /// paths relative to `rootDir`, or the directory of the file or the
+    /// project. Javascript files are not written without it.
+    #[clap(long)]
+    pub out_dir: Option<PathBuf>,
+
+    /// Write declaration files of the checked files.
+    #[clap(long)]
+    pub declaration: bool,
+
+    /// Write declaration files to the directory instead of `--outDir`, or the
+    /// directory of each source file.
+    #[clap(long)]
+    pub declaration_dir: Option<PathBuf>,
+
+    /// Write only declaration files. Implies `--declaration`.
+    #[clap(long)]
+    pub emit_declaration_only: bool,
+
+    /// Don't write any file, even if the project specifies outputs.
+    #[clap(long)]
+    pub no_emit: bool,
+
+    /// Don't write any file if an error is found.
+    #[clap(long)]
+    pub no_emit_on_error: bool,
+
+    /// Store the results of the check in `.stcbuildinfo` in the directory of
+    /// the file or the project, and re-check only the files affected by
+    /// changes in the next run.
+    #[clap(long)]
+    pub incremental: bool,
+
+    /// Keep running and re-check the files affected by each change in the
+    /// directory of the file or the project.
+    #[clap(long)]
+    pub watch: bool,
+
+    /// Don't clear the terminal before each check in watch mode.
+    #[clap(long)]
+    pub preserve_watch_output: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Messages with the source code, printed to stderr.
+    Human,
+    /// A SARIF 2.1.0 log.
+    Sarif,
+    /// `::error` workflow commands of GitHub Actions, printed to stdout.
+    Github,
 }
@@ -0,0 +1,198 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Error};
+use clap::{Args, ValueEnum};
+use stc_ts_builtin_types::Lib;
+use stc_ts_config::Project;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::{env::EnvFactory, recorder::TypeRecorder};
+use stc_ts_module_loader::{
+    fs::{FileSystem, RealFs},
+    resolvers::{cache::CachingResolver, ModuleResolver, Resolve},
+};
+use stc_ts_type_checker::{coverage::TypeCoverage, loader::ModuleLoader, Checker};
+use swc_common::{errors::Handler, FileName, SourceMap};
+use swc_ecma_ast::EsVersion;
+
+/// Print the type coverage of a file or a project, which is the percentage of
+/// identifiers not typed as `any`.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct CoverageCommand {
+    #[clap(name = "file", required_unless_present = "project")]
+    pub file: Option<String>,
+
+    /// Measure the root files of a `tsconfig.json`, or `tsconfig.json` in a
+    /// directory, instead of a file.
+    #[clap(short, long)]
+    pub project: Option<PathBuf>,
+
+    /// The builtin libraries to load. Defaults to `es5`, or the libraries of
+    /// the project.
+    #[clap(long)]
+    pub libs: Option<Vec<String>>,
+
+    /// Fail if the coverage of all files is lower than the percentage.
+    #[clap(long, value_name = "PERCENT")]
+    pub threshold: Option<f64>,
+
+    /// The format of the report.
+    #[clap(long, value_enum, default_value_t = CoverageFormat::Text)]
+    pub format: CoverageFormat,
+
+    /// Write the report to the file instead of stdout.
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// List the identifiers typed as `any` in the text report.
+    #[clap(long)]
+    pub detail: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CoverageFormat {
+    /// The coverage of each directory and file.
+    Text,
+    /// The coverage of each directory and file, and the identifiers typed as
+    /// `any`.
+    Json,
+    /// A standalone HTML page with the same content as `json`.
+    Html,
+}
+
+impl CoverageCommand {
+    /// Returns `false` if the coverage is lower than `--threshold`.
+    pub fn run(self, cm: &Arc<SourceMap>, handler: &Arc<Handler>) -> Result<bool, Error> {
+        let project = match &self.project {
+            Some(path) => Some(Project::load(&RealFs, path)?),
+            None => None,
+        };
+        let options = project.as_ref().map(|project| project.options().clone()).unwrap_or_default();
+
+        let mut libs = match (self.libs, &project) {
+            (Some(libs), _) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+            (None, Some(..)) => options.libs()?,
+            (None, None) => Lib::load("es5"),
+        };
+        libs.sort();
+        libs.dedup();
+
+        let (env, entries, root) = match (&project, self.file) {
+            (Some(project), _) => (
+                Env::simple(project.rule()?, options.target()?, options.module()?, &libs),
+                project.root_files(&RealFs),
+                project.dir().to_path_buf(),
+            ),
+            (None, Some(file)) => {
+                let path = PathBuf::from(file);
+                let root = path.parent().unwrap_or(&path).to_path_buf();
+
+                (
+                    Env::simple(options.rule()?, EsVersion::latest(), ModuleConfig::None, &libs),
+                    vec![path],
+                    root,
+                )
+            }
+            (None, None) => unreachable!("clap requires a file or a project"),
+        };
+        if entries.is_empty() {
+            bail!("no inputs were found in the project");
+        }
+
+        let fs: Arc<dyn FileSystem> = Arc::new(RealFs);
+        let resolver: Arc<dyn Resolve> = match &project {
+            Some(project) => Arc::new(project.resolver(project.module_resolver(fs.clone())?)),
+            None => Arc::new(ModuleResolver::new(env.module().module_resolution()).with_fs(fs.clone())),
+        };
+        // Checkers for typings and the entries share the cache.
+        let resolver = CachingResolver::new(resolver, fs);
+
+        Checker::new(
+            cm.clone(),
+            handler.clone(),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm.clone(), env.clone(), resolver.clone()),
+        )
+        .load_typings(&root, options.type_roots.as_deref(), options.types.as_deref());
+
+        let mut checker = Checker::new(
+            cm.clone(),
+            handler.clone(),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm.clone(), env, resolver),
+        )
+        .with_type_recorder(TypeRecorder::default());
+        checker.check_all(entries.into_iter().map(|path| Arc::new(FileName::Real(path))).collect());
+
+        // Errors don't affect the coverage, and they are reported by `stc test`.
+        let errors = checker.take_errors();
+        log::info!("Found {} errors", errors.len());
+
+        let coverage = checker.type_coverage();
+        let report = match self.format {
+            CoverageFormat::Text => text_report(&coverage, &root, self.detail),
+            CoverageFormat::Json => serde_json::to_string_pretty(&coverage)?,
+            CoverageFormat::Html => coverage.to_html(&root),
+        };
+        match &self.output {
+            Some(path) => {
+                std::fs::write(path, report)?;
+                eprintln!("Wrote the report to `{}`", path.display());
+            }
+            None => println!("{}", report),
+        }
+
+        let percentage = coverage.total.percentage();
+        if let Some(threshold) = self.threshold {
+            if percentage < threshold {
+                eprintln!("Type coverage {:.2}% is lower than the threshold {:.2}%", percentage, threshold);
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+fn text_report(coverage: &TypeCoverage, root: &Path, detail: bool) -> String {
+    let mut lines = vec![format!(
+        "Type coverage: {:.2}% ({} of {} identifiers typed, {} explicit any, {} implicit any)",
+        coverage.total.percentage(),
+        coverage.total.typed,
+        coverage.total.total,
+        coverage.total.explicit_any,
+        coverage.total.implicit_any
+    )];
+
+    lines.push(String::new());
+    for dir in coverage.directories(root) {
+        lines.push(format!("{:>7.2}%  {}/", dir.counts.percentage(), dir.dir));
+    }
+
+    lines.push(String::new());
+    for file in &coverage.files {
+        let path = Path::new(&file.file);
+        let path = path.strip_prefix(root).unwrap_or(path).display();
+
+        lines.push(format!("{:>7.2}%  {}", file.counts.percentage(), path));
+        if detail {
+            for any in &file.anys {
+                lines.push(format!(
+                    "          {}:{}:{} `{}` is {} any",
+                    path,
+                    any.span.line,
+                    any.span.column + 1,
+                    any.name,
+                    if any.implicit { "implicitly" } else { "explicitly" }
+                ));
+            }
+        }
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,286 @@
+//! `stc daemon`, which keeps the checked project in memory and re-checks only
+//! the files changed since the previous request.
+//!
+//! Requests and responses are JSON objects, one per line, sent over a Unix
+//! domain socket. A request is `{"command":"check"}` or `{"command":"stop"}`,
+//! and the response of `check` is `{"diagnostics":[...]}` with
+//! [Diagnostic]s. Failed requests are answered with `{"error":"..."}`.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use anyhow::{anyhow, bail, Context, Error};
+use clap::Args;
+use serde_json::{json, Value};
+use stc_ts_config::Project;
+use stc_ts_errors::Severity;
+use stc_ts_module_loader::{
+    fs::{FileSystem, RealFs},
+    resolvers::{cache::CachingResolver, Resolve},
+};
+use stc_ts_type_checker::{build_info::BuildInfo, loader::ModuleLoader, program::Diagnostic, Checker};
+use swc_common::{
+    errors::{EmitterWriter, Handler},
+    FileName, SourceMap, Spanned,
+};
+
+/// Keep the project checked in memory, and answer check requests sent with
+/// `--connect`, re-checking only the files changed since the last request.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct DaemonCommand {
+    /// The `tsconfig.json` of the project, or the directory containing it.
+    #[clap(short, long, default_value = ".")]
+    pub project: PathBuf,
+
+    /// The path of the socket. Defaults to `.stc.sock` in the directory of
+    /// the project.
+    #[clap(long)]
+    pub socket: Option<PathBuf>,
+
+    /// Ask the running daemon to check the project, and print the errors
+    /// instead of starting a daemon. Fails if an error is found.
+    #[clap(long, conflicts_with = "stop")]
+    pub connect: bool,
+
+    /// Stop the running daemon.
+    #[clap(long)]
+    pub stop: bool,
+}
+
+impl DaemonCommand {
+    /// Returns `false` if `--connect` found errors.
+    pub fn run(self) -> Result<bool, Error> {
+        let project = Project::load(&RealFs, &self.project)?;
+        let socket = self.socket.clone().unwrap_or_else(|| project.dir().join(".stc.sock"));
+
+        if self.stop {
+            request(&socket, "stop")?;
+            return Ok(true);
+        }
+        if self.connect {
+            return connect(&socket);
+        }
+
+        serve(&project, &socket)?;
+        Ok(true)
+    }
+}
+
+/// Sends a check request and prints the errors like `tsc --pretty false`.
+fn connect(socket: &Path) -> Result<bool, Error> {
+    let response = request(socket, "check")?;
+    let diagnostics: Vec<Diagnostic> = serde_json::from_value(response["diagnostics"].clone())?;
+
+    for d in &diagnostics {
+        let severity = match d.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+        match &d.file {
+            Some(file) => println!("{}({},{}): {} TS{}: {}", file, d.line, d.column + 1, severity, d.code, d.message),
+            None => println!("{} TS{}: {}", severity, d.code, d.message),
+        }
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.severity == Severity::Error).count();
+    println!("Found {} errors and {} warnings", errors, diagnostics.len() - errors);
+
+    Ok(errors == 0)
+}
+
+#[cfg(unix)]
+fn request(socket: &Path, command: &str) -> Result<Value, Error> {
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(socket).with_context(|| format!("failed to connect to the daemon at `{}`", socket.display()))?;
+    writeln!(stream, "{}", json!({ "command": command }))?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    let response: Value = serde_json::from_str(&line).context("invalid response from the daemon")?;
+
+    match response["error"].as_str() {
+        Some(err) => Err(anyhow!("{}", err)),
+        None => Ok(response),
+    }
+}
+
+#[cfg(not(unix))]
+fn request(_: &Path, _: &str) -> Result<Value, Error> {
+    bail!("`stc daemon` is supported only on Unix")
+}
+
+/// The checked project.
+struct Daemon<'a> {
+    project: &'a Project,
+    cm: Arc<SourceMap>,
+    checker: Checker<ModuleLoader<CachingResolver<Arc<dyn Resolve>>>>,
+    resolver: CachingResolver<Arc<dyn Resolve>>,
+    entries: Vec<PathBuf>,
+    /// The errors of all checked files.
+    errors: Vec<stc_ts_errors::Error>,
+    /// The hashes of the checked files, which are used to find changed files.
+    info: BuildInfo,
+}
+
+impl<'a> Daemon<'a> {
+    fn new(project: &'a Project) -> Result<Self, Error> {
+        let start = Instant::now();
+
+        let cm = Arc::new(SourceMap::default());
+        // Errors are sent to clients instead of being printed.
+        let handler = Arc::new(Handler::with_emitter(
+            false,
+            false,
+            Box::new(EmitterWriter::new(Box::new(io::sink()), Some(cm.clone()), false, false)),
+        ));
+
+        let env = project.env()?;
+        let options = project.options();
+        let fs: Arc<dyn FileSystem> = Arc::new(RealFs);
+        let resolver: Arc<dyn Resolve> = Arc::new(project.resolver(project.module_resolver(fs.clone())?));
+        let resolver = CachingResolver::new(resolver, fs.clone());
+
+        // Libraries and typings are analyzed once, which is what makes the daemon fast.
+        Checker::new(
+            cm.clone(),
+            handler.clone(),
+            env.clone(),
+            None,
+            ModuleLoader::new(cm.clone(), env.clone(), resolver.clone()),
+        )
+        .load_typings(project.dir(), options.type_roots.as_deref(), options.types.as_deref());
+
+        let mut checker = Checker::new(
+            cm.clone(),
+            handler,
+            env.clone(),
+            None,
+            ModuleLoader::new(cm.clone(), env, resolver.clone()),
+        );
+
+        let entries = project.root_files(&*fs);
+        if entries.is_empty() {
+            bail!("no inputs were found in the project");
+        }
+        checker.check_all(entries.iter().map(|path| Arc::new(FileName::Real(path.clone()))).collect());
+        let errors = checker.take_errors();
+
+        let mut info = BuildInfo::new(String::new());
+        checker.update_build_info(&mut info, &errors, &entries);
+
+        log::info!("Initial check took {:?}", start.elapsed());
+
+        Ok(Daemon {
+            project,
+            cm,
+            checker,
+            resolver,
+            entries,
+            errors,
+            info,
+        })
+    }
+
+    /// Re-checks the files changed since the last check, and returns the
+    /// errors of all files.
+    fn check(&mut self) -> Vec<Diagnostic> {
+        let start = Instant::now();
+
+        // Root files may be created or deleted.
+        self.entries = self.project.root_files(&RealFs);
+        let changed = self.info.affected_files(&RealFs, &self.entries);
+        if !changed.is_empty() {
+            // Imports which could not be resolved may be resolved to new files.
+            self.resolver.clear();
+        }
+
+        let graph = self.checker.module_graph();
+        let (loaded, new): (Vec<_>, Vec<_>) = changed
+            .iter()
+            .partition(|path| graph.modules.iter().any(|module| Path::new(&module.path) == path.as_path()));
+
+        for path in loaded {
+            for file in self.checker.invalidate_file(path).files {
+                let cm = &self.cm;
+                self.errors.retain(|err| cm.span_to_filename(err.span()) != *file.file);
+                self.errors.extend(file.errors);
+            }
+        }
+        if !new.is_empty() {
+            self.checker
+                .check_all(new.into_iter().map(|path| Arc::new(FileName::Real(path.clone()))).collect());
+        }
+        self.errors.extend(self.checker.take_errors());
+
+        self.checker.update_build_info(&mut self.info, &self.errors, &self.entries);
+
+        log::info!("Re-checking {} files took {:?}", changed.len(), start.elapsed());
+
+        self.errors.iter().map(|err| self.checker.to_diagnostic(err)).collect()
+    }
+}
+
+#[cfg(unix)]
+fn serve(project: &Project, socket: &Path) -> Result<(), Error> {
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if UnixStream::connect(socket).is_ok() {
+        bail!("a daemon is already running at `{}`", socket.display());
+    }
+    // The socket of a daemon which is not stopped properly.
+    let _ = std::fs::remove_file(socket);
+
+    let mut daemon = Daemon::new(project)?;
+
+    let listener = UnixListener::bind(socket).with_context(|| format!("failed to listen to `{}`", socket.display()))?;
+    eprintln!("Listening to `{}`", socket.display());
+
+    for stream in listener.incoming() {
+        // A failed connection or request should not stop the daemon.
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                log::warn!("Failed to accept a connection: {}", err);
+                continue;
+            }
+        };
+
+        let mut line = String::new();
+        if let Err(err) = BufReader::new(&stream).read_line(&mut line) {
+            log::warn!("Failed to read a request: {}", err);
+            continue;
+        }
+
+        let request = serde_json::from_str::<Value>(&line).unwrap_or_default();
+        let response = match request["command"].as_str() {
+            Some("check") => json!({ "diagnostics": daemon.check() }),
+            Some("stop") => {
+                if let Err(err) = writeln!(stream, "{}", json!({})) {
+                    log::warn!("Failed to respond: {}", err);
+                }
+                break;
+            }
+            _ => json!({ "error": format!("invalid request `{}`", line.trim()) }),
+        };
+
+        // The client may be gone.
+        if let Err(err) = writeln!(stream, "{}", response) {
+            log::warn!("Failed to respond: {}", err);
+        }
+    }
+
+    std::fs::remove_file(socket)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve(_: &Project, _: &Path) -> Result<(), Error> {
+    bail!("`stc daemon` is supported only on Unix")
+}
@@ -0,0 +1,95 @@
+//! Writing javascript files and declaration files of checked files.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Error};
+use stc_ts_config::CompilerOptions;
+use stc_ts_type_checker::{emit::EmittedFile, loader::LoadModule, Checker};
+
+/// Options which control which files are written and where.
+///
+/// Javascript files are written only if `out_dir` is specified, so the CLI
+/// acts as a pure checker by default.
+#[derive(Debug, Clone)]
+pub struct EmitOptions {
+    pub no_emit: bool,
+    pub no_emit_on_error: bool,
+    pub declaration: bool,
+    pub emit_declaration_only: bool,
+    /// Paths of the outputs are relative to this directory, and files outside
+    /// of it are not written.
+    pub root_dir: PathBuf,
+    pub out_dir: Option<PathBuf>,
+    /// Defaults to `out_dir`, and declaration files are written next to the
+    /// source files if both are not specified.
+    pub declaration_dir: Option<PathBuf>,
+}
+
+impl EmitOptions {
+    /// `dir` is used if `rootDir` is not specified. `composite` implies
+    /// `declaration`.
+    pub fn new(options: &CompilerOptions, dir: &Path) -> Self {
+        EmitOptions {
+            no_emit: options.no_emit.unwrap_or_default(),
+            no_emit_on_error: options.no_emit_on_error.unwrap_or_default(),
+            declaration: options.declaration.unwrap_or_default() || options.composite.unwrap_or_default(),
+            emit_declaration_only: options.emit_declaration_only.unwrap_or_default(),
+            root_dir: options.root_dir.clone().unwrap_or_else(|| dir.to_path_buf()),
+            out_dir: options.out_dir.clone(),
+            declaration_dir: options.declaration_dir.clone(),
+        }
+    }
+}
+
+/// Writes the outputs of the files checked by `checker`, and returns the
+/// number of written files.
+///
+/// Nothing is written if `noEmit` is enabled, or if `has_errors` is `true` and
+/// `noEmitOnError` is enabled.
+pub fn emit<L>(checker: &Checker<L>, options: &EmitOptions, has_errors: bool) -> Result<usize, Error>
+where
+    L: LoadModule,
+{
+    if options.no_emit || (has_errors && options.no_emit_on_error) {
+        return Ok(0);
+    }
+
+    let mut count = 0;
+
+    if let (Some(out_dir), false) = (&options.out_dir, options.emit_declaration_only) {
+        count += write_all(checker.emit()?, &options.root_dir, out_dir)?;
+    }
+
+    if options.declaration || options.emit_declaration_only {
+        let dir = options
+            .declaration_dir
+            .as_ref()
+            .or(options.out_dir.as_ref())
+            .unwrap_or(&options.root_dir);
+        count += write_all(checker.emit_declarations()?, &options.root_dir, dir)?;
+    }
+
+    Ok(count)
+}
+
+/// Writes `files` to `out_dir`, keeping the paths relative to `root_dir`.
+/// Files from outside of `root_dir`, like packages, are not written.
+fn write_all(files: Vec<EmittedFile>, root_dir: &Path, out_dir: &Path) -> Result<usize, Error> {
+    let mut count = 0;
+
+    for file in files {
+        let relative = match file.path.strip_prefix(root_dir) {
+            Ok(relative) => relative,
+            Err(..) => continue,
+        };
+
+        let dest = out_dir.join(relative);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, &file.code).with_context(|| format!("failed to write `{}`", dest.display()))?;
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -0,0 +1,63 @@
+use anyhow::{bail, Context, Error};
+use clap::Args;
+use stc_ts_errors::{
+    codes::{error_code, message_template, ERROR_CODES},
+    explain::explain,
+};
+
+/// Print the description, common causes and an example of an error code.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct ExplainCommand {
+    /// The error code, like `TS2322` or `2322`.
+    #[clap(required_unless_present = "list")]
+    pub code: Option<String>,
+
+    /// Print all error codes stc reports as JSON instead, with their names,
+    /// descriptions and whether tsc reports the same code.
+    #[clap(long, conflicts_with = "code")]
+    pub list: bool,
+}
+
+impl ExplainCommand {
+    pub fn run(self) -> Result<(), Error> {
+        if self.list {
+            println!("{}", serde_json::to_string_pretty(ERROR_CODES)?);
+            return Ok(());
+        }
+
+        let input = self.code.unwrap_or_default();
+        let code = input
+            .trim_start_matches("TS")
+            .trim_start_matches("ts")
+            .parse::<usize>()
+            .with_context(|| format!("`{}` is not an error code", input))?;
+
+        let explanation = match explain(code) {
+            Some(explanation) => explanation,
+            // Codes without a message of tsc are described by the registry.
+            None => match message_template(code).or_else(|| Some(error_code(code)?.description)) {
+                Some(message) => {
+                    println!("TS{}: {}\n\nThere is no detailed explanation of this error yet.", code, message);
+                    return Ok(());
+                }
+                None => bail!("unknown error code `TS{}`", code),
+            },
+        };
+
+        println!("TS{}: {}\n", code, explanation.message);
+        println!("{}\n", explanation.description);
+
+        println!("Common causes:\n");
+        for cause in explanation.causes {
+            println!("- {}", cause);
+        }
+
+        println!("\nExample:\n");
+        for line in explanation.example.lines() {
+            println!("    {}", line);
+        }
+
+        Ok(())
+    }
+}
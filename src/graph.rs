@@ -0,0 +1,158 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Error};
+use clap::{Args, ValueEnum};
+use stc_ts_builtin_types::Lib;
+use stc_ts_config::Project;
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{
+    fs::{FileSystem, RealFs},
+    resolvers::{ModuleResolver, Resolve},
+};
+use stc_ts_type_checker::{
+    graph::ModuleGraph,
+    loader::{LoadModule, ModuleLoader},
+};
+use swc_common::{FileName, SourceMap};
+use swc_ecma_ast::EsVersion;
+
+/// Print the graph of the modules imported by a file or a project, without
+/// checking them.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct GraphCommand {
+    #[clap(name = "file", required_unless_present = "project")]
+    pub file: Option<String>,
+
+    /// Start from the root files of a `tsconfig.json`, or `tsconfig.json` in a
+    /// directory, instead of a file.
+    #[clap(short, long)]
+    pub project: Option<PathBuf>,
+
+    /// The format of the graph.
+    #[clap(long, value_enum, default_value_t = GraphFormat::Tree)]
+    pub format: GraphFormat,
+
+    /// Write the graph to the file instead of stdout.
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Show only the imports which are part of a cycle.
+    #[clap(long, conflicts_with = "between")]
+    pub cycles: bool,
+
+    /// Show only the files on the paths of imports from the first file to the
+    /// second file.
+    #[clap(long, num_args = 2, value_names = ["FROM", "TO"])]
+    pub between: Option<Vec<PathBuf>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// The imports of each file as a tree, starting from the entries. Files
+    /// which are already printed are marked with `(*)`.
+    Tree,
+    /// The DOT language of graphviz.
+    Dot,
+    /// The files and the imports, including unresolved imports.
+    Json,
+}
+
+impl GraphCommand {
+    pub fn run(self, cm: &Arc<SourceMap>) -> Result<(), Error> {
+        let project = match &self.project {
+            Some(path) => Some(Project::load(&RealFs, path)?),
+            None => None,
+        };
+
+        let (env, entries) = match (&project, self.file) {
+            (Some(project), _) => (project.env()?, project.root_files(&RealFs)),
+            (None, Some(file)) => (
+                Env::simple(Default::default(), EsVersion::latest(), ModuleConfig::None, &Lib::load("es5")),
+                vec![PathBuf::from(file)],
+            ),
+            (None, None) => unreachable!("clap requires a file or a project"),
+        };
+        if entries.is_empty() {
+            bail!("no inputs were found in the project");
+        }
+        // Paths in the graph are compared with `--between`.
+        let entries = entries.iter().map(|path| path.canonicalize()).collect::<Result<Vec<_>, _>>()?;
+
+        let fs: Arc<dyn FileSystem> = Arc::new(RealFs);
+        let resolver: Arc<dyn Resolve> = match &project {
+            Some(project) => Arc::new(project.resolver(project.module_resolver(fs.clone())?)),
+            None => Arc::new(ModuleResolver::new(env.module().module_resolution()).with_fs(fs)),
+        };
+
+        let loader = ModuleLoader::new(cm.clone(), env, resolver);
+        for entry in &entries {
+            loader.load_module(&Arc::new(FileName::Real(entry.clone())), true)?;
+        }
+
+        let mut graph = loader.module_graph();
+        if self.cycles {
+            graph = graph.cycle_graph();
+        }
+        if let Some(between) = &self.between {
+            let from = between[0].canonicalize()?;
+            let to = between[1].canonicalize()?;
+            graph = graph.paths_between(&from.display().to_string(), &to.display().to_string());
+        }
+
+        let cwd = std::env::current_dir()?;
+        let graph = relative_graph(graph, &cwd);
+
+        let output = match self.format {
+            GraphFormat::Tree => {
+                let roots = match &self.between {
+                    // Paths from the first file.
+                    Some(between) => vec![relative_path(&between[0].canonicalize()?.display().to_string(), &cwd)],
+                    // Each cycle starts from the file with the smallest path.
+                    None if self.cycles => graph.cycles().into_iter().map(|cycle| cycle[0].clone()).collect(),
+                    None => entries
+                        .iter()
+                        .map(|entry| relative_path(&entry.display().to_string(), &cwd))
+                        .collect(),
+                };
+                graph.to_tree(&roots.iter().map(|root| &**root).collect::<Vec<_>>())
+            }
+            GraphFormat::Dot => graph.to_dot(),
+            GraphFormat::Json => format!("{}\n", graph.to_json()?),
+        };
+
+        match &self.output {
+            Some(path) => {
+                std::fs::write(path, output)?;
+                eprintln!("Wrote the graph to `{}`", path.display());
+            }
+            None => print!("{}", output),
+        }
+
+        Ok(())
+    }
+}
+
+/// Makes the paths in `graph` relative to `dir` for readability.
+fn relative_graph(mut graph: ModuleGraph, dir: &Path) -> ModuleGraph {
+    for module in &mut graph.modules {
+        module.path = relative_path(&module.path, dir);
+    }
+    for import in &mut graph.imports {
+        import.from = relative_path(&import.from, dir);
+        if let Some(resolved) = &mut import.resolved {
+            *resolved = relative_path(resolved, dir);
+        }
+    }
+
+    graph
+}
+
+fn relative_path(path: &str, dir: &Path) -> String {
+    let path = Path::new(path);
+    path.strip_prefix(dir).unwrap_or(path).display().to_string()
+}
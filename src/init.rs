@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Error};
+use clap::Args;
+use stc_ts_config::init;
+use stc_ts_module_loader::fs::RealFs;
+
+/// Write a `tsconfig.json` recommended for the project in the directory,
+/// based on `package.json` and the source files.
+#[derive(Debug, Args)]
+#[clap(rename_all = "camel-case")]
+pub struct InitCommand {
+    /// The directory of the project. Defaults to the current directory.
+    #[clap(default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Overwrite `tsconfig.json` if it exists.
+    #[clap(long)]
+    pub force: bool,
+}
+
+impl InitCommand {
+    pub fn run(self) -> Result<(), Error> {
+        let path = self.dir.join("tsconfig.json");
+        if path.exists() && !self.force {
+            bail!("`{}` already exists. Use `--force` to overwrite it", path.display());
+        }
+
+        let init = init(&RealFs, &self.dir)?;
+        std::fs::write(&path, &init.content)?;
+
+        println!("Created `{}`", path.display());
+        for warning in &init.warnings {
+            println!("warning: {}", warning);
+        }
+
+        Ok(())
+    }
+}
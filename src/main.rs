@@ -1,15 +1,29 @@
 extern crate swc_node_base;
 
-use std::{path::PathBuf, sync::Arc, time::Instant};
+use std::{io::Read, path::PathBuf, sync::Arc, time::Instant};
 
-use anyhow::Error;
+use anyhow::{bail, Error};
 use clap::Parser;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig, Rule};
-use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_config::{filter_files, Project};
+use stc_ts_env::{Env, ModuleConfig};
+use stc_ts_errors::{FrameRenderer, Report, Severity};
+use stc_ts_file_analyzer::{env::EnvFactory, recorder::TypeRecorder};
 use stc_ts_lang_server::LspCommand;
-use stc_ts_module_loader::resolvers::node::NodeResolver;
-use stc_ts_type_checker::{loader::ModuleLoader, Checker};
+use stc_ts_module_loader::{
+    fs::{FileSystem, MemoryFs, OverlayFs, RealFs},
+    resolvers::{cache::CachingResolver, trace::Tracer, ModuleResolver, Resolve},
+};
+use stc_ts_type_checker::{
+    build_info::BuildInfo,
+    github::error_command,
+    limit::{truncation_summary, ErrorLimit},
+    loader::ModuleLoader,
+    program::Diagnostic,
+    sarif::SarifLog,
+    Checker,
+};
+use stc_utils::profile::Profiler;
 use swc_common::{
     errors::{ColorConfig, EmitterWriter, Handler},
     FileName, SourceMap,
@@ -17,15 +31,44 @@ use swc_common::{
 use swc_ecma_ast::EsVersion;
 use tracing_subscriber::EnvFilter;
 
-use crate::check::TestCommand;
+use crate::{
+    bench::BenchCommand,
+    build::build,
+    changed::{affected_entries, changed_files},
+    check::{Format, TestCommand},
+    coverage::CoverageCommand,
+    daemon::DaemonCommand,
+    emit::{emit, EmitOptions},
+    explain::ExplainCommand,
+    graph::GraphCommand,
+    init::InitCommand,
+    watch::{watch, WatchOptions},
+};
 
+mod bench;
+mod build;
+mod changed;
 mod check;
+mod coverage;
+mod daemon;
+mod emit;
+mod explain;
+mod graph;
+mod init;
+mod watch;
 
 #[derive(Debug, Parser)]
 #[command(name = "stc", about = "Super fast type checker for typescript", author, rename_all = "camel")]
 enum Command {
+    #[command(visible_alias = "check")]
     Test(TestCommand),
     Lsp(LspCommand),
+    Init(InitCommand),
+    Explain(ExplainCommand),
+    Coverage(CoverageCommand),
+    Graph(GraphCommand),
+    Bench(BenchCommand),
+    Daemon(DaemonCommand),
 }
 
 #[tokio::main]
@@ -39,6 +82,8 @@ async fn main() -> Result<(), Error> {
         .with_ansi(true)
         .without_time()
         .with_env_filter(EnvFilter::new("STC_LOG"))
+        // stdout is used by reports and the protocol of `stc lsp`.
+        .with_writer(std::io::stderr)
         .pretty()
         .finish();
 
@@ -51,8 +96,13 @@ async fn main() -> Result<(), Error> {
         let emitter = Box::new(EmitterWriter::stderr(ColorConfig::Always, Some(cm.clone()), false, false));
         Arc::new(Handler::with_emitter(true, false, emitter))
     };
+    let renderer = FrameRenderer::default().with_color(true).with_cwd(std::env::current_dir()?);
 
-    rayon::ThreadPoolBuilder::new().build_global().unwrap();
+    let mut thread_pool = rayon::ThreadPoolBuilder::new();
+    if let Command::Test(TestCommand { jobs: Some(jobs), .. }) = &command {
+        thread_pool = thread_pool.num_threads(*jobs);
+    }
+    thread_pool.build_global().unwrap();
 
     {
         let end = Instant::now();
@@ -60,14 +110,49 @@ async fn main() -> Result<(), Error> {
         log::info!("Initialization took {:?}", end - start);
     }
 
+    // Whether the process should exit with an error code.
+    let mut failed = false;
+
     match command {
+        Command::Test(TestCommand {
+            build: Some(path),
+            baseline,
+            ..
+        }) => {
+            let errors = build(&cm, &handler, &renderer, &path)?;
+
+            log::info!("Found {} errors", errors);
+
+            failed = errors > baseline.unwrap_or(0);
+        }
         Command::Test(cmd) => {
+            let project = match &cmd.project {
+                Some(path) => {
+                    // Paths reported by the watcher are absolute, and paths in the build info
+                    // should not depend on the working directory.
+                    let path = if cmd.watch || cmd.incremental {
+                        path.canonicalize()?
+                    } else {
+                        path.clone()
+                    };
+
+                    Some(Project::load(&RealFs, &path)?)
+                }
+                None => None,
+            };
+            let options = project.as_ref().map(|project| project.options().clone()).unwrap_or_default();
+            for option in options.unsupported_options() {
+                eprintln!("warning: {}", option);
+            }
+            let incremental = cmd.incremental || options.incremental.unwrap_or_default();
+
             let libs = {
                 let start = Instant::now();
 
-                let mut libs = match cmd.libs {
-                    Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
-                    None => Lib::load("es5"),
+                let mut libs = match (cmd.libs, &project) {
+                    (Some(libs), _) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+                    (None, Some(..)) => options.libs()?,
+                    (None, None) => Lib::load("es5"),
                 };
                 libs.sort();
                 libs.dedup();
@@ -79,11 +164,171 @@ async fn main() -> Result<(), Error> {
                 libs
             };
 
-            let env = Env::simple(Rule { ..Default::default() }, EsVersion::latest(), ModuleConfig::None, &libs);
+            let mut rule = match &project {
+                Some(project) => project.rule()?,
+                None => options.rule()?,
+            };
+            rule.allow_js |= cmd.allow_js || cmd.check_js;
+            rule.check_js |= cmd.check_js;
+            rule.skip_lib_check |= cmd.skip_lib_check;
+            rule.isolated_modules |= cmd.isolated_modules;
+            rule.no_circular_imports |= cmd.no_circular_imports;
+            rule.no_unused_exports |= cmd.no_unused_exports;
+
+            let env = match &project {
+                Some(..) => Env::simple(rule, options.target()?, options.module()?, &libs),
+                None => Env::simple(rule, EsVersion::latest(), ModuleConfig::None, &libs),
+            };
 
-            let path = PathBuf::from(cmd.file);
+            // The entries, and the directory containing them.
+            let (entries, root) = match (&project, cmd.file, &cmd.stdin_filepath) {
+                // Only the file is checked, using the options of the project.
+                (_, _, Some(path)) => {
+                    let root = match &project {
+                        Some(project) => project.dir().to_path_buf(),
+                        None => path.parent().unwrap_or(path).to_path_buf(),
+                    };
 
-            {
+                    (vec![path.clone()], root)
+                }
+                (Some(project), ..) => (project.root_files(&RealFs), project.dir().to_path_buf()),
+                (None, Some(file), None) => {
+                    let mut path = PathBuf::from(file);
+                    if cmd.watch || incremental {
+                        path = path.canonicalize()?;
+                    }
+                    let root = path.parent().unwrap_or(&path).to_path_buf();
+
+                    (vec![path], root)
+                }
+                (None, None, None) => unreachable!("clap requires a file, a project, --stdin or --build"),
+            };
+            if entries.is_empty() {
+                bail!("no inputs were found in the project");
+            }
+            let entries = match &cmd.filter {
+                Some(patterns) => {
+                    let case_sensitive = project.as_ref().map_or(true, |project| project.case_sensitive);
+                    let entries = filter_files(&entries, &std::env::current_dir()?, patterns, case_sensitive);
+                    if entries.is_empty() {
+                        bail!("no inputs match `--filter`");
+                    }
+                    entries
+                }
+                None => entries,
+            };
+
+            let fs: Arc<dyn FileSystem> = match &cmd.stdin_filepath {
+                Some(path) => {
+                    let mut content = String::new();
+                    std::io::stdin().read_to_string(&mut content)?;
+
+                    // The content shadows the file on the disk.
+                    let stdin = MemoryFs::new();
+                    stdin.insert(path.clone(), content);
+                    Arc::new(OverlayFs::new(Arc::new(stdin), Arc::new(RealFs)))
+                }
+                None => Arc::new(RealFs),
+            };
+            let tracer = cmd.trace_resolution.then(|| Tracer::new(|msg| eprintln!("{}", msg)));
+
+            let mut resolver = match &project {
+                Some(project) => project.module_resolver(fs.clone())?,
+                None => ModuleResolver::new(env.module().module_resolution()).with_fs(fs.clone()),
+            };
+            resolver = resolver
+                .with_allow_js(env.rule().allow_js)
+                .with_preserve_symlinks(cmd.preserve_symlinks || options.preserve_symlinks.unwrap_or_default());
+            if let Some(module_suffixes) = cmd.module_suffixes {
+                resolver = resolver.with_module_suffixes(module_suffixes);
+            }
+            if let Some(tracer) = &tracer {
+                resolver = resolver.with_tracer(tracer.clone());
+            }
+            let resolver: Arc<dyn Resolve> = match &project {
+                Some(project) => {
+                    let mut resolver = project.resolver(resolver);
+                    if let Some(tracer) = tracer {
+                        resolver = resolver.with_tracer(tracer);
+                    }
+                    Arc::new(resolver)
+                }
+                None => Arc::new(resolver),
+            };
+            // Checkers for typings and the entry share the cache.
+            let resolver = CachingResolver::new(resolver, fs.clone());
+            let watch_resolver = resolver.clone();
+
+            let entries = match cmd.changed_only {
+                true => {
+                    let changed = changed_files(&root)?;
+                    let loader = ModuleLoader::with_fs(cm.clone(), env.clone(), resolver.clone(), fs.clone());
+                    let entries = affected_entries(&loader, &entries, &changed)?;
+                    if entries.is_empty() {
+                        eprintln!("No files are affected by the changes");
+                        return Ok(());
+                    }
+                    log::info!("Checking {} files affected by {} changed files", entries.len(), changed.len());
+                    entries
+                }
+                false => entries,
+            };
+
+            let type_roots = cmd.type_roots.or_else(|| options.type_roots.clone());
+            let types = cmd.types.or_else(|| options.types.clone());
+
+            let build_info_path = match &project {
+                Some(project) => project.build_info_path(),
+                None => root.join(".stcbuildinfo"),
+            };
+            let mut build_info = incremental.then(|| {
+                // Results are reused only if they are checked with the same options.
+                let build_info_options = format!(
+                    "{:?}",
+                    (env.rule(), env.target(), env.module().to_string(), &libs, &type_roots, &types)
+                );
+
+                // All files are checked by the initial check of the watch mode, because all
+                // errors are reported again after each change.
+                let previous = match cmd.watch {
+                    true => None,
+                    false => std::fs::read_to_string(&build_info_path)
+                        .ok()
+                        .and_then(|s| BuildInfo::from_json(&s).ok()),
+                };
+
+                previous
+                    .filter(|info| info.is_compatible(&build_info_options))
+                    .unwrap_or_else(|| BuildInfo::new(build_info_options))
+            });
+            // A new build info returns all entries.
+            let files = match &build_info {
+                Some(info) => info.affected_files(&RealFs, &entries),
+                None => entries.clone(),
+            };
+
+            let mut emit_options = EmitOptions::new(&options, &root);
+            emit_options.no_emit |= cmd.no_emit;
+            emit_options.no_emit_on_error |= cmd.no_emit_on_error;
+            emit_options.declaration |= cmd.declaration;
+            emit_options.emit_declaration_only |= cmd.emit_declaration_only;
+            if let Some(out_dir) = cmd.out_dir {
+                emit_options.out_dir = Some(out_dir);
+            }
+            if let Some(declaration_dir) = cmd.declaration_dir {
+                emit_options.declaration_dir = Some(declaration_dir);
+            }
+
+            let profiler = cmd.trace.is_some().then(Profiler::new);
+            let loader = |env: Env, resolver: CachingResolver<Arc<dyn Resolve>>| {
+                let loader = ModuleLoader::with_fs(cm.clone(), env, resolver, fs.clone());
+                match &profiler {
+                    Some(profiler) => loader.with_profiler(profiler.clone()),
+                    None => loader,
+                }
+            };
+
+            let typing_errors = {
                 let start = Instant::now();
 
                 let checker = Checker::new(
@@ -91,57 +336,184 @@ async fn main() -> Result<(), Error> {
                     handler.clone(),
                     env.clone(),
                     None,
-                    ModuleLoader::new(cm.clone(), env.clone(), NodeResolver),
+                    loader(env.clone(), resolver.clone()),
                 );
 
-                checker.load_typings(&path, None, cmd.types.as_deref());
+                let typing_errors = checker.load_typings(&root, type_roots.as_deref(), types.as_deref());
 
                 let end = Instant::now();
 
                 log::info!("Loading typing libraries took {:?}", end - start);
-            }
+                if let Some(profiler) = &profiler {
+                    profiler.record("phase", "load typings", start);
+                }
 
-            let mut errors = vec![];
+                typing_errors
+            };
+
+            let stc_options = project.as_ref().and_then(|project| project.config.stc.as_ref());
+            let limit = ErrorLimit::default()
+                .with_max_errors(cmd.max_errors.or_else(|| stc_options?.max_errors))
+                .with_max_errors_per_file(cmd.max_errors_per_file.or_else(|| stc_options?.max_errors_per_file));
+
+            let mut errors = typing_errors;
+            // Errors of unchanged files, which are loaded from the build info.
+            let mut stored = vec![];
 
             let start = Instant::now();
-            {
-                let mut checker = Checker::new(
-                    cm.clone(),
-                    handler.clone(),
-                    env.clone(),
-                    None,
-                    ModuleLoader::new(cm, env, NodeResolver),
-                );
+            let mut checker = {
+                let mut checker = Checker::new(cm.clone(), handler.clone(), env.clone(), None, loader(env, resolver));
+                if let Some(profiler) = &profiler {
+                    checker = checker.with_profiler(profiler.clone());
+                }
+                if cmd.emit_types {
+                    checker = checker.with_type_recorder(TypeRecorder::default());
+                }
 
-                checker.check(Arc::new(FileName::Real(path)));
+                if !files.is_empty() {
+                    checker.check_all(files.into_iter().map(|path| Arc::new(FileName::Real(path))).collect());
+                }
 
                 errors.extend(checker.take_errors());
-            }
+
+                if let Some(info) = &mut build_info {
+                    let checked = checker
+                        .module_graph()
+                        .modules
+                        .into_iter()
+                        .map(|module| module.path)
+                        .collect::<Vec<_>>();
+                    stored = info.stored_reports(&RealFs, &cm, &checked);
+
+                    checker.update_build_info(info, &errors, &entries);
+                    std::fs::write(&build_info_path, info.to_json()?)?;
+                }
+
+                if cmd.emit_types {
+                    println!("{}", serde_json::to_string_pretty(&checker.all_declaration_types())?);
+                }
+
+                let written = emit(&checker, &emit_options, !errors.is_empty() || !stored.is_empty())?;
+                log::info!("Wrote {} files", written);
+
+                checker
+            };
             let end = Instant::now();
 
             log::info!("Checking took {:?}", end - start);
+            if let Some(profiler) = &profiler {
+                profiler.record("phase", "check", start);
+            }
 
             {
                 let start = Instant::now();
-                for err in &errors {
-                    err.emit(&handler);
+                let omitted = match cmd.format {
+                    Format::Human => {
+                        let reports = stored.iter().cloned().chain(errors.iter().map(Report::new)).collect();
+                        let (reports, omitted) = limit.truncate(reports, |report| cm.span_to_filename(report.span));
+                        for report in &reports {
+                            eprint!("{}", renderer.render(&cm, report));
+                        }
+
+                        omitted
+                    }
+                    Format::Sarif | Format::Github => {
+                        let diagnostics = errors
+                            .iter()
+                            .map(|err| checker.to_diagnostic(err))
+                            .chain(stored.iter().map(|report| Diagnostic::from_report(&cm, report)))
+                            .collect::<Vec<_>>();
+                        let (diagnostics, omitted) = limit.truncate(diagnostics, |d| d.file.clone());
+                        let cwd = std::env::current_dir()?;
+
+                        if cmd.format == Format::Sarif {
+                            println!("{}", SarifLog::new(&diagnostics, &cwd).to_json()?);
+                        } else {
+                            for diagnostic in &diagnostics {
+                                println!("{}", error_command(diagnostic, &cwd));
+                            }
+                        }
+
+                        omitted
+                    }
+                };
+                if let Some(summary) = truncation_summary(omitted) {
+                    eprintln!("{}", summary);
                 }
 
+                let total = errors.len() + stored.len();
                 let end = Instant::now();
 
-                log::info!("Found {} errors", errors.len());
+                // Infos are counted as warnings.
+                let warnings = errors.iter().filter(|err| err.severity() != Severity::Error).count()
+                    + stored.iter().filter(|report| report.severity != Severity::Error).count();
+                log::info!("Found {} errors and {} warnings", total - warnings, warnings);
+
+                let baseline = cmd
+                    .baseline
+                    .or_else(|| project.as_ref()?.config.stc.as_ref()?.error_baseline)
+                    .unwrap_or_default();
+                let count = if cmd.error_on_warning { total } else { total - warnings };
+                failed = count > baseline;
 
                 log::info!("Error reporting took {:?}", end - start);
+                if let Some(profiler) = &profiler {
+                    profiler.record("phase", "report", start);
+                }
+            }
+
+            if let (Some(dir), Some(profiler)) = (&cmd.trace, &profiler) {
+                std::fs::create_dir_all(dir)?;
+                let path = dir.join("trace.json");
+                std::fs::write(&path, profiler.to_chrome_trace())?;
+
+                log::info!("Wrote trace to `{}`", path.display());
+            }
+
+            if cmd.watch {
+                watch(
+                    &cm,
+                    &renderer,
+                    &mut checker,
+                    &watch_resolver,
+                    &root,
+                    errors,
+                    WatchOptions {
+                        preserve_output: cmd.preserve_watch_output,
+                    },
+                )?;
             }
         }
         Command::Lsp(cmd) => {
             cmd.run().await?;
         }
+        Command::Init(cmd) => {
+            cmd.run()?;
+        }
+        Command::Explain(cmd) => {
+            cmd.run()?;
+        }
+        Command::Coverage(cmd) => {
+            failed = !cmd.run(&cm, &handler)?;
+        }
+        Command::Graph(cmd) => {
+            cmd.run(&cm)?;
+        }
+        Command::Bench(cmd) => {
+            cmd.run()?;
+        }
+        Command::Daemon(cmd) => {
+            failed = !cmd.run()?;
+        }
     }
 
     let end = Instant::now();
 
     log::info!("Done in {:?}", end - start);
 
+    if failed {
+        std::process::exit(1);
+    }
+
     Ok(())
 }
@@ -0,0 +1,166 @@
+//! `--watch`, which re-checks files affected by changes until the process is
+//! killed.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+use anyhow::Error;
+use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use stc_ts_errors::{FrameRenderer, Report};
+use stc_ts_module_loader::resolvers::{cache::CachingResolver, Resolve};
+use stc_ts_type_checker::{loader::LoadModule, Checker};
+use swc_common::{SourceMap, Spanned};
+
+/// Changes are collected until no change is made for this duration, so that
+/// saving many files at once triggers only one check.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+const EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "json"];
+
+pub struct WatchOptions {
+    /// Don't clear the terminal before each check.
+    pub preserve_output: bool,
+}
+
+/// Watches the directory `root` and re-checks the files affected by each
+/// change, reporting all errors again after each check.
+///
+/// `errors` are the errors of the initial check, which are already reported.
+pub fn watch<L, R>(
+    cm: &SourceMap,
+    renderer: &FrameRenderer,
+    checker: &mut Checker<L>,
+    resolver: &CachingResolver<R>,
+    root: &Path,
+    mut errors: Vec<stc_ts_errors::Error>,
+    options: WatchOptions,
+) -> Result<(), Error>
+where
+    L: LoadModule,
+    R: Resolve,
+{
+    let (tx, rx) = channel();
+    let mut watcher = RecommendedWatcher::new(
+        move |event| {
+            let _ = tx.send(event);
+        },
+        Default::default(),
+    )?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+
+    print_summary(errors.len());
+
+    loop {
+        let changes = match next_changes(&rx)? {
+            Some(changes) => changes,
+            None => return Ok(()),
+        };
+
+        if !options.preserve_output {
+            // Same as `tsc --watch`.
+            print!("\x1B[2J\x1B[3J\x1B[H");
+        }
+        println!("File change detected. Starting incremental compilation...\n");
+
+        let start = Instant::now();
+
+        let mut changed = changes.changed;
+        if changes.structure_changed {
+            // Imports which could not be resolved may be resolved to new files, and
+            // imports of deleted files should fail.
+            resolver.clear();
+
+            let graph = checker.module_graph();
+            changed.extend(
+                graph
+                    .imports
+                    .iter()
+                    .filter(|import| import.resolved.is_none())
+                    .map(|import| PathBuf::from(&import.from)),
+            );
+        }
+        changed.sort();
+        changed.dedup();
+
+        for path in &changed {
+            for file in checker.invalidate_file(path).files {
+                errors.retain(|err| cm.span_to_filename(err.span()) != *file.file);
+                errors.extend(file.errors);
+            }
+        }
+        errors.extend(checker.take_errors());
+
+        log::info!("Re-checking {} changed files took {:?}", changed.len(), start.elapsed());
+
+        for err in &errors {
+            eprint!("{}", renderer.render(cm, &Report::new(err)));
+        }
+        print_summary(errors.len());
+    }
+}
+
+struct Changes {
+    changed: Vec<PathBuf>,
+    /// `true` if a file is created or removed.
+    structure_changed: bool,
+}
+
+/// Waits for a change, and returns it with the changes made until no change
+/// is made for [DEBOUNCE]. Returns [None] if the watcher is dropped.
+fn next_changes(rx: &Receiver<notify::Result<Event>>) -> Result<Option<Changes>, Error> {
+    let mut changes = Changes {
+        changed: vec![],
+        structure_changed: false,
+    };
+
+    let mut event = match rx.recv() {
+        Ok(event) => event,
+        Err(..) => return Ok(None),
+    };
+    loop {
+        let event = event?;
+        let paths = event.paths.into_iter().filter(|path| is_relevant(path));
+
+        match event.kind {
+            EventKind::Access(..) => {}
+            // Renaming is reported as a modification, but it creates or removes a file.
+            EventKind::Create(..) | EventKind::Remove(..) | EventKind::Modify(ModifyKind::Name(..)) => {
+                let len = changes.changed.len();
+                changes.changed.extend(paths);
+                changes.structure_changed |= changes.changed.len() != len;
+            }
+            _ => changes.changed.extend(paths),
+        }
+
+        event = match rx.recv_timeout(DEBOUNCE) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) if changes.changed.is_empty() => match rx.recv() {
+                Ok(event) => event,
+                Err(..) => return Ok(None),
+            },
+            Err(RecvTimeoutError::Timeout) => return Ok(Some(changes)),
+            Err(RecvTimeoutError::Disconnected) => return Ok(None),
+        };
+    }
+}
+
+/// Changes of files in `node_modules` are ignored, as packages are rarely
+/// modified while watching.
+fn is_relevant(path: &Path) -> bool {
+    let is_source = match path.extension() {
+        Some(ext) => EXTENSIONS.iter().any(|e| ext == *e),
+        None => false,
+    };
+
+    is_source && !path.components().any(|c| c.as_os_str() == "node_modules")
+}
+
+fn print_summary(errors: usize) {
+    match errors {
+        1 => println!("Found 1 error. Watching for file changes."),
+        n => println!("Found {} errors. Watching for file changes.", n),
+    }
+}